@@ -0,0 +1,81 @@
+// diff the same pair of commits with different algorithms and thresholds,
+// eg. `compare_configs INRIA/spoon <before> <after> gumtree_lazy gumtree_lazy,min_height=2 change_distiller_lazy,sim_threshold=0.5`
+
+use hyper_diff::algorithms::{self, DiffConfig};
+use hyperast::types::WithStats;
+use hyperast_vcs_git::multi_preprocessed::PreProcessedRepositories;
+
+#[cfg(not(target_env = "msvc"))]
+use jemallocator::Jemalloc;
+
+#[cfg(not(target_env = "msvc"))]
+#[global_allocator]
+static GLOBAL: Jemalloc = Jemalloc;
+
+fn main() {
+    hyperast_benchmark_diffs::setup_env_logger();
+    let args: Vec<String> = std::env::args().collect();
+    log::warn!("args: {:?}", args);
+    let mut repo = args
+        .get(1)
+        .expect("give an argument like openjdk/jdk or INRIA/spoon")
+        .split('/');
+    let repo_user = repo.next().unwrap();
+    let repo_name = repo.next().unwrap();
+    let before = args.get(2).expect("give the commit before");
+    let after = args.get(3).expect("give the commit after");
+    let configs: Vec<DiffConfig> = if args.len() > 4 {
+        args[4..].iter().map(|x| x.parse().unwrap()).collect()
+    } else {
+        algorithms::Algorithm::ALL
+            .into_iter()
+            .map(DiffConfig::new)
+            .collect()
+    };
+
+    let mut repositories = PreProcessedRepositories::default();
+    let repo = hyperast_vcs_git::git::Forge::Github.repo(repo_user, repo_name);
+    let config = hyperast_vcs_git::processing::RepoConfig::JavaMaven;
+    let repo_configured = repositories.register_config(repo, config).fetch();
+
+    let oid_src = repositories
+        .pre_process_with_limit(&repo_configured, "", before, 1)
+        .unwrap()[0];
+    let oid_dst = repositories
+        .pre_process_with_limit(&repo_configured, "", after, 1)
+        .unwrap()[0];
+    log::warn!("diff of {oid_src} and {oid_dst}");
+
+    let stores = &repositories.processor.main_stores;
+    let commit = |oid| {
+        repositories
+            .get_commit(&repo_configured.config, oid)
+            .unwrap()
+            .ast_root
+    };
+    let src_tr = commit(&oid_src);
+    let dst_tr = commit(&oid_dst);
+    let src_s = stores.node_store.resolve(src_tr).size();
+    let dst_s = stores.node_store.resolve(dst_tr).size();
+
+    let hyperast = hyperast_vcs_git::no_space::as_nospaces2(stores);
+
+    println!("config,src_s,dst_s,mappings,actions,subtree_t,bottomup_t,prepare_gen_t,gen_t");
+    for config in configs {
+        let diff = algorithms::diff(&hyperast, &src_tr, &dst_tr, &config);
+        let summary = diff.summarize();
+        let exec_data = &summary.exec_data;
+        println!(
+            "\"{}\",{},{},{},{},{},{},{},{}",
+            config,
+            src_s,
+            dst_s,
+            summary.mappings,
+            summary.actions.map_or(-1, |x| x as isize),
+            exec_data.phase1().mapping.0.as_secs_f64(),
+            exec_data.phase2().mapping.0.as_secs_f64(),
+            exec_data.phase3().prep.0.as_secs_f64(),
+            exec_data.phase3().mapping.0.as_secs_f64(),
+        );
+    }
+}
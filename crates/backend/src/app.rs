@@ -11,7 +11,7 @@ use tower::ServiceBuilder;
 use tower_http::trace::TraceLayer;
 
 use crate::{
-    commit, fetch, file, pull_requests, querying,
+    commit, diff, fetch, file, gumtree, pull_requests, querying,
     scriptingv1::{self, ScriptContent, ScriptContentDepth, ScriptingError, ScriptingParam},
     smells, track, view, SharedState,
 };
@@ -44,6 +44,7 @@ impl IntoResponse for crate::tsg::QueryingError {
 mod tsg {
     #[derive(serde::Deserialize)]
     pub struct Param {
+        forge: String,
         user: String,
         name: String,
         commit: String,
@@ -72,6 +73,24 @@ async fn scripting_depth(
     Ok(r)
 }
 
+async fn scripting_lua(
+    axum::extract::Path(path): axum::extract::Path<ScriptingParam>,
+    axum::extract::State(state): axum::extract::State<SharedState>,
+    axum::extract::Json(script): axum::extract::Json<crate::scripting_lua::ScriptContent>,
+) -> axum::response::Result<Json<crate::scripting_lua::ComputeResults>> {
+    let r = crate::scripting_lua::simple(script, state, path)?;
+    Ok(Json(r))
+}
+
+async fn scripting_series(
+    axum::extract::Path(path): axum::extract::Path<ScriptingParam>,
+    axum::extract::State(state): axum::extract::State<SharedState>,
+    axum::extract::Json(script): axum::extract::Json<scriptingv1::ScriptContentSeries>,
+) -> axum::response::Result<Response> {
+    let r = scriptingv1::simple_series(script, state, path)?;
+    Ok(r)
+}
+
 pub fn scripting_app(_st: SharedState) -> Router<SharedState> {
     let scripting_service_config = ServiceBuilder::new()
         .layer(HandleErrorLayer::new(|e: BoxError| async move {
@@ -86,13 +105,21 @@ pub fn scripting_app(_st: SharedState) -> Router<SharedState> {
         .layer(TraceLayer::new_for_http());
     Router::new()
         .route(
-            "/script/github/:user/:name/:commit",
+            "/script/:forge/:user/:name/:commit",
             post(scripting).layer(scripting_service_config.clone()),
         )
         .route(
-            "/script-depth/github/:user/:name/:commit",
+            "/script-depth/:forge/:user/:name/:commit",
             post(scripting_depth).layer(scripting_service_config.clone()),
         )
+        .route(
+            "/script-lua/:forge/:user/:name/:commit",
+            post(scripting_lua).layer(scripting_service_config.clone()),
+        )
+        .route(
+            "/script-series/:forge/:user/:name/:commit",
+            post(scripting_series).layer(scripting_service_config.clone()),
+        )
         .route("/sharing-scripts/shared-db", get(crate::ws::connect_db))
         .route(
             "/sharing-scripts/shared/:session",
@@ -114,23 +141,16 @@ async fn querying(
         .get(http::header::ACCEPT)
         .map_or("", |x| x.to_str().unwrap_or_default());
 
+    let locations = script.locations;
     let r = querying::simple(script, state, path);
     if accept.contains("csv") {
         match r {
             Ok(x) => {
-                let r = x.results.into_iter().filter_map(|x| x.ok()).map(|x| {
-                    format!(
-                        "{},{},{}\n",
-                        &x.commit[..8],
-                        x.inner.result[0],
-                        x.inner.compute_time
-                    )
-                });
-                let mut r = Some("id,result0,compute_time\n".to_string())
-                    .into_iter()
-                    .chain(r)
-                    .collect::<String>()
-                    .into_response();
+                let mut r = if locations {
+                    locations_csv(&x).into_response()
+                } else {
+                    counts_csv(&x).into_response()
+                };
                 r.headers_mut()
                     .insert("prepare_time", x.prepare_time.to_string().parse().unwrap());
                 r.headers_mut().insert(
@@ -150,6 +170,54 @@ async fn querying(
     }
 }
 
+fn counts_csv(x: &querying::ComputeResults) -> String {
+    let r = x.results.iter().filter_map(|x| x.as_ref().ok()).map(|x| {
+        format!(
+            "{},{},{}\n",
+            &x.commit[..8],
+            x.inner.result[0],
+            x.inner.compute_time
+        )
+    });
+    Some("id,result0,compute_time\n".to_string())
+        .into_iter()
+        .chain(r)
+        .collect()
+}
+
+/// One row per located match,
+/// the columns of the captures contain space separated `<name>=<node id>`
+fn locations_csv(results: &querying::ComputeResults) -> String {
+    let mut r = "id,pattern,file,start,end,start_line,start_column,end_line,end_column,captures\n"
+        .to_string();
+    let results = results.results.iter().map(|x| match x {
+        Ok(x)
+        | Err(querying::MatchingError::TimeOut(x) | querying::MatchingError::MaxMatches(x)) => x,
+    });
+    for x in results {
+        let Some(page) = &x.inner.page else {
+            continue;
+        };
+        for l in &page.locations {
+            let captures = l.captures.iter().map(|c| format!("{}={}", c.name, c.id));
+            r += &format!(
+                "{},{},\"{}\",{},{},{},{},{},{},{}\n",
+                &x.commit[..8],
+                l.pattern,
+                l.file.replace('"', "\"\""),
+                l.start,
+                l.end,
+                l.start_line,
+                l.start_column,
+                l.end_line,
+                l.end_column,
+                captures.collect::<Vec<_>>().join(" "),
+            );
+        }
+    }
+    r
+}
+
 #[axum_macros::debug_handler]
 async fn querying_streamed(
     axum::extract::Path(path): axum::extract::Path<querying::Param>,
@@ -168,6 +236,15 @@ async fn querying_differential(
     Ok(r)
 }
 
+async fn querying_differential_matches(
+    axum::extract::Path(path): axum::extract::Path<querying::ParamDifferential>,
+    axum::extract::State(state): axum::extract::State<SharedState>,
+    axum::extract::Json(script): axum::extract::Json<querying::Content>,
+) -> axum::response::Result<Json<querying::ComputeResultsClassified>> {
+    let r = querying::differential_matches(script, state, path)?;
+    Ok(r)
+}
+
 pub fn querying_app(_st: SharedState) -> Router<SharedState> {
     let querying_service_config = ServiceBuilder::new()
         .layer(HandleErrorLayer::new(|e: BoxError| async move {
@@ -182,17 +259,21 @@ pub fn querying_app(_st: SharedState) -> Router<SharedState> {
         .layer(TraceLayer::new_for_http());
     Router::new()
         .route(
-            "/query/github/:user/:name/*commit",
+            "/query/:forge/:user/:name/*commit",
             post(querying).layer(querying_service_config.clone()), // .with_state(Arc::clone(&shared_state)),
         )
         .route(
-            "/query-st/github/:user/:name/*commit",
+            "/query-st/:forge/:user/:name/*commit",
             post(querying_streamed).layer(querying_service_config.clone()), // .with_state(Arc::clone(&shared_state)),
         )
         .route(
-            "/query-differential/github/:user/:name/:commit/:baseline",
+            "/query-differential/:forge/:user/:name/:commit/:baseline",
             post(querying_differential).layer(querying_service_config.clone()), // .with_state(Arc::clone(&shared_state)),
         )
+        .route(
+            "/query-differential-matches/:forge/:user/:name/:commit/:baseline",
+            post(querying_differential_matches).layer(querying_service_config.clone()),
+        )
         .route(
             "/sharing-queries/shared-db",
             get(crate::ws::connect_db), // .with_state(Arc::clone(&shared_state)),
@@ -234,7 +315,7 @@ pub fn tsg_app(_st: SharedState) -> Router<SharedState> {
         .layer(TraceLayer::new_for_http());
     Router::new()
         .route(
-            "/tsg/github/:user/:name/:commit",
+            "/tsg/:forge/:user/:name/:commit",
             post(tsg).layer(tsg_service_config.clone()), // .with_state(Arc::clone(&shared_state)),
         )
         .route(
@@ -278,11 +359,11 @@ pub fn smells_app(_st: SharedState) -> Router<SharedState> {
         .layer(TraceLayer::new_for_http());
     Router::new()
         .route(
-            "/smells/github/:user/:name/:commit/:len",
+            "/smells/:forge/:user/:name/:commit/:len",
             post(smells).layer(smells_service_config.clone()),
         )
         .route(
-            "/smells_ex_from_diffs/github/:user/:name/:commit/:len",
+            "/smells_ex_from_diffs/:forge/:user/:name/:commit/:len",
             post(smells_ex_from_diffs).layer(smells_service_config.clone()),
         )
 }
@@ -300,7 +381,7 @@ pub fn fetch_git_file(_st: SharedState) -> Router<SharedState> {
         .timeout(Duration::from_secs(10))
         .layer(TraceLayer::new_for_http());
     Router::new().route(
-        "/file/github/:user/:name/:commit/*file",
+        "/file/:forge/:user/:name/:commit/*file",
         get(file).layer(service_config.clone()), // .with_state(Arc::clone(&shared_state)),
     )
 }
@@ -328,15 +409,15 @@ pub fn track_code_route(_st: SharedState) -> Router<SharedState> {
         .layer(TraceLayer::new_for_http());
     Router::new()
         .route(
-            "/track/github/:user/:name/:commit/*file",
+            "/track/:forge/:user/:name/:commit/*file",
             get(track_code).layer(service_config.clone()), // .with_state(Arc::clone(&shared_state)),
         )
         .route(
-            "/track_at_path/github/:user/:name/:commit/*path",
+            "/track_at_path/:forge/:user/:name/:commit/*path",
             get(track_code_at_path).layer(service_config.clone()),
         )
         .route(
-            "/track_at_path_with_changes/github/:user/:name/:commit/*path",
+            "/track_at_path_with_changes/:forge/:user/:name/:commit/*path",
             get(track_code_at_path_with_changes).layer(service_config.clone()),
         )
 }
@@ -384,15 +465,16 @@ pub fn view_code_route(_st: SharedState) -> Router<SharedState> {
         .layer(TraceLayer::new_for_http());
     Router::new()
         .route(
-            "/view/github/:user/:name/:commit/*path",
+            "/view/:forge/:user/:name/:commit/*path",
             get(view_code).layer(service_config.clone()), // .with_state(Arc::clone(&shared_state)),
         )
         .route(
-            "/view/github/:user/:name/:commit/",
+            "/view/:forge/:user/:name/:commit/",
             get(view_code).layer(service_config.clone()), // .with_state(Arc::clone(&shared_state)),
         )
+        // the node id shares the name of the forge segment above, otherwise the routes conflict
         .route(
-            "/view/:id",
+            "/view/:forge",
             get(view_code_with_node_id).layer(service_config.clone()), // .with_state(Arc::clone(&shared_state)),
         )
 }
@@ -426,11 +508,11 @@ pub fn fetch_code_route(_st: SharedState) -> Router<SharedState> {
         .layer(TraceLayer::new_for_http());
     Router::new()
         .route(
-            "/fetch/github/:user/:name/:commit/*path",
+            "/fetch/:forge/:user/:name/:commit/*path",
             get(fetch_code).layer(service_config.clone()), // .with_state(Arc::clone(&shared_state)),
         )
         .route(
-            "/fetch/github/:user/:name/:commit/",
+            "/fetch/:forge/:user/:name/:commit/",
             get(fetch_code).layer(service_config.clone()), // .with_state(Arc::clone(&shared_state)),
         )
         .route(
@@ -486,6 +568,77 @@ impl IntoResponse for fetch::FetchedNodes {
     }
 }
 
+pub fn gumtree_diff_route(_st: SharedState) -> Router<SharedState> {
+    let service_config = ServiceBuilder::new()
+        .layer(HandleErrorLayer::new(|e: BoxError| async move {
+            dbg!(e);
+        }))
+        .load_shed()
+        .concurrency_limit(4)
+        .buffer(20)
+        .rate_limit(2, Duration::from_secs(5))
+        // .request_body_limit(1024 * 5_000 /* ~5mb */)
+        .timeout(Duration::from_secs(60))
+        .layer(TraceLayer::new_for_http());
+    Router::new().route(
+        "/gumtree/:forge/:user/:name/:before/:after",
+        get(gumtree_diff).layer(service_config.clone()),
+    )
+}
+
+async fn gumtree_diff(
+    axum::extract::Path(path): axum::extract::Path<gumtree::Param>,
+    axum::extract::Query(query): axum::extract::Query<gumtree::GumtreeQuery>,
+    axum::extract::State(state): axum::extract::State<SharedState>,
+) -> axum::response::Result<Response> {
+    dbg!(&path);
+    gumtree::gumtree_diff(state, path, query).map_err(|err| err.into())
+}
+
+pub fn diff_route(_st: SharedState) -> Router<SharedState> {
+    let service_config = ServiceBuilder::new()
+        .layer(HandleErrorLayer::new(|e: BoxError| async move {
+            dbg!(e);
+        }))
+        .load_shed()
+        .concurrency_limit(4)
+        .buffer(20)
+        .rate_limit(2, Duration::from_secs(5))
+        // .request_body_limit(1024 * 5_000 /* ~5mb */)
+        .timeout(Duration::from_secs(60))
+        .layer(TraceLayer::new_for_http());
+    Router::new()
+        .route(
+            "/diff/:forge/:user/:name/:before/:after",
+            get(commit_diffs).layer(service_config.clone()),
+        )
+        .route(
+            "/diff-st/:forge/:user/:name/:before/:after",
+            get(commit_diffs_streamed).layer(service_config.clone()),
+        )
+}
+
+async fn commit_diffs(
+    axum::extract::Path(path): axum::extract::Path<diff::Param>,
+    axum::extract::Query(query): axum::extract::Query<diff::DiffQuery>,
+    axum::extract::State(state): axum::extract::State<SharedState>,
+) -> axum::response::Result<Json<Vec<diff::CommitDiff>>> {
+    let diffs = diff::commit_diffs(state, path, query)?;
+    Ok(Json(diffs.collect()))
+}
+
+/// one json object per line and commit, sent as soon as computed
+async fn commit_diffs_streamed(
+    axum::extract::Path(path): axum::extract::Path<diff::Param>,
+    axum::extract::Query(query): axum::extract::Query<diff::DiffQuery>,
+    axum::extract::State(state): axum::extract::State<SharedState>,
+) -> axum::response::Result<Response> {
+    let diffs = diff::commit_diffs(state, path, query)?;
+    let lines = diffs.map(|x| serde_json::to_string(&x).map(|x| x + "\n"));
+    let body = axum::body::Body::from_stream(futures::stream::iter(lines));
+    Ok(([(http::header::CONTENT_TYPE, "application/x-ndjson")], body).into_response())
+}
+
 pub fn commit_metadata_route(_st: SharedState) -> Router<SharedState> {
     let service_config = ServiceBuilder::new()
         .layer(HandleErrorLayer::new(|e: BoxError| async move {
@@ -500,7 +653,7 @@ pub fn commit_metadata_route(_st: SharedState) -> Router<SharedState> {
         .layer(TraceLayer::new_for_http());
     Router::new()
         .route(
-            "/commit/github/:user/:name/:version",
+            "/commit/:forge/:user/:name/:version",
             get(commit_metadata).layer(service_config.clone()), // .with_state(Arc::clone(&shared_state)),
         )
         .route(
@@ -549,3 +702,77 @@ impl<T: IntoResponse> IntoResponse for Timed<T> {
         resp
     }
 }
+
+pub fn persist_route(_st: SharedState) -> Router<SharedState> {
+    let router = Router::new();
+    #[cfg(feature = "persist")]
+    let router = router.route("/admin/snapshot", post(snapshot));
+    router
+}
+
+#[cfg(feature = "persist")]
+async fn snapshot(
+    axum::extract::State(state): axum::extract::State<SharedState>,
+) -> axum::response::Result<Json<crate::persist::Snapshotted>> {
+    crate::persist::snapshot(state).map_err(|err| err.into())
+}
+
+pub fn gc_route(_st: SharedState) -> Router<SharedState> {
+    Router::new().route("/admin/gc", post(collect_garbage))
+}
+
+pub fn refs_route(_st: SharedState) -> Router<SharedState> {
+    Router::new().route(
+        "/refs/:forge/:user/:name",
+        get(list_refs).post(process_refs),
+    )
+}
+
+async fn list_refs(
+    axum::extract::Path(path): axum::extract::Path<crate::refs::Param>,
+    axum::extract::State(state): axum::extract::State<SharedState>,
+) -> axum::response::Result<Json<crate::refs::Refs>> {
+    crate::refs::refs(state, path, None).map_err(|err| err.into())
+}
+
+async fn process_refs(
+    axum::extract::Path(path): axum::extract::Path<crate::refs::Param>,
+    axum::extract::Query(query): axum::extract::Query<crate::refs::ProcessQuery>,
+    axum::extract::State(state): axum::extract::State<SharedState>,
+) -> axum::response::Result<Json<crate::refs::Refs>> {
+    crate::refs::refs(state, path, Some(query)).map_err(|err| err.into())
+}
+
+pub fn watch_route(_st: SharedState) -> Router<SharedState> {
+    Router::new()
+        .route("/watch/:forge/:user/:name", post(watch).delete(unwatch))
+        .route("/notify/:forge/:user/:name", post(notify))
+}
+
+async fn watch(
+    axum::extract::Path(path): axum::extract::Path<crate::watch::Param>,
+    axum::extract::State(state): axum::extract::State<SharedState>,
+) -> axum::response::Result<Json<crate::watch::Watched>> {
+    crate::watch::watch(state, path).map_err(|err| err.into())
+}
+
+async fn unwatch(
+    axum::extract::Path(path): axum::extract::Path<crate::watch::Param>,
+    axum::extract::State(state): axum::extract::State<SharedState>,
+) -> axum::response::Result<()> {
+    crate::watch::unwatch(state, path).map_err(|err| err.into())
+}
+
+async fn notify(
+    axum::extract::Path(path): axum::extract::Path<crate::watch::Param>,
+    axum::extract::State(state): axum::extract::State<SharedState>,
+) -> axum::response::Result<Json<crate::watch::Polled>> {
+    crate::watch::notify(state, path).map_err(|err| err.into())
+}
+
+async fn collect_garbage(
+    axum::extract::State(state): axum::extract::State<SharedState>,
+    axum::extract::Json(param): axum::extract::Json<crate::gc::GcParam>,
+) -> axum::response::Result<Json<crate::gc::Collected>> {
+    crate::gc::collect(state, param).map_err(|err| err.into())
+}
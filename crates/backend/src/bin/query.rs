@@ -0,0 +1,207 @@
+use std::path::PathBuf;
+
+use backend::querying::{
+    ComputeResult, MatchingError, compute_aux, default_max_matches, default_timeout, located_aux,
+    repo_config,
+};
+use clap::Parser;
+use hyperast_vcs_git::{git::Forge, multi_preprocessed::PreProcessedRepositories};
+use serde::Serialize;
+use tracing_subscriber::EnvFilter;
+
+#[derive(Parser)]
+/// Match a tree-sitter query on the history of a local repository
+///
+/// outputs, per commit, the number of matches of each pattern and the location of each match
+///
+/// set the env variable RUST_LOG=debug to display logs during computation
+struct Cli {
+    /// Path to a local git repository, eg. /srv/git/INRIA/spoon,
+    /// the last two directories give the user and the name of the repository
+    repository: PathBuf,
+    /// Commits to query, either a single commit or <before>..<after> (both included),
+    /// <after> defaults to HEAD
+    range: String,
+    /// File containing the query
+    query: PathBuf,
+    /// Language of the query, eg. Java, Cpp, Python or TypeScript
+    #[clap(short, long)]
+    language: String,
+    /// Maximum number of commits to query, following first parents
+    #[clap(short, long)]
+    commits: Option<usize>,
+    /// Maximum number of matches per pattern and commit with --counts,
+    /// otherwise maximum number of matches located per commit, whatever their pattern,
+    /// if reached on the first commit it stops,
+    /// otherwise it halves the number of remaining commits to query
+    #[clap(long, default_value_t = default_max_matches())]
+    max_matches: u64,
+    /// Timeout in milliseconds per commit, same behavior as --max-matches
+    #[clap(long, default_value_t = default_timeout())]
+    timeout: u64,
+    /// Capture locating matches, defaults to the first capture of each match
+    #[clap(long)]
+    capture: Option<String>,
+    /// Output format, either csv or json (one object per commit and line)
+    #[clap(short, long, default_value = "csv")]
+    format: String,
+    /// Only output the number of matches, not their locations
+    #[clap(long)]
+    counts: bool,
+}
+
+#[derive(Serialize)]
+struct CommitResult {
+    commit: String,
+    #[serde(flatten)]
+    inner: ComputeResult,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Cli::parse();
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::builder()
+                .with_default_directive(tracing::level_filters::LevelFilter::OFF.into())
+                .from_env_lossy(),
+        )
+        .try_init()
+        .unwrap();
+    let csv = match args.format.as_str() {
+        "csv" => true,
+        "json" => false,
+        f => return Err(format!("unknown format {f}, expected csv or json").into()),
+    };
+    let path = std::fs::canonicalize(&args.repository)?;
+    // local forges hold repositories at <root>/<user>/<name>
+    let parent = path.parent();
+    let (Some(root), Some(user), Some(name)) = (
+        parent.and_then(|x| x.parent()),
+        parent.and_then(|x| x.file_name()),
+        path.file_name(),
+    ) else {
+        return Err(format!("{} is not in a user directory", path.display()).into());
+    };
+    let name = name.to_string_lossy();
+    let name = name.strip_suffix(".git").unwrap_or(&name);
+    let repo_spec = Forge::local(root).try_repo(user.to_string_lossy(), name)?;
+    let config = repo_config(&args.language);
+    let language = hyperast_vcs_git::resolve_language(&args.language)
+        .ok_or_else(|| format!("missing language {}", args.language))?;
+    let query = std::fs::read_to_string(&args.query)?;
+
+    let mut repositories = PreProcessedRepositories::default();
+    let repository = repositories.register_config(repo_spec, config).fetch();
+    let query = match repositories.get_precomp_query(repository.config, &args.language) {
+        Some(precomputeds) => {
+            hyperast_tsquery::Query::with_precomputed(&query, language, precomputeds).map(|x| x.1)
+        }
+        None => hyperast_tsquery::Query::new(&query, language),
+    }
+    .map_err(|e| e.to_string())?;
+    let capture = match &args.capture {
+        Some(name) => Some(
+            query
+                .capture_index_for_name(name)
+                .ok_or_else(|| format!("no capture named {name} in the query"))?,
+        ),
+        None => None,
+    };
+    let timeout = std::time::Duration::from_millis(args.timeout);
+
+    let (before, after) =
+        (args.range.split_once("..")).unwrap_or((args.range.as_str(), args.range.as_str()));
+    let mut rw = hyperast_vcs_git::git::Builder::new(&repository.repo)?
+        .first_parents()?
+        .before(before)?
+        .after(after)?
+        .walk()?
+        .map(|x| x.unwrap());
+
+    if csv && args.counts {
+        let patterns = (0..query.enabled_pattern_count()).map(|i| format!(",{i}"));
+        println!("commit,compute_time,error{}", patterns.collect::<String>());
+    } else if csv {
+        println!("commit,pattern,file,start,end,start_line,start_column,end_line,end_column");
+    }
+    let mut proc_commit_limit = args.commits.unwrap_or(usize::MAX);
+    let mut i = 0;
+    while i < proc_commit_limit {
+        let commits = repositories.pre_process_chunk(&mut rw, &repository, 1)?;
+        let Some(commit_oid) = commits.first() else {
+            break;
+        };
+        let commit = repositories
+            .get_commit(&repository.config, commit_oid)
+            .unwrap();
+        let stores = &repositories.processor.main_stores;
+        let result = if args.counts {
+            let locations = false;
+            compute_aux(
+                stores,
+                commit.ast_root,
+                &query,
+                timeout,
+                args.max_matches,
+                locations,
+                0,
+            )
+        } else {
+            located_aux(
+                stores,
+                commit.ast_root,
+                &query,
+                capture,
+                timeout,
+                args.max_matches,
+                0,
+            )
+        };
+        let truncated =
+            matches!(&result, Ok(x) if x.page.as_ref().is_some_and(|p| p.next_offset.is_some()));
+        if result.is_err() || truncated {
+            if i == 0 {
+                return Err(format!("stopping on first commit {commit_oid}: {result:?}").into());
+            }
+            log::warn!("{:?}", result);
+            proc_commit_limit /= 2;
+        }
+        let commit = commit_oid.to_string();
+        if csv {
+            let (inner, error) = match &result {
+                Ok(inner) if truncated => (inner, "MaxMatches"),
+                Ok(inner) => (inner, ""),
+                Err(MatchingError::TimeOut(inner)) => (inner, "TimeOut"),
+                Err(MatchingError::MaxMatches(inner)) => (inner, "MaxMatches"),
+            };
+            if args.counts {
+                let counts = inner.result.iter().map(|x| format!(",{x}"));
+                let counts = counts.collect::<String>();
+                println!("{commit},{},{error}{counts}", inner.compute_time);
+            } else {
+                for l in inner.page.iter().flat_map(|p| &p.locations) {
+                    let file = l.file.replace('"', "\"\"");
+                    let (line, column) = (l.start_line, l.start_column);
+                    let (end_line, end_column) = (l.end_line, l.end_column);
+                    println!(
+                        "{commit},{},\"{file}\",{},{},{line},{column},{end_line},{end_column}",
+                        l.pattern, l.start, l.end
+                    );
+                }
+            }
+        } else {
+            // without locations when only counting
+            let with = |inner: ComputeResult| CommitResult {
+                commit: commit.clone(),
+                inner,
+            };
+            let result = match result {
+                Ok(inner) => serde_json::to_string(&with(inner)),
+                Err(err) => serde_json::to_string(&err.map(with)),
+            };
+            println!("{}", result?);
+        }
+        i += 1;
+    }
+    Ok(())
+}
@@ -115,7 +115,7 @@ fn scripting(
         .repositories
         .write()
         .unwrap()
-        .register_config_with_prepro(repo_spec.clone(), config, script.into());
+        .register_config_with_prepro(repo_spec.clone(), config, script.into())?;
     let repo = state
         .repositories
         .read()
@@ -147,7 +147,7 @@ fn _scripting(
             .repositories
             .write()
             .unwrap()
-            .pre_process_chunk(&mut rw, &repository, 1);
+            .pre_process_chunk(&mut rw, &repository, 1)?;
     for _ in 0..200 {
         if commits.is_empty() {
             break;
@@ -156,11 +156,12 @@ fn _scripting(
         for oid in commits {
             after_prepared(&state, &repository, oid);
         }
-        commits = state
-            .repositories
-            .write()
-            .unwrap()
-            .pre_process_chunk(&mut rw, &repository, 100);
+        commits =
+            state
+                .repositories
+                .write()
+                .unwrap()
+                .pre_process_chunk(&mut rw, &repository, 100)?;
     }
     Ok(())
 }
@@ -22,24 +22,91 @@ pub struct Options {
     ///
     /// use the following syntax: <forge>/<user>/<name>:<config>
     /// example: github.com/INRIA/spoon:Java
+    /// or github.com/junit-team/junit5:Gradle for java projects built with gradle
+    /// where the forge can also be an url or a local directory,
+    /// example: https://git.example.org/team/project:Cpp or /srv/git/team/project:Python
+    /// optionally followed by path filters to only process a slice of the repository,
+    /// example: github.com/acme/monorepo:Java?include=services/payments/**&exclude=**/generated/**&submodules
     #[clap(short, long)]
     pub repository: Vec<RepoConfig>,
+
+    /// name a forge to use it in routes, eg. /view/<name>/<user>/<name>/<commit>/ (multiple uses)
+    ///
+    /// use the following syntax: <name>=<url or local directory>
+    /// example: gitea=https://git.example.org or mirrors=/srv/git
+    #[clap(short, long)]
+    pub forge: Vec<ForgeConfig>,
+
+    /// directory where remote repositories are cloned,
+    /// defaults to the HYPERAST_CACHE_ROOT env variable or to /tmp/hyperastgitresources/repo/
+    #[clap(long)]
+    pub cache_root: Option<std::path::PathBuf>,
+
+    /// directory where preprocessed repositories are persisted,
+    /// restored on startup and appended on shutdown or on POST /admin/snapshot
+    #[cfg(feature = "persist")]
+    #[clap(long)]
+    pub snapshot: Option<std::path::PathBuf>,
+
+    /// seconds between two polls of the repositories watched with POST /watch/<forge>/<user>/<name>,
+    /// without it they are only processed when notified with POST /notify/<forge>/<user>/<name>
+    #[clap(long)]
+    pub watch_interval: Option<u64>,
+
+    /// maximum memory in bytes of the luau scripts of POST /script-lua, requests asking for more are clamped
+    #[clap(long, default_value_t = crate::scripting_lua::default_memory_limit())]
+    pub lua_memory_limit: usize,
+
+    /// maximum instructions per commit of the luau scripts of POST /script-lua, requests asking for more are clamped
+    #[clap(long, default_value_t = crate::scripting_lua::default_instruction_limit())]
+    pub lua_instruction_limit: u64,
 }
 
 pub struct RepoConfig {
     pub repo: hyperast_vcs_git::git::Repo,
     pub config: hyperast_vcs_git::processing::RepoConfig,
+    pub filter: hyperast_vcs_git::path_filter::PathFilter,
 }
 
 impl std::str::FromStr for RepoConfig {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (repo, config) = s.split_once(":").ok_or("")?;
+        let (repo, config) = s.rsplit_once(":").ok_or("")?;
+        let (config, filter) = config.split_once("?").unwrap_or((config, ""));
         let repo = repo.parse()?;
         let config = config.parse()?;
+        let filter: hyperast_vcs_git::path_filter::PathFilter = filter.parse()?;
+        use hyperast_vcs_git::processing::RepoConfig as Config;
+        if !filter.is_identity() && !matches!(config, Config::JavaMaven | Config::CppMake) {
+            return Err(format!("no path filters on {config:?} repositories"));
+        }
+
+        Ok(Self {
+            repo,
+            config,
+            filter,
+        })
+    }
+}
 
-        Ok(Self { repo, config })
+pub struct ForgeConfig {
+    pub name: String,
+    pub forge: hyperast_vcs_git::git::Forge,
+}
+
+impl std::str::FromStr for ForgeConfig {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, forge) = s
+            .split_once("=")
+            .ok_or("use the following syntax: <name>=<url or local directory>")?;
+        let forge = forge.parse()?;
+        Ok(Self {
+            name: name.to_string(),
+            forge,
+        })
     }
 }
 
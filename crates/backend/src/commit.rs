@@ -7,6 +7,9 @@ use crate::SharedState;
 
 #[derive(Serialize, Deserialize, Clone, Debug, Hash, PartialEq, Eq)]
 pub struct Param {
+    /// github when absent, e.g. for pull requests
+    #[serde(default = "github")]
+    pub forge: String,
     pub user: String,
     pub name: String,
     /// either a commit id or a tag
@@ -32,14 +35,22 @@ pub struct Metadata {
     pub(crate) forth_timestamp: i64,
 }
 
+fn github() -> String {
+    "github".to_string()
+}
+
 // TODO prefetch a list of parent ids in power of 2 [2,4,8,16,32]
-pub fn commit_metadata(_state: SharedState, path: Param) -> Result<Json<Metadata>, String> {
+pub fn commit_metadata(state: SharedState, path: Param) -> Result<Json<Metadata>, String> {
     let Param {
+        forge,
         user,
         name,
         version,
     } = path.clone();
-    let repo = fetch_github_repository(&format!("{}/{}", user, name));
+    let repo = state
+        .repo(&forge, &user, &name)?
+        .try_fetch()
+        .map_err(|e| e.to_string())?;
     log::debug!("done cloning {user}/{name}");
     let commit = retrieve_commit(&repo, &version);
     if let Err(err) = &commit {
@@ -0,0 +1,230 @@
+//! Edit statistics over a range of commits,
+//! for each consecutive pair of commits the number of inserts, deletes, updates and moves,
+//! in total and per file.
+use std::collections::BTreeMap;
+
+use hyper_diff::{
+    actions::{
+        action_vec::ActionsVec,
+        script_generator2::{Act, SimpleAction},
+    },
+    algorithms::{Algorithm, DiffConfig},
+};
+use hyperast::{
+    store::defaults::NodeIdentifier,
+    types::{HyperAST, HyperType, LabelStore, Labeled, WithChildren},
+};
+use hyperast_vcs_git::{git::Oid, processing::ConfiguredRepo2};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    SharedState,
+    smells::diffing::{A, Stores},
+};
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct Param {
+    pub forge: String,
+    pub user: String,
+    pub name: String,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct DiffQuery {
+    /// either `gumtree_lazy` (the default), `gumtree_hybrid_lazy` or `change_distiller_lazy`,
+    /// the other algorithms of `hyper_diff` are rejected, their thresholds are not configurable
+    pub algorithm: Option<String>,
+    /// maximum number of commits to diff with their first parent, starting from `after`
+    #[serde(default = "default_commits")]
+    pub commits: usize,
+    /// thresholds overriding the defaults of the algorithm, see [`DiffConfig`]
+    pub min_height: Option<usize>,
+    pub max_size: Option<usize>,
+    pub sim_threshold: Option<f64>,
+    pub sim_threshold2: Option<f64>,
+    pub label_sim_threshold: Option<f64>,
+}
+
+fn default_commits() -> usize {
+    10
+}
+
+impl DiffQuery {
+    fn config(&self) -> Result<DiffConfig, String> {
+        let algorithm = match &self.algorithm {
+            Some(a) => a.parse()?,
+            None => Algorithm::default(),
+        };
+        let default = DiffConfig::new(algorithm);
+        Ok(DiffConfig {
+            algorithm,
+            min_height: self.min_height.unwrap_or(default.min_height),
+            max_size: self.max_size.unwrap_or(default.max_size),
+            sim_threshold: self.sim_threshold.unwrap_or(default.sim_threshold),
+            sim_threshold2: self.sim_threshold2.unwrap_or(default.sim_threshold2),
+            label_sim_threshold: self
+                .label_sim_threshold
+                .unwrap_or(default.label_sim_threshold),
+        })
+    }
+}
+
+#[derive(Serialize, Default, Clone, Copy, Debug)]
+pub struct ActionCounts {
+    pub insert: usize,
+    pub delete: usize,
+    pub update: usize,
+    pub r#move: usize,
+}
+
+#[derive(Serialize, Default, Debug)]
+pub struct CommitDiff {
+    pub before: String,
+    pub after: String,
+    /// time spent matching and generating the edit script, in seconds
+    pub compute_time: f64,
+    pub mappings: usize,
+    #[serde(flatten)]
+    pub total: ActionCounts,
+    /// counts per file, by path from the root of the repository
+    pub files: BTreeMap<String, ActionCounts>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Prepares the range and returns the lazily computed diff of each commit with its first parent
+pub fn commit_diffs(
+    state: SharedState,
+    path: Param,
+    query: DiffQuery,
+) -> Result<impl Iterator<Item = CommitDiff>, String> {
+    let config = query.config()?;
+    let Param {
+        forge,
+        user,
+        name,
+        before,
+        after,
+    } = path;
+    let repo_spec = state.repo(&forge, &user, &name)?;
+    let repo_handle = state
+        .repositories
+        .read()
+        .unwrap()
+        .get_config(repo_spec)
+        .ok_or_else(|| "missing config for repository".to_string())?;
+    let repository = repo_handle.try_fetch().map_err(|e| e.to_string())?;
+    log::info!("done cloning {}", repository.spec);
+    let rw = hyperast_vcs_git::git::Builder::new(&repository.repo)
+        .and_then(|x| x.first_parents())
+        .and_then(|x| x.before(&before))
+        .and_then(|x| x.after(&after))
+        .and_then(|x| x.walk())
+        .map_err(|e| e.to_string())?
+        .take(query.commits + 1)
+        .map(|x| x.unwrap());
+    let commits = crate::utils::handle_pre_processing_aux(&state, &repository, rw)
+        .map_err(|e| e.to_string())?;
+    log::info!(
+        "done construction of {} commits in {}",
+        commits.len(),
+        repository.spec
+    );
+    let pairs = (1..commits.len()).map(move |i| (commits[i], commits[i - 1]));
+    Ok(pairs.map(move |(before, after)| diff_commits(&state, &repository, &config, before, after)))
+}
+
+fn diff_commits(
+    state: &crate::AppState,
+    repository: &ConfiguredRepo2,
+    config: &DiffConfig,
+    before: Oid,
+    after: Oid,
+) -> CommitDiff {
+    let mut result = CommitDiff {
+        before: before.to_string(),
+        after: after.to_string(),
+        ..Default::default()
+    };
+    let repositories = state.repositories.read().unwrap();
+    let commit = |oid| repositories.get_commit(&repository.config, oid).unwrap();
+    let src_tr = commit(&before).ast_root;
+    let dst_tr = commit(&after).ast_root;
+    if src_tr == dst_tr {
+        return result;
+    }
+    let stores = &hyperast_vcs_git::no_space::as_nospaces2(&repositories.processor.main_stores);
+    let diff = hyper_diff::algorithms::diff(stores, &src_tr, &dst_tr, config);
+    let summary = diff.summarize();
+    result.mappings = summary.mappings;
+    result.compute_time =
+        (summary.exec_data.sum::<std::time::Duration>()).map_or(0.0, |x| x.as_secs_f64());
+    match &diff.actions {
+        Some(actions) => count_actions(stores, src_tr, dst_tr, actions, &mut result),
+        None => result.error = Some("failed to generate the edit script".to_string()),
+    }
+    result
+}
+
+fn count_actions(
+    stores: &Stores,
+    src_tr: NodeIdentifier,
+    dst_tr: NodeIdentifier,
+    actions: &ActionsVec<A>,
+    result: &mut CommitDiff,
+) {
+    let mut it = actions.iter().peekable();
+    while let Some(a) = it.next() {
+        let (file, counts) = match &a.action {
+            Act::Delete {} => (file_of(stores, src_tr, a.path.ori.iter()), [0, 1, 0, 0]),
+            Act::Update { .. } => {
+                // the update of a moved node comes just before its move,
+                // with the destination of the move as path
+                let file = match it.peek() {
+                    Some(SimpleAction {
+                        path,
+                        action: Act::Move { from },
+                    }) if path.ori.iter().eq(a.path.ori.iter()) => {
+                        file_of(stores, src_tr, from.ori.iter())
+                    }
+                    _ => file_of(stores, src_tr, a.path.ori.iter()),
+                };
+                (file, [0, 0, 1, 0])
+            }
+            Act::Move { .. } => (file_of(stores, dst_tr, a.path.ori.iter()), [0, 0, 0, 1]),
+            Act::MovUpd { .. } => (file_of(stores, dst_tr, a.path.ori.iter()), [0, 0, 1, 1]),
+            Act::Insert { .. } => (file_of(stores, dst_tr, a.path.ori.iter()), [1, 0, 0, 0]),
+        };
+        let [insert, delete, update, r#move] = counts;
+        for c in [&mut result.total, result.files.entry(file).or_default()] {
+            c.insert += insert;
+            c.delete += delete;
+            c.update += update;
+            c.r#move += r#move;
+        }
+    }
+}
+
+/// Path of the file containing the node at `path`, or of the deepest directory
+fn file_of(stores: &Stores, root: NodeIdentifier, mut path: impl Iterator<Item = u16>) -> String {
+    let mut segments = vec![];
+    let mut id = root;
+    loop {
+        let n = stores.node_store.resolve(id);
+        let t = stores.resolve_type(&id);
+        if t.is_directory() || t.is_file() {
+            let l = n.try_get_label().map(|l| stores.label_store.resolve(l));
+            segments.extend(l.filter(|l| !l.is_empty()));
+        }
+        if t.is_file() {
+            break;
+        }
+        let Some(child) = path.next().and_then(|o| n.child(&o)) else {
+            break;
+        };
+        id = child;
+    }
+    segments.join("/")
+}
@@ -17,6 +17,7 @@ use crate::{app::Timed, SharedState};
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct Parameters {
+    forge: String,
     user: String,
     name: String,
     commit: String,
@@ -48,25 +49,41 @@ pub struct FetchedNodes {
     #[serde(skip_serializing_if = "Vec::is_empty")]
     root: Vec<NodeIdentifier>,
     node_store: fetched::SimplePacked<&'static str>,
+    #[serde(skip_serializing_if = "FetchedDerived::is_empty")]
+    derived: FetchedDerived,
+}
+
+/// Data computed by the prepro script of the repository config, for the fetched nodes having some
+#[derive(Serialize, Default)]
+pub struct FetchedDerived {
+    ids: Vec<NodeIdentifier>,
+    data: Vec<rhai::Map>,
+}
+
+impl FetchedDerived {
+    fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
 }
 
 pub fn fetch(mut state: SharedState, path: Parameters) -> Result<FetchedNodes, String> {
     let now = Instant::now();
     let Parameters {
+        forge,
         user,
         name,
         commit,
         path,
     } = path;
     dbg!(&path);
-    let repo_spec = hyperast_vcs_git::git::Forge::Github.repo(user, name);
+    let repo_spec = state.repo(&forge, &user, &name)?;
     let repo = state
         .repositories
         .read()
         .unwrap()
         .get_config(repo_spec)
         .ok_or_else(|| "missing config for repository".to_string())?;
-    let mut repo = repo.fetch();
+    let mut repo = repo.try_fetch().map_err(|e| e.to_string())?;
     log::info!("done cloning {}", repo.spec);
 
     let commits = crate::utils::handle_pre_processing(&state, &mut repo, "", &commit, 2)
@@ -96,11 +113,13 @@ pub fn fetch(mut state: SharedState, path: Parameters) -> Result<FetchedNodes, S
     };
     let ids = vec![curr];
     let node_store = extract_nodes(&ids, &repositories.processor.main_stores);
+    let derived = extract_derived(&ids, &repositories.processor.main_stores);
     dbg!(&ids);
     let ids = ids.into_iter().map(|x| x.into()).collect();
     Ok(FetchedNodes {
         node_store,
         root: ids,
+        derived,
     })
 }
 
@@ -237,11 +256,13 @@ pub fn fetch_with_node_ids<'a>(
         &ids,
         &repositories.processor.main_stores, //label_store
     );
+    let derived = extract_derived(&ids, &repositories.processor.main_stores);
     Ok(Timed {
         time: now.elapsed().as_secs_f64(),
         content: FetchedNodes {
             node_store,
             root: vec![],
+            derived,
         },
     })
 }
@@ -313,6 +334,21 @@ fn extract_nodes(
     )
 }
 
+fn extract_derived(
+    ids: &[defaults::NodeIdentifier],
+    store: &hyperast::store::SimpleStores<TStore>,
+) -> FetchedDerived {
+    let mut derived = FetchedDerived::default();
+    for id in ids {
+        let n = store.node_store.resolve(*id);
+        if let Ok(dd) = n.get_component::<hyperast::scripting::DerivedData>() {
+            derived.ids.push((*id).into());
+            derived.data.push(dd.0.clone());
+        }
+    }
+    derived
+}
+
 #[derive(Default)]
 struct BuffOut {
     buff: String,
@@ -0,0 +1,60 @@
+//! Reclaims the subtrees and labels of the commits that are not served anymore.
+use std::collections::HashSet;
+
+use axum::Json;
+use hyperast_vcs_git::git::Oid;
+use serde::{Deserialize, Serialize};
+
+use crate::SharedState;
+
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct GcParam {
+    /// commits still served, by default all processed commits are kept
+    /// and only the subtrees that none of them reaches are reclaimed
+    pub keep: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Collected {
+    nodes_before: usize,
+    nodes_after: usize,
+    labels_before: usize,
+    labels_after: usize,
+    /// deduplicated subtrees per height
+    #[cfg(feature = "subtree-stats")]
+    height_counts_before: Vec<u32>,
+    #[cfg(feature = "subtree-stats")]
+    height_counts_after: Vec<u32>,
+    /// in seconds
+    time: f64,
+}
+
+pub fn collect(state: SharedState, param: GcParam) -> Result<Json<Collected>, String> {
+    let keep = param
+        .keep
+        .map(|commits| {
+            commits
+                .iter()
+                .map(|c| Oid::from_str(c).map_err(|err| err.to_string()))
+                .collect::<Result<HashSet<_>, _>>()
+        })
+        .transpose()?;
+    let now = std::time::Instant::now();
+    let mut repos = state.repositories.write().unwrap();
+    // cached mappings and decompressions refer to nodes that might get collected
+    state.mappings.clear();
+    state.mappings_alone.clear();
+    state.partial_decomps.clear();
+    let stats = repos.collect_garbage(|oid, _| keep.as_ref().is_none_or(|keep| keep.contains(oid)));
+    Ok(Json(Collected {
+        nodes_before: stats.nodes_before,
+        nodes_after: stats.nodes_after,
+        labels_before: stats.labels_before,
+        labels_after: stats.labels_after,
+        #[cfg(feature = "subtree-stats")]
+        height_counts_before: stats.height_counts_before,
+        #[cfg(feature = "subtree-stats")]
+        height_counts_after: stats.height_counts_after,
+        time: now.elapsed().as_secs_f64(),
+    }))
+}
@@ -0,0 +1,109 @@
+//! Edit scripts between two commits in the formats of GumTree's `textdiff`,
+//! see [`hyper_diff::actions::gumtree_export`].
+use axum::response::{IntoResponse, Response};
+use hyper_diff::actions::gumtree_export::{GumtreeDiff, resolve_node};
+use hyperast::{
+    store::defaults::{LabelIdentifier, NodeIdentifier},
+    types::LabelStore,
+};
+use serde::Deserialize;
+
+use crate::{
+    SharedState,
+    smells::diffing::{EditScript, edit_script},
+};
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct Param {
+    pub forge: String,
+    pub user: String,
+    pub name: String,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct GumtreeQuery {
+    /// either `json` (the default) or `xml`
+    pub format: Option<String>,
+    /// also export matched nodes, they cover most of both commits
+    #[serde(default)]
+    pub matches: bool,
+}
+
+enum Format {
+    Json,
+    Xml,
+}
+
+pub(crate) fn gumtree_diff(
+    state: SharedState,
+    path: Param,
+    query: GumtreeQuery,
+) -> Result<Response, String> {
+    let format = match query.format.as_deref() {
+        None | Some("json") => Format::Json,
+        Some("xml") => Format::Xml,
+        Some(f) => return Err(format!("unknown format {f}, expected json or xml")),
+    };
+    let Param {
+        forge,
+        user,
+        name,
+        before,
+        after,
+    } = path;
+    let repo_spec = state.repo(&forge, &user, &name)?;
+    let repo_handle = state
+        .repositories
+        .read()
+        .unwrap()
+        .get_config(repo_spec)
+        .ok_or_else(|| "missing config for repository".to_string())?;
+    let mut repository = repo_handle.try_fetch().map_err(|e| e.to_string())?;
+    log::info!("done cloning {}", repository.spec);
+    let before = crate::utils::handle_pre_processing(&state, &mut repository, "", &before, 1)
+        .map_err(|e| e.to_string())?[0];
+    let after = crate::utils::handle_pre_processing(&state, &mut repository, "", &after, 1)
+        .map_err(|e| e.to_string())?[0];
+    log::info!(
+        "done construction of {before:?} and {after:?} in {}",
+        repository.spec
+    );
+    let EditScript {
+        src_tr,
+        dst_tr,
+        actions,
+        matches,
+    } = edit_script(&state, &repository, before, after, query.matches)?;
+
+    let repositories = state.repositories.read().unwrap();
+    let with_spaces_stores = &repositories.processor.main_stores;
+    // paths in the edit script ignore spaces, offsets must not
+    let node = |root: NodeIdentifier, path: &[u16]| {
+        let (path, _) = hyperast::position::path_with_spaces(
+            root,
+            &mut path.iter().copied(),
+            with_spaces_stores,
+        );
+        resolve_node(with_spaces_stores, root, &path)
+    };
+    let label = |l: &LabelIdentifier| with_spaces_stores.label_store.resolve(l).to_string();
+    let diff = GumtreeDiff::from_actions(&actions, |p| node(src_tr, p), |p| node(dst_tr, p), label)
+        .with_matches(
+            matches
+                .iter()
+                .map(|(s, d)| (node(src_tr, s), node(dst_tr, d))),
+        );
+    let resp = match format {
+        Format::Json => (
+            [(http::header::CONTENT_TYPE, "application/json")],
+            serde_json::to_string(&diff).map_err(|e| e.to_string())?,
+        ),
+        Format::Xml => (
+            [(http::header::CONTENT_TYPE, "application/xml")],
+            diff.to_xml(),
+        ),
+    };
+    Ok(resp.into_response())
+}
@@ -9,7 +9,10 @@ use std::{
 
 use dashmap::DashMap;
 use hyper_diff::matchers::mapping_store::VecStore;
-use hyperast_vcs_git::multi_preprocessed::PreProcessedRepositories;
+use hyperast_vcs_git::{
+    git::{Forge, Repo},
+    multi_preprocessed::PreProcessedRepositories,
+};
 
 use axum::body::Bytes;
 use hyperast::store::nodes::legion::NodeIdentifier;
@@ -18,12 +21,19 @@ pub mod app;
 mod changes;
 pub mod cli;
 mod commit;
+pub mod diff;
 pub mod examples;
 mod fetch;
 mod file;
+mod gc;
+mod gumtree;
 mod matching;
+#[cfg(feature = "persist")]
+pub mod persist;
 mod pull_requests;
-mod querying;
+mod refs;
+pub mod querying;
+pub mod scripting_lua;
 mod scriptingv1;
 pub mod smells;
 pub mod track;
@@ -31,6 +41,7 @@ pub mod track;
 mod tsg;
 mod utils;
 mod view;
+pub mod watch;
 mod ws;
 pub use ws::ws_handler;
 
@@ -38,6 +49,8 @@ pub use ws::ws_handler;
 pub struct AppState {
     pub db: DashMap<String, Bytes>,
     pub repositories: RwLock<PreProcessedRepositories>,
+    /// forges addressed by the `:forge` segment of routes, eg. `github` in `/view/github/:user/:name/:commit/`
+    pub forges: std::collections::HashMap<String, Forge>,
     // configs: RwLock<RepoConfigs>,
     mappings: MappingCache,
     mappings_alone: MappingAloneCache,
@@ -54,6 +67,11 @@ pub struct AppState {
     // Multiple shared docs
     doc2: ws::SharedDocs,
     pr_cache: RwLock<std::collections::HashMap<commit::Param, pull_requests::RawPrData>>,
+    #[cfg(feature = "persist")]
+    snapshot_dir: std::sync::OnceLock<std::path::PathBuf>,
+    watchers: DashMap<Repo, hyperast_vcs_git::watcher::Watcher>,
+    /// bounds the limits requested on `/script-lua`
+    pub lua_limits: scripting_lua::Limits,
 }
 
 impl Default for AppState {
@@ -61,6 +79,14 @@ impl Default for AppState {
         Self {
             db: Default::default(),
             repositories: Default::default(),
+            forges: [
+                ("github", Forge::Github),
+                ("gitlab", Forge::Gitlab),
+                ("gitlab-inria", Forge::GitlabInria),
+            ]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
             mappings: Default::default(),
             mappings_alone: Default::default(),
             partial_decomps: Default::default(),
@@ -71,10 +97,24 @@ impl Default for AppState {
             )),
             doc2: Default::default(),
             pr_cache: Default::default(),
+            #[cfg(feature = "persist")]
+            snapshot_dir: Default::default(),
+            watchers: Default::default(),
+            lua_limits: Default::default(),
         }
     }
 }
 
+impl AppState {
+    pub(crate) fn repo(&self, forge: &str, user: &str, name: &str) -> Result<Repo, String> {
+        let forge = self
+            .forges
+            .get(forge)
+            .ok_or_else(|| format!("unknown forge {}", forge))?;
+        forge.clone().try_repo(user, name)
+    }
+}
+
 // pub(crate) type PartialDecompCache = DashMap<NodeIdentifier, DS<NodeIdentifier>>;
 pub(crate) type PartialDecompCache = clashmap::ClashMap<NodeIdentifier, DS<NodeIdentifier>>;
 pub(crate) type MappingAloneCache =
@@ -395,7 +435,7 @@ fn run_scripting(
         .repositories
         .write()
         .unwrap()
-        .register_config_with_prepro(repo_spec.clone(), config, prepro.into());
+        .register_config_with_prepro(repo_spec.clone(), config, prepro.into())?;
     // state
     //     .repositories
     //     .write()
@@ -407,7 +447,7 @@ fn run_scripting(
         .unwrap()
         .get_config(repo_spec)
         .ok_or_else(|| "missing config for repository".to_string())?;
-    let mut repository = repo.fetch();
+    let mut repository = repo.try_fetch()?;
     log::debug!("done cloning {}", repository.spec);
     let commits = state.repositories.write().unwrap().pre_process_with_limit(
         &mut repository,
@@ -517,14 +557,14 @@ fn run_tsg(
         .repositories
         .write()
         .unwrap()
-        .register_config_with_tsg(repo_spec.clone(), config, tsg.into());
+        .register_config_with_tsg(repo_spec.clone(), config, tsg.into())?;
     let repo = state
         .repositories
         .read()
         .unwrap()
         .get_config(repo_spec)
         .ok_or_else(|| "missing config for repository".to_string())?;
-    let mut repository = repo.fetch();
+    let mut repository = repo.try_fetch()?;
     log::debug!("done cloning {}", repository.spec);
     let commits = state.repositories.write().unwrap().pre_process_with_limit(
         &mut repository,
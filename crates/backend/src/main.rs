@@ -8,8 +8,9 @@ use backend::*;
 use axum::Router;
 use backend::{
     app::{
-        commit_metadata_route, fetch_code_route, fetch_git_file, querying_app, scripting_app,
-        smells_app, track_code_route, tsg_app, view_code_route,
+        commit_metadata_route, diff_route, fetch_code_route, fetch_git_file, gc_route,
+        gumtree_diff_route, persist_route, querying_app, refs_route, scripting_app, smells_app,
+        track_code_route, tsg_app, view_code_route, watch_route,
     },
     examples::{example_app, kv_store_app},
 };
@@ -37,7 +38,18 @@ async fn main() {
             log::error!("error logging languages: {}", e)
         };
     }
-    let shared_state = SharedState::default();
+    if let Some(cache_root) = opts.cache_root.clone() {
+        hyperast_vcs_git::git::set_cache_root(cache_root).unwrap();
+    }
+    let mut state = AppState::default();
+    state
+        .forges
+        .extend(opts.forge.iter().map(|x| (x.name.clone(), x.forge.clone())));
+    state.lua_limits = backend::scripting_lua::Limits {
+        memory: opts.lua_memory_limit,
+        instructions: opts.lua_instruction_limit,
+    };
+    let shared_state = SharedState::new(state);
     {
         use hyperast_vcs_git::processing::RepoConfig;
         let mut repos = shared_state.repositories.write().unwrap();
@@ -57,9 +69,32 @@ async fn main() {
             RepoConfig::CppMake,
         );
         opts.repository.iter().for_each(|x| {
-            repos.register_config(x.repo.clone(), x.config);
+            if let Err(err) =
+                repos.register_config_with_filter(x.repo.clone(), x.config, x.filter.clone())
+            {
+                log::error!("error registering {}: {}", x.repo, err);
+            }
         })
     }
+    #[cfg(feature = "persist")]
+    if let Some(dir) = opts.snapshot.clone() {
+        match backend::persist::restore(&shared_state, dir) {
+            Ok(commits) => log::info!("restored {commits} preprocessed commits"),
+            Err(err) => log::error!("error restoring snapshot: {}", err),
+        }
+    }
+    if let Some(secs) = opts.watch_interval {
+        let state = Arc::clone(&shared_state);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(secs));
+            loop {
+                interval.tick().await;
+                let state = Arc::clone(&state);
+                // preprocessing is blocking and long
+                let _ = tokio::task::spawn_blocking(move || backend::watch::poll_all(&state)).await;
+            }
+        });
+    }
     let app = Router::new()
         .fallback(fallback)
         .route("/ws", axum::routing::get(backend::ws_handler))
@@ -73,6 +108,12 @@ async fn main() {
         .merge(view_code_route(Arc::clone(&shared_state)))
         .merge(fetch_code_route(Arc::clone(&shared_state)))
         .merge(commit_metadata_route(Arc::clone(&shared_state)))
+        .merge(gumtree_diff_route(Arc::clone(&shared_state)))
+        .merge(diff_route(Arc::clone(&shared_state)))
+        .merge(persist_route(Arc::clone(&shared_state)))
+        .merge(gc_route(Arc::clone(&shared_state)))
+        .merge(watch_route(Arc::clone(&shared_state)))
+        .merge(refs_route(Arc::clone(&shared_state)))
         .merge(example_app())
         .layer(CorsLayer::permissive()) // WARN unwanted for deployment
         .layer(TraceLayer::new_for_http())
@@ -91,6 +132,13 @@ async fn main() {
     .with_graceful_shutdown(shutdown_signal())
     .await
     .unwrap();
+    #[cfg(feature = "persist")]
+    if opts.snapshot.is_some() {
+        match backend::persist::snapshot(shared_state) {
+            Ok(stats) => log::info!("snapshot on shutdown: {:?}", stats.0),
+            Err(err) => log::error!("error while snapshotting: {}", err),
+        }
+    }
 }
 /// axum handler for any request that fails to match the router routes.
 /// This implementation returns HTTP status code Not Found (404).
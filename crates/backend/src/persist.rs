@@ -0,0 +1,44 @@
+use std::path::PathBuf;
+
+use axum::Json;
+use serde::Serialize;
+
+use crate::SharedState;
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Snapshotted {
+    /// nodes added to the snapshot
+    nodes: usize,
+    /// labels added to the snapshot
+    labels: usize,
+    /// size of the new segment
+    bytes: u64,
+    /// in seconds
+    time: f64,
+}
+
+/// Restores the repositories persisted in `dir`, and use `dir` for later snapshots.
+pub fn restore(state: &SharedState, dir: PathBuf) -> Result<usize, String> {
+    let mut repos = state.repositories.write().unwrap();
+    let restored = repos.restore(&dir).map_err(|err| err.to_string())?;
+    state
+        .snapshot_dir
+        .set(dir)
+        .map_err(|_| "snapshot directory already set".to_string())?;
+    Ok(restored)
+}
+
+pub fn snapshot(state: SharedState) -> Result<Json<Snapshotted>, String> {
+    let Some(dir) = state.snapshot_dir.get() else {
+        return Err("no snapshot directory, start the server with --snapshot".to_string());
+    };
+    let now = std::time::Instant::now();
+    let mut repos = state.repositories.write().unwrap();
+    let stats = repos.snapshot(dir).map_err(|err| err.to_string())?;
+    Ok(Json(Snapshotted {
+        nodes: stats.nodes,
+        labels: stats.labels,
+        bytes: stats.bytes,
+        time: now.elapsed().as_secs_f64(),
+    }))
+}
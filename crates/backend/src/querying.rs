@@ -16,6 +16,7 @@ use std::time::Instant;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Param {
+    forge: String,
     user: String,
     name: String,
     commit: String,
@@ -24,11 +25,14 @@ pub struct Param {
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Content {
     pub language: String,
+    /// ignored with [`Content::queries`]
+    #[serde(default)]
     pub query: String,
     pub precomp: Option<String>,
     pub commits: usize,
     // TODO disable the incriminated pattern for subsequent matches
-    /// checked per individual match
+    /// checked per individual match,
+    /// counted per pattern, or per commit across patterns with `locations`
     /// if triggered on first search (ie. first commit searched) it return directly
     /// if triggered later, divide the numer of commits remaining to analyze by 2 each time (ie. `commits`` field)
     #[serde(default = "default_max_matches")]
@@ -36,13 +40,25 @@ pub struct Content {
     /// checked each match (in milli seconds)
     #[serde(default = "default_timeout")]
     pub timeout: u64,
+    /// also return the location and captured nodes of each match,
+    /// paginated by `max_matches` per commit
+    #[serde(default)]
+    pub locations: bool,
+    /// number of matches to skip per commit, i.e. the `next_offset` of the previous page
+    #[serde(default)]
+    pub offset: usize,
+    /// a query per language, e.g. `{"Java": .., "Xml": ..}`, each one only matching the files of its language,
+    /// the processing of the repository is still configured by `language`.
+    /// Matches are only counted, i.e. [`Content::locations`] is ignored
+    #[serde(default)]
+    pub queries: std::collections::BTreeMap<String, String>,
 }
 
-fn default_max_matches() -> u64 {
+pub fn default_max_matches() -> u64 {
     500
 }
 
-fn default_timeout() -> u64 {
+pub fn default_timeout() -> u64 {
     1000
 }
 
@@ -50,6 +66,7 @@ fn default_timeout() -> u64 {
 pub enum QueryingError {
     ProcessingError(String),
     MissingLanguage(String),
+    UnknownForge(String),
     ParsingError(String),
     MatchingErrOnFirst(MatchingError<ComputeResultIdentified>),
     MatchingError(MatchingError<ComputeResult>),
@@ -61,7 +78,7 @@ pub enum MatchingError<T> {
     MaxMatches(T),
 }
 impl<T> MatchingError<T> {
-    fn map<U>(self, f: impl Fn(T) -> U) -> MatchingError<U> {
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> MatchingError<U> {
         match self {
             MatchingError::TimeOut(x) => MatchingError::TimeOut(f(x)),
             MatchingError::MaxMatches(x) => MatchingError::MaxMatches(f(x)),
@@ -87,6 +104,13 @@ pub struct ComputeResultIdentified {
 pub struct ComputeResult {
     pub compute_time: f64,
     pub result: Vec<u64>,
+    /// with [`Content::locations`]
+    #[serde(flatten)]
+    pub page: Option<MatchPage>,
+    /// with [`Content::queries`], the counts of each language,
+    /// also concatenated in `result` following the order of the languages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub languages: Option<std::collections::BTreeMap<&'static str, Vec<u64>>>,
 }
 impl ComputeResult {
     fn with(self, commit_oid: &Oid) -> ComputeResultIdentified {
@@ -105,7 +129,12 @@ pub fn simple(
     path: Param,
 ) -> Result<ComputeResults, QueryingError> {
     let now = Instant::now();
-    let Param { user, name, commit } = path.clone();
+    let Param {
+        forge,
+        user,
+        name,
+        commit,
+    } = path.clone();
     let mut additional = commit.split("/");
     let commit = additional.next().unwrap();
     let Content {
@@ -115,42 +144,16 @@ pub fn simple(
         commits,
         max_matches,
         timeout,
+        locations,
+        offset,
+        queries,
     } = query;
     let timeout = std::time::Duration::from_millis(timeout);
     let mut proc_commit_limit = commits;
-    let config = if language == "Java" {
-        hyperast_vcs_git::processing::RepoConfig::JavaMaven
-    } else if language == "Cpp" {
-        hyperast_vcs_git::processing::RepoConfig::CppMake
-    } else {
-        hyperast_vcs_git::processing::RepoConfig::Any
-    };
     let lang = &language;
     let language = hyperast_vcs_git::resolve_language(&language)
         .ok_or_else(|| QueryingError::MissingLanguage(language.to_string()))?;
-    let repo_spec = hyperast_vcs_git::git::Forge::Github.repo(user, name);
-    let repo = state
-        .repositories
-        .read()
-        .unwrap()
-        .get_config(repo_spec.clone());
-    let repo = match repo {
-        Some(repo) => repo,
-        None => {
-            let configs = &mut state.repositories.write().unwrap();
-            if let Some(precomp) = precomp {
-                configs.register_config_with_prequeries(repo_spec.clone(), config, &[&precomp]);
-            } else {
-                // configs.register_config_alt_lang(repo_spec.clone(), config, "C");
-                configs.register_config(repo_spec.clone(), config);
-            }
-            log::error!("missing config for {}", repo_spec);
-            configs.get_config(repo_spec.clone()).unwrap()
-        }
-    };
-
-    let mut repo = repo.fetch();
-    log::warn!("done cloning {}", &repo.spec);
+    let mut repo = configured_repo(&state, &forge, &user, &name, lang, precomp.as_deref())?;
     let commits = crate::utils::handle_pre_processing(&state, &mut repo, "", &commit, commits)
         .map_err(|x| QueryingError::ProcessingError(x.to_string()))?;
     log::info!("done construction of {commits:?} in  {}", repo.spec);
@@ -166,12 +169,18 @@ pub fn simple(
         })
         .flatten();
 
-    let query = if let Some(precomputeds) = precomputeds {
-        hyperast_tsquery::Query::with_precomputed(&query, language, precomputeds).map(|x| x.1)
+    let queries = pre_queries(&state, &queries, repo.config)?;
+    let query = if !queries.is_empty() {
+        None
+    } else if let Some(precomputeds) = precomputeds {
+        Some(hyperast_tsquery::Query::with_precomputed(&query, language, precomputeds).map(|x| x.1))
     } else {
-        hyperast_tsquery::Query::new(&query, language)
+        Some(hyperast_tsquery::Query::new(&query, language))
     }
-    .map_err(|e| QueryingError::ParsingError(e.to_string()))?;
+    .transpose()
+    .map_err(|e| QueryingError::ParsingError(e.to_string()))?
+    .map(with_predicates)
+    .transpose()?;
 
     log::info!("done query construction");
     let prepare_time = now.elapsed().as_secs_f64();
@@ -192,7 +201,12 @@ pub fn simple(
         let commit = repositories.get_commit(&repo.config, commit_oid).unwrap();
         let code = commit.ast_root;
         let stores = &repositories.processor.main_stores;
-        let result = simple_aux(stores, code, &query, timeout, max_matches);
+        let result = match &query {
+            Some(query) => {
+                compute_aux(stores, code, query, timeout, max_matches, locations, offset)
+            }
+            None => multi_aux(stores, code, &queries, timeout, max_matches),
+        };
         let result = match result {
             Ok(inner) => Ok(inner.with(commit_oid)),
             Err(err) if results.is_empty() => {
@@ -247,20 +261,27 @@ pub fn streamed(mut state: SharedState, path: Param, content: Content) -> axum::
 
     headers.insert("commits", commits.len().into());
 
-    let pre_query = pre_query(&mut state, &path, &content, repo.config);
+    let pre_query = if content.queries.is_empty() {
+        pre_query(&mut state, &path, &content, repo.config).map(Some)
+    } else {
+        Ok(None)
+    };
+    let pre_queries = pre_queries(&state, &content.queries, repo.config);
     let Content {
         commits: mut proc_commit_limit,
         max_matches,
         timeout,
+        locations,
+        offset,
         ..
     } = content.clone();
     let timeout = std::time::Duration::from_millis(timeout);
     log::info!("done query construction");
     let prepare_time = now.elapsed().as_secs_f64();
     headers.insert("prepare_time", prepare_time.to_string().try_into().unwrap());
-    let query = match pre_query {
-        Ok(x) => x,
-        Err(err) => {
+    let (query, queries) = match (pre_query, pre_queries) {
+        (Ok(query), Ok(queries)) => (query, queries),
+        (Err(err), _) | (_, Err(err)) => {
             headers.insert(
                 "error_query",
                 serde_json::to_string(&err).unwrap().try_into().unwrap(),
@@ -269,16 +290,23 @@ pub fn streamed(mut state: SharedState, path: Param, content: Content) -> axum::
         }
     };
 
+    let table_head: Vec<_> = match &query {
+        Some(query) => (0..query.enabled_pattern_count())
+            .map(|x| x.to_string())
+            .collect(),
+        None => queries
+            .iter()
+            .flat_map(|q| {
+                (0..q.query.enabled_pattern_count()).map(|x| format!("{}:{}", q.language, x))
+            })
+            .collect(),
+    };
     headers.insert(
         "table_head",
-        serde_json::to_string(
-            &(0..query.enabled_pattern_count())
-                .map(|x| x.to_string())
-                .collect::<Vec<_>>(),
-        )
-        .unwrap()
-        .try_into()
-        .unwrap(),
+        serde_json::to_string(&table_head)
+            .unwrap()
+            .try_into()
+            .unwrap(),
     );
 
     let it = commits
@@ -298,7 +326,12 @@ pub fn streamed(mut state: SharedState, path: Param, content: Content) -> axum::
             let commit = repositories.get_commit(&repo.config, &commit_oid).unwrap();
             let code = commit.ast_root;
             let stores = &repositories.processor.main_stores;
-            let result = simple_aux(stores, code, &query, timeout, max_matches);
+            let result = match &query {
+                Some(query) => {
+                    compute_aux(stores, code, query, timeout, max_matches, locations, offset)
+                }
+                None => multi_aux(stores, code, &queries, timeout, max_matches),
+            };
             let result = match result {
                 Ok(inner) => Ok(inner.with(&commit_oid)),
                 Err(err) => {
@@ -339,12 +372,67 @@ pub fn streamed(mut state: SharedState, path: Param, content: Content) -> axum::
         .into_response()
 }
 
+/// The processing of the repositories queried in `language`, when they are not configured yet
+pub fn repo_config(language: &str) -> hyperast_vcs_git::processing::RepoConfig {
+    use hyperast_vcs_git::processing::RepoConfig;
+    match language {
+        "Java" => RepoConfig::JavaMaven,
+        "Cpp" | "C" => RepoConfig::CppMake,
+        "Python" => RepoConfig::PythonPip,
+        "Ts" | "TypeScript" => RepoConfig::TsNpm,
+        _ => RepoConfig::Any,
+    }
+}
+
+/// Fetches the repository, first configuring it with [`repo_config`] if needed
+fn configured_repo(
+    state: &SharedState,
+    forge: &str,
+    user: &str,
+    name: &str,
+    language: &str,
+    precomp: Option<&str>,
+) -> Result<hyperast_vcs_git::processing::ConfiguredRepo2, QueryingError> {
+    let repo_spec = state
+        .repo(forge, user, name)
+        .map_err(QueryingError::UnknownForge)?;
+    let repo = state
+        .repositories
+        .read()
+        .unwrap()
+        .get_config(repo_spec.clone());
+    let repo = match repo {
+        Some(repo) => repo,
+        None => {
+            let configs = &mut state.repositories.write().unwrap();
+            let config = repo_config(language);
+            if let Some(precomp) = precomp {
+                configs.register_config_with_prequeries(repo_spec.clone(), config, &[precomp]);
+            } else {
+                configs.register_config(repo_spec.clone(), config);
+            }
+            log::error!("missing config for {}", repo_spec);
+            configs.get_config(repo_spec.clone()).unwrap()
+        }
+    };
+    let repo = repo
+        .try_fetch()
+        .map_err(|e| QueryingError::ProcessingError(e.to_string()))?;
+    log::info!("done cloning {}", &repo.spec);
+    Ok(repo)
+}
+
 fn pre_repo(
     state: &mut SharedState,
     path: &Param,
     content: &Content,
 ) -> Result<(hyperast_vcs_git::processing::ConfiguredRepo2, Vec<Oid>), Box<dyn std::error::Error>> {
-    let Param { user, name, commit } = path.clone();
+    let Param {
+        forge,
+        user,
+        name,
+        commit,
+    } = path.clone();
     let mut additional = commit.split("/");
     let commit = additional.next().unwrap();
     let Content {
@@ -354,15 +442,12 @@ fn pre_repo(
         commits,
         max_matches: _,
         timeout: _,
+        locations: _,
+        offset: _,
+        queries: _,
     } = content.clone();
-    let config = if language == "Java" {
-        hyperast_vcs_git::processing::RepoConfig::JavaMaven
-    } else if language == "Cpp" {
-        hyperast_vcs_git::processing::RepoConfig::CppMake
-    } else {
-        hyperast_vcs_git::processing::RepoConfig::Any
-    };
-    let repo_spec = hyperast_vcs_git::git::Forge::Github.repo(user, name);
+    let config = repo_config(&language);
+    let repo_spec = state.repo(&forge, &user, &name)?;
     let repo = state
         .repositories
         .read()
@@ -381,12 +466,12 @@ fn pre_repo(
             configs.get_config(repo_spec.clone()).unwrap()
         }
     };
-    let repo = repo.fetch();
+    let repo = repo.try_fetch()?;
     log::warn!("done cloning {}", &repo.spec);
     let afters = [commit].into_iter().chain(additional.into_iter());
     let rw = crate::utils::walk_commits_multi(&repo, afters)?.take(commits);
     assert!(state.repositories.try_write().is_ok());
-    let commits = crate::utils::handle_pre_processing_aux(state, &repo, rw);
+    let commits = crate::utils::handle_pre_processing_aux(state, &repo, rw)?;
     log::info!("done construction of {commits:?} in  {}", repo.spec);
 
     Ok((repo, commits))
@@ -398,7 +483,9 @@ fn pre_query(
     content: &Content,
     repo_config: hyperast_vcs_git::processing::ParametrizedCommitProcessorHandle,
 ) -> Result<hyperast_tsquery::Query, QueryingError> {
-    let Param { user, name, commit } = path.clone();
+    let Param {
+        user, name, commit, ..
+    } = path.clone();
     let mut additional = commit.split("/");
     let commit = additional.next().unwrap();
     let Content {
@@ -408,14 +495,10 @@ fn pre_query(
         commits: _,
         max_matches: _,
         timeout: _,
+        locations: _,
+        offset: _,
+        queries: _,
     } = &content;
-    let config = if language == "Java" {
-        hyperast_vcs_git::processing::RepoConfig::JavaMaven
-    } else if language == "Cpp" {
-        hyperast_vcs_git::processing::RepoConfig::CppMake
-    } else {
-        hyperast_vcs_git::processing::RepoConfig::Any
-    };
     let lang = &language;
     let language: tree_sitter::Language = hyperast_vcs_git::resolve_language(&language)
         .ok_or_else(|| QueryingError::MissingLanguage(language.to_string()))?;
@@ -434,9 +517,143 @@ fn pre_query(
         hyperast_tsquery::Query::new(&query, language)
     }
     .map_err(|e| QueryingError::ParsingError(e.to_string()))?;
+    with_predicates(query)
+}
+
+/// A query only matching the files of its language, see [`Content::queries`]
+pub struct LanguageQuery {
+    /// as given by [`hyperast_vcs_git::language_name`]
+    pub language: &'static str,
+    pub query: hyperast_tsquery::Query,
+}
+
+/// The queries of [`Content::queries`], ordered by language
+fn pre_queries(
+    state: &SharedState,
+    queries: &std::collections::BTreeMap<String, String>,
+    repo_config: hyperast_vcs_git::processing::ParametrizedCommitProcessorHandle,
+) -> Result<Vec<LanguageQuery>, QueryingError> {
+    let mut r: Vec<LanguageQuery> = vec![];
+    for (language, query) in queries {
+        let name = hyperast_vcs_git::language_name(language)
+            .ok_or_else(|| QueryingError::MissingLanguage(language.to_string()))?;
+        let ts_language = hyperast_vcs_git::resolve_language(name)
+            .ok_or_else(|| QueryingError::MissingLanguage(language.to_string()))?;
+        if r.iter().any(|x| x.language == name) {
+            return Err(QueryingError::ParsingError(format!(
+                "more than one query for {}",
+                name
+            )));
+        }
+        let precomputeds = INCREMENTAL_QUERIES
+            .then(|| {
+                state
+                    .repositories
+                    .read()
+                    .unwrap()
+                    .get_precomp_query(repo_config, name)
+            })
+            .flatten();
+        let query = if let Some(precomputeds) = precomputeds {
+            hyperast_tsquery::Query::with_precomputed(query, ts_language, precomputeds).map(|x| x.1)
+        } else {
+            hyperast_tsquery::Query::new(query, ts_language)
+        }
+        .map_err(|e| QueryingError::ParsingError(format!("{}: {}", name, e)))?;
+        let query = with_predicates(query)?;
+        r.push(LanguageQuery {
+            language: name,
+            query,
+        });
+    }
+    r.sort_by_key(|x| x.language);
+    Ok(r)
+}
+
+/// Installs the custom predicates usable in the queries of the routes,
+/// e.g. `(#derived-gt? @m "mcc" "10")` on the data computed by the prepro script of the repository config
+fn with_predicates(
+    mut query: hyperast_tsquery::Query,
+) -> Result<hyperast_tsquery::Query, QueryingError> {
+    use hyperast_tsquery::predicate::{DerivedCmp, MetricCmp};
+    use std::cmp::Ordering::{Equal, Greater, Less};
+    for (name, ord) in [("eq?", Equal), ("gt?", Greater), ("lt?", Less)] {
+        let metric = query.register_predicate(&format!("metric-{name}"), MetricCmp(ord));
+        metric.map_err(QueryingError::ParsingError)?;
+        let derived = query.register_predicate(&format!("derived-{name}"), DerivedCmp(ord));
+        derived.map_err(QueryingError::ParsingError)?;
+    }
     Ok(query)
 }
 
+/// Counts the matches of each query on the files of its language, in a single traversal of the directories.
+///
+/// The matches of a file are computed once and reused for its other occurrences.
+fn multi_aux(
+    stores: &hyperast::store::SimpleStores<hyperast_vcs_git::TStore>,
+    code: NodeIdentifier,
+    queries: &[LanguageQuery],
+    timeout: std::time::Duration,
+    max_matches: u64,
+) -> Result<ComputeResult, MatchingError<ComputeResult>> {
+    use hyperast::types::{HyperType, LangRef};
+    let now = Instant::now();
+    let mut counts: Vec<Vec<u64>> = queries
+        .iter()
+        .map(|q| vec![0; q.query.enabled_pattern_count()])
+        .collect();
+    let result = |counts: Vec<Vec<u64>>| {
+        let compute_time = now.elapsed().as_secs_f64();
+        ComputeResult {
+            result: counts.concat(),
+            compute_time,
+            page: None,
+            languages: Some(queries.iter().map(|q| q.language).zip(counts).collect()),
+        }
+    };
+    let mut files: std::collections::HashMap<NodeIdentifier, Option<(usize, Vec<u64>)>> =
+        Default::default();
+    let mut stack = vec![code];
+    while let Some(curr) = stack.pop() {
+        if now.elapsed() >= timeout {
+            return Err(MatchingError::TimeOut(result(counts)));
+        }
+        let n = stores.resolve(&curr);
+        if stores.resolve_type(&curr).is_directory() {
+            if let Some(cs) = n.children() {
+                stack.extend(cs.iter_children());
+            }
+            continue;
+        }
+        if !files.contains_key(&curr) {
+            let lang = stores.resolve_lang(&n);
+            let i = hyperast_vcs_git::node_language(lang.name())
+                .and_then(|name| queries.iter().position(|q| q.language == name));
+            let file = i.map(|i| {
+                let query = &queries[i].query;
+                let pos = hyperast::position::StructuralPosition::new(curr);
+                let cursor = hyperast_tsquery::hyperast_cursor::TreeCursor::new(stores, pos);
+                let mut file = vec![0; query.enabled_pattern_count()];
+                for m in query.matches(cursor) {
+                    let j = query.enabled_pattern_index(m.pattern_index).unwrap();
+                    file[j as usize] += 1;
+                }
+                (i, file)
+            });
+            files.insert(curr, file);
+        }
+        if let Some((i, file)) = &files[&curr] {
+            for (c, x) in counts[*i].iter_mut().zip(file) {
+                *c += x;
+            }
+            if counts[*i].iter().any(|c| *c > max_matches) {
+                return Err(MatchingError::MaxMatches(result(counts)));
+            }
+        }
+    }
+    Ok(result(counts))
+}
+
 fn simple_aux(
     stores: &hyperast::store::SimpleStores<hyperast_vcs_git::TStore>,
     code: NodeIdentifier,
@@ -459,6 +676,8 @@ fn simple_aux(
             return Err(MatchingError::TimeOut(ComputeResult {
                 result,
                 compute_time,
+                page: None,
+                languages: None,
             }));
         } else if result[i as usize] > max_matches {
             // TODO disable the pattern, return the new query
@@ -466,6 +685,8 @@ fn simple_aux(
             return Err(MatchingError::MaxMatches(ComputeResult {
                 result,
                 compute_time,
+                page: None,
+                languages: None,
             }));
         }
 
@@ -486,9 +707,172 @@ fn simple_aux(
     Ok(ComputeResult {
         result,
         compute_time,
+        page: None,
+        languages: None,
+    })
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct MatchPage {
+    pub locations: Vec<MatchLocation>,
+    /// offset of the next page, matching stopped there so the counts are partial
+    pub next_offset: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct MatchLocation {
+    /// index among enabled patterns
+    pub pattern: usize,
+    pub file: String,
+    pub start: usize,
+    pub end: usize,
+    /// from 0, like the columns which are in bytes
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    pub captures: Vec<CapturedNode>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CapturedNode {
+    pub name: &'static str,
+    /// can be viewed with /view/:id
+    pub id: u64,
+}
+
+/// Same timeout as [`simple_aux`], also locating each match by its `capture`,
+/// or by its first capture when `capture` is None.
+///
+/// Matches are paginated, the first `offset` matches are skipped,
+/// and the matching stops after locating `max_matches` matches.
+pub fn located_aux(
+    stores: &hyperast::store::SimpleStores<hyperast_vcs_git::TStore>,
+    code: NodeIdentifier,
+    query: &hyperast_tsquery::Query,
+    capture: Option<hyperast_tsquery::CaptureId>,
+    timeout: std::time::Duration,
+    max_matches: u64,
+    offset: usize,
+) -> Result<ComputeResult, MatchingError<ComputeResult>> {
+    use hyperast::position::TreePath;
+    let pos = hyperast::position::StructuralPosition::new(code);
+    let cursor = hyperast_tsquery::hyperast_cursor::TreeCursor::new(stores, pos);
+    let qcursor = query.matches(cursor);
+    let now = Instant::now();
+    let mut result = vec![0; query.enabled_pattern_count()];
+    let mut lines = FileLines::default();
+    let mut page = MatchPage {
+        locations: vec![],
+        next_offset: None,
+    };
+    for (n, m) in qcursor.enumerate() {
+        if page.locations.len() as u64 >= max_matches {
+            page.next_offset = Some(n);
+            break;
+        }
+        let i = m.pattern_index;
+        let i = query.enabled_pattern_index(i).unwrap();
+        result[i as usize] += 1;
+        let node = match capture {
+            Some(capture) => m.nodes_for_capture_index(capture).next(),
+            None => m.captures.iter().next().map(|c| &c.node),
+        };
+        if let Some(node) = node.filter(|_| n >= offset) {
+            let position = node.pos.make_position(stores);
+            let range = position.range();
+            let (start_line, start_column, end_line, end_column) =
+                lines.line_columns(stores, &node.pos, range.clone());
+            let captures = m.captures.iter().map(|c| CapturedNode {
+                name: query.capture_name(c.index),
+                id: unsafe { std::mem::transmute(*c.node.pos.node().unwrap()) },
+            });
+            page.locations.push(MatchLocation {
+                pattern: i as usize,
+                file: position.file().to_string_lossy().to_string(),
+                start: range.start,
+                end: range.end,
+                start_line,
+                start_column,
+                end_line,
+                end_column,
+                captures: captures.collect(),
+            });
+        }
+        if now.elapsed() >= timeout {
+            let compute_time = now.elapsed().as_secs_f64();
+            return Err(MatchingError::TimeOut(ComputeResult {
+                result,
+                compute_time,
+                page: Some(page),
+                languages: None,
+            }));
+        }
+    }
+    let compute_time = now.elapsed().as_secs_f64();
+    Ok(ComputeResult {
+        result,
+        compute_time,
+        page: Some(page),
+        languages: None,
     })
 }
 
+/// The offsets of the lines of the files containing matches
+#[derive(Default)]
+struct FileLines(std::collections::HashMap<NodeIdentifier, Vec<usize>>);
+
+impl FileLines {
+    fn line_columns(
+        &mut self,
+        stores: &hyperast::store::SimpleStores<hyperast_vcs_git::TStore>,
+        pos: &hyperast::position::StructuralPosition<NodeIdentifier, u16>,
+        range: std::ops::Range<usize>,
+    ) -> (usize, usize, usize, usize) {
+        use hyperast::position::{TreePath, TreePathMut};
+        use hyperast::types::HyperType;
+        let mut pos = pos.clone();
+        let file = loop {
+            let Some(n) = pos.node().copied() else {
+                return (0, range.start, 0, range.end);
+            };
+            if stores.resolve_type(&n).is_file() {
+                break n;
+            }
+            pos.pop();
+        };
+        let starts = self.0.entry(file).or_insert_with(|| {
+            let text = hyperast::nodes::TextSerializer::new(stores, file).to_string();
+            let ends = text.match_indices('\n').map(|(i, _)| i + 1);
+            std::iter::once(0).chain(ends).collect()
+        });
+        let line_column = |offset: usize| {
+            let line = starts.partition_point(|x| *x <= offset) - 1;
+            (line, offset - starts[line])
+        };
+        let (start_line, start_column) = line_column(range.start);
+        let (end_line, end_column) = line_column(range.end);
+        (start_line, start_column, end_line, end_column)
+    }
+}
+
+/// Counts the matches, also locating them with [`Content::locations`]
+pub fn compute_aux(
+    stores: &hyperast::store::SimpleStores<hyperast_vcs_git::TStore>,
+    code: NodeIdentifier,
+    query: &hyperast_tsquery::Query,
+    timeout: std::time::Duration,
+    max_matches: u64,
+    locations: bool,
+    offset: usize,
+) -> Result<ComputeResult, MatchingError<ComputeResult>> {
+    if locations {
+        located_aux(stores, code, query, None, timeout, max_matches, offset)
+    } else {
+        simple_aux(stores, code, query, timeout, max_matches)
+    }
+}
+
 #[derive(Serialize)]
 pub struct ComputeResultsDifferential {
     pub prepare_time: f64,
@@ -496,6 +880,7 @@ pub struct ComputeResultsDifferential {
 }
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ParamDifferential {
+    forge: String,
     user: String,
     name: String,
     commit: String,
@@ -509,6 +894,7 @@ pub fn differential(
 ) -> Result<Json<ComputeResultsDifferential>, QueryingError> {
     let now = Instant::now();
     let ParamDifferential {
+        forge,
         user,
         name,
         commit,
@@ -523,37 +909,10 @@ pub fn differential(
         ..
     } = query;
     let timeout = std::time::Duration::from_millis(timeout);
-    let config = if language == "Java" {
-        hyperast_vcs_git::processing::RepoConfig::JavaMaven
-    } else if language == "Cpp" {
-        hyperast_vcs_git::processing::RepoConfig::CppMake
-    } else {
-        hyperast_vcs_git::processing::RepoConfig::Any
-    };
     let lang = &language;
     let language: tree_sitter::Language = hyperast_vcs_git::resolve_language(&language)
         .ok_or_else(|| QueryingError::MissingLanguage(language.to_string()))?;
-    let repo_spec = hyperast_vcs_git::git::Forge::Github.repo(user, name);
-    let repo = state
-        .repositories
-        .read()
-        .unwrap()
-        .get_config(repo_spec.clone());
-    let repo = match repo {
-        Some(repo) => repo,
-        None => {
-            let configs = &mut state.repositories.write().unwrap();
-            if let Some(precomp) = precomp {
-                configs.register_config_with_prequeries(repo_spec.clone(), config, &[&precomp]);
-            } else {
-                configs.register_config(repo_spec.clone(), config);
-            }
-            log::error!("missing config for {}", repo_spec);
-            configs.get_config(repo_spec.clone()).unwrap()
-        }
-    };
-    let mut repo = repo.fetch();
-    log::info!("done cloning {}", &repo.spec);
+    let mut repo = configured_repo(&state, &forge, &user, &name, lang, precomp.as_deref())?;
     let commit = crate::utils::handle_pre_processing(&state, &mut repo, "", &commit, 1)
         .map_err(|x| QueryingError::ProcessingError(x.to_string()))?[0];
     let baseline = crate::utils::handle_pre_processing(&state, &mut repo, "", &baseline, 1)
@@ -576,7 +935,8 @@ pub fn differential(
     } else {
         hyperast_tsquery::Query::new(&query, language)
     }
-    .map_err(|e| QueryingError::ParsingError(e.to_string()))?
+    .map_err(|e| QueryingError::ParsingError(e.to_string()))
+    .and_then(with_predicates)?
     .with_one_pattern_enabled(0)
     .map_err(|_| {
         QueryingError::ParsingError("exactly one enabled pattern is expected".to_string())
@@ -819,3 +1179,343 @@ fn differential_aux(
     let compute_time = now.elapsed().as_secs_f64();
     Ok(results)
 }
+
+/// How a match of the baseline relates to a match of the commit, given the mappings of their trees
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Evolution {
+    /// no mapped match in the baseline
+    Added,
+    /// no mapped match in the commit
+    Removed,
+    /// mapped to a match of the same pattern under a parent that is not mapped to its own parent
+    Moved,
+    /// mapped to a match of the same pattern, and so are their parents
+    Unchanged,
+}
+
+#[derive(Serialize)]
+pub struct ClassifiedMatch {
+    pub pattern: usize,
+    pub evolution: Evolution,
+    /// `None` when [`Evolution::Added`]
+    pub baseline: Option<crate::smells::CodeRange>,
+    /// `None` when [`Evolution::Removed`]
+    pub commit: Option<crate::smells::CodeRange>,
+}
+
+#[derive(Serialize)]
+pub struct ComputeResultsClassified {
+    pub prepare_time: f64,
+    pub matching_time: f64,
+    pub mapping_time: f64,
+    pub matches: Vec<ClassifiedMatch>,
+}
+
+/// Classifies every match of `query` on `path.commit` against the matches on `path.baseline`.
+///
+/// A match is located by its `@root` capture, or else by its first capture,
+/// then it is paired through the full mappings of the diff between the two commits.
+pub fn differential_matches(
+    query: Content,
+    state: SharedState,
+    path: ParamDifferential,
+) -> Result<Json<ComputeResultsClassified>, QueryingError> {
+    let now = Instant::now();
+    let ParamDifferential {
+        forge,
+        user,
+        name,
+        commit,
+        baseline,
+    } = path;
+    let Content {
+        language,
+        query,
+        precomp,
+        max_matches,
+        timeout,
+        ..
+    } = query;
+    let timeout = std::time::Duration::from_millis(timeout);
+    let lang = &language;
+    let language: tree_sitter::Language = hyperast_vcs_git::resolve_language(&language)
+        .ok_or_else(|| QueryingError::MissingLanguage(language.to_string()))?;
+    let mut repo = configured_repo(&state, &forge, &user, &name, lang, precomp.as_deref())?;
+    let commit = crate::utils::handle_pre_processing(&state, &mut repo, "", &commit, 1)
+        .map_err(|x| QueryingError::ProcessingError(x.to_string()))?[0];
+    let baseline = crate::utils::handle_pre_processing(&state, &mut repo, "", &baseline, 1)
+        .map_err(|x| QueryingError::ProcessingError(x.to_string()))?[0];
+    log::info!(
+        "done construction of {commit:?} and {baseline:?} in  {}",
+        repo.spec
+    );
+
+    let precomputeds = INCREMENTAL_QUERIES
+        .then(|| {
+            state
+                .repositories
+                .read()
+                .unwrap()
+                .get_precomp_query(repo.config, lang)
+        })
+        .flatten();
+    let query = if let Some(precomputeds) = precomputeds {
+        hyperast_tsquery::Query::with_precomputed(&query, language, precomputeds).map(|x| x.1)
+    } else {
+        hyperast_tsquery::Query::new(&query, language)
+    }
+    .map_err(|e| QueryingError::ParsingError(e.to_string()))
+    .and_then(with_predicates)?;
+    log::info!("done query construction");
+    let prepare_time = now.elapsed().as_secs_f64();
+
+    let now = Instant::now();
+    let repositories = state.repositories.read().unwrap();
+    let baseline_tr = repositories
+        .get_commit(&repo.config, &baseline)
+        .unwrap()
+        .ast_root;
+    let commit_tr = repositories
+        .get_commit(&repo.config, &commit)
+        .unwrap()
+        .ast_root;
+    let stores = &repositories.processor.main_stores;
+    let baseline_matches = matched_nodes_aux(stores, baseline_tr, &query, timeout, max_matches)
+        .map_err(QueryingError::MatchingError)?;
+    let commit_matches = matched_nodes_aux(stores, commit_tr, &query, timeout, max_matches)
+        .map_err(QueryingError::MatchingError)?;
+    let matching_time = now.elapsed().as_secs_f64();
+    log::info!(
+        "lengths results/baseline_results: {}/{}",
+        commit_matches.len(),
+        baseline_matches.len()
+    );
+
+    let now = Instant::now();
+    let globalize_at = |oid, p: &hyperast::position::StructuralPosition<NodeIdentifier, u16>| {
+        globalize(
+            &repo,
+            oid,
+            (p.make_position(stores), p.iter_offsets().collect()),
+        )
+    };
+    if baseline_tr == commit_tr {
+        let matches = baseline_matches
+            .iter()
+            .zip(&commit_matches)
+            .map(|((pattern, b), (_, c))| ClassifiedMatch {
+                pattern: *pattern,
+                evolution: Evolution::Unchanged,
+                baseline: Some(globalize_at(baseline, b)),
+                commit: Some(globalize_at(commit, c)),
+            })
+            .collect();
+        return Ok(Json(ComputeResultsClassified {
+            prepare_time,
+            matching_time,
+            mapping_time: 0.0,
+            matches,
+        }));
+    }
+
+    let hyperast = &hyperast_vcs_git::no_space::as_nospaces2(stores);
+    let binding = crate::utils::bind_tree_pair(&state.partial_decomps, &baseline_tr, &commit_tr);
+    let mapped = match state.mappings_alone.entry((baseline_tr, commit_tr)) {
+        dashmap::mapref::entry::Entry::Occupied(entry) => entry.into_ref().downgrade(),
+        dashmap::mapref::entry::Entry::Vacant(entry) => {
+            use hyper_diff::matchers::mapping_store::MappingStore;
+            let mut locked = binding.lock();
+            let (src_arena, dst_arena) = locked.as_mut(hyperast);
+            let mut mapper = hyper_diff::matchers::Mapper {
+                hyperast,
+                mapping: hyper_diff::matchers::Mapping {
+                    src_arena: Decompressible {
+                        hyperast,
+                        decomp: src_arena,
+                    },
+                    dst_arena: Decompressible {
+                        hyperast,
+                        decomp: dst_arena,
+                    },
+                    mappings: hyper_diff::matchers::mapping_store::VecStore::default(),
+                },
+            };
+            mapper.mapping.mappings.topit(
+                mapper.mapping.src_arena.len(),
+                mapper.mapping.dst_arena.len(),
+            );
+            crate::matching::full2(&mut mapper);
+            entry
+                .insert((crate::MappingStage::Bottomup, mapper.mapping.mappings))
+                .downgrade()
+        }
+    };
+    let mappings = &mapped.1;
+    let mut locked = binding.lock();
+    let (src_arena, dst_arena) = locked.as_mut(hyperast);
+    let mut src_arena = Decompressible {
+        hyperast,
+        decomp: src_arena,
+    };
+    let mut dst_arena = Decompressible {
+        hyperast,
+        decomp: dst_arena,
+    };
+
+    // the decompressed node of each match along with its decompressed parent
+    let baseline_nodes: Vec<_> = baseline_matches
+        .iter()
+        .map(|(_, p)| {
+            let (_, _, path) = hyperast::position::compute_position_with_no_spaces(
+                baseline_tr,
+                &mut p.iter_offsets(),
+                stores,
+            );
+            let root = src_arena.root();
+            let parent = (!path.is_empty()).then(|| {
+                src_arena.child_decompressed(&root, path[..path.len() - 1].iter().copied())
+            });
+            (
+                src_arena.child_decompressed(&root, path.into_iter()),
+                parent,
+            )
+        })
+        .collect();
+    let commit_nodes: Vec<_> = commit_matches
+        .iter()
+        .map(|(_, p)| {
+            let (_, _, path) = hyperast::position::compute_position_with_no_spaces(
+                commit_tr,
+                &mut p.iter_offsets(),
+                stores,
+            );
+            let root = dst_arena.root();
+            let parent = (!path.is_empty()).then(|| {
+                dst_arena.child_decompressed(&root, path[..path.len() - 1].iter().copied())
+            });
+            (
+                dst_arena.child_decompressed(&root, path.into_iter()),
+                parent,
+            )
+        })
+        .collect();
+    // several matches of a pattern can share a node, each one is paired at most once, in order
+    let mut commit_index: std::collections::HashMap<_, std::collections::VecDeque<usize>> =
+        Default::default();
+    for (i, ((node, _), (pattern, _))) in commit_nodes.iter().zip(&commit_matches).enumerate() {
+        commit_index
+            .entry((*pattern, *node))
+            .or_default()
+            .push_back(i);
+    }
+    let mapping_time = now.elapsed().as_secs_f64();
+
+    use hyper_diff::matchers::mapping_store::MonoMappingStore;
+    let mut paired = vec![false; commit_matches.len()];
+    let mut matches = vec![];
+    for ((pattern, b), (node, parent)) in baseline_matches.iter().zip(&baseline_nodes) {
+        let i = mappings
+            .get_dst(node)
+            .and_then(|dst| commit_index.get_mut(&(*pattern, dst)))
+            .and_then(|unpaired| unpaired.pop_front());
+        let Some(i) = i else {
+            matches.push(ClassifiedMatch {
+                pattern: *pattern,
+                evolution: Evolution::Removed,
+                baseline: Some(globalize_at(baseline, b)),
+                commit: None,
+            });
+            continue;
+        };
+        paired[i] = true;
+        let parents_mapped = match (parent, &commit_nodes[i].1) {
+            (Some(p), Some(q)) => mappings.get_dst(p) == Some(*q),
+            (None, None) => true,
+            _ => false,
+        };
+        matches.push(ClassifiedMatch {
+            pattern: *pattern,
+            evolution: if parents_mapped {
+                Evolution::Unchanged
+            } else {
+                Evolution::Moved
+            },
+            baseline: Some(globalize_at(baseline, b)),
+            commit: Some(globalize_at(commit, &commit_matches[i].1)),
+        });
+    }
+    for ((pattern, c), _) in commit_matches.iter().zip(paired).filter(|(_, x)| !x) {
+        matches.push(ClassifiedMatch {
+            pattern: *pattern,
+            evolution: Evolution::Added,
+            baseline: None,
+            commit: Some(globalize_at(commit, c)),
+        });
+    }
+    log::info!(
+        "done classifying matches of {commit:?} and {baseline:?} in  {}",
+        repo.spec
+    );
+
+    Ok(Json(ComputeResultsClassified {
+        prepare_time,
+        matching_time,
+        mapping_time,
+        matches,
+    }))
+}
+
+/// The pattern and the `@root` capture (or else the first capture) of each match
+fn matched_nodes_aux(
+    stores: &hyperast::store::SimpleStores<hyperast_vcs_git::TStore>,
+    code: NodeIdentifier,
+    query: &hyperast_tsquery::Query,
+    timeout: std::time::Duration,
+    max_matches: u64,
+) -> Result<
+    Vec<(
+        usize,
+        hyperast::position::StructuralPosition<NodeIdentifier, u16>,
+    )>,
+    MatchingError<ComputeResult>,
+> {
+    let pos = hyperast::position::StructuralPosition::new(code);
+    let cursor = hyperast_tsquery::hyperast_cursor::TreeCursor::new(stores, pos);
+    let qcursor = query.matches(cursor);
+    let now = Instant::now();
+    let root = query.capture_index_for_name("root");
+    let mut result = vec![0; query.enabled_pattern_count()];
+    let mut nodes = vec![];
+    for m in qcursor {
+        let i = m.pattern_index;
+        let i = query.enabled_pattern_index(i).unwrap() as usize;
+        result[i] += 1;
+        let node = root
+            .and_then(|root| m.nodes_for_capture_index(root).next())
+            .or_else(|| m.captures.iter().next().map(|c| &c.node));
+        if let Some(node) = node {
+            nodes.push((i, node.pos.clone()));
+        } else {
+            log::debug!("no capture to locate a match of pattern {}", i);
+        }
+        if now.elapsed() >= timeout {
+            let compute_time = now.elapsed().as_secs_f64();
+            return Err(MatchingError::TimeOut(ComputeResult {
+                result,
+                compute_time,
+                page: None,
+                languages: None,
+            }));
+        } else if result[i] > max_matches {
+            let compute_time = now.elapsed().as_secs_f64();
+            return Err(MatchingError::MaxMatches(ComputeResult {
+                result,
+                compute_time,
+                page: None,
+                languages: None,
+            }));
+        }
+    }
+    Ok(nodes)
+}
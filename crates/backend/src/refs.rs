@@ -0,0 +1,103 @@
+//! Branches and tags of a repository with the root of their preprocessed commit,
+//! so that queries and tracking can start from a release tag or a feature branch.
+//!
+//! Processing is done in chunks of at most [`MAX_COMMITS`] commits per request,
+//! the response telling how many remain so that the client can post again.
+use axum::Json;
+use hyperast_vcs_git::multi_preprocessed::{DEFAULT_REF_GLOBS, fetch_refs};
+use serde::{Deserialize, Serialize};
+
+use crate::SharedState;
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct Param {
+    forge: String,
+    user: String,
+    name: String,
+}
+
+/// Upper bound of the commits processed by a single request
+pub const MAX_COMMITS: usize = 500;
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ProcessQuery {
+    /// clamped to [`MAX_COMMITS`]
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+fn default_limit() -> usize {
+    MAX_COMMITS
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Ref {
+    name: String,
+    commit: String,
+    /// node id of the root of the commit, absent while it is not processed
+    root: Option<u64>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Refs {
+    refs: Vec<Ref>,
+    /// number of commits processed by this request
+    processed: usize,
+    /// number of commits reachable from the refs that are still not processed
+    remaining: usize,
+    /// in seconds
+    time: f64,
+}
+
+/// Lists the branches and tags, after fetching them,
+/// when `process` is set it first preprocesses some of their commits, merged branches included
+pub fn refs(
+    state: SharedState,
+    path: Param,
+    process: Option<ProcessQuery>,
+) -> Result<Json<Refs>, String> {
+    let now = std::time::Instant::now();
+    let repo_spec = state.repo(&path.forge, &path.user, &path.name)?;
+    let repo = state
+        .repositories
+        .read()
+        .unwrap()
+        .get_config(repo_spec)
+        .ok_or_else(|| "missing config for repository".to_string())?
+        .try_fetch()
+        .map_err(|e| e.to_string())?;
+    let processed = if let Some(process) = process {
+        let limit = process.limit.min(MAX_COMMITS);
+        let mut repositories = state.repositories.write().unwrap();
+        repositories
+            .pre_process_refs(&repo, DEFAULT_REF_GLOBS, limit)
+            .map_err(|err| err.to_string())?
+            .len()
+    } else {
+        fetch_refs(&repo, DEFAULT_REF_GLOBS).map_err(|err| err.to_string())?;
+        0
+    };
+    let repositories = state.repositories.read().unwrap();
+    let remaining = repositories
+        .unprocessed_from_refs(&repo, DEFAULT_REF_GLOBS)
+        .map_err(|err| err.to_string())?
+        .len();
+    let refs = repositories
+        .refs(&repo, DEFAULT_REF_GLOBS)
+        .map_err(|err| err.to_string())?
+        .into_iter()
+        .map(|r| Ref {
+            name: r.name,
+            commit: r.commit.to_string(),
+            root: r
+                .ast_root
+                .map(|id| unsafe { std::mem::transmute::<_, u64>(id) }),
+        })
+        .collect();
+    Ok(Json(Refs {
+        refs,
+        processed,
+        remaining,
+        time: now.elapsed().as_secs_f64(),
+    }))
+}
@@ -0,0 +1,301 @@
+//! Luau scripts evaluated on the HyperAST of commits,
+//! an alternative to the rhai engines of [`crate::scriptingv1`].
+//!
+//! The script is a chunk, evaluated once per commit with the root of the commit in the global `root`.
+//! Its return value is the result. Nodes expose
+//! `kind`, `label`, `role`, `is_file`, `is_directory`, `size`, `height`, `line_count`,
+//! `start_byte`, `end_byte` (relative to the containing file), `derived` (the data computed by the prepro script of the repository config)
+//! and `children`.
+//! The limits requested by a script are clamped to the ones of the server, see [`Limits`].
+use crate::SharedState;
+use crate::scriptingv1::{ScriptingError, ScriptingParam};
+use hyperast::store::defaults::NodeIdentifier;
+use hyperast::types::{
+    HyperAST, HyperType, LabelStore, Labeled, WithChildren, WithRoles, WithSerialization, WithStats,
+};
+use mlua::{Lua, UserData, UserDataFields, Value};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+#[derive(Deserialize, Clone)]
+pub struct ScriptContent {
+    pub script: String,
+    #[serde(default = "default_commits")]
+    pub commits: usize,
+    /// in bytes, for the whole request
+    #[serde(default = "default_memory_limit")]
+    pub memory_limit: usize,
+    /// per commit, counted at each function call and loop iteration
+    #[serde(default = "default_instruction_limit")]
+    pub instruction_limit: u64,
+}
+
+pub fn default_commits() -> usize {
+    1
+}
+
+pub fn default_memory_limit() -> usize {
+    64 * 1024 * 1024
+}
+
+pub fn default_instruction_limit() -> u64 {
+    10_000_000
+}
+
+/// Maxima of the limits requested by scripts, set by the server
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    /// in bytes
+    pub memory: usize,
+    pub instructions: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            memory: default_memory_limit(),
+            instructions: default_instruction_limit(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ComputeResult {
+    pub compute_time: f64,
+    pub result: serde_json::Value,
+}
+
+#[derive(Serialize)]
+pub struct ComputeResultIdentified {
+    pub commit: String,
+    #[serde(flatten)]
+    pub inner: ComputeResult,
+}
+
+#[derive(Serialize)]
+pub struct ComputeResults {
+    pub prepare_time: f64,
+    pub results: Vec<Result<ComputeResultIdentified, String>>,
+}
+
+pub fn simple(
+    script: ScriptContent,
+    state: SharedState,
+    path: ScriptingParam,
+) -> Result<ComputeResults, ScriptingError> {
+    let now = Instant::now();
+    let ScriptingParam {
+        forge,
+        user,
+        name,
+        commit,
+    } = path;
+    let lua = Lua::new();
+    lua.sandbox(true)
+        .map_err(|e| ScriptingError::Other(e.to_string()))?;
+    let limits = state.lua_limits;
+    lua.set_memory_limit(script.memory_limit.min(limits.memory))
+        .map_err(|e| ScriptingError::Other(e.to_string()))?;
+    let steps = std::rc::Rc::new(std::cell::Cell::new(0u64));
+    let limit = script.instruction_limit.min(limits.instructions);
+    let s = steps.clone();
+    lua.set_interrupt(move |_| {
+        s.set(s.get() + 1);
+        if s.get() > limit {
+            return Err(mlua::Error::runtime("instruction limit exceeded"));
+        }
+        Ok(mlua::VmState::Continue)
+    });
+    let function = lua
+        .load(script.script.as_str())
+        .set_name("script")
+        .into_function()
+        .map_err(|e| ScriptingError::AtCompilation(e.to_string()))?;
+    let repo_spec = state
+        .repo(&forge, &user, &name)
+        .map_err(ScriptingError::Other)?;
+    let repo = state
+        .repositories
+        .read()
+        .unwrap()
+        .get_config(repo_spec)
+        .ok_or_else(|| ScriptingError::Other("missing config for repository".to_string()))?;
+    let mut repo = repo
+        .try_fetch()
+        .map_err(|e| ScriptingError::Other(e.to_string()))?;
+    log::warn!("done cloning {}", &repo.spec);
+    let commits = state
+        .repositories
+        .write()
+        .unwrap()
+        .pre_process_with_limit(&mut repo, "", &commit, script.commits)
+        .map_err(|e| ScriptingError::Other(e.to_string()))?;
+    let prepare_time = now.elapsed().as_secs_f64();
+    let mut results = vec![];
+    for commit_oid in &commits {
+        let now = Instant::now();
+        let root = {
+            let repositories = state.repositories.read().unwrap();
+            let commit = repositories.get_commit(&repo.config, commit_oid).unwrap();
+            commit.ast_root
+        };
+        let root = Node {
+            state: state.clone(),
+            id: root,
+            start: 0,
+            role: None,
+        };
+        steps.set(0);
+        let r = lua
+            .globals()
+            .set("root", root)
+            .and_then(|_| function.call::<_, Value>(()))
+            .map_err(|e| e.to_string())
+            .and_then(|x| to_json(x, 0));
+        results.push(r.map(|result| ComputeResultIdentified {
+            commit: commit_oid.to_string(),
+            inner: ComputeResult {
+                compute_time: now.elapsed().as_secs_f64(),
+                result,
+            },
+        }));
+    }
+    Ok(ComputeResults {
+        prepare_time,
+        results,
+    })
+}
+
+/// A node of the HyperAST, as seen by scripts
+#[derive(Clone)]
+struct Node {
+    state: SharedState,
+    id: NodeIdentifier,
+    /// offset in the containing file
+    start: usize,
+    role: Option<hyperast::types::Role>,
+}
+
+macro_rules! stores {
+    ($s:expr) => {
+        $s.repositories.read().unwrap().processor.main_stores
+    };
+}
+
+impl UserData for Node {
+    fn add_fields<'lua, F: UserDataFields<'lua, Self>>(fields: &mut F) {
+        fields.add_field_method_get("kind", |_, this| {
+            let stores = &stores!(this.state);
+            Ok(stores.resolve_type(&this.id).to_string())
+        });
+        fields.add_field_method_get("label", |_, this| {
+            let stores = &stores!(this.state);
+            let n = stores.node_store.resolve(this.id);
+            let l = n.try_get_label();
+            Ok(l.map(|l| stores.label_store.resolve(l).to_string()))
+        });
+        fields.add_field_method_get("role", |_, this| Ok(this.role.map(|r| r.to_string())));
+        fields.add_field_method_get("is_file", |_, this| {
+            let stores = &stores!(this.state);
+            Ok(stores.resolve_type(&this.id).is_file())
+        });
+        fields.add_field_method_get("is_directory", |_, this| {
+            let stores = &stores!(this.state);
+            Ok(stores.resolve_type(&this.id).is_directory())
+        });
+        fields.add_field_method_get("size", |_, this| {
+            Ok(stores!(this.state).node_store.resolve(this.id).size())
+        });
+        fields.add_field_method_get("height", |_, this| {
+            Ok(stores!(this.state).node_store.resolve(this.id).height())
+        });
+        fields.add_field_method_get("line_count", |_, this| {
+            Ok(stores!(this.state).node_store.resolve(this.id).line_count())
+        });
+        fields.add_field_method_get("start_byte", |_, this| Ok(this.start));
+        fields.add_field_method_get("end_byte", |_, this| {
+            let stores = &stores!(this.state);
+            let n = stores.node_store.resolve(this.id);
+            Ok(this.start + n.try_bytes_len().unwrap_or_default())
+        });
+        fields.add_field_method_get("derived", |lua, this| {
+            let stores = &stores!(this.state);
+            let n = stores.node_store.resolve(this.id);
+            let Ok(dd) = n.get_component::<hyperast::scripting::DerivedData>() else {
+                return Ok(Value::Nil);
+            };
+            let t = lua.create_table()?;
+            for (k, v) in dd.0.iter() {
+                if let Ok(v) = v.as_int() {
+                    t.set(k.as_str(), v)?;
+                } else if let Ok(v) = v.as_float() {
+                    t.set(k.as_str(), v)?;
+                } else if let Ok(v) = v.as_bool() {
+                    t.set(k.as_str(), v)?;
+                } else if let Ok(v) = v.as_immutable_string_ref() {
+                    t.set(k.as_str(), v.as_str())?;
+                }
+            }
+            Ok(Value::Table(t))
+        });
+        fields.add_field_method_get("children", |lua, this| {
+            let stores = &stores!(this.state);
+            let n = stores.node_store.resolve(this.id);
+            // each file of a directory starts at 0
+            let is_directory = stores.resolve_type(&this.id).is_directory();
+            let mut start = if is_directory { 0 } else { this.start };
+            let mut children = vec![];
+            let cs = n.children();
+            for (i, id) in cs.iter().flat_map(|cs| cs.0.iter()).enumerate() {
+                let role = n.role_at::<hyperast::types::Role>(i as u16);
+                children.push(Node {
+                    state: this.state.clone(),
+                    id: *id,
+                    start,
+                    role,
+                });
+                if !is_directory {
+                    start += stores
+                        .node_store
+                        .resolve(*id)
+                        .try_bytes_len()
+                        .unwrap_or_default();
+                }
+            }
+            lua.create_sequence_from(children)
+        });
+    }
+}
+
+const MAX_DEPTH: usize = 64;
+
+/// Converts the result of a script, tables being arrays when they are sequences
+fn to_json(value: Value, depth: usize) -> Result<serde_json::Value, String> {
+    use serde_json::Value as J;
+    if depth > MAX_DEPTH {
+        return Err("result is too deep".to_string());
+    }
+    Ok(match value {
+        Value::Nil => J::Null,
+        Value::Boolean(b) => J::Bool(b),
+        Value::Integer(i) => J::from(i),
+        // luau only has floats
+        Value::Number(f) if f.fract() == 0.0 && f.abs() < (1u64 << 53) as f64 => J::from(f as i64),
+        Value::Number(f) => serde_json::Number::from_f64(f).map_or(J::Null, J::Number),
+        Value::String(s) => J::String(s.to_str().map_err(|e| e.to_string())?.to_string()),
+        Value::Table(t) if t.raw_len() > 0 => J::Array(
+            t.sequence_values::<Value>()
+                .map(|x| to_json(x.map_err(|e| e.to_string())?, depth + 1))
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Table(t) => {
+            let mut map = serde_json::Map::new();
+            for x in t.pairs::<String, Value>() {
+                let (k, v) = x.map_err(|e| e.to_string())?;
+                map.insert(k, to_json(v, depth + 1)?);
+            }
+            J::Object(map)
+        }
+        x => return Err(format!("cannot return a {}", x.type_name())),
+    })
+}
@@ -8,13 +8,16 @@ mod named_container;
 mod quantile;
 #[cfg(feature = "impact")]
 mod refs;
+mod series;
 mod stats;
 
+pub use series::{ScriptContentSeries, SeriesResults, series, simple_series};
+
 use crate::SharedState;
 use average::Merge;
 use axum::Json;
 use hyperast::store::nodes::compo::Flags;
-use hyperast::types::HyperAST;
+use hyperast::types::{HyperAST, WithRoles};
 use hyperast::{
     store::defaults::NodeIdentifier,
     types::{HyperType, LabelStore, Labeled, WithChildren, WithStats},
@@ -28,9 +31,10 @@ use std::fmt::Display;
 
 #[derive(Deserialize, Clone)]
 pub struct ScriptingParam {
-    user: String,
-    name: String,
-    commit: String,
+    pub(crate) forge: String,
+    pub(crate) user: String,
+    pub(crate) name: String,
+    pub(crate) commit: String,
 }
 
 #[derive(Deserialize, Clone)]
@@ -125,7 +129,12 @@ pub fn simple_depth(
         commits,
     } = script;
     let now = Instant::now();
-    let ScriptingParam { user, name, commit } = path.clone();
+    let ScriptingParam {
+        forge,
+        user,
+        name,
+        commit,
+    } = path.clone();
     let mut engine = Engine::new();
     engine.disable_symbol("/");
     add_utils(&mut engine);
@@ -138,7 +147,9 @@ pub fn simple_depth(
     let accumulate_script = engine.compile(script.accumulate.clone()).map_err(|x| {
         ScriptingError::AtCompilation(format!("Acc: {}, {}", x, script.accumulate.clone()))
     })?;
-    let repo_spec = hyperast_vcs_git::git::Forge::Github.repo(user, name);
+    let repo_spec = state
+        .repo(&forge, &user, &name)
+        .map_err(ScriptingError::Other)?;
     let repo = state
         .repositories
         .write()
@@ -157,7 +168,9 @@ pub fn simple_depth(
         }
     };
     // .ok_or_else(|| ScriptingError::Other("missing config for repository".to_string()))?;
-    let mut repo = repo.fetch();
+    let mut repo = repo
+        .try_fetch()
+        .map_err(|e| ScriptingError::Other(e.to_string()))?;
     log::warn!("done cloning {}", &repo.spec);
     let commits = state
         .repositories
@@ -210,7 +223,12 @@ fn simple_prepare(
     ),
     ScriptingError,
 > {
-    let ScriptingParam { user, name, commit } = path.clone();
+    let ScriptingParam {
+        forge,
+        user,
+        name,
+        commit,
+    } = path.clone();
     let mut engine = Engine::new();
     engine.disable_symbol("/");
     add_utils(&mut engine);
@@ -223,14 +241,18 @@ fn simple_prepare(
     let accumulate_script = engine.compile(script.accumulate.clone()).map_err(|x| {
         ScriptingError::AtCompilation(format!("Acc: {}, {}", x, script.accumulate.clone()))
     })?;
-    let repo_spec = hyperast_vcs_git::git::Forge::Github.repo(user, name);
+    let repo_spec = state
+        .repo(&forge, &user, &name)
+        .map_err(ScriptingError::Other)?;
     let repo = state
         .repositories
         .write()
         .unwrap()
         .get_config(repo_spec)
         .ok_or_else(|| ScriptingError::Other("missing config for repository".to_string()))?;
-    let repo = repo.fetch();
+    let repo = repo
+        .try_fetch()
+        .map_err(|e| ScriptingError::Other(e.to_string()))?;
     log::warn!("done cloning {}", &repo.spec);
     Ok((
         commit,
@@ -258,6 +280,27 @@ fn simple_aux(
     let node_store = &repositories.processor.main_stores.node_store;
     // let size = node_store.resolve(src_tr).size();
     drop(repositories);
+    simple_aux_from(
+        state,
+        src_tr,
+        engine,
+        init_script,
+        filter_script,
+        accumulate_script,
+        now,
+    )
+}
+
+/// Evaluates the scripts on the subtree rooted at `src_tr`
+fn simple_aux_from(
+    state: rhai::Shared<crate::AppState>,
+    src_tr: NodeIdentifier,
+    engine: &Engine,
+    init_script: &rhai::AST,
+    filter_script: &rhai::AST,
+    accumulate_script: &rhai::AST,
+    now: Instant,
+) -> Result<ComputeResult, ScriptingError> {
     macro_rules! ns {
         ($s:expr) => {
             $s.repositories
@@ -411,6 +454,7 @@ fn simple_aux(
                         x.contains(SemFlag::HoldMainFolder) || x.contains(SemFlag::HoldTestFolder)
                     })
             });
+            add_node_utils(&mut filter_engine, &state, current);
             add_utils(&mut filter_engine);
             let prepared: Dynamic = filter_engine
                 .eval_ast_with_scope(&mut scope, &filter_script)
@@ -560,6 +604,7 @@ fn simple_aux(
                 },
             );
         }
+        add_node_utils(&mut acc_engine, &state, current);
         add_utils(&mut acc_engine);
         acc_engine
             .eval_ast_with_scope(&mut scope, &accumulate_script)
@@ -575,6 +620,62 @@ fn simple_aux(
     Ok(r)
 }
 
+/// Registers the functions on the `current` node that apply to any language
+fn add_node_utils(engine: &mut Engine, state: &SharedState, current: NodeIdentifier) {
+    macro_rules! stores {
+        ($s:expr) => {
+            $s.repositories.read().unwrap().processor.main_stores
+        };
+    }
+    let s = state.clone();
+    engine.register_fn("is_type_declaration", move || {
+        let stores = &stores!(s);
+        let t = stores.resolve_type(&current);
+        t.as_abstract()
+            .contains(hyperast::types::Abstract::Declaration)
+    });
+    let s = state.clone();
+    engine.register_fn("is_executable_member", move || {
+        let stores = &stores!(s);
+        let t = stores.resolve_type(&current);
+        t.as_abstract()
+            .contains(hyperast::types::Abstract::Executable)
+    });
+    let s = state.clone();
+    engine.register_fn("language", move || {
+        let stores = &stores!(s);
+        let t = stores.resolve_type(&current);
+        use hyperast::types::LangRef;
+        t.get_lang().name().to_string()
+    });
+    let s = state.clone();
+    engine.register_fn("roles", move || {
+        let node_store = &stores!(s).node_store;
+        let n = node_store.resolve(current);
+        (0..n.child_count())
+            .map(|i| {
+                let r = n.role_at::<hyperast::types::Role>(i);
+                Dynamic::from(r.map_or(String::new(), |r| r.to_string()))
+            })
+            .collect::<Array>()
+    });
+    let s = state.clone();
+    engine.register_fn("file_extension", move || {
+        let stores = &stores!(s);
+        let n = stores.node_store.resolve(current);
+        let t = stores.resolve_type(&current);
+        if t.is_file() || t.is_directory() {
+            let name = stores.label_store.resolve(n.get_label_unchecked());
+            let ext = std::path::Path::new(name).extension();
+            Ok(ext.map_or(String::new(), |x| x.to_string_lossy().to_string()))
+        } else {
+            Err(Box::<rhai::EvalAltResult>::from(
+                "file_extension() should be called on a file or a directory",
+            ))
+        }
+    });
+}
+
 use self::{max::Max, mean::Mean, min::Min, quantile::Quantile, stats::Stats};
 use finalize::Finalize;
 
@@ -0,0 +1,302 @@
+//! Results of a script over a set of commits, per group of nodes,
+//! as a tidy table with one row per commit, group and metric.
+use axum::{
+    Json,
+    response::{IntoResponse, Response},
+};
+use enumset::EnumSet;
+use hyperast::store::{defaults::NodeIdentifier, nodes::compo::Flags};
+use hyperast::types::{HyperAST, HyperType, LabelStore, Labeled, WithChildren};
+use hyperast_vcs_git::maven::SemFlag;
+use rhai::{Dynamic, Instant};
+use serde::{Deserialize, Serialize};
+
+use super::{ScriptContent, ScriptingError, ScriptingParam, simple_aux_from, simple_prepare};
+use crate::SharedState;
+
+#[derive(Deserialize, Clone)]
+pub struct ScriptContentSeries {
+    #[serde(flatten)]
+    inner: ScriptContent,
+    /// `before..after`, both included, `after` defaults to the commit of the path
+    range: Option<String>,
+    /// tags or commits evaluated instead of a range
+    #[serde(default)]
+    tags: Vec<String>,
+    /// maximum number of commits taken from the range, all of them by default,
+    /// without range only the commit of the path by default
+    commits: Option<usize>,
+    #[serde(default)]
+    group: Grouping,
+    #[serde(default)]
+    format: Format,
+}
+
+/// The roots on which the scripts are evaluated
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Grouping {
+    /// the root of the commit
+    #[default]
+    Commit,
+    File,
+    /// each directory, e.g. a java package
+    Directory,
+    /// each maven module
+    Module,
+}
+
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    #[default]
+    Json,
+    Csv,
+}
+
+#[derive(Serialize)]
+pub struct Row {
+    pub commit: String,
+    /// commit time, in seconds since the epoch
+    pub time: i64,
+    /// path of the group, empty for the root of the commit
+    pub group: String,
+    /// key of the result, dot separated for nested maps, `value` if the result is not a map
+    pub metric: String,
+    pub value: Dynamic,
+}
+
+#[derive(Serialize)]
+pub struct SeriesError {
+    pub commit: String,
+    /// commit time, in seconds since the epoch
+    pub time: i64,
+    pub group: String,
+    pub error: String,
+}
+
+#[derive(Serialize)]
+pub struct SeriesResults {
+    pub prepare_time: f64,
+    pub compute_time: f64,
+    pub rows: Vec<Row>,
+    pub errors: Vec<SeriesError>,
+}
+
+pub fn simple_series(
+    script: ScriptContentSeries,
+    state: SharedState,
+    path: ScriptingParam,
+) -> Result<Response, ScriptingError> {
+    let format = script.format;
+    let results = series(script, state, path)?;
+    let resp = match format {
+        Format::Json => Json(results).into_response(),
+        Format::Csv => {
+            ([(http::header::CONTENT_TYPE, "text/csv")], results.to_csv()).into_response()
+        }
+    };
+    Ok(resp)
+}
+
+/// Evaluates the script on each group of each commit, see [`simple_series`]
+pub fn series(
+    script: ScriptContentSeries,
+    state: SharedState,
+    path: ScriptingParam,
+) -> Result<SeriesResults, ScriptingError> {
+    let ScriptContentSeries {
+        inner: script,
+        range,
+        tags,
+        commits,
+        group,
+        format: _,
+    } = script;
+    let now = Instant::now();
+    let (commit, engine, init_script, accumulate_script, filter_script, mut repo) =
+        simple_prepare(path, script, &state)?;
+    let mut commit_oids = vec![];
+    if !tags.is_empty() {
+        for tag in &tags {
+            let oids = crate::utils::handle_pre_processing(&state, &mut repo, "", tag, 1)
+                .map_err(|e| ScriptingError::Other(format!("{tag}: {e}")))?;
+            commit_oids.extend(oids);
+        }
+    } else {
+        let (before, after) = match range.as_deref() {
+            Some(range) => range.split_once("..").ok_or_else(|| {
+                ScriptingError::Other(format!("{range} is not a range, expected before..after"))
+            })?,
+            None => ("", ""),
+        };
+        let commits = commits.unwrap_or(if range.is_some() { usize::MAX } else { 1 });
+        let after = if after.is_empty() {
+            commit.as_str()
+        } else {
+            after
+        };
+        let oids = crate::utils::handle_pre_processing(&state, &mut repo, before, after, commits)
+            .map_err(|e| ScriptingError::Other(e.to_string()))?;
+        commit_oids.extend(oids);
+    }
+    log::info!("done construction of {commit_oids:?} in {}", repo.spec);
+    let prepare_time = now.elapsed().as_secs_f64();
+    let now = Instant::now();
+    let mut rows = vec![];
+    let mut errors = vec![];
+    for commit_oid in &commit_oids {
+        let time = repo
+            .repo
+            .find_commit(*commit_oid)
+            .map_err(|e| ScriptingError::Other(e.to_string()))?
+            .time()
+            .seconds();
+        let root = {
+            let repositories = state.repositories.read().unwrap();
+            let commit = repositories
+                .get_commit(&repo.config, commit_oid)
+                .ok_or_else(|| ScriptingError::Other(format!("{commit_oid} is not processed")))?;
+            commit.ast_root
+        };
+        for (group, root) in group_roots(&state, root, group) {
+            let r = simple_aux_from(
+                state.clone(),
+                root,
+                &engine,
+                &init_script,
+                &filter_script,
+                &accumulate_script,
+                Instant::now(),
+            );
+            match r {
+                Ok(r) => flatten(r.result, String::new(), &mut |metric, value| {
+                    rows.push(Row {
+                        commit: commit_oid.to_string(),
+                        time,
+                        group: group.clone(),
+                        metric,
+                        value,
+                    })
+                }),
+                Err(ScriptingError::AtEvaluation(error)) => errors.push(SeriesError {
+                    commit: commit_oid.to_string(),
+                    time,
+                    group,
+                    error,
+                }),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    let compute_time = now.elapsed().as_secs_f64();
+    Ok(SeriesResults {
+        prepare_time,
+        compute_time,
+        rows,
+        errors,
+    })
+}
+
+/// Finds the roots of the groups in the commit, along with their paths
+fn group_roots(
+    state: &SharedState,
+    root: NodeIdentifier,
+    group: Grouping,
+) -> Vec<(String, NodeIdentifier)> {
+    if let Grouping::Commit = group {
+        return vec![(String::new(), root)];
+    }
+    let repositories = state.repositories.read().unwrap();
+    let stores = &repositories.processor.main_stores;
+    let mut result = vec![];
+    let mut stack = vec![(String::new(), root)];
+    while let Some((path, id)) = stack.pop() {
+        let t = stores.resolve_type(&id);
+        let n = stores.node_store.resolve(id);
+        let is_group = match group {
+            Grouping::Commit => unreachable!(),
+            Grouping::File => t.is_file(),
+            Grouping::Directory => t.is_directory(),
+            Grouping::Module => n
+                .get_component::<Flags<EnumSet<SemFlag>>>()
+                .map_or(false, |x| x.contains(SemFlag::IsMavenModule)),
+        };
+        if is_group {
+            result.push((path.clone(), id));
+        }
+        if !t.is_directory() {
+            // groups are files or directories
+            continue;
+        }
+        let cs = n.children();
+        for c in cs.iter().flat_map(|cs| cs.0.iter()).rev() {
+            let child = stores.node_store.resolve(*c);
+            let Some(l) = child.try_get_label() else {
+                continue;
+            };
+            let name = stores.label_store.resolve(l);
+            let path = if path.is_empty() {
+                name.to_string()
+            } else {
+                format!("{path}/{name}")
+            };
+            stack.push((path, *c));
+        }
+    }
+    result
+}
+
+/// Splits a finalized result into metrics, nested maps giving dot separated names
+fn flatten(value: Dynamic, prefix: String, f: &mut impl FnMut(String, Dynamic)) {
+    if !value.is_map() {
+        let metric = if prefix.is_empty() {
+            "value".to_string()
+        } else {
+            prefix
+        };
+        return f(metric, value);
+    }
+    for (k, v) in value.cast::<rhai::Map>() {
+        let metric = if prefix.is_empty() {
+            k.to_string()
+        } else {
+            format!("{prefix}.{k}")
+        };
+        flatten(v, metric, f);
+    }
+}
+
+impl SeriesResults {
+    /// One line per row then one per error, errors only filling the `error` column
+    pub fn to_csv(&self) -> String {
+        fn escape(s: &str) -> String {
+            if s.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", s.replace('"', "\"\""))
+            } else {
+                s.to_string()
+            }
+        }
+        let mut out = "commit,time,group,metric,value,error\n".to_string();
+        for row in &self.rows {
+            out.push_str(&format!(
+                "{},{},{},{},{},\n",
+                row.commit,
+                row.time,
+                escape(&row.group),
+                escape(&row.metric),
+                escape(&row.value.to_string()),
+            ));
+        }
+        for e in &self.errors {
+            out.push_str(&format!(
+                "{},{},{},,,{}\n",
+                e.commit,
+                e.time,
+                escape(&e.group),
+                escape(&e.error),
+            ));
+        }
+        out
+    }
+}
@@ -19,12 +19,13 @@ use crate::SharedState;
 
 pub(crate) mod matching;
 
-mod diffing;
+pub(crate) mod diffing;
 
 type Idx = u16;
 
 #[derive(Deserialize, Clone)]
 pub struct Param {
+    forge: String,
     user: String,
     name: String,
     commit: String,
@@ -33,6 +34,7 @@ pub struct Param {
 
 #[derive(Deserialize, Clone)]
 pub struct Diffs {
+    forge: String,
     user: String,
     name: String,
     commit: String,
@@ -116,6 +118,7 @@ pub(crate) fn smells(
 ) -> Result<Json<SearchResults>, String> {
     let now = Instant::now();
     let Param {
+        forge,
         user,
         name,
         commit,
@@ -137,14 +140,14 @@ pub(crate) fn smells(
         true
     };
 
-    let repo_spec = hyperast_vcs_git::git::Forge::Github.repo(user, name);
+    let repo_spec = state.repo(&forge, &user, &name)?;
     let repo_handle = state
         .repositories
         .write()
         .unwrap()
         .get_config(repo_spec)
         .ok_or_else(|| "missing config for repository".to_string())?;
-    let mut repository = repo_handle.fetch();
+    let mut repository = repo_handle.try_fetch().map_err(|e| e.to_string())?;
     log::warn!("done cloning {}", repository.spec);
     let commits = state
         .repositories
@@ -282,20 +285,21 @@ pub(crate) fn smells_ex_from_diffs(
 ) -> Result<Json<ExamplesResults>, String> {
     let now = Instant::now();
     let Diffs {
+        forge,
         user,
         name,
         commit,
         len,
     } = path;
     log::warn!("use len value={len}");
-    let repo_spec = hyperast_vcs_git::git::Forge::Github.repo(user, name);
+    let repo_spec = state.repo(&forge, &user, &name)?;
     let repo_handle = state
         .repositories
         .write()
         .unwrap()
         .get_config(repo_spec)
         .ok_or_else(|| "missing config for repository".to_string())?;
-    let mut repository = repo_handle.fetch();
+    let mut repository = repo_handle.try_fetch().map_err(|e| e.to_string())?;
     log::warn!("done cloning {}", repository.spec);
     let commits = state
         .repositories
@@ -81,14 +81,25 @@ impl hyperast::types::Labeled for T {
     }
 }
 
-pub(crate) fn diff(
-    state: std::sync::Arc<crate::AppState>,
+/// Actions transforming the tree of a commit into the tree of another,
+/// with paths from the roots without spaces.
+pub(crate) struct EditScript {
+    pub(crate) src_tr: NodeIdentifier,
+    pub(crate) dst_tr: NodeIdentifier,
+    pub(crate) actions: ActionsVec<A>,
+    /// paths of mapped nodes, only filled when requested
+    pub(crate) matches: Vec<(Vec<Idx>, Vec<Idx>)>,
+}
+
+pub(crate) fn edit_script(
+    state: &crate::AppState,
     repo_handle: &impl hyperast_vcs_git::processing::ConfiguredRepoTrait<
         Config = hyperast_vcs_git::processing::ParametrizedCommitProcessorHandle,
     >,
     src_oid: hyperast_vcs_git::git::Oid,
     dst_oid: hyperast_vcs_git::git::Oid,
-) -> Result<Diff, String> {
+    with_matches: bool,
+) -> Result<EditScript, String> {
     let repositories = state.repositories.read().unwrap();
     let commit_src = repositories
         .get_commit(repo_handle.config(), &src_oid)
@@ -102,11 +113,11 @@ pub(crate) fn diff(
     let stores = &hyperast_vcs_git::no_space::as_nospaces2(with_spaces_stores);
 
     if src_tr == dst_tr {
-        return Ok(Diff {
-            focuses: Default::default(),
-            deletes: Default::default(),
-            inserts: Default::default(),
-            moves: Default::default(),
+        return Ok(EditScript {
+            src_tr,
+            dst_tr,
+            actions: Default::default(),
+            matches: Default::default(),
         });
     }
 
@@ -191,13 +202,22 @@ pub(crate) fn diff(
         hyperast: stores,
         decomp: dst_arena,
     };
-    let dst_arena = SimpleBfsMapper::with_store(stores, dst_arena);
-    dbg!();
     let ms = &mapped.1;
     let src_arena = Decompressible {
         hyperast: stores,
         decomp: src_arena,
     };
+    let matches = if with_matches {
+        use hyper_diff::decompressed_tree_store::DecompressedWithParent;
+        use hyper_diff::matchers::mapping_store::MonoMappingStore;
+        ms.iter()
+            .map(|(s, d)| (src_arena.path_rooted(&s), dst_arena.path_rooted(&d)))
+            .collect()
+    } else {
+        vec![]
+    };
+    let dst_arena = SimpleBfsMapper::with_store(stores, dst_arena);
+    dbg!();
     let mapping = hyper_diff::matchers::Mapping {
         src_arena,
         dst_arena,
@@ -216,6 +236,40 @@ pub(crate) fn diff(
         this.actions
     };
 
+    Ok(EditScript {
+        src_tr,
+        dst_tr,
+        actions,
+        matches,
+    })
+}
+
+pub(crate) fn diff(
+    state: std::sync::Arc<crate::AppState>,
+    repo_handle: &impl hyperast_vcs_git::processing::ConfiguredRepoTrait<
+        Config = hyperast_vcs_git::processing::ParametrizedCommitProcessorHandle,
+    >,
+    src_oid: hyperast_vcs_git::git::Oid,
+    dst_oid: hyperast_vcs_git::git::Oid,
+) -> Result<Diff, String> {
+    let EditScript {
+        src_tr,
+        dst_tr,
+        actions,
+        ..
+    } = edit_script(&state, repo_handle, src_oid, dst_oid, false)?;
+    if src_tr == dst_tr {
+        return Ok(Diff {
+            focuses: Default::default(),
+            deletes: Default::default(),
+            inserts: Default::default(),
+            moves: Default::default(),
+        });
+    }
+    let repositories = state.repositories.read().unwrap();
+    let with_spaces_stores = &repositories.processor.main_stores;
+    let stores = &hyperast_vcs_git::no_space::as_nospaces2(with_spaces_stores);
+
     dbg!(&actions.len());
 
     enum Choice {
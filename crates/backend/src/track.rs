@@ -38,6 +38,7 @@ use crate::{
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct TrackingParam {
+    pub forge: String,
     pub user: String,
     pub name: String,
     pub commit: String,
@@ -46,6 +47,7 @@ pub struct TrackingParam {
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct TrackingAtPathParam {
+    forge: String,
     user: String,
     name: String,
     commit: String,
@@ -258,6 +260,7 @@ pub fn track_code(
 ) -> Result<TrackingResult<IdN, Idx>, TrackingError> {
     let now = Instant::now();
     let TrackingParam {
+        forge,
         user,
         name,
         commit,
@@ -269,7 +272,14 @@ pub fn track_code(
         before,
         flags,
     } = query;
-    let repo_specifier = hyperast_vcs_git::git::Forge::Github.repo(user, name);
+    let repo_specifier = state
+        .repo(&forge, &user, &name)
+        .map_err(|message| TrackingError {
+            compute_time: now.elapsed().as_secs_f64(),
+            commits_processed: 0,
+            node_processed: 0,
+            message,
+        })?;
     let repo_handle = state
         .repositories
         .write()
@@ -281,7 +291,12 @@ pub fn track_code(
             node_processed: 0,
             message: "missing config for repository".to_string(),
         })?;
-    let mut repository = repo_handle.fetch();
+    let mut repository = repo_handle.try_fetch().map_err(|e| TrackingError {
+        compute_time: now.elapsed().as_secs_f64(),
+        commits_processed: 0,
+        node_processed: 0,
+        message: e.to_string(),
+    })?;
     log::debug!("done cloning {}", repository.spec);
     let mut commit = commit.clone();
     let mut node_processed = 0;
@@ -419,12 +434,20 @@ pub(crate) fn track_code_at_path(
         flags,
     } = query;
     let TrackingAtPathParam {
+        forge,
         user,
         name,
         commit,
         path,
     } = path;
-    let repo_specifier = hyperast_vcs_git::git::Forge::Github.repo(user, name);
+    let repo_specifier = state
+        .repo(&forge, &user, &name)
+        .map_err(|message| TrackingError {
+            compute_time: now.elapsed().as_secs_f64(),
+            commits_processed: 0,
+            node_processed: 0,
+            message,
+        })?;
     let repository = state
         .repositories
         .write()
@@ -436,7 +459,12 @@ pub(crate) fn track_code_at_path(
             node_processed: 0,
             message: "missing config for repository".to_string(),
         })?;
-    let mut repository = repository.fetch();
+    let mut repository = repository.try_fetch().map_err(|e| TrackingError {
+        compute_time: now.elapsed().as_secs_f64(),
+        commits_processed: 0,
+        node_processed: 0,
+        message: e.to_string(),
+    })?;
     log::debug!("done cloning {}", repository.spec);
     // let mut get_mut = state.write().unwrap();
     // let state = get_mut.deref_mut();
@@ -576,12 +604,20 @@ pub(crate) fn track_code_at_path_with_changes(
         flags,
     } = query;
     let TrackingAtPathParam {
+        forge,
         user,
         name,
         commit,
         path,
     } = path;
-    let repo_spec = hyperast_vcs_git::git::Forge::Github.repo(user, name);
+    let repo_spec = state
+        .repo(&forge, &user, &name)
+        .map_err(|message| TrackingError {
+            compute_time: now.elapsed().as_secs_f64(),
+            commits_processed: 0,
+            node_processed: 0,
+            message,
+        })?;
     let repo_handle = state
         .repositories
         .write()
@@ -593,7 +629,12 @@ pub(crate) fn track_code_at_path_with_changes(
             node_processed: 0,
             message: "missing config for repository".to_string(),
         })?;
-    let mut repository = repo_handle.fetch();
+    let mut repository = repo_handle.try_fetch().map_err(|e| TrackingError {
+        compute_time: now.elapsed().as_secs_f64(),
+        commits_processed: 0,
+        node_processed: 0,
+        message: e.to_string(),
+    })?;
     log::debug!("done cloning {}", repository.spec);
     let mut ori_oid = None;
     let mut commit = commit.clone();
@@ -10,6 +10,7 @@ use std::time::Instant;
 
 #[derive(Deserialize, Clone)]
 pub struct Param {
+    forge: String,
     user: String,
     name: String,
     commit: String,
@@ -26,6 +27,7 @@ pub struct Content {
 #[derive(Debug, Serialize, Clone)]
 pub enum QueryingError {
     MissingLanguage(String),
+    UnknownForge(String),
     TsgParsing(String),
 }
 
@@ -54,7 +56,12 @@ pub fn simple(
     path: Param,
 ) -> Result<Json<ComputeResults>, QueryingError> {
     let now = Instant::now();
-    let Param { user, name, commit } = path.clone();
+    let Param {
+        forge,
+        user,
+        name,
+        commit,
+    } = path.clone();
     let Content {
         language: lang_name,
         query,
@@ -63,7 +70,9 @@ pub fn simple(
     } = query;
     let language: tree_sitter::Language = hyperast_vcs_git::resolve_language(&lang_name)
         .ok_or_else(|| QueryingError::MissingLanguage(lang_name.clone()))?;
-    let repo_spec = hyperast_vcs_git::git::Forge::Github.repo(user, name);
+    let repo_spec = state
+        .repo(&forge, &user, &name)
+        .map_err(QueryingError::UnknownForge)?;
     let repo = state
         .repositories
         .write()
@@ -82,7 +91,9 @@ pub fn simple(
         }
     };
     // .ok_or_else(|| ScriptingError::Other("missing config for repository".to_string()))?;
-    let mut repo = repo.fetch();
+    let mut repo = repo
+        .try_fetch()
+        .map_err(|e| ScriptingError::Other(e.to_string()))?;
     log::warn!("done cloning {}", &repo.spec);
     let commits = state
         .repositories
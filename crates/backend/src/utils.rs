@@ -159,7 +159,7 @@ pub(crate) fn handle_pre_processing(
     // NOTE the read with a fallback on a write ensures that we are not waiting to, in the end, not writing anything
     // TODO later start processing the commit subset and schedule the remaining range for processing
     // NOTE a sceduling approach would be much cleaner than the current lock approach
-    Ok(handle_pre_processing_aux(state, repo, rw))
+    Ok(handle_pre_processing_aux(state, repo, rw)?)
 }
 
 pub(crate) fn walk_commits_multi<'a, R: AsRef<str>>(
@@ -179,7 +179,7 @@ pub(crate) fn handle_pre_processing_aux(
     state: &std::sync::Arc<crate::AppState>,
     repo: &hyperast_vcs_git::processing::ConfiguredRepo2,
     rw: impl Iterator<Item = hyperast_vcs_git::git::Oid>,
-) -> Vec<hyperast_vcs_git::git::Oid> {
+) -> Result<Vec<hyperast_vcs_git::git::Oid>, hyperast_vcs_git::git::Error> {
     let mut rw = rw.peekable();
     let commits = {
         state
@@ -190,11 +190,11 @@ pub(crate) fn handle_pre_processing_aux(
             .ensure_prepro(&mut rw, repo)
     };
     match commits {
-        Ok(commits) => commits,
+        Ok(commits) => Ok(commits),
         Err(mut commits) => {
             let repository_processor = &mut state.repositories.write().unwrap().processor;
-            commits.extend(repository_processor.pre_pro(&mut rw, repo, usize::MAX));
-            commits
+            commits.extend(repository_processor.pre_pro(&mut rw, repo, usize::MAX)?);
+            Ok(commits)
         }
     }
 }
@@ -14,6 +14,7 @@ use crate::SharedState;
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct Parameters {
+    forge: String,
     user: String,
     name: String,
     commit: String,
@@ -40,6 +41,7 @@ pub struct View {
     children: ViewChildren,
     both: ViewBoth,
     typed: ViewTyped,
+    derived: ViewDerived,
 }
 
 #[derive(Serialize, Clone, Debug, Default)]
@@ -64,6 +66,13 @@ pub struct ViewChildren {
     children: Vec<NodeId>,
 }
 
+/// Data computed by the prepro script of the repository config, for the viewed nodes having some
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct ViewDerived {
+    ids: Vec<NodeId>,
+    data: Vec<rhai::Map>,
+}
+
 #[derive(Serialize, Clone, Debug, Default)]
 pub struct ViewBoth {
     ids: Vec<NodeId>,
@@ -77,20 +86,21 @@ pub struct ViewBoth {
 pub fn view(state: SharedState, path: Parameters) -> Result<Json<ViewRes>, String> {
     let now = Instant::now();
     let Parameters {
+        forge,
         user,
         name,
         commit,
         path,
     } = path;
     dbg!(&path);
-    let repo_spec = hyperast_vcs_git::git::Forge::Github.repo(user, name);
+    let repo_spec = state.repo(&forge, &user, &name)?;
     let repo = state
         .repositories
         .write()
         .unwrap()
         .get_config(repo_spec)
         .ok_or_else(|| "missing config for repository".to_string())?;
-    let mut repo = repo.fetch();
+    let mut repo = repo.try_fetch().map_err(|e| e.to_string())?;
     log::info!("done cloning {}", repo.spec);
     let commits = state
         .repositories
@@ -181,6 +191,7 @@ where
     let mut with_children = ViewChildren::default();
     let mut with_both = ViewBoth::default();
     let mut only_typed = ViewTyped::default();
+    let mut derived = ViewDerived::default();
     // let mut ids = vec![];
     // let mut kinds = vec![];
     // let mut cs_ofs = vec![];
@@ -221,6 +232,15 @@ where
         let nid = id.finish();
         let n = stores.node_store().resolve(&curr); //hyperast::types::NodeStore::resolve(stores, &curr);
         let k = stores.resolve_type(&curr);
+        {
+            use hyperast::scripting::DerivedData;
+            use hyperast::store::nodes::ErasedHolder;
+            let tid = std::any::TypeId::of::<DerivedData>();
+            if let Some(dd) = n.unerase_ref::<DerivedData>(tid) {
+                derived.ids.push(nid);
+                derived.data.push(dd.0.clone());
+            }
+        }
         if let Some(l) = n.try_get_label() {
             let l = label_map.entry(*l).or_insert_with(|| {
                 let i = label_list.len() as u32;
@@ -287,6 +307,7 @@ where
         children: with_children,
         both: with_both,
         typed: only_typed,
+        derived,
     };
     view
 }
@@ -0,0 +1,135 @@
+//! Keeps watched repositories preprocessed as commits get pushed,
+//! either polled periodically (see `--watch-interval`) or notified by a post-receive hook, e.g.
+//! `curl -X POST http://127.0.0.1:8888/notify/<forge>/<user>/<name>`
+use axum::Json;
+use hyperast_vcs_git::watcher::{Update, Watcher};
+use serde::{Deserialize, Serialize};
+
+use crate::SharedState;
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct Param {
+    forge: String,
+    user: String,
+    name: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Watched {
+    /// watched refs and their current tip
+    tips: Vec<(String, String)>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct MovedRef {
+    name: String,
+    before: Option<String>,
+    after: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Polled {
+    moved: Vec<MovedRef>,
+    deleted: Vec<String>,
+    /// newly processed commits
+    processed: Vec<String>,
+    /// in seconds
+    time: f64,
+}
+
+/// Starts watching a configured repository, only commits pushed afterward are processed
+pub fn watch(state: SharedState, path: Param) -> Result<Json<Watched>, String> {
+    let repo_spec = state.repo(&path.forge, &path.user, &path.name)?;
+    let repo = state
+        .repositories
+        .read()
+        .unwrap()
+        .get_config(repo_spec.clone())
+        .ok_or_else(|| "missing config for repository".to_string())?
+        .try_fetch()
+        .map_err(|e| e.to_string())?;
+    let mut watcher = Watcher::new();
+    watcher
+        .skip_current(&repo.repo)
+        .map_err(|err| err.to_string())?;
+    let tips = watcher
+        .tips()
+        .map(|(name, oid)| (name.to_string(), oid.to_string()))
+        .collect();
+    state.watchers.insert(repo_spec, watcher);
+    Ok(Json(Watched { tips }))
+}
+
+pub fn unwatch(state: SharedState, path: Param) -> Result<(), String> {
+    let repo_spec = state.repo(&path.forge, &path.user, &path.name)?;
+    state
+        .watchers
+        .remove(&repo_spec)
+        .map(|_| ())
+        .ok_or_else(|| "repository is not watched".to_string())
+}
+
+/// Processes the commits pushed to a watched repository since its last poll
+pub fn notify(state: SharedState, path: Param) -> Result<Json<Polled>, String> {
+    let repo_spec = state.repo(&path.forge, &path.user, &path.name)?;
+    let now = std::time::Instant::now();
+    let update = poll(&state, &repo_spec)?;
+    Ok(Json(Polled {
+        moved: update
+            .moved
+            .into_iter()
+            .map(|m| MovedRef {
+                name: m.name,
+                before: m.before.map(|x| x.to_string()),
+                after: m.after.to_string(),
+            })
+            .collect(),
+        deleted: update.deleted,
+        processed: update.processed.iter().map(|x| x.to_string()).collect(),
+        time: now.elapsed().as_secs_f64(),
+    }))
+}
+
+fn poll(state: &SharedState, repo_spec: &hyperast_vcs_git::git::Repo) -> Result<Update, String> {
+    // polls a copy, the shard of the watcher is not locked while fetching
+    let mut watcher = state
+        .watchers
+        .get(repo_spec)
+        .ok_or_else(|| "repository is not watched".to_string())?
+        .clone();
+    let repo = state
+        .repositories
+        .read()
+        .unwrap()
+        .get_config(repo_spec.clone())
+        .ok_or_else(|| "missing config for repository".to_string())?
+        .try_fetch()
+        .map_err(|e| e.to_string())?;
+    let update = {
+        let mut repositories = state.repositories.write().unwrap();
+        watcher
+            .poll(&repo, &mut repositories)
+            .map_err(|err| err.to_string())?
+    };
+    // unless unwatched in the meantime
+    if let Some(mut w) = state.watchers.get_mut(repo_spec) {
+        *w = watcher;
+    }
+    Ok(update)
+}
+
+/// Polls all the watched repositories
+pub fn poll_all(state: &SharedState) {
+    let watched: Vec<_> = state.watchers.iter().map(|x| x.key().clone()).collect();
+    for repo_spec in watched {
+        match poll(state, &repo_spec) {
+            Ok(update) if !update.processed.is_empty() => log::info!(
+                "processed {} new commits of {}",
+                update.processed.len(),
+                repo_spec.url()
+            ),
+            Ok(_) => (),
+            Err(err) => log::error!("error polling {}: {}", repo_spec.url(), err),
+        }
+    }
+}
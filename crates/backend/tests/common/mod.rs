@@ -0,0 +1,92 @@
+//! Fixtures shared by the integration tests
+#![allow(dead_code)]
+use backend::AppState;
+use hyperast_vcs_git::git::{Forge, Oid, Repo, Repository};
+use std::path::PathBuf;
+
+/// Bare repositories in a temporary directory, served by [`Forge::local`] as `team/<name>`.
+/// The directory is removed when dropped, even if the test panics.
+pub struct TempForge {
+    root: PathBuf,
+}
+
+impl TempForge {
+    pub fn new(name: &str) -> Self {
+        let root = std::env::temp_dir().join(format!("hyperast_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        Self { root }
+    }
+
+    pub fn forge(&self) -> Forge {
+        Forge::local(&self.root)
+    }
+
+    pub fn repo(&self, name: &str) -> Repo {
+        self.forge().repo("team", name)
+    }
+
+    pub fn init(&self, name: &str) -> Repository {
+        let repository = Repository::init_bare(self.root.join(format!("team/{name}.git"))).unwrap();
+        let mut config = repository.config().unwrap();
+        config.set_str("user.name", "hyperast").unwrap();
+        config
+            .set_str("user.email", "hyperast@example.org")
+            .unwrap();
+        repository
+    }
+
+    /// An app state serving these repositories as the `local` forge
+    pub fn state(&self) -> AppState {
+        let mut state = AppState::default();
+        state.forges.insert("local".to_string(), self.forge());
+        state
+    }
+}
+
+impl Drop for TempForge {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.root);
+    }
+}
+
+/// Commits `files` on HEAD, as a child of the current HEAD if any
+pub fn commit(repository: &Repository, files: &[(&str, &str)]) -> Oid {
+    let tree = repository.find_tree(write_tree(repository, files)).unwrap();
+    let sig = repository.signature().unwrap();
+    let parent = repository.head().ok().and_then(|h| h.peel_to_commit().ok());
+    let parents: Vec<_> = parent.iter().collect();
+    repository
+        .commit(Some("HEAD"), &sig, &sig, "commit", &tree, &parents)
+        .unwrap()
+}
+
+/// Writes the nested trees of `files`, given their `/` separated paths and contents
+pub fn write_tree(repository: &Repository, files: &[(&str, &str)]) -> Oid {
+    let mut tree = repository.treebuilder(None).unwrap();
+    let mut dirs: std::collections::BTreeMap<&str, Vec<(&str, &str)>> = Default::default();
+    for (path, text) in files {
+        match path.split_once('/') {
+            Some((dir, rest)) => dirs.entry(dir).or_default().push((rest, *text)),
+            None => {
+                let blob = repository.blob(text.as_bytes()).unwrap();
+                tree.insert(*path, blob, 0o100644).unwrap();
+            }
+        }
+    }
+    for (dir, files) in dirs {
+        let oid = write_tree(repository, &files);
+        tree.insert(dir, oid, 0o040000).unwrap();
+    }
+    tree.write().unwrap()
+}
+
+/// The path parameters of a route on `team/project` at `commit`
+pub fn path<T: serde::de::DeserializeOwned>(commit: Oid) -> T {
+    serde_json::from_value(serde_json::json!({
+        "forge": "local",
+        "user": "team",
+        "name": "project",
+        "commit": commit.to_string(),
+    }))
+    .unwrap()
+}
@@ -0,0 +1,63 @@
+mod common;
+
+#[test]
+fn test_commit_diffs() {
+    let forge = common::TempForge::new("diff");
+    let repository = forge.init("project");
+    let first = common::commit(&repository, &[("util.py", "def a():\n    pass\n")]);
+    let files = [
+        ("util.py", "def a():\n    return 1\n"),
+        ("other.py", "x = 1\n"),
+    ];
+    let second = common::commit(&repository, &files);
+    // same tree as the second commit
+    let third = common::commit(&repository, &files);
+
+    let state = forge.state();
+    let config = hyperast_vcs_git::processing::RepoConfig::Any;
+    state
+        .repositories
+        .write()
+        .unwrap()
+        .register_config(forge.repo("project"), config);
+    let state = std::sync::Arc::new(state);
+    let path = serde_json::from_value(serde_json::json!({
+        "forge": "local",
+        "user": "team",
+        "name": "project",
+        "before": first.to_string(),
+        "after": third.to_string(),
+    }))
+    .unwrap();
+    let query = serde_json::from_value(serde_json::json!({})).unwrap();
+    let diffs: Vec<_> = backend::diff::commit_diffs(state, path, query)
+        .unwrap()
+        .collect();
+
+    // from the most recent commit
+    assert_eq!(diffs.len(), 2);
+    let same = &diffs[0];
+    assert_eq!(same.before, second.to_string());
+    assert_eq!(same.after, third.to_string());
+    assert_eq!(same.total.insert + same.total.delete, 0);
+    assert!(same.files.is_empty());
+
+    let diff = &diffs[1];
+    assert_eq!(diff.before, first.to_string());
+    assert_eq!(diff.after, second.to_string());
+    assert!(diff.error.is_none(), "{:?}", diff.error);
+    assert!(diff.mappings > 0);
+    let other = &diff.files["other.py"];
+    assert!(other.insert > 0);
+    assert_eq!((other.delete, other.update, other.r#move), (0, 0, 0));
+    let util = &diff.files["util.py"];
+    assert!(util.insert + util.delete + util.update > 0);
+    // the totals are the sums of the counts per file
+    let sum = |f: fn(&backend::diff::ActionCounts) -> usize| -> usize {
+        diff.files.values().map(f).sum()
+    };
+    assert_eq!(diff.total.insert, sum(|c| c.insert));
+    assert_eq!(diff.total.delete, sum(|c| c.delete));
+    assert_eq!(diff.total.update, sum(|c| c.update));
+    assert_eq!(diff.total.r#move, sum(|c| c.r#move));
+}
@@ -1,5 +1,7 @@
 use backend::AppState;
 
+mod common;
+
 #[ignore] // ignore (from normal cargo test) for now, later make a feature
 #[test_log::test]
 // slow test, more of an integration test, try using release
@@ -248,3 +250,230 @@ fn compare_querying_cpp_with_and_without_skipping(
 }
 
 // TODO test more of the high level API
+
+#[test]
+fn test_querying_locations() -> Result<(), Box<dyn std::error::Error>> {
+    let forge = common::TempForge::new("locations");
+    let repository = forge.init("project");
+    let text = "def a():\n    pass\n\ndef b():\n    pass\n";
+    let commit = common::commit(&repository, &[("util.py", text)]);
+
+    let state = forge.state();
+    let repo_spec = forge.repo("project");
+    let config = hyperast_vcs_git::processing::RepoConfig::Any;
+    let repository = state
+        .repositories
+        .write()
+        .unwrap()
+        .register_config(repo_spec, config)
+        .fetch();
+    let commits = state.repositories.write().unwrap().pre_process_with_limit(
+        &repository,
+        "",
+        &commit.to_string(),
+        1,
+    )?;
+    let repositories = state.repositories.read().unwrap();
+    let code = repositories
+        .get_commit(&repository.config, &commits[0])
+        .unwrap()
+        .ast_root;
+    let stores = &repositories.processor.main_stores;
+    let language = hyperast_vcs_git::resolve_language("Python").unwrap();
+    let query = "(function_definition name: (identifier) @name)";
+    let query = hyperast_tsquery::Query::new(query, language).map_err(|e| e.to_string())?;
+    let timeout = std::time::Duration::from_secs(10);
+    let locate = |offset| {
+        backend::querying::located_aux(stores, code, &query, None, timeout, 1, offset)
+            .unwrap()
+            .page
+            .unwrap()
+    };
+    let first = locate(0);
+    assert_eq!(first.next_offset, Some(1));
+    let l = &first.locations[0];
+    assert!(l.file.ends_with("util.py"));
+    assert_eq!((l.start, l.end), (4, 5));
+    assert_eq!((l.start_line, l.start_column), (0, 4));
+    assert_eq!(l.captures[0].name, "name");
+    let second = locate(1);
+    assert_eq!(second.next_offset, None);
+    let l = &second.locations[0];
+    assert_eq!((l.start_line, l.start_column, l.end_column), (3, 4, 5));
+    Ok(())
+}
+
+#[test]
+fn test_querying_differential_matches() -> Result<(), Box<dyn std::error::Error>> {
+    use backend::querying::Evolution;
+    use hyperast_vcs_git::git::Oid;
+    let forge = common::TempForge::new("differential");
+    let repository = forge.init("project");
+    let baseline = common::commit(&repository, &[("util.py", "def a():\n    pass\n")]);
+    let text = "def a():\n    pass\n\ndef sum(x, y):\n    return x + y\n";
+    let head = common::commit(&repository, &[("util.py", text)]);
+
+    let state = forge.state();
+    let repo_spec = forge.repo("project");
+    let config = hyperast_vcs_git::processing::RepoConfig::Any;
+    state
+        .repositories
+        .write()
+        .unwrap()
+        .register_config(repo_spec, config);
+    let state = std::sync::Arc::new(state);
+    let content: backend::querying::Content = serde_json::from_value(serde_json::json!({
+        "language": "Python",
+        "query": "(function_definition name: (identifier) @name)",
+        "commits": 1,
+    }))?;
+    let classify = |commit: Oid, baseline: Oid| {
+        let path = serde_json::from_value(serde_json::json!({
+            "forge": "local",
+            "user": "team",
+            "name": "project",
+            "commit": commit.to_string(),
+            "baseline": baseline.to_string(),
+        }))
+        .unwrap();
+        let r = backend::querying::differential_matches(content.clone(), state.clone(), path);
+        let r = r.ok().expect("classified matches").0.matches;
+        r.into_iter()
+            .map(|x| (x.evolution, x.baseline.is_some(), x.commit.is_some()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        classify(head, baseline),
+        vec![
+            (Evolution::Unchanged, true, true),
+            (Evolution::Added, false, true)
+        ]
+    );
+    assert_eq!(
+        classify(baseline, head),
+        vec![
+            (Evolution::Unchanged, true, true),
+            (Evolution::Removed, true, false)
+        ]
+    );
+
+    // one match per child, all located at the same function
+    let content: backend::querying::Content = serde_json::from_value(serde_json::json!({
+        "language": "Python",
+        "query": "(function_definition (_)) @root",
+        "commits": 1,
+    }))?;
+    let path = serde_json::from_value(serde_json::json!({
+        "forge": "local",
+        "user": "team",
+        "name": "project",
+        "commit": head.to_string(),
+        "baseline": baseline.to_string(),
+    }))?;
+    let r = backend::querying::differential_matches(content, state.clone(), path);
+    let r = r.ok().expect("classified matches").0.matches;
+    let count = |evolution| r.iter().filter(|x| x.evolution == evolution).count();
+    assert!(count(Evolution::Unchanged) > 1);
+    assert_eq!(count(Evolution::Unchanged), count(Evolution::Added));
+    assert_eq!(count(Evolution::Removed), 0);
+    Ok(())
+}
+
+#[test]
+fn test_querying_multiple_languages() -> Result<(), Box<dyn std::error::Error>> {
+    let forge = common::TempForge::new("languages");
+    let repository = forge.init("project");
+    let py = "def a():\n    pass\n\ndef b():\n    pass\n";
+    let cpp = "int main() {\n  return 0;\n}\n";
+    let c = "int inc(int x) {\n  return x + 1;\n}\n";
+    let files = [("util.py", py), ("main.cpp", cpp), ("inc.c", c)];
+    let commit = common::commit(&repository, &files);
+
+    let state = forge.state();
+    let repo_spec = forge.repo("project");
+    let config = hyperast_vcs_git::processing::RepoConfig::Any;
+    state
+        .repositories
+        .write()
+        .unwrap()
+        .register_config(repo_spec, config);
+    let path = common::path(commit);
+    // both grammars have a function_definition
+    let content = serde_json::from_value(serde_json::json!({
+        "language": "Python",
+        "queries": {
+            "python": "(function_definition) @f",
+            "Cpp": "(function_definition) @f",
+            "c": "(function_definition) @f",
+        },
+        "commits": 1,
+    }))?;
+    let r = backend::querying::simple(content, std::sync::Arc::new(state), path);
+    let r = r.ok().expect("counts per language");
+    let r = &r.results[0].as_ref().unwrap().inner;
+    let languages = r.languages.as_ref().unwrap();
+    assert_eq!(languages["C"], vec![1]);
+    assert_eq!(languages["Cpp"], vec![1]);
+    assert_eq!(languages["Python"], vec![2]);
+    assert_eq!(r.result, vec![1, 1, 2]);
+    Ok(())
+}
+
+#[test]
+fn test_querying_derived_predicates() -> Result<(), Box<dyn std::error::Error>> {
+    let forge = common::TempForge::new("derived");
+    let repository = forge.init("project");
+    let lists = "project(demo CXX)\nadd_executable(demo main.cpp)\n";
+    let cpp = "int a() { return 0; }\n\
+        int b() { int x = 1; int y = 2; int z = 3; return x + y + z; }\n";
+    let commit = common::commit(&repository, &[("CMakeLists.txt", lists), ("main.cpp", cpp)]);
+
+    let state = forge.state();
+    let repo_spec = forge.repo("project");
+    let config = hyperast_vcs_git::processing::RepoConfig::CppMake;
+    let prepro = hyperast::scripting::lua_scripting::PREPRO_SIZE_WITH_FINISH;
+    state
+        .repositories
+        .write()
+        .unwrap()
+        .register_config_with_prepro(repo_spec, config, prepro.into())?;
+    let state = std::sync::Arc::new(state);
+    let count = |query: &str| -> Result<Vec<u64>, Box<dyn std::error::Error>> {
+        let path = common::path(commit);
+        let content = serde_json::from_value(serde_json::json!({
+            "language": "Cpp",
+            "query": query,
+            "commits": 1,
+        }))?;
+        let r = backend::querying::simple(content, state.clone(), path);
+        let r = r.ok().expect("query results");
+        Ok(r.results[0].as_ref().unwrap().inner.result.clone())
+    };
+    assert_eq!(count("(function_definition) @f")?, vec![2]);
+    // the size computed by the lua script
+    let q = "(function_definition) @f (#derived-gt? @f \"size\" \"30\")";
+    assert_eq!(count(q)?, vec![1]);
+    let q = "(function_definition) @f (#derived-gt? @f \"missing\" \"0\")";
+    assert_eq!(count(q)?, vec![0]);
+    Ok(())
+}
+
+#[test]
+fn test_query_missing_local_repository() -> Result<(), Box<dyn std::error::Error>> {
+    let forge = common::TempForge::new("query_missing");
+    let state = std::sync::Arc::new(forge.state());
+    let content = serde_json::from_value(serde_json::json!({
+        "language": "Python",
+        "query": "(function_definition) @f",
+        "commits": 1,
+    }))?;
+    // team/project was never created, the request fails instead of panicking
+    let path = common::path(hyperast_vcs_git::git::Oid::zero());
+    match backend::querying::simple(content, state, path) {
+        Err(backend::querying::QueryingError::ProcessingError(err)) => {
+            assert!(err.contains("failed to open"), "{}", err)
+        }
+        _ => panic!("expected a processing error"),
+    }
+    Ok(())
+}
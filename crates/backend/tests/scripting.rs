@@ -0,0 +1,278 @@
+mod common;
+
+#[test]
+fn test_scripting_lua() -> Result<(), Box<dyn std::error::Error>> {
+    let forge = common::TempForge::new("lua");
+    let repository = forge.init("project");
+    let py = "def a():\n    pass\n\ndef b():\n    pass\n";
+    let commit = common::commit(&repository, &[("util.py", py)]);
+    let lib = forge.init("lib");
+    let files = [
+        ("lib/a.py", py),
+        ("lib/b.py", "x = 1\ndef b():\n    pass\n"),
+    ];
+    let lib_commit = common::commit(&lib, &files);
+
+    let mut state = forge.state();
+    state.lua_limits.memory = 4 * 1024 * 1024;
+    let repo_spec = forge.repo("project");
+    let config = hyperast_vcs_git::processing::RepoConfig::Any;
+    state
+        .repositories
+        .write()
+        .unwrap()
+        .register_config(repo_spec, config);
+    state
+        .repositories
+        .write()
+        .unwrap()
+        .register_config(forge.repo("lib"), config);
+    let state = std::sync::Arc::new(state);
+    let run = |script: serde_json::Value| {
+        let path = common::path(commit);
+        let script = serde_json::from_value(script).unwrap();
+        backend::scripting_lua::simple(script, state.clone(), path)
+    };
+
+    let script = r#"
+local function count(n, kind)
+  local c = 0
+  if n.kind == kind then
+    c = 1
+  end
+  for _, x in n.children do
+    c += count(x, kind)
+  end
+  return c
+end
+local function find(n, kind)
+  if n.kind == kind then
+    return n
+  end
+  for _, x in n.children do
+    local r = find(x, kind)
+    if r then
+      return r
+    end
+  end
+  return nil
+end
+local file = root.children[1]
+local f = find(file, "function_definition")
+return {
+  name = file.label,
+  functions = count(root, "function_definition"),
+  first = { f.start_byte, f.end_byte },
+}
+"#;
+    let r = run(serde_json::json!({ "script": script })).ok().unwrap();
+    let r = &r.results[0].as_ref().unwrap().inner.result;
+    assert_eq!(r["name"], "util.py");
+    assert_eq!(r["functions"], 2);
+    assert_eq!(r["first"], serde_json::json!([0, 17]));
+
+    let script = "while true do end";
+    let limited = serde_json::json!({ "script": script, "instruction_limit": 1000 });
+    let r = run(limited).ok().unwrap();
+    let err = r.results[0].as_ref().err().unwrap();
+    assert!(err.contains("instruction limit exceeded"), "{}", err);
+
+    // asking for more than the server allows is not enough to exhaust it
+    let script = "local t = {} for i = 1, 10000000 do t[i] = i end return #t";
+    let greedy = serde_json::json!({
+        "script": script,
+        "memory_limit": 1usize << 40,
+        "instruction_limit": u64::MAX,
+    });
+    let r = run(greedy).ok().unwrap();
+    let err = r.results[0].as_ref().err().unwrap();
+    assert!(err.contains("memory"), "{}", err);
+
+    assert!(run(serde_json::json!({ "script": "return (" })).is_err());
+
+    // offsets are relative to each file, not to the previous files of the directory
+    let script = r#"
+local function child(n, f)
+  for _, x in n.children do
+    if f(x) then
+      return x
+    end
+  end
+  return nil
+end
+local b = child(root.children[1], function(x) return x.label == "b.py" end)
+local f = child(b, function(x) return x.kind == "function_definition" end)
+return { name = b.label, file = { b.start_byte, b.end_byte }, function_definition = { f.start_byte, f.end_byte } }
+"#;
+    let path = serde_json::from_value(serde_json::json!({
+        "forge": "local",
+        "user": "team",
+        "name": "lib",
+        "commit": lib_commit.to_string(),
+    }))?;
+    let script = serde_json::from_value(serde_json::json!({ "script": script }))?;
+    let r = backend::scripting_lua::simple(script, state.clone(), path)
+        .ok()
+        .unwrap();
+    let r = &r.results[0].as_ref().unwrap().inner.result;
+    assert_eq!(r["name"], "b.py");
+    assert_eq!(r["file"], serde_json::json!([0, 24]));
+    assert_eq!(r["function_definition"], serde_json::json!([6, 23]));
+    Ok(())
+}
+
+#[test]
+fn test_scripting_across_languages() -> Result<(), Box<dyn std::error::Error>> {
+    let forge = common::TempForge::new("rhai_languages");
+    let files: &[(&str, &str, &str)] = &[
+        (
+            "cpp",
+            "a.cpp",
+            "class A {\n  int f() { return 1; }\n};\nstruct B {};\n",
+        ),
+        (
+            "ts",
+            "a.ts",
+            "class A {\n  f() { return 1; }\n}\ninterface I {}\nfunction g() {}\n",
+        ),
+        (
+            "xml",
+            "pom.xml",
+            "<project><modelVersion>4.0.0</modelVersion></project>\n",
+        ),
+    ];
+    let state = forge.state();
+    let mut commits = vec![];
+    for (name, file, text) in files {
+        let repository = forge.init(name);
+        commits.push(common::commit(&repository, &[(file, text)]));
+        let config = hyperast_vcs_git::processing::RepoConfig::Any;
+        state
+            .repositories
+            .write()
+            .unwrap()
+            .register_config(forge.repo(name), config);
+    }
+    let state = std::sync::Arc::new(state);
+
+    let script: backend::scriptingv1::ScriptContent = serde_json::from_value(serde_json::json!({
+        "init": "#{ decls: 0, functions: 0, roles: 0 }",
+        "filter": r#"
+let r = [];
+for c in children() {
+    r.push([c, #{ decls: 0, functions: 0, roles: 0 }]);
+}
+r"#,
+        "accumulate": r#"
+if is_type_declaration() { s.decls += 1; }
+if is_executable_member() { s.functions += 1; }
+for r in roles() { if r != "" { s.roles += 1; } }
+p.decls += s.decls;
+p.functions += s.functions;
+p.roles += s.roles;"#,
+    }))?;
+    let mut results = vec![];
+    for ((name, _, _), commit) in files.iter().zip(commits) {
+        let path = serde_json::from_value(serde_json::json!({
+            "forge": "local",
+            "user": "team",
+            "name": name,
+            "commit": commit.to_string(),
+        }))?;
+        let r = backend::scriptingv1::simple(script.clone(), state.clone(), path);
+        let r = serde_json::to_value(&r.expect("aggregated").0.result)?;
+        results.push((r["decls"].clone(), r["functions"].clone()));
+    }
+    assert_eq!(
+        results,
+        vec![
+            (serde_json::json!(2), serde_json::json!(1)),
+            (serde_json::json!(2), serde_json::json!(2)),
+            (serde_json::json!(0), serde_json::json!(0)),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_scripting_series() -> Result<(), Box<dyn std::error::Error>> {
+    let forge = common::TempForge::new("series");
+    let repository = forge.init("project");
+    let first = common::commit(&repository, &[("src/a.py", "a = 1\n")]);
+    let files = [("src/a.py", "a = 1\n"), ("lib/b.py", "b = 2\n")];
+    let second = common::commit(&repository, &files);
+
+    let state = forge.state();
+    let config = hyperast_vcs_git::processing::RepoConfig::Any;
+    state
+        .repositories
+        .write()
+        .unwrap()
+        .register_config(forge.repo("project"), config);
+    let state = std::sync::Arc::new(state);
+    let run = |extra: serde_json::Value| {
+        let mut script = serde_json::json!({
+            "init": "#{ files: 0, sizes: #{ nodes: 0 } }",
+            "filter": r#"
+let r = [];
+for c in children() {
+    r.push([c, #{ files: 0, sizes: #{ nodes: 0 } }]);
+}
+r"#,
+            "accumulate": r#"
+if is_file() { s.files += 1; }
+s.sizes.nodes += 1;
+p.files += s.files;
+p.sizes.nodes += s.sizes.nodes;"#,
+        });
+        script
+            .as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        let script = serde_json::from_value(script).unwrap();
+        backend::scriptingv1::series(script, state.clone(), common::path(second))
+            .ok()
+            .unwrap()
+    };
+    let value = |r: &backend::scriptingv1::SeriesResults,
+                 commit: hyperast_vcs_git::git::Oid,
+                 group: &str,
+                 metric: &str| {
+        let commit = commit.to_string();
+        r.rows
+            .iter()
+            .find(|x| x.commit == commit && x.group == group && x.metric == metric)
+            .map(|x| x.value.as_int().unwrap())
+    };
+
+    // the whole range, nested maps being flattened
+    let range = format!("{first}..{second}");
+    let r = run(serde_json::json!({ "range": range, "group": "directory" }));
+    assert!(r.errors.is_empty());
+    assert_eq!(r.rows.len(), 2 * (2 + 3));
+    assert_eq!(value(&r, first, "", "files"), Some(1));
+    assert_eq!(value(&r, second, "", "files"), Some(2));
+    assert_eq!(value(&r, second, "lib", "files"), Some(1));
+    assert_eq!(value(&r, first, "lib", "files"), None);
+    assert!(value(&r, second, "src", "sizes.nodes").unwrap() > 1);
+
+    // only the commit of the path without range
+    let r = run(serde_json::json!({ "group": "file" }));
+    let mut groups: Vec<_> = r.rows.iter().map(|x| x.group.as_str()).collect();
+    groups.dedup();
+    assert_eq!(groups, vec!["lib/b.py", "src/a.py"]);
+    assert!(r.rows.iter().all(|x| x.commit == second.to_string()));
+
+    // errors are kept in csv
+    let r = run(serde_json::json!({ "range": range, "accumulate": "throw \"boom\";" }));
+    assert!(r.rows.is_empty());
+    assert_eq!(r.errors.len(), 2);
+    let csv = r.to_csv();
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("commit,time,group,metric,value,error"));
+    let line = lines.next().unwrap();
+    assert!(line.starts_with(&second.to_string()) || line.starts_with(&first.to_string()));
+    assert!(line.contains(",,,") && line.contains("boom"), "{}", line);
+    assert_eq!(lines.count(), 1);
+    Ok(())
+}
@@ -121,3 +121,13 @@ pub mod tests {
     // do {a} while x ; b
     // v = 3 - 3 + 2 = 2
 }
+
+#[cfg(feature = "persist")]
+impl crate::store::nodes::legion::persist::Persist for Mcc {
+    fn write(&self, w: &mut crate::store::nodes::legion::persist::Writer<'_>) -> std::io::Result<()> {
+        w.u32(self.value)
+    }
+    fn read(r: &mut crate::store::nodes::legion::persist::Reader<'_>) -> std::io::Result<Self> {
+        Ok(Self { value: r.u32()? })
+    }
+}
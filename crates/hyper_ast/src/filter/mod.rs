@@ -300,3 +300,78 @@ mod bloom_64 {
         }
     }
 }
+
+#[cfg(feature = "persist")]
+mod persist {
+    use super::*;
+    use crate::store::nodes::legion::persist::{Persist, Reader, Writer};
+    use std::io;
+
+    impl Persist for BloomSize {
+        fn write(&self, w: &mut Writer<'_>) -> io::Result<()> {
+            w.u8(match self {
+                BloomSize::None => 0,
+                BloomSize::B16 => 1,
+                BloomSize::B32 => 2,
+                BloomSize::B64 => 3,
+                BloomSize::B128 => 4,
+                BloomSize::B256 => 5,
+                BloomSize::B512 => 6,
+                BloomSize::B1024 => 7,
+                BloomSize::B2048 => 8,
+                BloomSize::B4096 => 9,
+                BloomSize::Much => 10,
+            })
+        }
+        fn read(r: &mut Reader<'_>) -> io::Result<Self> {
+            Ok(match r.u8()? {
+                0 => BloomSize::None,
+                1 => BloomSize::B16,
+                2 => BloomSize::B32,
+                3 => BloomSize::B64,
+                4 => BloomSize::B128,
+                5 => BloomSize::B256,
+                6 => BloomSize::B512,
+                7 => BloomSize::B1024,
+                8 => BloomSize::B2048,
+                9 => BloomSize::B4096,
+                10 => BloomSize::Much,
+                x => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("bad bloom size {}", x),
+                    ));
+                }
+            })
+        }
+    }
+
+    /// bits are packed in bytes, lowest first
+    impl<V: BitViewSized + 'static> Persist for Bloom<&'static [u8], V> {
+        fn write(&self, w: &mut Writer<'_>) -> io::Result<()> {
+            let mut bytes = vec![0u8; self.bits.len().div_ceil(8)];
+            for i in self.bits.iter_ones() {
+                bytes[i / 8] |= 1 << (i % 8);
+            }
+            w.bytes(&bytes)
+        }
+        fn read(r: &mut Reader<'_>) -> io::Result<Self> {
+            let bytes = r.bytes()?;
+            let mut s = Self::default();
+            if bytes.len() != s.bits.len().div_ceil(8) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "bloom filter of the wrong size",
+                ));
+            }
+            for (i, b) in bytes.into_iter().enumerate() {
+                for j in 0..8 {
+                    if b & (1 << j) != 0 {
+                        s.bits.set(i * 8 + j, true);
+                    }
+                }
+            }
+            Ok(s)
+        }
+    }
+}
@@ -92,6 +92,15 @@ pub struct DerivedData(
     pub rhai::Map,
 );
 
+#[cfg(feature = "scripting")]
+impl DerivedData {
+    /// Numeric value derived under `name`, integers are converted to floats
+    pub fn get_number(&self, name: &str) -> Option<f64> {
+        let d = self.0.get(name)?;
+        d.as_int().map(|x| x as f64).or_else(|_| d.as_float()).ok()
+    }
+}
+
 #[derive(PartialEq, Eq)]
 pub struct Prepro<HAST, Acc> {
     txt: std::sync::Arc<str>,
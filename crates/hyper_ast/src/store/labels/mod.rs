@@ -88,4 +88,75 @@ impl LabelStore {
         r.get_or_insert("length"); // TODO verify/model statically
         r
     }
+
+    /// number of distinct labels
+    pub fn len(&self) -> usize {
+        self.internal.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.internal.is_empty()
+    }
+
+    /// labels are indexed in insertion order
+    pub fn resolve_index(&self, index: usize) -> Result<&DefaultLabelValue, String> {
+        DefaultSymbol::try_from_usize(index)
+            .and_then(|id| self.internal.resolve(id))
+            .ok_or_else(|| format!("no label at {} in a store of {}", index, self.len()))
+    }
+
+    /// Drops the labels that are not `used`, the others are reinterned in insertion order.
+    /// Returns the new identifier of each kept label, indexed by its previous index.
+    ///
+    /// The first label should be kept, as [`LabelStore::new`] interns it.
+    pub(crate) fn retain_indexes(&mut self, used: &[bool]) -> Vec<Option<DefaultLabelIdentifier>> {
+        let old = std::mem::take(&mut self.internal);
+        old.into_iter()
+            .map(|(id, label)| {
+                used[id.to_usize()]
+                    .then(|| DefaultLabelIdentifier(self.internal.get_or_intern(label)))
+            })
+            .collect()
+    }
+
+    /// Interns a label read from a snapshot, it must end up at `index`.
+    #[cfg(feature = "persist")]
+    pub(crate) fn restore_label(&mut self, index: usize, label: &str) -> Result<(), String> {
+        let id = self.get_or_insert(label);
+        if id.0.to_usize() == index {
+            Ok(())
+        } else {
+            Err(format!(
+                "label {:?} restored at {} instead of {}",
+                label,
+                id.0.to_usize(),
+                index
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retain_indexes_reinterns_in_order() {
+        let mut store = LabelStore::new();
+        let a = store.get_or_insert("a");
+        let b = store.get_or_insert("b");
+        let c = store.get_or_insert("c");
+        let mut used = vec![true; store.len()];
+        used[b.0.to_usize()] = false;
+        let remap = store.retain_indexes(&used);
+        assert_eq!(store.len(), 3);
+        assert_eq!(remap[b.0.to_usize()], None);
+        let a = remap[a.0.to_usize()].unwrap();
+        let c = remap[c.0.to_usize()].unwrap();
+        assert_eq!((a.0.to_usize(), c.0.to_usize()), (1, 2));
+        assert_eq!((store.resolve(&a), store.resolve(&c)), ("a", "c"));
+        assert_eq!(store.resolve_index(0), Ok("length"));
+        assert!(store.resolve_index(3).is_err());
+        assert_eq!(store.get("b"), None);
+    }
 }
@@ -50,7 +50,9 @@ pub struct Flags<F>(pub F);
 
 // TODO unify with `Precomp` so we directly use the code patterns, way more flexible
 // To be done cleanly it would require to preregister them in priority to custom ones
+#[repr(transparent)]
 pub struct StmtCount(pub u8);
+#[repr(transparent)]
 pub struct MemberImportCount(pub u8);
 pub struct LeafCount(pub u8);
 
@@ -0,0 +1,917 @@
+//! Read-only node and label stores backed by a memory-mapped file,
+//! to share a preprocessed history between processes without rebuilding it in each of them.
+//!
+//! A frozen file is written by [`freeze`] from the legion stores,
+//! keeping only the subtrees reachable from the given named roots,
+//! then any number of processes can [`open`] it, which only maps the file and checks its layout.
+//!
+//! Nodes are fixed-size records stored after their children,
+//! children arrays, label offsets and label bytes are flat sections following them.
+//! Types are stored as the `u16` of their language,
+//! languages are matched by name with the ones registered in a [`Langs`].
+//! Only what [`crate::types::HyperAST`] exposes is kept,
+//! e.g. children without spaces and derived data are not frozen.
+use std::any::TypeId;
+use std::fmt::{Debug, Display};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use num::ToPrimitive;
+use string_interner::Symbol;
+
+use super::compo::{self, MemberImportCount, StmtCount};
+use super::legion;
+use crate::hashed::{NodeHashs, SyntaxNodeHashs, SyntaxNodeHashsKinds};
+use crate::nodes::HashSize;
+use crate::store::{SimpleStores, labels};
+use crate::types::{AAAA, LLang, NodeId, TypeU16, TypedNodeId, WithChildren};
+
+const MAGIC: &[u8; 8] = b"HASTFRZN";
+const VERSION: u32 = 1;
+const HEADER_LEN: usize = 64;
+
+const HAS_LABEL: u8 = 1;
+const HAS_STMT_COUNT: u8 = 1 << 1;
+const HAS_MEMBER_IMPORT_COUNT: u8 = 1 << 2;
+const HAS_BYTES_LEN: u8 = 1 << 3;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Hash)]
+#[repr(transparent)]
+pub struct NodeIdentifier(u32);
+
+impl AAAA for NodeIdentifier {}
+impl NodeId for NodeIdentifier {
+    type IdN = Self;
+    fn as_id(&self) -> &Self::IdN {
+        self
+    }
+    unsafe fn from_id(id: Self::IdN) -> Self {
+        id
+    }
+
+    unsafe fn from_ref_id(id: &Self::IdN) -> &Self {
+        id
+    }
+}
+
+impl TypedNodeId for NodeIdentifier {
+    type Ty = crate::types::AnyType;
+    type TyErazed = crate::types::AnyType;
+
+    fn unerase(ty: Self::TyErazed) -> Self::Ty {
+        ty
+    }
+}
+
+impl NodeIdentifier {
+    /// nodes are indexed in topological order, children first
+    pub fn to_u32(&self) -> u32 {
+        self.0
+    }
+}
+
+impl Display for NodeIdentifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Hash)]
+#[repr(transparent)]
+pub struct LabelIdentifier(u32);
+
+impl LabelIdentifier {
+    pub fn to_u32(&self) -> u32 {
+        self.0
+    }
+}
+
+/// Fixed-size record of a node, any bit pattern is valid
+#[repr(C)]
+struct RawNode {
+    /// index of the first child in the children section
+    children: u32,
+    child_count: u16,
+    ty: u16,
+    lang: u8,
+    flags: u8,
+    stmt_count: StmtCount,
+    member_import_count: MemberImportCount,
+    label: LabelIdentifier,
+    size: u32,
+    size_no_spaces: u32,
+    height: u32,
+    line_count: u32,
+    bytes_len: u32,
+    hashs: [HashSize; 3],
+}
+
+const RAW_NODE_LEN: usize = std::mem::size_of::<RawNode>();
+const _: () = assert!(RAW_NODE_LEN == 48);
+
+impl RawNode {
+    fn has(&self, flag: u8) -> bool {
+        self.flags & flag != 0
+    }
+
+    fn write(&self, w: &mut impl Write) -> io::Result<()> {
+        w.write_all(&self.children.to_le_bytes())?;
+        w.write_all(&self.child_count.to_le_bytes())?;
+        w.write_all(&self.ty.to_le_bytes())?;
+        w.write_all(&[
+            self.lang,
+            self.flags,
+            self.stmt_count.0,
+            self.member_import_count.0,
+        ])?;
+        for x in [
+            self.label.0,
+            self.size,
+            self.size_no_spaces,
+            self.height,
+            self.line_count,
+            self.bytes_len,
+        ]
+        .into_iter()
+        .chain(self.hashs)
+        {
+            w.write_all(&x.to_le_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+/// Languages whose types can be frozen, matched by name when opening a frozen file
+#[derive(Default)]
+pub struct Langs(Vec<Lang>);
+
+struct Lang {
+    name: &'static str,
+    /// of the type component, i.e. `TypeU16<L>`
+    tid: TypeId,
+    ty: fn(&legion::HashedNodeRef<'_, legion::NodeIdentifier>) -> Option<u16>,
+}
+
+impl Langs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<L>(&mut self) -> &mut Self
+    where
+        L: 'static + LLang<TypeU16<L>, I = u16>,
+    {
+        fn ty<L: 'static + LLang<TypeU16<L>, I = u16>>(
+            n: &legion::HashedNodeRef<'_, legion::NodeIdentifier>,
+        ) -> Option<u16> {
+            let t = n.get_component::<TypeU16<L>>().ok()?;
+            Some(<L as crate::types::Lang<L::E>>::to_u16(t.e()))
+        }
+        self.0.push(Lang {
+            name: L::as_lang_wrapper().name(),
+            tid: TypeId::of::<TypeU16<L>>(),
+            ty: ty::<L>,
+        });
+        self
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct FreezeStats {
+    pub nodes: usize,
+    pub labels: usize,
+    pub roots: usize,
+    /// size of the frozen file in bytes
+    pub bytes: usize,
+}
+
+/// Sections of a frozen file, derived from the counts of its header
+#[derive(Clone, Copy, Debug)]
+struct Layout {
+    node_count: usize,
+    children_count: usize,
+    label_count: usize,
+    nodes: usize,
+    children: usize,
+    label_ends: usize,
+    label_bytes: usize,
+    langs: usize,
+    roots: usize,
+    end: usize,
+}
+
+impl Layout {
+    fn new(
+        node_count: usize,
+        children_count: usize,
+        label_count: usize,
+        label_bytes_len: usize,
+        langs_len: usize,
+        roots_len: usize,
+    ) -> Option<Self> {
+        fn align(x: usize) -> usize {
+            x.next_multiple_of(8)
+        }
+        let nodes = HEADER_LEN;
+        let children = align(nodes.checked_add(node_count.checked_mul(RAW_NODE_LEN)?)?);
+        let label_ends = align(children.checked_add(children_count.checked_mul(4)?)?);
+        let label_bytes = label_ends.checked_add(label_count.checked_add(1)?.checked_mul(8)?)?;
+        let langs = align(label_bytes.checked_add(label_bytes_len)?);
+        let roots = langs.checked_add(langs_len)?;
+        let end = roots.checked_add(roots_len)?;
+        Some(Self {
+            node_count,
+            children_count,
+            label_count,
+            nodes,
+            children,
+            label_ends,
+            label_bytes,
+            langs,
+            roots,
+            end,
+        })
+    }
+}
+
+/// Writes the subtrees reachable from `roots` to a frozen file at `path`.
+///
+/// Types of languages missing from `langs` make the freezing fail.
+pub fn freeze<TS>(
+    stores: &SimpleStores<TS, legion::NodeStore, labels::LabelStore>,
+    langs: &Langs,
+    roots: impl IntoIterator<Item = (String, legion::NodeIdentifier)>,
+    path: impl AsRef<Path>,
+) -> io::Result<FreezeStats> {
+    let roots: Vec<(String, legion::NodeIdentifier)> = roots.into_iter().collect();
+    let node_store = &stores.node_store;
+
+    // topological order, children first, with an explicit stack to avoid overflowing on deep subtrees
+    let mut index: hashbrown::HashMap<legion::NodeIdentifier, u32> = Default::default();
+    let mut order: Vec<legion::NodeIdentifier> = vec![];
+    let mut stack: Vec<(legion::NodeIdentifier, bool)> =
+        roots.iter().map(|(_, id)| (*id, false)).collect();
+    while let Some((id, expanded)) = stack.pop() {
+        if index.contains_key(&id) {
+            continue;
+        }
+        if expanded {
+            index.insert(id, order.len() as u32);
+            order.push(id);
+            continue;
+        }
+        let Some(n) = node_store.try_resolve(id) else {
+            return Err(invalid_input(format!("{:?} is not in the store", id)));
+        };
+        stack.push((id, true));
+        if let Some(cs) = n.children() {
+            stack.extend(cs.filter(|c| !index.contains_key(c)).map(|c| (c, false)));
+        }
+    }
+
+    let mut labels: Vec<Option<u32>> = vec![None; stores.label_store.len()];
+    let mut label_ends: Vec<u64> = vec![0];
+    let mut label_bytes: Vec<u8> = vec![];
+    let mut children: Vec<u32> = vec![];
+    let mut nodes: Vec<RawNode> = Vec::with_capacity(order.len());
+    for id in &order {
+        let n = node_store.resolve(*id);
+        let Some((lang, ty)) = langs
+            .0
+            .iter()
+            .enumerate()
+            .find_map(|(i, l)| Some((i as u8, (l.ty)(&n)?)))
+        else {
+            return Err(invalid_input(format!(
+                "the type of {:?} is not in a registered language",
+                id
+            )));
+        };
+        let mut flags = 0;
+        let mut label = LabelIdentifier(0);
+        if let Ok(l) = n.get_component::<crate::store::defaults::LabelIdentifier>() {
+            flags |= HAS_LABEL;
+            let i = l.0.to_usize();
+            label = LabelIdentifier(match labels[i] {
+                Some(x) => x,
+                None => {
+                    let s = stores.label_store.resolve_index(i).map_err(invalid_input)?;
+                    label_bytes.extend_from_slice(s.as_bytes());
+                    label_ends.push(label_bytes.len() as u64);
+                    let x = (label_ends.len() - 2) as u32;
+                    labels[i] = Some(x);
+                    x
+                }
+            });
+        }
+        let start = children.len() as u32;
+        if let Some(cs) = n.children() {
+            children.extend(cs.map(|c| index[&c]));
+        }
+        let child_count = (children.len() as u32 - start) as u16;
+        let stmt_count = n.get_component::<StmtCount>().map_or(0, |x| {
+            flags |= HAS_STMT_COUNT;
+            x.0
+        });
+        let member_import_count = n.get_component::<MemberImportCount>().map_or(0, |x| {
+            flags |= HAS_MEMBER_IMPORT_COUNT;
+            x.0
+        });
+        let bytes_len = n.get_component::<compo::BytesLen>().map_or(0, |x| {
+            flags |= HAS_BYTES_LEN;
+            x.0
+        });
+        let hashs = n
+            .get_component::<SyntaxNodeHashs<HashSize>>()
+            .map_or([0; 3], |h| [h.structt, h.label, h.syntax]);
+        nodes.push(RawNode {
+            children: start,
+            child_count,
+            ty,
+            lang,
+            flags,
+            stmt_count: StmtCount(stmt_count),
+            member_import_count: MemberImportCount(member_import_count),
+            label,
+            size: n.get_component::<compo::Size>().map_or(1, |x| x.0),
+            size_no_spaces: n.get_component::<compo::SizeNoSpaces>().map_or(1, |x| x.0),
+            height: n.get_component::<compo::Height>().map_or(1, |x| x.0),
+            line_count: n
+                .get_component::<compo::LineCount>()
+                .map_or(0, |x| x.0 as u32),
+            bytes_len,
+            hashs,
+        });
+    }
+
+    let mut langs_section = vec![];
+    langs_section.extend_from_slice(&(langs.0.len() as u32).to_le_bytes());
+    for l in &langs.0 {
+        langs_section.extend_from_slice(&(l.name.len() as u32).to_le_bytes());
+        langs_section.extend_from_slice(l.name.as_bytes());
+    }
+    let mut roots_section = vec![];
+    roots_section.extend_from_slice(&(roots.len() as u32).to_le_bytes());
+    for (name, id) in &roots {
+        roots_section.extend_from_slice(&index[id].to_le_bytes());
+        roots_section.extend_from_slice(&(name.len() as u32).to_le_bytes());
+        roots_section.extend_from_slice(name.as_bytes());
+    }
+
+    let layout = Layout::new(
+        nodes.len(),
+        children.len(),
+        label_ends.len() - 1,
+        label_bytes.len(),
+        langs_section.len(),
+        roots_section.len(),
+    )
+    .ok_or_else(|| invalid_input("too many nodes or labels".to_string()))?;
+
+    let path = path.as_ref();
+    // written next to the destination then renamed, so readers never map a partial file
+    let tmp = path.with_extension("tmp");
+    let mut w = io::BufWriter::new(std::fs::File::create(&tmp)?);
+    w.write_all(MAGIC)?;
+    w.write_all(&VERSION.to_le_bytes())?;
+    w.write_all(&0u32.to_le_bytes())?;
+    for x in [
+        nodes.len(),
+        children.len(),
+        layout.label_count,
+        label_bytes.len(),
+        langs_section.len(),
+        roots_section.len(),
+    ] {
+        w.write_all(&(x as u64).to_le_bytes())?;
+    }
+    for n in &nodes {
+        n.write(&mut w)?;
+    }
+    pad(
+        &mut w,
+        layout.nodes + nodes.len() * RAW_NODE_LEN,
+        layout.children,
+    )?;
+    for c in &children {
+        w.write_all(&c.to_le_bytes())?;
+    }
+    pad(
+        &mut w,
+        layout.children + children.len() * 4,
+        layout.label_ends,
+    )?;
+    for e in &label_ends {
+        w.write_all(&e.to_le_bytes())?;
+    }
+    w.write_all(&label_bytes)?;
+    pad(&mut w, layout.label_bytes + label_bytes.len(), layout.langs)?;
+    w.write_all(&langs_section)?;
+    w.write_all(&roots_section)?;
+    w.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+    std::fs::rename(&tmp, path)?;
+
+    let stats = FreezeStats {
+        nodes: layout.node_count,
+        labels: layout.label_count,
+        roots: roots.len(),
+        bytes: layout.end,
+    };
+    log::info!(
+        "froze {} nodes and {} labels of {} roots in {} bytes",
+        stats.nodes,
+        stats.labels,
+        stats.roots,
+        stats.bytes
+    );
+    Ok(stats)
+}
+
+/// Zeros from `pos` to the start of the next section at `to`
+fn pad(w: &mut impl Write, pos: usize, to: usize) -> io::Result<()> {
+    w.write_all(&vec![0; to - pos])
+}
+
+fn invalid_input(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid frozen file: {msg}"),
+    )
+}
+
+/// Maps the frozen file at `path`.
+///
+/// The indexes of nodes, children and labels are checked once,
+/// so that a corrupted or truncated file is rejected instead of making resolving panic.
+/// The file must not be modified while it is mapped,
+/// [`freeze`] replaces files atomically thus can be used to update it.
+pub fn open<TS>(path: impl AsRef<Path>, langs: &Langs) -> io::Result<FrozenStores<TS>> {
+    if cfg!(target_endian = "big") {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "frozen files are little-endian",
+        ));
+    }
+    let file = std::fs::File::open(path)?;
+    let map = Arc::new(unsafe { memmap2::Mmap::map(&file)? });
+    let header = map
+        .get(..HEADER_LEN)
+        .ok_or_else(|| invalid_data("truncated header"))?;
+    if &header[..8] != MAGIC {
+        return Err(invalid_data("wrong magic number"));
+    }
+    let version = u32::from_le_bytes(header[8..12].try_into().unwrap());
+    if version != VERSION {
+        return Err(invalid_data(&format!(
+            "version {version} instead of {VERSION}"
+        )));
+    }
+    let count = |i: usize| {
+        let x = u64::from_le_bytes(header[16 + 8 * i..24 + 8 * i].try_into().unwrap());
+        x.to_usize().ok_or_else(|| invalid_data("count overflow"))
+    };
+    let layout = Layout::new(
+        count(0)?,
+        count(1)?,
+        count(2)?,
+        count(3)?,
+        count(4)?,
+        count(5)?,
+    )
+    .ok_or_else(|| invalid_data("count overflow"))?;
+    if layout.end != map.len() {
+        return Err(invalid_data("wrong length"));
+    }
+    // mappings are page-aligned and sections are 8-aligned
+    if map.as_ptr() as usize % std::mem::align_of::<u64>() != 0 {
+        return Err(invalid_data("misaligned mapping"));
+    }
+
+    let mut r = &map[layout.langs..layout.roots];
+    let mut frozen_langs = vec![];
+    for _ in 0..read_u32(&mut r)? {
+        let name = read_str(&mut r)?;
+        let tid = langs.0.iter().find(|l| l.name == name).map(|l| l.tid);
+        if tid.is_none() {
+            log::warn!("{name} is not registered, its nodes are not typed");
+        }
+        frozen_langs.push(tid);
+    }
+    let mut r = &map[layout.roots..layout.end];
+    let mut roots = vec![];
+    for _ in 0..read_u32(&mut r)? {
+        let id = read_u32(&mut r)?;
+        if id as usize >= layout.node_count {
+            return Err(invalid_data("root out of bounds"));
+        }
+        roots.push((read_str(&mut r)?.to_string(), NodeIdentifier(id)));
+    }
+    let store = NodeStore {
+        map: map.clone(),
+        layout,
+        langs: frozen_langs.into(),
+        roots: roots.into(),
+    };
+    let label_store = LabelStore { map, layout };
+    validate(&store, &label_store)?;
+    Ok(SimpleStores {
+        label_store,
+        node_store: store,
+        type_store: Default::default(),
+    })
+}
+
+/// Checks the indexes that resolving relies on
+fn validate(store: &NodeStore, label_store: &LabelStore) -> io::Result<()> {
+    let l = &store.layout;
+    for raw in store.raw_nodes() {
+        if raw.lang as usize >= store.langs.len() {
+            return Err(invalid_data("language out of bounds"));
+        }
+        if raw.children as usize + raw.child_count as usize > l.children_count {
+            return Err(invalid_data("children out of bounds"));
+        }
+        if raw.has(HAS_LABEL) && raw.label.0 as usize >= l.label_count {
+            return Err(invalid_data("label out of bounds"));
+        }
+    }
+    if store
+        .children()
+        .iter()
+        .any(|c| c.0 as usize >= l.node_count)
+    {
+        return Err(invalid_data("child out of bounds"));
+    }
+    let ends = label_store.ends();
+    let bytes = &label_store.map[l.label_bytes..l.langs];
+    for w in ends.windows(2) {
+        let (start, end) = (w[0] as usize, w[1] as usize);
+        if start > end || end > bytes.len() {
+            return Err(invalid_data("label out of bounds"));
+        }
+        if std::str::from_utf8(&bytes[start..end]).is_err() {
+            return Err(invalid_data("label is not utf-8"));
+        }
+    }
+    Ok(())
+}
+
+fn read_u32(r: &mut &[u8]) -> io::Result<u32> {
+    let (x, rest) = r
+        .split_first_chunk::<4>()
+        .ok_or_else(|| invalid_data("truncated section"))?;
+    *r = rest;
+    Ok(u32::from_le_bytes(*x))
+}
+
+fn read_str<'a>(r: &mut &'a [u8]) -> io::Result<&'a str> {
+    let len = read_u32(r)? as usize;
+    if r.len() < len {
+        return Err(invalid_data("truncated section"));
+    }
+    let (s, rest) = r.split_at(len);
+    *r = rest;
+    std::str::from_utf8(s).map_err(|_| invalid_data("name is not utf-8"))
+}
+
+pub type FrozenStores<TS> = SimpleStores<TS, NodeStore, LabelStore>;
+
+/// Read-only view of the nodes of a frozen file
+pub struct NodeStore {
+    map: Arc<memmap2::Mmap>,
+    layout: Layout,
+    /// type component of each frozen language, if registered in this process
+    langs: Box<[Option<TypeId>]>,
+    roots: Box<[(String, NodeIdentifier)]>,
+}
+
+impl Debug for NodeStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NodeStore")
+            .field("nodes", &self.layout.node_count)
+            .field("roots", &self.roots.len())
+            .finish()
+    }
+}
+
+impl NodeStore {
+    fn raw_nodes(&self) -> &[RawNode] {
+        let bytes =
+            &self.map[self.layout.nodes..self.layout.nodes + self.layout.node_count * RAW_NODE_LEN];
+        // SAFETY: in bounds, aligned (checked by [`open`]) and any bit pattern is a valid RawNode
+        unsafe {
+            std::slice::from_raw_parts(bytes.as_ptr() as *const RawNode, self.layout.node_count)
+        }
+    }
+
+    fn children(&self) -> &[NodeIdentifier] {
+        let l = &self.layout;
+        let bytes = &self.map[l.children..l.children + l.children_count * 4];
+        // SAFETY: in bounds, aligned and NodeIdentifier is a transparent u32
+        unsafe {
+            std::slice::from_raw_parts(bytes.as_ptr() as *const NodeIdentifier, l.children_count)
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.layout.node_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layout.node_count == 0
+    }
+
+    /// The root frozen under `name`
+    pub fn root(&self, name: &str) -> Option<NodeIdentifier> {
+        self.roots
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, id)| *id)
+    }
+
+    pub fn roots(&self) -> impl Iterator<Item = (&str, NodeIdentifier)> {
+        self.roots.iter().map(|(n, id)| (n.as_str(), *id))
+    }
+
+    pub fn try_resolve(&self, id: NodeIdentifier) -> Option<HashedNodeRef<'_>> {
+        let raw = self.raw_nodes().get(id.0 as usize)?;
+        Some(HashedNodeRef { raw, store: self })
+    }
+}
+
+impl crate::types::NStore for NodeStore {
+    type IdN = NodeIdentifier;
+    type Idx = u16;
+}
+
+impl<'a> crate::types::NLending<'a, NodeIdentifier> for NodeStore {
+    type N = HashedNodeRef<'a>;
+}
+
+impl crate::types::NodeStore<NodeIdentifier> for NodeStore {
+    fn resolve(&self, id: &NodeIdentifier) -> HashedNodeRef<'_> {
+        self.try_resolve(*id).unwrap()
+    }
+}
+
+impl crate::types::NStore for &NodeStore {
+    type IdN = NodeIdentifier;
+    type Idx = u16;
+}
+
+impl<'a> crate::types::NLending<'a, NodeIdentifier> for &NodeStore {
+    type N = HashedNodeRef<'a>;
+}
+
+impl crate::types::NodeStore<NodeIdentifier> for &NodeStore {
+    fn resolve(&self, id: &NodeIdentifier) -> HashedNodeRef<'_> {
+        self.try_resolve(*id).unwrap()
+    }
+}
+
+/// Read-only view of the labels of a frozen file
+pub struct LabelStore {
+    map: Arc<memmap2::Mmap>,
+    layout: Layout,
+}
+
+impl Debug for LabelStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LabelStore")
+            .field("labels", &self.layout.label_count)
+            .finish()
+    }
+}
+
+impl LabelStore {
+    fn ends(&self) -> &[u64] {
+        let l = &self.layout;
+        let bytes = &self.map[l.label_ends..l.label_bytes];
+        // SAFETY: in bounds and aligned
+        unsafe { std::slice::from_raw_parts(bytes.as_ptr() as *const u64, l.label_count + 1) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.layout.label_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layout.label_count == 0
+    }
+}
+
+impl crate::types::LStore for LabelStore {
+    type I = LabelIdentifier;
+}
+
+impl crate::types::LStore for &LabelStore {
+    type I = LabelIdentifier;
+}
+
+impl crate::types::LabelStore<str> for LabelStore {
+    type I = LabelIdentifier;
+    /// frozen stores are read-only, only returns labels already in the store
+    ///
+    /// panics if the label is missing, linear in the number of labels
+    fn get_or_insert<T: std::borrow::Borrow<str>>(&mut self, node: T) -> Self::I {
+        self.get(node).expect("label not in frozen store")
+    }
+    /// linear in the number of labels
+    fn get<T: std::borrow::Borrow<str>>(&self, node: T) -> Option<Self::I> {
+        let node = node.borrow();
+        (0..self.len() as u32)
+            .map(LabelIdentifier)
+            .find(|l| self.resolve(l) == node)
+    }
+    fn resolve(&self, id: &Self::I) -> &str {
+        let ends = self.ends();
+        let (start, end) = (ends[id.0 as usize], ends[id.0 as usize + 1]);
+        let start = self.layout.label_bytes + start as usize;
+        let end = self.layout.label_bytes + end as usize;
+        std::str::from_utf8(&self.map[start..end]).expect("label is not utf-8")
+    }
+}
+
+impl crate::types::LabelStore<str> for &LabelStore {
+    type I = LabelIdentifier;
+    fn get_or_insert<T: std::borrow::Borrow<str>>(&mut self, node: T) -> Self::I {
+        self.get(node).expect("label not in frozen store")
+    }
+    fn get<T: std::borrow::Borrow<str>>(&self, node: T) -> Option<Self::I> {
+        (*self).get(node)
+    }
+    fn resolve(&self, id: &Self::I) -> &str {
+        (*self).resolve(id)
+    }
+}
+
+impl<'a, TS> crate::types::StoreLending<'a> for FrozenStores<TS>
+where
+    TS: Copy + crate::types::TypeStore,
+{
+    type S = SimpleStores<TS, &'a NodeStore, &'a LabelStore>;
+}
+
+impl<TS> crate::types::StoreRefAssoc for FrozenStores<TS>
+where
+    TS: Copy + crate::types::TypeStore,
+{
+    type S<'a> = SimpleStores<TS, &'a NodeStore, &'a LabelStore>;
+}
+
+#[derive(Clone, Copy)]
+pub struct HashedNodeRef<'a> {
+    raw: &'a RawNode,
+    store: &'a NodeStore,
+}
+
+impl Debug for HashedNodeRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HashedNodeRef")
+            .field("lang", &self.raw.lang)
+            .field("ty", &self.raw.ty)
+            .field("label", &self._label())
+            .field("child_count", &self.raw.child_count)
+            .finish()
+    }
+}
+
+impl<'a> HashedNodeRef<'a> {
+    fn _children(&self) -> &'a [NodeIdentifier] {
+        let start = self.raw.children as usize;
+        &self.store.children()[start..start + self.raw.child_count as usize]
+    }
+
+    pub fn size_no_spaces(&self) -> usize {
+        self.raw.size_no_spaces as usize
+    }
+
+    /// The type as the `u16` of its language, even if the language is not registered
+    pub fn raw_type(&self) -> u16 {
+        self.raw.ty
+    }
+
+    fn _label(&self) -> Option<&'a LabelIdentifier> {
+        self.raw.has(HAS_LABEL).then_some(&self.raw.label)
+    }
+}
+
+impl crate::types::Node for HashedNodeRef<'_> {}
+
+impl crate::types::Stored for HashedNodeRef<'_> {
+    type TreeId = NodeIdentifier;
+}
+
+impl<'a> crate::types::CLending<'a, u16, NodeIdentifier> for HashedNodeRef<'_> {
+    type Children = crate::types::ChildrenSlice<'a, NodeIdentifier>;
+}
+
+impl crate::types::WithChildren for HashedNodeRef<'_> {
+    type ChildIdx = u16;
+
+    fn child_count(&self) -> Self::ChildIdx {
+        self.raw.child_count
+    }
+
+    fn child(&self, idx: &Self::ChildIdx) -> Option<<Self::TreeId as NodeId>::IdN> {
+        self._children().get(*idx as usize).copied()
+    }
+
+    fn child_rev(&self, idx: &Self::ChildIdx) -> Option<<Self::TreeId as NodeId>::IdN> {
+        self._children().iter().rev().nth(*idx as usize).copied()
+    }
+
+    fn children(
+        &self,
+    ) -> Option<crate::types::LendC<'_, Self, Self::ChildIdx, <Self::TreeId as NodeId>::IdN>> {
+        (self.raw.child_count != 0).then(|| self._children().into())
+    }
+}
+
+impl crate::types::Labeled for HashedNodeRef<'_> {
+    type Label = LabelIdentifier;
+
+    fn get_label_unchecked(&self) -> &LabelIdentifier {
+        self._label().expect("node has no label")
+    }
+
+    fn try_get_label(&self) -> Option<&LabelIdentifier> {
+        self._label()
+    }
+}
+
+impl super::ErasedHolder for HashedNodeRef<'_> {
+    fn unerase_ref<T: 'static + Send + Sync>(&self, tid: TypeId) -> Option<&T> {
+        if tid != TypeId::of::<T>() || self.store.langs[self.raw.lang as usize] != Some(tid) {
+            return None;
+        }
+        // SAFETY: T is the `TypeU16` of the language of the node, a transparent u16
+        Some(unsafe { &*(&self.raw.ty as *const u16 as *const T) })
+    }
+}
+
+impl crate::types::Tree for HashedNodeRef<'_> {
+    fn has_children(&self) -> bool {
+        self.raw.child_count != 0
+    }
+
+    fn has_label(&self) -> bool {
+        self.raw.has(HAS_LABEL)
+    }
+}
+
+impl crate::types::WithStats for HashedNodeRef<'_> {
+    fn size(&self) -> usize {
+        self.raw.size as usize
+    }
+
+    fn height(&self) -> usize {
+        self.raw.height as usize
+    }
+
+    fn line_count(&self) -> usize {
+        self.raw.line_count as usize
+    }
+}
+
+impl crate::types::WithSerialization for HashedNodeRef<'_> {
+    fn try_bytes_len(&self) -> Option<usize> {
+        self.raw
+            .has(HAS_BYTES_LEN)
+            .then_some(self.raw.bytes_len as usize)
+    }
+}
+
+impl crate::types::WithHashs for HashedNodeRef<'_> {
+    type HK = SyntaxNodeHashsKinds;
+    type HP = HashSize;
+
+    fn hash(&self, kind: impl std::ops::Deref<Target = Self::HK>) -> Self::HP {
+        let [structt, label, syntax] = self.raw.hashs;
+        SyntaxNodeHashs {
+            structt,
+            label,
+            syntax,
+        }
+        .hash(&kind)
+    }
+}
+
+impl crate::types::WithMetaData<StmtCount> for HashedNodeRef<'_> {
+    fn get_metadata(&self) -> Option<&StmtCount> {
+        self.raw.has(HAS_STMT_COUNT).then_some(&self.raw.stmt_count)
+    }
+}
+
+impl crate::types::WithMetaData<MemberImportCount> for HashedNodeRef<'_> {
+    fn get_metadata(&self) -> Option<&MemberImportCount> {
+        self.raw
+            .has(HAS_MEMBER_IMPORT_COUNT)
+            .then_some(&self.raw.member_import_count)
+    }
+}
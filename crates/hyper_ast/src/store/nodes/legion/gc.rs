@@ -0,0 +1,180 @@
+//! Reachability-based garbage collection of the legion [`NodeStore`] and of the [`LabelStore`].
+//!
+//! Subtrees are deduplicated thus shared between commits, so a node can only be reclaimed
+//! when none of the given roots reaches it through children.
+//! Collection is a stop-the-world mark and sweep:
+//! nodes reachable from the roots are marked, other entities are removed from the world
+//! and from the deduplication map, then the labels that are not used anymore are dropped.
+//!
+//! Labels are compacted, i.e. surviving labels get new identifiers,
+//! thus identifiers of labels and nodes kept outside of the stores must be roots or be discarded,
+//! e.g. caches of generators.
+use legion::{EntityStore, IntoQuery};
+use string_interner::Symbol;
+
+use super::{EntryRef, NodeIdentifier, NodeStore};
+use crate::store::{SimpleStores, defaults::LabelIdentifier, labels::LabelStore, nodes::compo};
+
+#[derive(Debug, Default, Clone)]
+pub struct GcStats {
+    pub nodes_before: usize,
+    pub nodes_after: usize,
+    pub labels_before: usize,
+    pub labels_after: usize,
+    /// deduplicated subtrees per height, before the collection
+    #[cfg(feature = "subtree-stats")]
+    pub height_counts_before: Vec<u32>,
+    /// deduplicated subtrees per height, after the collection
+    #[cfg(feature = "subtree-stats")]
+    pub height_counts_after: Vec<u32>,
+}
+
+impl GcStats {
+    pub fn reclaimed_nodes(&self) -> usize {
+        self.nodes_before - self.nodes_after
+    }
+    pub fn reclaimed_labels(&self) -> usize {
+        self.labels_before - self.labels_after
+    }
+}
+
+/// References to other nodes, i.e. the children
+pub(super) fn children<'a>(entry: &'a EntryRef<'_>) -> &'a [NodeIdentifier] {
+    if let Ok(cs) = entry.get_component::<compo::CS<NodeIdentifier>>() {
+        &cs.0
+    } else if let Ok(cs) = entry.get_component::<compo::CS0<NodeIdentifier, 1>>() {
+        &cs.0
+    } else if let Ok(cs) = entry.get_component::<compo::CS0<NodeIdentifier, 2>>() {
+        &cs.0
+    } else {
+        &[]
+    }
+}
+
+/// Removes the nodes unreachable from `roots` and the labels they were the only ones to use.
+///
+/// Roots that are not in the store are ignored.
+pub fn collect<TS>(
+    stores: &mut SimpleStores<TS, NodeStore, LabelStore>,
+    roots: impl IntoIterator<Item = NodeIdentifier>,
+) -> GcStats {
+    let mut stats = GcStats {
+        nodes_before: stores.node_store.len(),
+        labels_before: stores.label_store.len(),
+        #[cfg(feature = "subtree-stats")]
+        height_counts_before: stores.node_store.inner.stats.height_counts.clone(),
+        ..Default::default()
+    };
+    let live = stores.node_store.mark(roots);
+    stores.node_store.sweep(&live);
+    compact_labels(&mut stores.node_store, &mut stores.label_store, &live);
+    #[cfg(feature = "subtree-stats")]
+    recompute_stats(&mut stores.node_store, &live);
+    stats.nodes_after = stores.node_store.len();
+    stats.labels_after = stores.label_store.len();
+    #[cfg(feature = "subtree-stats")]
+    {
+        stats.height_counts_after = stores.node_store.inner.stats.height_counts.clone();
+    }
+    log::info!(
+        "collected {} nodes and {} labels, {} nodes and {} labels remaining",
+        stats.reclaimed_nodes(),
+        stats.reclaimed_labels(),
+        stats.nodes_after,
+        stats.labels_after
+    );
+    stats
+}
+
+impl NodeStore {
+    /// Nodes reachable from `roots`
+    pub fn mark(
+        &self,
+        roots: impl IntoIterator<Item = NodeIdentifier>,
+    ) -> hashbrown::HashSet<NodeIdentifier> {
+        let world = &self.inner.internal;
+        let mut live: hashbrown::HashSet<NodeIdentifier> = Default::default();
+        // explicit stack to avoid overflowing on deep subtrees
+        let mut stack: Vec<NodeIdentifier> = roots.into_iter().collect();
+        while let Some(id) = stack.pop() {
+            if live.contains(&id) {
+                continue;
+            }
+            let Ok(entry) = world.entry_ref(id) else {
+                log::warn!("{:?} is not in the store", id);
+                continue;
+            };
+            live.insert(id);
+            stack.extend(children(&entry).iter().filter(|c| !live.contains(*c)));
+        }
+        live
+    }
+
+    /// Removes the nodes that are not `live`, they must not be referenced by `live` nodes.
+    pub fn sweep(&mut self, live: &hashbrown::HashSet<NodeIdentifier>) {
+        self.dedup.retain(|id, _| live.contains(id));
+        let world = &mut self.inner.internal;
+        let dead: Vec<NodeIdentifier> = <legion::Entity>::query()
+            .iter(world)
+            .filter(|id| !live.contains(*id))
+            .copied()
+            .collect();
+        for id in dead {
+            world.remove(id);
+        }
+    }
+}
+
+/// Drops the labels not used by `live` nodes and remaps the labels of `live` nodes
+fn compact_labels(
+    node_store: &mut NodeStore,
+    label_store: &mut LabelStore,
+    live: &hashbrown::HashSet<NodeIdentifier>,
+) {
+    let world = &mut node_store.inner.internal;
+    let mut used = vec![false; label_store.len()];
+    // interned by [`LabelStore::new`], see [`LabelStore::retain_indexes`]
+    if let Some(first) = used.first_mut() {
+        *first = true;
+    }
+    for id in live {
+        let entry = world.entry_ref(*id).unwrap();
+        if let Ok(l) = entry.get_component::<LabelIdentifier>() {
+            used[l.0.to_usize()] = true;
+        }
+        if let Ok(ls) = entry.get_component::<compo::CS<LabelIdentifier>>() {
+            for l in ls.0.iter() {
+                used[l.0.to_usize()] = true;
+            }
+        }
+    }
+    if used.iter().all(|x| *x) {
+        return;
+    }
+    let remap = label_store.retain_indexes(&used);
+    for id in live {
+        let mut entry = world.entry(*id).unwrap();
+        if let Ok(l) = entry.get_component_mut::<LabelIdentifier>() {
+            *l = remap[l.0.to_usize()].unwrap();
+        }
+        if let Ok(ls) = entry.get_component_mut::<compo::CS<LabelIdentifier>>() {
+            for l in ls.0.iter_mut() {
+                *l = remap[l.0.to_usize()].unwrap();
+            }
+        }
+    }
+}
+
+#[cfg(feature = "subtree-stats")]
+fn recompute_stats(node_store: &mut NodeStore, live: &hashbrown::HashSet<NodeIdentifier>) {
+    let inner = &mut node_store.inner;
+    inner.stats.clear_dedup();
+    for id in live {
+        let entry = inner.internal.entry_ref(*id).unwrap();
+        let Ok(hashs) = entry.get_component::<crate::hashed::SyntaxNodeHashs<u32>>() else {
+            continue;
+        };
+        let height = entry.get_component::<compo::Height>().map_or(1, |h| h.0);
+        inner.stats.add_height_dedup(height, *hashs);
+    }
+}
@@ -10,6 +10,9 @@ use std::{fmt::Debug, hash::Hash, ops::Deref};
 
 pub mod dyn_builder;
 mod elem;
+pub mod gc;
+#[cfg(feature = "persist")]
+pub mod persist;
 pub use elem::{EntryRef, HashedNode, HashedNodeRef, NodeIdentifier};
 
 pub struct NodeStore<I = NodeStoreInner, D = hashbrown::HashMap<NodeIdentifier, (), ()>> {
@@ -0,0 +1,792 @@
+//! Versioned on-disk snapshots of the legion [`NodeStore`] and of the [`LabelStore`].
+//!
+//! A snapshot is a directory containing a sequence of append-only segments.
+//! Each segment holds the labels and the nodes that were not already written by previous segments,
+//! so appending to a snapshot only writes the subtrees deduplicated since the last append,
+//! and restoring replays the segments in order.
+//!
+//! Nodes are written in topological order (children first) and references between nodes
+//! are written as indexes in the snapshot, which are remapped to fresh entities on restore.
+//! Components are (de)serialized through a [`Registry`],
+//! language crates must register their type components using [`Registry::register_type`].
+//!
+//! # Format of a segment
+//! ```text
+//! MAGIC FORMAT_VERSION
+//! first_label:u64 label_count:u64 (len:u32 utf8)*
+//! component_count:u32 (len:u32 name)*
+//! first_node:u64 node_count:u64 (compo_count:u16 (compo_idx:u16 payload)*)*
+//! ```
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use legion::{EntityStore, IntoQuery, storage::ComponentTypeId};
+
+use super::{EntryRef, HashedNodeRef, NodeIdentifier, NodeStore, dyn_builder, gc::children};
+use crate::store::{SimpleStores, defaults::LabelIdentifier, labels::LabelStore, nodes::compo};
+use crate::utils::make_hash;
+
+pub const MAGIC: &[u8; 8] = b"HASTSNAP";
+/// Bump it each time the layout of segments or the payload of a registered component changes.
+pub const FORMAT_VERSION: u32 = 1;
+
+const SEGMENT_EXT: &str = "hseg";
+
+/// A component that can be written to and read from a snapshot segment.
+pub trait Persist: legion::storage::Component + Sized {
+    fn write(&self, w: &mut Writer<'_>) -> io::Result<()>;
+    fn read(r: &mut Reader<'_>) -> io::Result<Self>;
+}
+
+/// Writes the payload of components,
+/// node references are translated to their index in the snapshot.
+pub struct Writer<'a> {
+    out: &'a mut dyn Write,
+    indexes: &'a hashbrown::HashMap<NodeIdentifier, u64>,
+}
+
+impl<'a> Writer<'a> {
+    pub fn new(out: &'a mut dyn Write, snapshot: &'a Snapshot) -> Self {
+        Self {
+            out,
+            indexes: &snapshot.indexes,
+        }
+    }
+    pub fn u8(&mut self, x: u8) -> io::Result<()> {
+        self.out.write_all(&[x])
+    }
+    pub fn u16(&mut self, x: u16) -> io::Result<()> {
+        self.out.write_all(&x.to_le_bytes())
+    }
+    pub fn u32(&mut self, x: u32) -> io::Result<()> {
+        self.out.write_all(&x.to_le_bytes())
+    }
+    pub fn u64(&mut self, x: u64) -> io::Result<()> {
+        self.out.write_all(&x.to_le_bytes())
+    }
+    pub fn i64(&mut self, x: i64) -> io::Result<()> {
+        self.out.write_all(&x.to_le_bytes())
+    }
+    pub fn f64(&mut self, x: f64) -> io::Result<()> {
+        self.out.write_all(&x.to_le_bytes())
+    }
+    pub fn bytes(&mut self, x: &[u8]) -> io::Result<()> {
+        self.u32(x.len() as u32)?;
+        self.out.write_all(x)
+    }
+    pub fn str(&mut self, x: &str) -> io::Result<()> {
+        self.bytes(x.as_bytes())
+    }
+    pub fn label(&mut self, x: &LabelIdentifier) -> io::Result<()> {
+        use string_interner::Symbol;
+        self.u32(x.0.to_usize() as u32)
+    }
+    /// Fails if `x` was not written before, i.e. if nodes are not written in topological order.
+    pub fn node(&mut self, x: &NodeIdentifier) -> io::Result<()> {
+        let Some(&i) = self.indexes.get(x) else {
+            return Err(invalid_data(format!("{:?} was not persisted before", x)));
+        };
+        self.u64(i)
+    }
+    pub fn nodes(&mut self, xs: &[NodeIdentifier]) -> io::Result<()> {
+        self.u32(xs.len() as u32)?;
+        xs.iter().try_for_each(|x| self.node(x))
+    }
+}
+
+/// Reads the payload of components,
+/// node references are translated back to the restored entities.
+pub struct Reader<'a> {
+    input: &'a mut dyn Read,
+    ids: &'a [NodeIdentifier],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(input: &'a mut dyn Read, snapshot: &'a Snapshot) -> Self {
+        Self {
+            input,
+            ids: &snapshot.ids,
+        }
+    }
+    fn array<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        let mut b = [0; N];
+        self.input.read_exact(&mut b)?;
+        Ok(b)
+    }
+    pub fn u8(&mut self) -> io::Result<u8> {
+        self.array::<1>().map(|x| x[0])
+    }
+    pub fn u16(&mut self) -> io::Result<u16> {
+        self.array().map(u16::from_le_bytes)
+    }
+    pub fn u32(&mut self) -> io::Result<u32> {
+        self.array().map(u32::from_le_bytes)
+    }
+    pub fn u64(&mut self) -> io::Result<u64> {
+        self.array().map(u64::from_le_bytes)
+    }
+    pub fn i64(&mut self) -> io::Result<i64> {
+        self.array().map(i64::from_le_bytes)
+    }
+    pub fn f64(&mut self) -> io::Result<f64> {
+        self.array().map(f64::from_le_bytes)
+    }
+    pub fn bytes(&mut self) -> io::Result<Vec<u8>> {
+        let len = self.u32()? as usize;
+        let mut b = vec![0; len];
+        self.input.read_exact(&mut b)?;
+        Ok(b)
+    }
+    pub fn string(&mut self) -> io::Result<String> {
+        String::from_utf8(self.bytes()?).map_err(invalid_data)
+    }
+    pub fn label(&mut self) -> io::Result<LabelIdentifier> {
+        let i = self.u32()? as usize;
+        crate::store::labels::label_id_from_usize(i)
+            .ok_or_else(|| invalid_data(format!("bad label index {}", i)))
+    }
+    pub fn node(&mut self) -> io::Result<NodeIdentifier> {
+        let i = self.u64()?;
+        self.ids
+            .get(i as usize)
+            .copied()
+            .ok_or_else(|| invalid_data(format!("node {} is not restored yet", i)))
+    }
+    pub fn nodes(&mut self) -> io::Result<Box<[NodeIdentifier]>> {
+        let len = self.u32()? as usize;
+        (0..len).map(|_| self.node()).collect()
+    }
+}
+
+fn invalid_data<E: Into<Box<dyn std::error::Error + Send + Sync>>>(e: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+struct Codec {
+    name: Cow<'static, str>,
+    write: fn(&EntryRef<'_>, &mut Writer<'_>) -> io::Result<()>,
+    read: fn(&mut Reader<'_>, &mut dyn_builder::EntityBuilder) -> io::Result<()>,
+}
+
+fn write_erased<T: Persist>(e: &EntryRef<'_>, w: &mut Writer<'_>) -> io::Result<()> {
+    let c = e
+        .get_component::<T>()
+        .map_err(|e| invalid_data(format!("{:?}", e)))?;
+    c.write(w)
+}
+
+fn read_erased<T: Persist>(
+    r: &mut Reader<'_>,
+    builder: &mut dyn_builder::EntityBuilder,
+) -> io::Result<()> {
+    builder._add(T::read(r)?);
+    Ok(())
+}
+
+/// Maps components stored in the legion world to their codecs.
+///
+/// The name given at registration identifies the component in segments,
+/// thus it must stay stable between versions of the hyperast.
+pub struct Registry {
+    codecs: Vec<Codec>,
+    by_type: HashMap<ComponentTypeId, u16>,
+    by_name: HashMap<Cow<'static, str>, u16>,
+}
+
+impl Registry {
+    /// A registry without any component,
+    /// prefer [`Registry::with_defaults`].
+    pub fn empty() -> Self {
+        Self {
+            codecs: vec![],
+            by_type: Default::default(),
+            by_name: Default::default(),
+        }
+    }
+
+    pub fn register<T: Persist>(&mut self, name: impl Into<Cow<'static, str>>) -> &mut Self {
+        let name = name.into();
+        let tid = ComponentTypeId::of::<T>();
+        if let Some(&i) = self.by_type.get(&tid) {
+            assert_eq!(self.codecs[i as usize].name, name, "registered twice");
+            return self;
+        }
+        assert!(
+            !self.by_name.contains_key(&name),
+            "{} is already used by another component",
+            name
+        );
+        let i = self.codecs.len() as u16;
+        self.codecs.push(Codec {
+            name: name.clone(),
+            write: write_erased::<T>,
+            read: read_erased::<T>,
+        });
+        self.by_type.insert(tid, i);
+        self.by_name.insert(name, i);
+        self
+    }
+
+    /// Registers the type component of a language, named after the language.
+    pub fn register_type<L>(&mut self) -> &mut Self
+    where
+        L: 'static + crate::types::LLang<crate::types::TypeU16<L>, I = u16>,
+    {
+        use crate::types::LangRef;
+        let name = format!("type:{}", L::as_lang_wrapper().name());
+        self.register::<crate::types::TypeU16<L>>(name)
+    }
+
+    /// Components provided by the hyperast that are shared by the different generators.
+    pub fn with_defaults() -> Self {
+        use crate::filter::{Bloom, BloomSize};
+        use crate::hashed::SyntaxNodeHashs;
+        let mut r = Self::empty();
+        r.register::<LabelIdentifier>("label")
+            .register::<SyntaxNodeHashs<u32>>("hashs")
+            .register::<compo::Size>("size")
+            .register::<compo::SizeNoSpaces>("size_no_spaces")
+            .register::<compo::Height>("height")
+            .register::<compo::BytesLen>("bytes_len")
+            .register::<compo::LineCount>("line_count")
+            .register::<compo::VizCsCount>("viz_cs_count")
+            .register::<compo::StmtCount>("stmt_count")
+            .register::<compo::MemberImportCount>("member_import_count")
+            .register::<compo::CS<NodeIdentifier>>("cs")
+            .register::<compo::CS0<NodeIdentifier, 1>>("cs1")
+            .register::<compo::CS0<NodeIdentifier, 2>>("cs2")
+            .register::<compo::NoSpacesCS<NodeIdentifier>>("no_spaces_cs")
+            .register::<compo::NoSpacesCS0<NodeIdentifier, 1>>("no_spaces_cs1")
+            .register::<compo::NoSpacesCS0<NodeIdentifier, 2>>("no_spaces_cs2")
+            .register::<compo::CS<LabelIdentifier>>("cs_names")
+            .register::<compo::Roles<crate::types::Role>>("roles")
+            .register::<compo::RoleOffsets>("role_offsets")
+            .register::<compo::Precomp<u16>>("precomp")
+            .register::<compo::PrecompFlag>("precomp_flag")
+            .register::<crate::cyclomatic::Mcc>("mcc")
+            .register::<BloomSize>("bloom_size")
+            .register::<Bloom<&'static [u8], u16>>("bloom16")
+            .register::<Bloom<&'static [u8], u32>>("bloom32")
+            .register::<Bloom<&'static [u8], u64>>("bloom64")
+            .register::<Bloom<&'static [u8], [u64; 2]>>("bloom128")
+            .register::<Bloom<&'static [u8], [u64; 4]>>("bloom256")
+            .register::<Bloom<&'static [u8], [u64; 8]>>("bloom512")
+            .register::<Bloom<&'static [u8], [u64; 16]>>("bloom1024")
+            .register::<Bloom<&'static [u8], [u64; 32]>>("bloom2048")
+            .register::<Bloom<&'static [u8], [u64; 64]>>("bloom4096");
+        #[cfg(feature = "scripting")]
+        r.register::<crate::scripting::DerivedData>("derived_data");
+        r
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::with_defaults()
+    }
+}
+
+/// Tracks what is already persisted in a snapshot directory.
+///
+/// Keep it alongside the stores to incrementally [`Snapshot::append`] new subtrees.
+#[derive(Default)]
+pub struct Snapshot {
+    /// persisted node to its index in the snapshot
+    indexes: hashbrown::HashMap<NodeIdentifier, u64>,
+    /// index in the snapshot to node
+    ids: Vec<NodeIdentifier>,
+    labels: usize,
+    segments: usize,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SegmentStats {
+    pub nodes: usize,
+    pub labels: usize,
+    pub bytes: u64,
+}
+
+impl Snapshot {
+    pub fn node_count(&self) -> usize {
+        self.ids.len()
+    }
+    pub fn label_count(&self) -> usize {
+        self.labels
+    }
+    pub fn segment_count(&self) -> usize {
+        self.segments
+    }
+    /// The index of a persisted node, use it to refer to nodes outside of segments, e.g. commit roots
+    pub fn index_of(&self, id: &NodeIdentifier) -> Option<u64> {
+        self.indexes.get(id).copied()
+    }
+    /// The node restored at `index`, see [`Snapshot::index_of`]
+    pub fn node_at(&self, index: u64) -> Option<NodeIdentifier> {
+        self.ids.get(index as usize).copied()
+    }
+
+    fn segment_path(dir: &Path, i: usize) -> PathBuf {
+        dir.join(format!("{:06}.{}", i, SEGMENT_EXT))
+    }
+
+    /// Writes a new segment in `dir` with the labels and the nodes not yet persisted.
+    ///
+    /// Nothing is written when the stores did not grow since the last append.
+    /// The first append of a new snapshot removes the segments previously written in `dir`.
+    pub fn append<TS>(
+        &mut self,
+        dir: &Path,
+        registry: &Registry,
+        stores: &SimpleStores<TS, NodeStore, LabelStore>,
+    ) -> io::Result<SegmentStats> {
+        let nodes = self.unpersisted_nodes(&stores.node_store);
+        let labels = stores.label_store.len();
+        if nodes.is_empty() && labels == self.labels {
+            return Ok(SegmentStats::default());
+        }
+        std::fs::create_dir_all(dir)?;
+        let path = Self::segment_path(dir, self.segments);
+        let tmp = path.with_extension("tmp");
+        let mut out = BufWriter::new(std::fs::File::create(&tmp)?);
+        out.write_all(MAGIC)?;
+        out.write_all(&FORMAT_VERSION.to_le_bytes())?;
+
+        // labels
+        out.write_all(&(self.labels as u64).to_le_bytes())?;
+        out.write_all(&((labels - self.labels) as u64).to_le_bytes())?;
+        for i in self.labels..labels {
+            let l = stores.label_store.resolve_index(i).map_err(invalid_data)?;
+            out.write_all(&(l.len() as u32).to_le_bytes())?;
+            out.write_all(l.as_bytes())?;
+        }
+
+        // component table
+        out.write_all(&(registry.codecs.len() as u32).to_le_bytes())?;
+        for c in &registry.codecs {
+            out.write_all(&(c.name.len() as u32).to_le_bytes())?;
+            out.write_all(c.name.as_bytes())?;
+        }
+
+        // nodes
+        let first = self.ids.len() as u64;
+        out.write_all(&first.to_le_bytes())?;
+        out.write_all(&(nodes.len() as u64).to_le_bytes())?;
+        let world = &stores.node_store.inner.internal;
+        let mut indexes = std::mem::take(&mut self.indexes);
+        for (i, id) in nodes.iter().enumerate() {
+            let entry = world
+                .entry_ref(*id)
+                .map_err(|e| invalid_data(format!("{:?}", e)))?;
+            let types = entry.archetype().layout().component_types();
+            out.write_all(&(types.len() as u16).to_le_bytes())?;
+            for tid in types {
+                let Some(&c) = registry.by_type.get(tid) else {
+                    return Err(invalid_data(format!("unregistered component {:?}", tid)));
+                };
+                out.write_all(&c.to_le_bytes())?;
+                let mut w = Writer {
+                    out: &mut out,
+                    indexes: &indexes,
+                };
+                (registry.codecs[c as usize].write)(&entry, &mut w)?;
+            }
+            indexes.insert(*id, first + i as u64);
+        }
+        out.flush()?;
+        drop(out);
+        std::fs::rename(&tmp, &path)?;
+        if self.segments == 0 {
+            // a new snapshot replaces the previous one, e.g. after a garbage collection
+            let mut i = 1;
+            while std::fs::remove_file(Self::segment_path(dir, i)).is_ok() {
+                i += 1;
+            }
+        }
+
+        let stats = SegmentStats {
+            nodes: nodes.len(),
+            labels: labels - self.labels,
+            bytes: std::fs::metadata(&path)?.len(),
+        };
+        self.indexes = indexes;
+        self.ids.extend(nodes);
+        self.labels = labels;
+        self.segments += 1;
+        log::info!(
+            "persisted segment {} with {} nodes and {} labels ({}b)",
+            self.segments - 1,
+            stats.nodes,
+            stats.labels,
+            stats.bytes
+        );
+        Ok(stats)
+    }
+
+    /// Nodes of the store not yet persisted, children before parents.
+    fn unpersisted_nodes(&self, node_store: &NodeStore) -> Vec<NodeIdentifier> {
+        let world = &node_store.inner.internal;
+        let mut query = <legion::Entity>::query();
+        let mut visited: hashbrown::HashSet<NodeIdentifier> = Default::default();
+        let mut result = vec![];
+        for &root in query.iter(world) {
+            if self.indexes.contains_key(&root) || visited.contains(&root) {
+                continue;
+            }
+            // iterative post-order to avoid overflowing the stack on deep subtrees
+            let mut stack = vec![(root, false)];
+            while let Some((id, expanded)) = stack.pop() {
+                if expanded {
+                    result.push(id);
+                    continue;
+                }
+                if self.indexes.contains_key(&id) || !visited.insert(id) {
+                    continue;
+                }
+                stack.push((id, true));
+                let Ok(entry) = world.entry_ref(id) else {
+                    continue;
+                };
+                for c in children(&entry).iter().rev() {
+                    stack.push((*c, false));
+                }
+            }
+        }
+        result
+    }
+
+    /// Replays all the segments of `dir` in `stores`.
+    ///
+    /// The labels already in `stores` must be a prefix of the persisted ones,
+    /// which is the case for a new [`LabelStore`].
+    pub fn restore<TS>(
+        dir: &Path,
+        registry: &Registry,
+        stores: &mut SimpleStores<TS, NodeStore, LabelStore>,
+    ) -> io::Result<Self> {
+        let mut slf = Self::default();
+        loop {
+            let path = Self::segment_path(dir, slf.segments);
+            if !path.exists() {
+                break;
+            }
+            let mut input = BufReader::new(std::fs::File::open(&path)?);
+            slf.restore_segment(&mut input, registry, stores)
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+            slf.segments += 1;
+        }
+        log::info!(
+            "restored {} segments with {} nodes and {} labels",
+            slf.segments,
+            slf.ids.len(),
+            slf.labels
+        );
+        Ok(slf)
+    }
+
+    fn restore_segment<TS>(
+        &mut self,
+        input: &mut dyn Read,
+        registry: &Registry,
+        stores: &mut SimpleStores<TS, NodeStore, LabelStore>,
+    ) -> io::Result<()> {
+        let mut magic = [0; 8];
+        input.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("not a hyperast snapshot segment"));
+        }
+        let mut r = Reader {
+            input,
+            ids: &self.ids,
+        };
+        let version = r.u32()?;
+        if version != FORMAT_VERSION {
+            return Err(invalid_data(format!(
+                "unsupported format version {}, expected {}",
+                version, FORMAT_VERSION
+            )));
+        }
+
+        // labels
+        let first_label = r.u64()? as usize;
+        if first_label != self.labels {
+            return Err(invalid_data("labels are not contiguous between segments"));
+        }
+        let label_count = r.u64()? as usize;
+        for i in first_label..first_label + label_count {
+            let l = r.string()?;
+            stores
+                .label_store
+                .restore_label(i, &l)
+                .map_err(invalid_data)?;
+        }
+        self.labels += label_count;
+
+        // component table
+        let compo_count = r.u32()?;
+        let table = (0..compo_count)
+            .map(|_| {
+                let name = r.string()?;
+                registry
+                    .by_name
+                    .get(name.as_str())
+                    .copied()
+                    .ok_or_else(|| invalid_data(format!("unregistered component {}", name)))
+            })
+            .collect::<io::Result<Vec<u16>>>()?;
+
+        // nodes
+        let first = r.u64()?;
+        if first != self.ids.len() as u64 {
+            return Err(invalid_data("nodes are not contiguous between segments"));
+        }
+        let node_count = r.u64()?;
+        let input = r.input;
+        for i in 0..node_count {
+            // a new reader for each node, so that it sees the previously restored ones
+            let mut r = Reader {
+                input: &mut *input,
+                ids: &self.ids,
+            };
+            let mut builder = dyn_builder::EntityBuilder::new();
+            let count = r.u16()?;
+            for _ in 0..count {
+                let c = r.u16()?;
+                let Some(&c) = table.get(c as usize) else {
+                    return Err(invalid_data(format!("bad component index {}", c)));
+                };
+                (registry.codecs[c as usize].read)(&mut r, &mut builder)?;
+            }
+            let id = stores.node_store.insert_restored(builder.build());
+            self.indexes.insert(id, first + i);
+            self.ids.push(id);
+        }
+        Ok(())
+    }
+}
+
+impl NodeStore {
+    /// Inserts a node read from a snapshot, also indexing it for deduplication.
+    pub(crate) fn insert_restored(
+        &mut self,
+        components: dyn_builder::BuiltEntity,
+    ) -> NodeIdentifier {
+        let NodeStore { dedup, inner } = self;
+        let id = inner.internal.extend(components)[0];
+        let hash = {
+            let node: HashedNodeRef<'_, NodeIdentifier> =
+                HashedNodeRef::new(inner.internal.entry_ref(id).unwrap());
+            make_hash(&inner.hasher, &node)
+        };
+        let entry = dedup.raw_entry_mut().from_hash(hash, |x| *x == id);
+        if let hashbrown::hash_map::RawEntryMut::Vacant(vacant) = entry {
+            vacant.insert_with_hasher(hash, id, (), |id| {
+                let node: HashedNodeRef<'_, NodeIdentifier> =
+                    HashedNodeRef::new(inner.internal.entry_ref(*id).unwrap());
+                make_hash(&inner.hasher, &node)
+            });
+        }
+        id
+    }
+}
+
+// # Codecs of the components shared by generators
+
+macro_rules! persist_u {
+    ($($t:ty => $u:ident),* $(,)?) => {$(
+        impl Persist for $t {
+            fn write(&self, w: &mut Writer<'_>) -> io::Result<()> {
+                w.$u(self.0)
+            }
+            fn read(r: &mut Reader<'_>) -> io::Result<Self> {
+                r.$u().map(Self)
+            }
+        }
+    )*};
+}
+
+persist_u! {
+    compo::Size => u32,
+    compo::SizeNoSpaces => u32,
+    compo::Height => u32,
+    compo::BytesLen => u32,
+    compo::LineCount => u16,
+    compo::VizCsCount => u32,
+    compo::StmtCount => u8,
+    compo::MemberImportCount => u8,
+    compo::Precomp<u16> => u16,
+}
+
+impl Persist for compo::PrecompFlag {
+    fn write(&self, _w: &mut Writer<'_>) -> io::Result<()> {
+        Ok(())
+    }
+    fn read(_r: &mut Reader<'_>) -> io::Result<Self> {
+        Ok(Self)
+    }
+}
+
+impl Persist for LabelIdentifier {
+    fn write(&self, w: &mut Writer<'_>) -> io::Result<()> {
+        w.label(self)
+    }
+    fn read(r: &mut Reader<'_>) -> io::Result<Self> {
+        r.label()
+    }
+}
+
+impl Persist for crate::hashed::SyntaxNodeHashs<u32> {
+    fn write(&self, w: &mut Writer<'_>) -> io::Result<()> {
+        w.u32(self.structt)?;
+        w.u32(self.label)?;
+        w.u32(self.syntax)
+    }
+    fn read(r: &mut Reader<'_>) -> io::Result<Self> {
+        Ok(Self {
+            structt: r.u32()?,
+            label: r.u32()?,
+            syntax: r.u32()?,
+        })
+    }
+}
+
+impl Persist for compo::CS<NodeIdentifier> {
+    fn write(&self, w: &mut Writer<'_>) -> io::Result<()> {
+        w.nodes(&self.0)
+    }
+    fn read(r: &mut Reader<'_>) -> io::Result<Self> {
+        r.nodes().map(Self)
+    }
+}
+
+impl Persist for compo::NoSpacesCS<NodeIdentifier> {
+    fn write(&self, w: &mut Writer<'_>) -> io::Result<()> {
+        w.nodes(&self.0)
+    }
+    fn read(r: &mut Reader<'_>) -> io::Result<Self> {
+        r.nodes().map(Self)
+    }
+}
+
+impl<const N: usize> Persist for compo::CS0<NodeIdentifier, N> {
+    fn write(&self, w: &mut Writer<'_>) -> io::Result<()> {
+        self.0.iter().try_for_each(|x| w.node(x))
+    }
+    fn read(r: &mut Reader<'_>) -> io::Result<Self> {
+        let cs: Box<[_]> = (0..N).map(|_| r.node()).collect::<io::Result<_>>()?;
+        Ok(Self(cs.as_ref().try_into().unwrap()))
+    }
+}
+
+impl<const N: usize> Persist for compo::NoSpacesCS0<NodeIdentifier, N> {
+    fn write(&self, w: &mut Writer<'_>) -> io::Result<()> {
+        self.0.iter().try_for_each(|x| w.node(x))
+    }
+    fn read(r: &mut Reader<'_>) -> io::Result<Self> {
+        let cs: Box<[_]> = (0..N).map(|_| r.node()).collect::<io::Result<_>>()?;
+        Ok(Self(cs.as_ref().try_into().unwrap()))
+    }
+}
+
+impl Persist for compo::CS<LabelIdentifier> {
+    fn write(&self, w: &mut Writer<'_>) -> io::Result<()> {
+        w.u32(self.0.len() as u32)?;
+        self.0.iter().try_for_each(|x| w.label(x))
+    }
+    fn read(r: &mut Reader<'_>) -> io::Result<Self> {
+        let len = r.u32()?;
+        (0..len)
+            .map(|_| r.label())
+            .collect::<io::Result<_>>()
+            .map(Self)
+    }
+}
+
+impl Persist for compo::Roles<crate::types::Role> {
+    fn write(&self, w: &mut Writer<'_>) -> io::Result<()> {
+        w.u32(self.0.len() as u32)?;
+        self.0.iter().try_for_each(|x| w.str(&x.to_string()))
+    }
+    fn read(r: &mut Reader<'_>) -> io::Result<Self> {
+        let len = r.u32()?;
+        (0..len)
+            .map(|_| {
+                let s = r.string()?;
+                crate::types::Role::try_from(s.as_str())
+                    .map_err(|_| invalid_data(format!("unknown role {}", s)))
+            })
+            .collect::<io::Result<_>>()
+            .map(Self)
+    }
+}
+
+impl Persist for compo::RoleOffsets {
+    fn write(&self, w: &mut Writer<'_>) -> io::Result<()> {
+        w.bytes(&self.0)
+    }
+    fn read(r: &mut Reader<'_>) -> io::Result<Self> {
+        r.bytes().map(|x| Self(x.into_boxed_slice()))
+    }
+}
+
+impl<L> Persist for crate::types::TypeU16<L>
+where
+    L: 'static + crate::types::LLang<crate::types::TypeU16<L>, I = u16>,
+{
+    fn write(&self, w: &mut Writer<'_>) -> io::Result<()> {
+        w.u16(<L as crate::types::Lang<L::E>>::to_u16(self.e()))
+    }
+    fn read(r: &mut Reader<'_>) -> io::Result<Self> {
+        let t = r.u16()?;
+        Ok(Self::new(*<L as crate::types::Lang<L::E>>::make(t)))
+    }
+}
+
+#[cfg(feature = "scripting")]
+impl Persist for crate::scripting::DerivedData {
+    fn write(&self, w: &mut Writer<'_>) -> io::Result<()> {
+        w.u32(self.0.len() as u32)?;
+        for (k, v) in self.0.iter() {
+            w.str(k.as_str())?;
+            if let Ok(x) = v.as_int() {
+                w.u8(0)?;
+                w.i64(x)?;
+            } else if let Ok(x) = v.as_float() {
+                w.u8(1)?;
+                w.f64(x)?;
+            } else if let Ok(x) = v.as_bool() {
+                w.u8(2)?;
+                w.u8(x as u8)?;
+            } else if v.is_string() {
+                w.u8(3)?;
+                w.str(&v.clone().into_string().unwrap())?;
+            } else {
+                return Err(invalid_data(format!(
+                    "cannot persist derived data {}: {}",
+                    k,
+                    v.type_name()
+                )));
+            }
+        }
+        Ok(())
+    }
+    fn read(r: &mut Reader<'_>) -> io::Result<Self> {
+        let len = r.u32()?;
+        let mut map = rhai::Map::new();
+        for _ in 0..len {
+            let k = r.string()?;
+            let v = match r.u8()? {
+                0 => rhai::Dynamic::from_int(r.i64()?),
+                1 => rhai::Dynamic::from_float(r.f64()?),
+                2 => rhai::Dynamic::from_bool(r.u8()? != 0),
+                3 => rhai::Dynamic::from(r.string()?),
+                x => return Err(invalid_data(format!("bad derived data tag {}", x))),
+            };
+            map.insert(k.into(), v);
+        }
+        Ok(Self(map))
+    }
+}
@@ -8,6 +8,8 @@ pub mod bevy_ecs;
 pub mod boxed_components;
 #[cfg(feature = "fetched")]
 pub mod fetched;
+#[cfg(feature = "frozen")]
+pub mod frozen;
 #[cfg(feature = "hecs")]
 pub mod hecs;
 #[cfg(feature = "legion")]
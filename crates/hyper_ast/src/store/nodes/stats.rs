@@ -51,6 +51,15 @@ impl NodeStoreStats {
         self.add_height_structural(height, hashs.structt);
     }
 
+    /// Forgets the deduplicated subtrees, e.g. before recounting the ones surviving a collection
+    pub(crate) fn clear_dedup(&mut self) {
+        self.height_counts.clear();
+        self.height_counts_structural.clear();
+        self.structurals.clear();
+        self.height_counts_label.clear();
+        self.labels.clear();
+    }
+
     pub(crate) fn add_height(&mut self, height: u32) {
         Self::accumulate_height(&mut self.height_counts, height);
     }
@@ -203,6 +203,23 @@ role_impl!(
     TemplateParameters => "template_parameters",
     // Operator => "operator",
     // Condition => "condition",
+
+    // // Python
+    Alias => "alias",
+    Attribute => "attribute",
+    Cause => "cause",
+    Code => "code",
+    Definition => "definition",
+    Expression => "expression",
+    FormatSpecifier => "format_specifier",
+    Guard => "guard",
+    ModuleName => "module_name",
+    Operators => "operators",
+    ReturnType => "return_type",
+    Subject => "subject",
+    Subscript => "subscript",
+    Superclasses => "superclasses",
+    TypeConversion => "type_conversion",
 );
 
 #[allow(unused)]
@@ -1322,6 +1339,7 @@ impl<T> SizedIndex<u16> for [T] {
 }
 
 #[cfg_attr(feature = "bevy_ecs", derive(bevy_ecs::component::Component))]
+#[repr(transparent)]
 pub struct TypeU16<L: LLang<Self, I = u16>>(u16, std::marker::PhantomData<L>);
 
 unsafe impl<L: LLang<Self, I = u16>> Send for TypeU16<L> {}
@@ -174,6 +174,12 @@ pub fn memusage_linux() -> MemoryUsage {
 #[derive(Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct Bytes(isize);
 
+impl From<isize> for Bytes {
+    fn from(bytes: isize) -> Self {
+        Self(bytes)
+    }
+}
+
 impl Bytes {
     pub fn megabytes(self) -> isize {
         self.0 / 1024 / 1024
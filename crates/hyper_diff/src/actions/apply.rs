@@ -0,0 +1,410 @@
+//! Apply an edit script on [`SimpleStores`],
+//! producing the resulting tree in their legion node store.
+//!
+//! Actions are replayed in order, following their paths in the intermediate tree (`mid`),
+//! on a mutable copy of the modified parts of the tree.
+//! The modified nodes are then interned bottom-up, reusing existing nodes when identical,
+//! thus applying the edit script of a pair of trees on the first one gives back the second one.
+//!
+//! The edit script must be computed on the same stores, i.e. with spaces,
+//! otherwise the resulting tree cannot be reserialized to the expected text.
+//! Only the type, label, children, hashes and metrics are rebuilt,
+//! other components added by generators (e.g. roles or precomputed queries)
+//! are not recomputed on modified nodes.
+
+use hyperast::PrimInt;
+use hyperast::filter::BloomSize;
+use hyperast::hashed::{HashesBuilder, IndexingHashBuilder, MetaDataHashsBuilder, SyntaxNodeHashs};
+use hyperast::nodes::TextSerializer;
+use hyperast::store::SimpleStores;
+use hyperast::store::defaults::{LabelIdentifier, NodeIdentifier};
+use hyperast::store::nodes::legion::{NodeStore, dyn_builder, eq_node};
+use hyperast::store::nodes::{EntityBuilder, compo};
+use hyperast::tree_gen::{BasicAccumulator, SubTreeMetrics, add_cs_no_spaces};
+use hyperast::types::{
+    Childrn, HyperType, LabelStore, Labeled, TypeStore, WithChildren, WithSerialization, WithStats,
+};
+
+use super::action_vec::ActionsVec;
+use super::script_generator2::{Act, SimpleAction};
+use crate::tree::tree_path::TreePath;
+
+/// Applies `actions` on the tree rooted at `root`, returning the root of the resulting tree.
+pub fn apply_actions<TS, P, Idx>(
+    stores: &mut SimpleStores<TS>,
+    root: NodeIdentifier,
+    actions: &ActionsVec<SimpleAction<LabelIdentifier, P, NodeIdentifier>>,
+) -> Result<NodeIdentifier, String>
+where
+    TS: TypeStore,
+    P: TreePath<Item = Idx>,
+    Idx: PrimInt,
+{
+    let mut roots = vec![Some(MNode::Ori(root))];
+    let mut it = actions.iter().peekable();
+    while let Some(a) = it.next() {
+        let mid = to_path(&a.path.mid);
+        match &a.action {
+            Act::Delete {} => {
+                take(stores, &mut roots, &mid)?;
+            }
+            Act::Insert { sub } => {
+                let label = stores.node_store.resolve(*sub).try_get_label().copied();
+                let node = MNode::Mod {
+                    ori: *sub,
+                    label,
+                    children: vec![],
+                };
+                put(stores, &mut roots, &mid, node)?;
+            }
+            Act::Update { new } => match it.peek() {
+                // a renamed node that is also moved, its mid path points to the destination
+                Some(SimpleAction {
+                    path,
+                    action: Act::Move { from },
+                }) if path.ori.iter().eq(a.path.ori.iter()) => {
+                    let from = to_path(&from.mid);
+                    it.next();
+                    move_node(stores, &mut roots, &from, &mid, Some(*new))?;
+                }
+                _ => {
+                    let node = node_at(stores, &mut roots, &mid)?;
+                    *open(node, stores).0 = Some(*new);
+                }
+            },
+            Act::Move { from } => {
+                move_node(stores, &mut roots, &to_path(&from.mid), &mid, None)?;
+            }
+            Act::MovUpd { from, new } => {
+                move_node(stores, &mut roots, &to_path(&from.mid), &mid, Some(*new))?;
+            }
+        }
+    }
+    let root = roots
+        .into_iter()
+        .flatten()
+        .last()
+        .ok_or_else(|| "the whole tree was deleted".to_string())?;
+    build(stores, root)
+}
+
+/// Serializes the tree rooted at `root`, including spaces.
+pub fn reserialize<TS: TypeStore>(stores: &SimpleStores<TS>, root: NodeIdentifier) -> String {
+    TextSerializer::new(stores, root).to_string()
+}
+
+/// A node of the tree being modified,
+/// original subtrees are only opened when an action goes through them.
+enum MNode {
+    Ori(NodeIdentifier),
+    Mod {
+        ori: NodeIdentifier,
+        label: Option<LabelIdentifier>,
+        children: Vec<MNode>,
+    },
+}
+
+fn to_path<P: TreePath<Item = Idx>, Idx: PrimInt>(path: &P) -> Vec<usize> {
+    path.iter().map(|x| x.to_usize().unwrap()).collect()
+}
+
+fn open<'a, TS>(
+    node: &'a mut MNode,
+    stores: &SimpleStores<TS>,
+) -> (&'a mut Option<LabelIdentifier>, &'a mut Vec<MNode>) {
+    if let MNode::Ori(id) = *node {
+        let n = stores.node_store.resolve(id);
+        let label = n.try_get_label().copied();
+        let children = n
+            .children()
+            .map_or(vec![], |cs| cs.iter_children().map(MNode::Ori).collect());
+        *node = MNode::Mod {
+            ori: id,
+            label,
+            children,
+        };
+    }
+    match node {
+        MNode::Mod {
+            label, children, ..
+        } => (label, children),
+        MNode::Ori(_) => unreachable!(),
+    }
+}
+
+fn node_at<'a, TS>(
+    stores: &SimpleStores<TS>,
+    roots: &'a mut [Option<MNode>],
+    path: &[usize],
+) -> Result<&'a mut MNode, String> {
+    let (r, path) = path.split_first().ok_or("empty path")?;
+    let mut node = roots
+        .get_mut(*r)
+        .and_then(|x| x.as_mut())
+        .ok_or_else(|| format!("missing root {r}"))?;
+    for i in path {
+        let (_, cs) = open(node, stores);
+        node = cs
+            .get_mut(*i)
+            .ok_or_else(|| format!("missing child {i} in {path:?}"))?;
+    }
+    Ok(node)
+}
+
+fn take<TS>(
+    stores: &SimpleStores<TS>,
+    roots: &mut [Option<MNode>],
+    path: &[usize],
+) -> Result<MNode, String> {
+    match path {
+        [] => Err("empty path".to_string()),
+        [r] => roots
+            .get_mut(*r)
+            .and_then(|x| x.take())
+            .ok_or_else(|| format!("missing root {r}")),
+        [parent @ .., i] => {
+            let (_, cs) = open(node_at(stores, roots, parent)?, stores);
+            if *i >= cs.len() {
+                return Err(format!("missing child {i} in {path:?}"));
+            }
+            Ok(cs.remove(*i))
+        }
+    }
+}
+
+fn put<TS>(
+    stores: &SimpleStores<TS>,
+    roots: &mut Vec<Option<MNode>>,
+    path: &[usize],
+    node: MNode,
+) -> Result<(), String> {
+    match path {
+        [] => Err("empty path".to_string()),
+        [r] if *r < roots.len() => {
+            roots[*r] = Some(node);
+            Ok(())
+        }
+        [r] if *r == roots.len() => {
+            roots.push(Some(node));
+            Ok(())
+        }
+        [r] => Err(format!("cannot add root {r}")),
+        [parent @ .., i] => {
+            let (_, cs) = open(node_at(stores, roots, parent)?, stores);
+            if *i > cs.len() {
+                return Err(format!("cannot insert child {i} in {path:?}"));
+            }
+            cs.insert(*i, node);
+            Ok(())
+        }
+    }
+}
+
+fn move_node<TS>(
+    stores: &SimpleStores<TS>,
+    roots: &mut Vec<Option<MNode>>,
+    from: &[usize],
+    to: &[usize],
+    new: Option<LabelIdentifier>,
+) -> Result<(), String> {
+    let mut node = take(stores, roots, from)?;
+    if let Some(new) = new {
+        *open(&mut node, stores).0 = Some(new);
+    }
+    put(stores, roots, to, node)
+}
+
+fn build<TS: TypeStore>(
+    stores: &mut SimpleStores<TS>,
+    node: MNode,
+) -> Result<NodeIdentifier, String> {
+    match node {
+        MNode::Ori(id) => Ok(id),
+        MNode::Mod {
+            ori,
+            label,
+            children,
+        } => {
+            let children = children
+                .into_iter()
+                .map(|c| build(stores, c))
+                .collect::<Result<Vec<_>, _>>()?;
+            intern(stores, ori, label, children)
+        }
+    }
+}
+
+/// Inserts a node of the same type as `ori`, with the given label and children.
+fn intern<TS: TypeStore>(
+    stores: &mut SimpleStores<TS>,
+    ori: NodeIdentifier,
+    label: Option<LabelIdentifier>,
+    children: Vec<NodeIdentifier>,
+) -> Result<NodeIdentifier, String> {
+    let (kind, ori_bytes_len) = {
+        let n = stores.node_store.resolve(ori);
+        let kind = *n
+            .get_component::<TS::Ty>()
+            .map_err(|_| format!("cannot get the type of {ori:?}"))?;
+        let ori_children = n
+            .children()
+            .map_or(vec![], |cs| cs.iter_children().collect::<Vec<_>>());
+        if n.try_get_label() == label.as_ref() && ori_children == children {
+            return Ok(ori);
+        }
+        (kind, n.try_bytes_len())
+    };
+    let label_str = label.map(|l| stores.label_store.resolve(&l).to_string());
+
+    if kind.is_spaces() {
+        let Some(spacing) = label_str else {
+            return Err("spaces without label".to_string());
+        };
+        return Ok(intern_spaces(stores, kind, label.unwrap(), spacing));
+    }
+
+    let mut metrics = SubTreeMetrics::<SyntaxNodeHashs<u32>>::default();
+    let mut bytes_len = 0;
+    let mut no_spaces = vec![];
+    for c in &children {
+        let n = stores.node_store.resolve(*c);
+        let is_spaces = n.get_component::<TS::Ty>().is_ok_and(|t| t.is_spaces());
+        let hashs = *n
+            .get_component::<SyntaxNodeHashs<u32>>()
+            .map_err(|_| format!("cannot get the hashes of {c:?}"))?;
+        metrics.acc(SubTreeMetrics {
+            hashs,
+            size: n.size() as u32,
+            height: if is_spaces { 0 } else { n.height() as u32 },
+            size_no_spaces: if is_spaces {
+                0
+            } else {
+                n.size_no_spaces() as u32
+            },
+            line_count: n.line_count() as u16,
+        });
+        bytes_len += n.try_bytes_len().unwrap_or(0);
+        if !is_spaces {
+            no_spaces.push(*c);
+        }
+    }
+    if children.is_empty() {
+        bytes_len = match (&label_str, ori_bytes_len) {
+            (Some(l), _) => l.len(),
+            (None, Some(len)) => len,
+            (None, None) => 0,
+        };
+    }
+
+    let metrics = if kind.is_directory() {
+        let label = label.ok_or_else(|| "directory without name".to_string())?;
+        metrics.finalize(&kind, &label, 0)
+    } else {
+        let own_line_count = label_str
+            .as_ref()
+            .map_or(0, |l| l.matches("\n").count() as u16);
+        metrics.finalize(&kind, &label_str, own_line_count)
+    };
+    let hashable = &metrics.hashs.most_discriminating();
+    let eq = eq_node(&kind, label.as_ref(), &children);
+    let insertion = stores.node_store.prepare_insertion(hashable, eq);
+    if let Some(id) = insertion.occupied_id() {
+        return Ok(id);
+    }
+
+    let mut dyn_builder = dyn_builder::EntityBuilder::new();
+    if ori_bytes_len.is_some() {
+        dyn_builder.add(compo::BytesLen(bytes_len.try_into().unwrap()));
+    }
+    let metrics = metrics.map_hashs(|x| x.build());
+    let hashs = metrics.add_md_metrics(&mut dyn_builder, children.is_empty());
+    hashs.persist(&mut dyn_builder);
+    if no_spaces.len() != children.len() {
+        add_cs_no_spaces(&mut dyn_builder, no_spaces);
+    }
+    BasicAccumulator { kind, children }.add_primary(&mut dyn_builder, kind, label);
+    Ok(NodeStore::insert_built_after_prepare(
+        insertion.vacant(),
+        dyn_builder.build(),
+    ))
+}
+
+/// Spaces are hashed and stored like generators do, i.e. ignoring them in parents' structure and label hashes.
+fn intern_spaces<TS: TypeStore>(
+    stores: &mut SimpleStores<TS>,
+    kind: TS::Ty,
+    spacing_id: LabelIdentifier,
+    spacing: String,
+) -> NodeIdentifier {
+    let line_count = spacing.matches("\n").count() as u16;
+    let hbuilder: HashesBuilder<SyntaxNodeHashs<u32>> =
+        HashesBuilder::new(Default::default(), &kind, &spacing, 1);
+    let hashable = &hbuilder.most_discriminating();
+    let no_children: &[NodeIdentifier] = &[];
+    let eq = eq_node(&kind, Some(&spacing_id), no_children);
+    let insertion = stores.node_store.prepare_insertion(hashable, eq);
+    if let Some(id) = insertion.occupied_id() {
+        return id;
+    }
+    let mut hashs = hbuilder.build();
+    hashs.structt = 0;
+    hashs.label = 0;
+    let mut dyn_builder = dyn_builder::EntityBuilder::new();
+    dyn_builder.add(kind);
+    dyn_builder.add(compo::BytesLen(spacing.len().try_into().unwrap()));
+    dyn_builder.add(spacing_id);
+    dyn_builder.add(hashs);
+    dyn_builder.add(BloomSize::None);
+    if line_count != 0 {
+        dyn_builder.add(compo::LineCount(line_count));
+    }
+    NodeStore::insert_built_after_prepare(insertion.vacant(), dyn_builder.build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyperast_gen_ts_java::legion_with_refs::{self, JavaTreeGen};
+    use hyperast_gen_ts_java::types::TStore;
+
+    const SRC: &str = r#"class A {
+    int f(int a) {
+        return a + 1;
+    }
+    void g() {}
+}
+"#;
+    const DST: &str = r#"class A {
+    void g() {
+        h();
+    }
+    int f(int b) {
+        return b + 1;
+    }
+}
+"#;
+
+    #[test]
+    fn apply_java_edit_script() {
+        let mut stores = SimpleStores::<TStore>::default();
+        let mut md_cache = Default::default();
+        let mut tree_gen = JavaTreeGen::new(&mut stores, &mut md_cache);
+        let mut generate = |text: &str| {
+            let tree = match legion_with_refs::tree_sitter_parse(text.as_bytes()) {
+                Ok(t) => t,
+                Err(t) => t,
+            };
+            (tree_gen.generate_file(b"", text.as_bytes(), tree.walk()))
+                .local
+                .compressed_node
+        };
+        let src = generate(SRC);
+        let dst = generate(DST);
+        let actions = crate::algorithms::gumtree::diff(&stores, &src, &dst)
+            .actions
+            .unwrap();
+        let root = apply_actions(&mut stores, src, &actions).unwrap();
+        assert_eq!(reserialize(&stores, root), DST);
+        assert_eq!(root, dst);
+    }
+}
@@ -0,0 +1,371 @@
+//! Export edit scripts in the formats of GumTree's `textdiff` (`-f JSON` and `-f XML`),
+//! so that tooling built around GumTree can consume HyperAST diffs.
+//!
+//! Nodes are printed like GumTree does, i.e. `type: label [start,end]` with byte offsets.
+//! As HyperAST diffs usually span a whole commit, each node also carries the file containing it.
+use std::fmt::{Display, Write};
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use hyperast::PrimInt;
+use hyperast::position::compute_position;
+use hyperast::types::{
+    HyperAST, HyperType, LabelStore, Labeled, NodeId, WithChildren, WithSerialization,
+};
+
+use super::action_vec::ActionsVec;
+use super::script_generator2::{Act, SimpleAction};
+use crate::tree::tree_path::TreePath;
+
+/// A node as referenced by GumTree matches and actions
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GtNode {
+    pub ty: String,
+    pub label: Option<String>,
+    /// empty when the node is not inside a file
+    pub file: PathBuf,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Display for GtNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.label {
+            Some(l) if !l.is_empty() => {
+                write!(f, "{}: {} [{},{}]", self.ty, l, self.start, self.end)
+            }
+            _ => write!(f, "{} [{},{}]", self.ty, self.start, self.end),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GtAction {
+    UpdateNode {
+        tree: GtNode,
+        label: String,
+    },
+    MoveTree {
+        tree: GtNode,
+        parent: GtNode,
+        at: usize,
+    },
+    InsertNode {
+        tree: GtNode,
+        parent: GtNode,
+        at: usize,
+    },
+    DeleteNode {
+        tree: GtNode,
+    },
+}
+
+impl GtAction {
+    /// name of the action in GumTree's outputs
+    pub fn name(&self) -> &'static str {
+        match self {
+            GtAction::UpdateNode { .. } => "update-node",
+            GtAction::MoveTree { .. } => "move-tree",
+            GtAction::InsertNode { .. } => "insert-node",
+            GtAction::DeleteNode { .. } => "delete-node",
+        }
+    }
+
+    pub fn tree(&self) -> &GtNode {
+        match self {
+            GtAction::UpdateNode { tree, .. }
+            | GtAction::MoveTree { tree, .. }
+            | GtAction::InsertNode { tree, .. }
+            | GtAction::DeleteNode { tree } => tree,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GumtreeDiff {
+    pub matches: Vec<(GtNode, GtNode)>,
+    pub actions: Vec<GtAction>,
+}
+
+impl GumtreeDiff {
+    /// Converts `actions` given how to resolve paths in the source and destination trees.
+    ///
+    /// Deletes and move origins are resolved in the source tree,
+    /// inserts and move targets in the destination tree, as in GumTree.
+    pub fn from_actions<L, P, I, Idx>(
+        actions: &ActionsVec<SimpleAction<L, P, I>>,
+        mut src: impl FnMut(&[Idx]) -> GtNode,
+        mut dst: impl FnMut(&[Idx]) -> GtNode,
+        label: impl Fn(&L) -> String,
+    ) -> Self
+    where
+        P: TreePath<Item = Idx>,
+        Idx: PrimInt,
+    {
+        let mut result = vec![];
+        let mut it = actions.iter().peekable();
+        while let Some(a) = it.next() {
+            let ori: Vec<Idx> = a.path.ori.iter().collect();
+            match &a.action {
+                Act::Delete {} => result.push(GtAction::DeleteNode { tree: src(&ori) }),
+                Act::Update { new } => {
+                    // the script generator emits the update of a moved node just before
+                    // its move, and then the path designates the destination of the move
+                    let tree = match it.peek() {
+                        Some(SimpleAction {
+                            path,
+                            action: Act::Move { from },
+                        }) if path.ori.iter().eq(a.path.ori.iter()) => {
+                            src(&from.ori.iter().collect::<Vec<_>>())
+                        }
+                        _ => src(&ori),
+                    };
+                    let label = label(new);
+                    result.push(GtAction::UpdateNode { tree, label })
+                }
+                Act::Move { from } => {
+                    let tree = src(&from.ori.iter().collect::<Vec<_>>());
+                    let (parent, at) = parent_and_position(&ori, &mut dst);
+                    result.push(GtAction::MoveTree { tree, parent, at })
+                }
+                Act::MovUpd { from, new } => {
+                    let tree = src(&from.ori.iter().collect::<Vec<_>>());
+                    let (parent, at) = parent_and_position(&ori, &mut dst);
+                    result.push(GtAction::UpdateNode {
+                        tree: tree.clone(),
+                        label: label(new),
+                    });
+                    result.push(GtAction::MoveTree { tree, parent, at })
+                }
+                Act::Insert { .. } => {
+                    let tree = dst(&ori);
+                    let (parent, at) = parent_and_position(&ori, &mut dst);
+                    result.push(GtAction::InsertNode { tree, parent, at })
+                }
+            }
+        }
+        Self {
+            matches: vec![],
+            actions: result,
+        }
+    }
+
+    pub fn with_matches(mut self, matches: impl IntoIterator<Item = (GtNode, GtNode)>) -> Self {
+        self.matches.extend(matches);
+        self
+    }
+
+    pub fn to_xml(&self) -> String {
+        let mut out = String::new();
+        self.write_xml(&mut out).unwrap();
+        out
+    }
+
+    pub fn write_xml(&self, out: &mut impl Write) -> std::fmt::Result {
+        writeln!(out, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(out, "<diff>")?;
+        writeln!(out, "  <matches>")?;
+        for (s, d) in &self.matches {
+            write!(out, "    <match src=\"")?;
+            xml_attr(out, &s.to_string())?;
+            write!(out, "\" dest=\"")?;
+            xml_attr(out, &d.to_string())?;
+            write!(out, "\" src_file=\"")?;
+            xml_attr(out, &s.file.to_string_lossy())?;
+            write!(out, "\" dest_file=\"")?;
+            xml_attr(out, &d.file.to_string_lossy())?;
+            writeln!(out, "\"/>")?;
+        }
+        writeln!(out, "  </matches>")?;
+        writeln!(out, "  <actions>")?;
+        for a in &self.actions {
+            write!(out, "    <action type=\"{}\" tree=\"", a.name())?;
+            xml_attr(out, &a.tree().to_string())?;
+            write!(out, "\" file=\"")?;
+            xml_attr(out, &a.tree().file.to_string_lossy())?;
+            match a {
+                GtAction::UpdateNode { label, .. } => {
+                    write!(out, "\" label=\"")?;
+                    xml_attr(out, label)?;
+                }
+                GtAction::MoveTree { parent, at, .. } | GtAction::InsertNode { parent, at, .. } => {
+                    write!(out, "\" parent=\"")?;
+                    xml_attr(out, &parent.to_string())?;
+                    write!(out, "\" at=\"{}", at)?;
+                }
+                GtAction::DeleteNode { .. } => (),
+            }
+            writeln!(out, "\"/>")?;
+        }
+        writeln!(out, "  </actions>")?;
+        writeln!(out, "</diff>")
+    }
+}
+
+/// Serialized as the JSON of GumTree
+impl Serialize for GumtreeDiff {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let matches = self.matches.iter().map(|(s, d)| JsonMatch {
+            src: s.to_string(),
+            dest: d.to_string(),
+            src_file: s.file.to_string_lossy().into_owned(),
+            dest_file: d.file.to_string_lossy().into_owned(),
+        });
+        let actions = self.actions.iter().map(|a| {
+            let (label, parent, at) = match a {
+                GtAction::UpdateNode { label, .. } => (Some(label.as_str()), None, None),
+                GtAction::MoveTree { parent, at, .. } | GtAction::InsertNode { parent, at, .. } => {
+                    (None, Some(parent.to_string()), Some(*at))
+                }
+                GtAction::DeleteNode { .. } => (None, None, None),
+            };
+            JsonAction {
+                action: a.name(),
+                tree: a.tree().to_string(),
+                file: a.tree().file.to_string_lossy().into_owned(),
+                label,
+                parent,
+                at,
+            }
+        });
+        Json {
+            matches: matches.collect(),
+            actions: actions.collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[derive(Serialize)]
+struct Json<'a> {
+    matches: Vec<JsonMatch>,
+    actions: Vec<JsonAction<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonMatch {
+    src: String,
+    dest: String,
+    src_file: String,
+    dest_file: String,
+}
+
+#[derive(Serialize)]
+struct JsonAction<'a> {
+    action: &'static str,
+    tree: String,
+    file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    at: Option<usize>,
+}
+
+fn parent_and_position<Idx: PrimInt>(
+    path: &[Idx],
+    resolve: &mut impl FnMut(&[Idx]) -> GtNode,
+) -> (GtNode, usize) {
+    match path.split_last() {
+        Some((at, parent)) => (resolve(parent), at.to_usize().unwrap()),
+        None => (resolve(path), 0),
+    }
+}
+
+/// Resolves the node at `path` from `root`, its offsets are computed with [`compute_position`],
+/// so `stores` must include spaces for offsets to be exact.
+pub fn resolve_node<HAST>(stores: HAST, root: HAST::IdN, path: &[HAST::Idx]) -> GtNode
+where
+    HAST: HyperAST + Copy,
+    HAST::IdN: Clone + NodeId<IdN = HAST::IdN>,
+    HAST::Idx: Clone,
+    for<'t> <HAST as hyperast::types::AstLending<'t>>::RT: WithSerialization + WithChildren,
+{
+    let (pos, id) = compute_position(root, &mut path.iter().cloned(), stores);
+    let range = pos.range();
+    let ty = stores.resolve_type(&id);
+    let label = if ty.is_directory() || ty.is_file() {
+        None
+    } else {
+        stores
+            .resolve(&id)
+            .try_get_label()
+            .map(|l| stores.label_store().resolve(l).to_string())
+    };
+    GtNode {
+        ty: ty.to_string(),
+        label,
+        file: pos.file().to_path_buf(),
+        start: range.start,
+        end: range.end,
+    }
+}
+
+fn xml_attr(out: &mut impl Write, s: &str) -> std::fmt::Result {
+    for c in s.chars() {
+        match c {
+            '"' => out.write_str("&quot;")?,
+            '&' => out.write_str("&amp;")?,
+            '<' => out.write_str("&lt;")?,
+            '>' => out.write_str("&gt;")?,
+            '\n' => out.write_str("&#10;")?,
+            '\r' => out.write_str("&#13;")?,
+            '\t' => out.write_str("&#9;")?,
+            c => out.write_char(c)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(ty: &str, label: Option<&str>, start: usize, end: usize) -> GtNode {
+        GtNode {
+            ty: ty.to_string(),
+            label: label.map(|l| l.to_string()),
+            file: PathBuf::from("src/A.java"),
+            start,
+            end,
+        }
+    }
+
+    #[test]
+    fn gumtree_json_and_xml() {
+        let diff = GumtreeDiff {
+            matches: vec![(
+                node("identifier", Some("a"), 4, 5),
+                node("identifier", Some("b"), 4, 5),
+            )],
+            actions: vec![
+                GtAction::UpdateNode {
+                    tree: node("identifier", Some("a"), 4, 5),
+                    label: "b\"c".to_string(),
+                },
+                GtAction::InsertNode {
+                    tree: node("block", None, 10, 12),
+                    parent: node("method_declaration", None, 0, 12),
+                    at: 2,
+                },
+                GtAction::DeleteNode {
+                    tree: node("string", Some("<x>"), 20, 23),
+                },
+            ],
+        };
+        assert_eq!(
+            serde_json::to_string(&diff).unwrap(),
+            r#"{"matches":[{"src":"identifier: a [4,5]","dest":"identifier: b [4,5]","src_file":"src/A.java","dest_file":"src/A.java"}],"actions":[{"action":"update-node","tree":"identifier: a [4,5]","file":"src/A.java","label":"b\"c"},{"action":"insert-node","tree":"block [10,12]","file":"src/A.java","parent":"method_declaration [0,12]","at":2},{"action":"delete-node","tree":"string: <x> [20,23]","file":"src/A.java"}]}"#
+        );
+        let xml = diff.to_xml();
+        assert!(xml.contains(
+            r#"<action type="update-node" tree="identifier: a [4,5]" file="src/A.java" label="b&quot;c"/>"#
+        ));
+        assert!(xml.contains(
+            r#"<action type="insert-node" tree="block [10,12]" file="src/A.java" parent="method_declaration [0,12]" at="2"/>"#
+        ));
+        assert!(xml.contains(r#"tree="string: &lt;x&gt; [20,23]""#));
+    }
+}
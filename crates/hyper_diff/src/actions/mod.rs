@@ -1,6 +1,8 @@
 #[allow(unused)] // still very experimental
 pub mod action_tree;
 pub mod action_vec;
+pub mod apply;
+pub mod gumtree_export;
 pub mod script_generator;
 pub mod script_generator2;
 
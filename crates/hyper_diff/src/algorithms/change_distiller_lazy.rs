@@ -12,11 +12,12 @@ use hyperast::types::{self, HyperAST, NodeId};
 
 // use crate::decompressed_tree_store::lazy_post_order::LazyPostOrder;
 use super::DS;
+use super::config::{Algorithm, DiffConfig};
 
 use hyperast::store::nodes::compo;
 use hyperast::types::WithMetaData;
 
-use crate::matchers::heuristic::cd::lazy_bottom_up_matcher::BottomUpMatcher;
+use crate::matchers::heuristic::cd::lazy_bottom_up_matcher::{BottomUpMatcher, Thresholds};
 use crate::matchers::heuristic::cd::lazy_leaves_matcher::LazyLeavesMatcher;
 
 type M = VecStore<u32>;
@@ -26,6 +27,31 @@ pub fn diff<HAST: HyperAST + Copy>(
     src: &HAST::IdN,
     dst: &HAST::IdN,
 ) -> DiffRes<HAST>
+where
+    HAST::IdN: Copy + Debug + Eq,
+    HAST::IdN: NodeId<IdN = HAST::IdN>,
+    HAST::Label: Clone + Copy + Eq + Debug,
+    HAST::Idx: hyperast::PrimInt,
+    for<'t> types::LendT<'t, HAST>: types::WithHashs
+        + types::WithStats
+        + WithMetaData<compo::MemberImportCount>
+        + WithMetaData<compo::StmtCount>,
+{
+    diff_with_config(
+        hyperast,
+        src,
+        dst,
+        &DiffConfig::new(Algorithm::ChangeDistillerLazy),
+    )
+}
+
+/// Uses the `label_sim_threshold`, `max_size`, `sim_threshold` and `sim_threshold2` of `config`
+pub fn diff_with_config<HAST: HyperAST + Copy>(
+    hyperast: HAST,
+    src: &HAST::IdN,
+    dst: &HAST::IdN,
+    config: &DiffConfig,
+) -> DiffRes<HAST>
 where
     HAST::IdN: Copy + Debug + Eq,
     HAST::IdN: NodeId<IdN = HAST::IdN>,
@@ -41,14 +67,19 @@ where
     let mapper = Mapper::with_mut_decompressible(&mut mapper_owned);
     let measure = measure.start();
 
-    let mapper = LazyLeavesMatcher::<_, _, _, M>::match_it(mapper);
+    let mapper = LazyLeavesMatcher::<_, _, _, M>::match_it_with(mapper, config.label_sim_threshold);
     let subtree_mappings_s = mapper.mappings().len();
 
     tr!(subtree_mappings_s);
 
     let measure = measure.stop_then_skip_prepare();
 
-    let mapper = BottomUpMatcher::<_, _, _, _>::match_it(mapper);
+    let thresholds = Thresholds {
+        size: config.max_size,
+        sim: config.sim_threshold,
+        sim2: config.sim_threshold2,
+    };
+    let mapper = BottomUpMatcher::<_, _, _, _>::match_it_with(mapper, thresholds);
     let bottomup_mappings_s = mapper.mappings().len();
 
     tr!(bottomup_mappings_s);
@@ -0,0 +1,253 @@
+//! Selection of a lazy diff algorithm and of its thresholds at runtime,
+//! to compare configurations without recompiling.
+//! The other algorithms, e.g. `xy` or `gumtree_stable`, keep their thresholds as const generics,
+//! so their names are rejected.
+use std::fmt::Debug;
+
+use super::DiffRes;
+use hyperast::store::nodes::compo;
+use hyperast::types::{self, HyperAST, NodeId, WithMetaData};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Algorithm {
+    /// greedy subtree matcher then greedy bottom-up matcher
+    #[default]
+    GumtreeLazy,
+    /// greedy subtree matcher then hybrid bottom-up matcher
+    GumtreeHybridLazy,
+    /// leaves matcher then bottom-up matcher of ChangeDistiller
+    ChangeDistillerLazy,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 3] = [
+        Algorithm::GumtreeLazy,
+        Algorithm::GumtreeHybridLazy,
+        Algorithm::ChangeDistillerLazy,
+    ];
+
+    /// Modules of [`super`] whose thresholds are only known at compile time,
+    /// parsing their names returns an error instead of falling back to another algorithm.
+    ///
+    /// They cannot be selected by a [`DiffConfig`]:
+    /// `xy` and `gumtree_stable` decompress both trees eagerly
+    /// and their bottom-up matchers (`XYBottomUpMatcher`, `MarriageBottomUpMatcher`)
+    /// take their thresholds as const generics, with no runtime counterpart.
+    /// Call [`super::xy::diff`] or [`super::gumtree_stable::diff`] directly to use them.
+    pub const NOT_CONFIGURABLE: [&'static str; 11] = [
+        "gumtree",
+        "gumtree_hybrid",
+        "gumtree_simple",
+        "gumtree_simple_lazy",
+        "gumtree_stable",
+        "gumtree_stable_lazy",
+        "gumtree_partial_lazy",
+        "gumtree_hybrid_partial_lazy",
+        "change_distiller",
+        "change_distiller_partial_lazy",
+        "xy",
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Algorithm::GumtreeLazy => "gumtree_lazy",
+            Algorithm::GumtreeHybridLazy => "gumtree_hybrid_lazy",
+            Algorithm::ChangeDistillerLazy => "change_distiller_lazy",
+        }
+    }
+}
+
+impl std::fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|a| a.name() == s)
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(|a| a.name()).collect();
+                let names = names.join(", ");
+                if Self::NOT_CONFIGURABLE.contains(&s) {
+                    format!("{s} cannot be configured at runtime, expected one of {names}")
+                } else {
+                    format!("unknown algorithm {s}, expected one of {names}")
+                }
+            })
+    }
+}
+
+/// Algorithm and thresholds of a diff,
+/// each algorithm only reads the thresholds of the matchers it uses.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiffConfig {
+    pub algorithm: Algorithm,
+    /// minimum height of the subtrees matched by the greedy subtree matcher (gumtree)
+    pub min_height: usize,
+    /// maximum number of descendants for the optimal recovery of the bottom-up matchers (gumtree),
+    /// number of leaves above which `sim_threshold` is required instead of `sim_threshold2` (change distiller)
+    pub max_size: usize,
+    /// minimum similarity of matched containers (gumtree_lazy, change distiller)
+    pub sim_threshold: f64,
+    /// minimum similarity of matched containers with few leaves (change distiller)
+    pub sim_threshold2: f64,
+    /// minimum label similarity of matched leaves (change distiller)
+    pub label_sim_threshold: f64,
+}
+
+impl DiffConfig {
+    /// The thresholds hard-coded in the corresponding `algorithms` module
+    pub fn new(algorithm: Algorithm) -> Self {
+        match algorithm {
+            Algorithm::GumtreeLazy => Self {
+                algorithm,
+                min_height: 1,
+                max_size: 1000,
+                sim_threshold: 0.5,
+                sim_threshold2: 0.5,
+                label_sim_threshold: 0.5,
+            },
+            Algorithm::GumtreeHybridLazy => Self {
+                algorithm,
+                min_height: 1,
+                max_size: 100,
+                sim_threshold: 0.5,
+                sim_threshold2: 0.5,
+                label_sim_threshold: 0.5,
+            },
+            Algorithm::ChangeDistillerLazy => Self {
+                algorithm,
+                min_height: 1,
+                max_size: 4,
+                sim_threshold: 0.6,
+                sim_threshold2: 0.4,
+                label_sim_threshold: 0.5,
+            },
+        }
+    }
+
+    /// Overrides the threshold named `key`
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        fn parse<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, String> {
+            value
+                .parse()
+                .map_err(|_| format!("invalid value {value} for {key}"))
+        }
+        match key {
+            "min_height" => self.min_height = parse(key, value)?,
+            "max_size" => self.max_size = parse(key, value)?,
+            "sim_threshold" => self.sim_threshold = parse(key, value)?,
+            "sim_threshold2" => self.sim_threshold2 = parse(key, value)?,
+            "label_sim_threshold" => self.label_sim_threshold = parse(key, value)?,
+            key => return Err(format!("unknown threshold {key}")),
+        }
+        Ok(())
+    }
+}
+
+impl Default for DiffConfig {
+    fn default() -> Self {
+        Self::new(Algorithm::default())
+    }
+}
+
+impl std::fmt::Display for DiffConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{},min_height={},max_size={},sim_threshold={},sim_threshold2={},label_sim_threshold={}",
+            self.algorithm,
+            self.min_height,
+            self.max_size,
+            self.sim_threshold,
+            self.sim_threshold2,
+            self.label_sim_threshold
+        )
+    }
+}
+
+/// Parses an algorithm name followed by overridden thresholds,
+/// eg. `gumtree_hybrid_lazy,min_height=2,max_size=50`
+impl std::str::FromStr for DiffConfig {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut it = s.split(',');
+        let algorithm = it.next().unwrap_or_default().parse()?;
+        let mut config = Self::new(algorithm);
+        for param in it {
+            let (key, value) = param
+                .split_once('=')
+                .ok_or_else(|| format!("expected key=value, got {param}"))?;
+            config.set(key, value)?;
+        }
+        Ok(config)
+    }
+}
+
+/// Diffs `src` and `dst` with the algorithm and thresholds of `config`,
+/// only the lazy algorithms of [`Algorithm`] are available, see [`Algorithm::NOT_CONFIGURABLE`]
+pub fn diff<HAST: HyperAST + Copy>(
+    hyperast: HAST,
+    src: &HAST::IdN,
+    dst: &HAST::IdN,
+    config: &DiffConfig,
+) -> DiffRes<HAST>
+where
+    HAST::IdN: Copy + Debug + Eq,
+    HAST::IdN: NodeId<IdN = HAST::IdN>,
+    HAST::Label: Clone + Copy + Eq + Debug,
+    HAST::Idx: hyperast::PrimInt,
+    for<'t> types::LendT<'t, HAST>: types::WithHashs
+        + types::WithStats
+        + WithMetaData<compo::MemberImportCount>
+        + WithMetaData<compo::StmtCount>,
+{
+    match config.algorithm {
+        Algorithm::GumtreeLazy => super::gumtree_lazy::diff_with_config(hyperast, src, dst, config),
+        Algorithm::GumtreeHybridLazy => {
+            super::gumtree_hybrid_lazy::diff_with_config(hyperast, src, dst, config)
+        }
+        Algorithm::ChangeDistillerLazy => {
+            super::change_distiller_lazy::diff_with_config(hyperast, src, dst, config)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_config() {
+        let config: DiffConfig = "gumtree_hybrid_lazy,min_height=2,max_size=50"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            config,
+            DiffConfig {
+                min_height: 2,
+                max_size: 50,
+                ..DiffConfig::new(Algorithm::GumtreeHybridLazy)
+            }
+        );
+        let config: DiffConfig = "change_distiller_lazy".parse().unwrap();
+        assert_eq!(config, DiffConfig::new(Algorithm::ChangeDistillerLazy));
+        let config = DiffConfig::new(Algorithm::GumtreeLazy);
+        assert_eq!(config.to_string().parse::<DiffConfig>(), Ok(config));
+        for name in Algorithm::NOT_CONFIGURABLE {
+            let err = name.parse::<Algorithm>().unwrap_err();
+            assert!(err.contains("cannot be configured"), "{}", err);
+        }
+        assert!("gumtree".parse::<DiffConfig>().is_err());
+        let err = "gumtree_lazyy".parse::<Algorithm>().unwrap_err();
+        assert!(err.starts_with("unknown algorithm"), "{}", err);
+        assert!("gumtree_lazy,min_height".parse::<DiffConfig>().is_err());
+        assert!("gumtree_lazy,height=2".parse::<DiffConfig>().is_err());
+    }
+}
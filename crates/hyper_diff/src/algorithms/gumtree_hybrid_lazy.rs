@@ -12,6 +12,7 @@ use hyperast::types::{self, HyperAST, NodeId};
 
 // use crate::decompressed_tree_store::lazy_post_order::LazyPostOrder;
 use super::DS;
+use super::config::{Algorithm, DiffConfig};
 
 use crate::matchers::heuristic::gt::lazy_hybrid_bottom_up_matcher::LazyHybridBottomUpMatcher;
 use crate::matchers::heuristic::gt::lazy2_greedy_subtree_matcher::LazyGreedySubtreeMatcher;
@@ -47,6 +48,29 @@ pub fn diff_with_hyperparameters<
     src: &HAST::IdN,
     dst: &HAST::IdN,
 ) -> DiffRes<HAST>
+where
+    HAST::IdN: Clone + Debug + Eq,
+    HAST::IdN: NodeId<IdN = HAST::IdN>,
+    HAST::Label: Clone + Copy + Eq + Debug,
+    HAST::Idx: hyperast::PrimInt,
+    for<'t> types::LendT<'t, HAST>: types::WithHashs + types::WithStats,
+{
+    let config = DiffConfig {
+        min_height: MIN_HEIGHT,
+        max_size: SIZE_THRESHOLD,
+        sim_threshold: SIM_THRESHOLD_NUM as f64 / SIM_THRESHOLD_DEN as f64,
+        ..DiffConfig::new(Algorithm::GumtreeHybridLazy)
+    };
+    diff_with_config(hyperast, src, dst, &config)
+}
+
+/// Uses the `min_height` and `max_size` of `config`
+pub fn diff_with_config<HAST: HyperAST + Copy>(
+    hyperast: HAST,
+    src: &HAST::IdN,
+    dst: &HAST::IdN,
+    config: &DiffConfig,
+) -> DiffRes<HAST>
 where
     HAST::IdN: Clone + Debug + Eq,
     HAST::IdN: NodeId<IdN = HAST::IdN>,
@@ -59,23 +83,15 @@ where
     let mapper = Mapper::with_mut_decompressible(&mut mapper_owned);
     let measure = measure.start();
 
-    let mapper = LazyGreedySubtreeMatcher::<_, _, _, M, MIN_HEIGHT>::match_it::<MM>(mapper);
+    let mapper =
+        LazyGreedySubtreeMatcher::<_, _, _, M>::match_it_with::<MM>(mapper, config.min_height);
     let subtree_mappings_s = mapper.mappings().len();
 
     tr!(subtree_mappings_s);
 
     let measure = measure.stop_then_skip_prepare();
 
-    let mapper = LazyHybridBottomUpMatcher::<
-        _,
-        _,
-        _,
-        M,
-        M,
-        SIZE_THRESHOLD,
-        SIM_THRESHOLD_NUM,
-        SIM_THRESHOLD_DEN,
-    >::match_it(mapper);
+    let mapper = LazyHybridBottomUpMatcher::<_, _, _, M, M>::match_it_with(mapper, config.max_size);
     let bottomup_mappings_s = mapper.mappings().len();
 
     tr!(bottomup_mappings_s);
@@ -12,6 +12,7 @@ use hyperast::types::{self, HyperAST, NodeId};
 
 // use crate::decompressed_tree_store::lazy_post_order::LazyPostOrder;
 use super::DS;
+use super::config::{Algorithm, DiffConfig};
 
 use crate::matchers::heuristic::gt::lazy2_greedy_bottom_up_matcher::LazyGreedyBottomUpMatcher;
 use crate::matchers::heuristic::gt::lazy2_greedy_subtree_matcher::LazyGreedySubtreeMatcher;
@@ -24,6 +25,23 @@ pub fn diff<HAST: HyperAST + Copy>(
     src: &HAST::IdN,
     dst: &HAST::IdN,
 ) -> DiffRes<HAST>
+where
+    HAST::IdN: Clone + Debug + Eq,
+    HAST::IdN: NodeId<IdN = HAST::IdN>,
+    HAST::Label: Clone + Copy + Eq + Debug,
+    HAST::Idx: hyperast::PrimInt,
+    for<'t> types::LendT<'t, HAST>: types::WithHashs + types::WithStats,
+{
+    diff_with_config(hyperast, src, dst, &DiffConfig::new(Algorithm::GumtreeLazy))
+}
+
+/// Uses the `min_height`, `max_size` and `sim_threshold` of `config`
+pub fn diff_with_config<HAST: HyperAST + Copy>(
+    hyperast: HAST,
+    src: &HAST::IdN,
+    dst: &HAST::IdN,
+    config: &DiffConfig,
+) -> DiffRes<HAST>
 where
     HAST::IdN: Clone + Debug + Eq,
     HAST::IdN: NodeId<IdN = HAST::IdN>,
@@ -36,14 +54,19 @@ where
     let mapper = Mapper::with_mut_decompressible(&mut mapper_owned);
     let measure = measure.start();
 
-    let mapper = LazyGreedySubtreeMatcher::<_, _, _, M>::match_it::<MM>(mapper);
+    let mapper =
+        LazyGreedySubtreeMatcher::<_, _, _, M>::match_it_with::<MM>(mapper, config.min_height);
     let subtree_mappings_s = mapper.mappings().len();
 
     tr!(subtree_mappings_s);
 
     let measure = measure.stop_then_skip_prepare();
 
-    let mapper = LazyGreedyBottomUpMatcher::<_, _, _, _>::match_it(mapper);
+    let mapper = LazyGreedyBottomUpMatcher::<_, _, _, _>::match_it_with(
+        mapper,
+        config.max_size,
+        config.sim_threshold,
+    );
     let bottomup_mappings_s = mapper.mappings().len();
 
     tr!(bottomup_mappings_s);
@@ -18,6 +18,9 @@ pub mod gumtree_stable;
 pub mod gumtree_stable_lazy;
 pub mod xy;
 
+mod config;
+pub use config::{Algorithm, DiffConfig, diff};
+
 type DefaultMetric = <LatMem as RuntimeMeasurement>::M;
 type DefaultMetricSetup = Phased<Prepared<DefaultMetric>>;
 
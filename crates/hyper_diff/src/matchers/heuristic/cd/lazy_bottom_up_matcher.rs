@@ -27,6 +27,15 @@ pub struct BottomUpMatcher<
     internal: Mapper<HAST, Dsrc, Ddst, M>,
 }
 
+/// Runtime counterpart of the const thresholds of [`BottomUpMatcher`]
+#[derive(Clone, Copy, Debug)]
+pub struct Thresholds {
+    /// number of leaves above which `sim` is required, `sim2` being required below
+    pub size: usize,
+    pub sim: f64,
+    pub sim2: f64,
+}
+
 impl<
     Dsrc: LazyDecompressed<M::Src>,
     Ddst: LazyDecompressed<M::Dst>,
@@ -73,9 +82,28 @@ where
     HAST::IdN: Debug,
     HAST::IdN: NodeId<IdN = HAST::IdN>,
 {
+    pub const THRESHOLDS: Thresholds = Thresholds {
+        size: SIZE_THRESHOLD,
+        sim: SIM_THRESHOLD_NUM as f64 / SIM_THRESHOLD_DEN as f64,
+        sim2: SIM_THRESHOLD2_NUM as f64 / SIM_THRESHOLD2_DEN as f64,
+    };
+
     pub fn match_it(
         mapping: crate::matchers::Mapper<HAST, Dsrc, Ddst, M>,
     ) -> crate::matchers::Mapper<HAST, Dsrc, Ddst, M>
+    where
+        for<'t> <HAST as hyperast::types::AstLending<'t>>::RT: WithMetaData<compo::StmtCount>,
+        for<'t> <HAST as hyperast::types::AstLending<'t>>::RT:
+            WithMetaData<compo::MemberImportCount>,
+    {
+        Self::match_it_with(mapping, Self::THRESHOLDS)
+    }
+
+    /// Same as [`Self::match_it`] but with thresholds only known at runtime
+    pub fn match_it_with(
+        mapping: crate::matchers::Mapper<HAST, Dsrc, Ddst, M>,
+        thresholds: Thresholds,
+    ) -> crate::matchers::Mapper<HAST, Dsrc, Ddst, M>
     where
         for<'t> <HAST as hyperast::types::AstLending<'t>>::RT: WithMetaData<compo::StmtCount>,
         for<'t> <HAST as hyperast::types::AstLending<'t>>::RT:
@@ -86,7 +114,7 @@ where
             matcher.internal.mapping.src_arena.len(),
             matcher.internal.mapping.dst_arena.len(),
         );
-        Self::execute(&mut matcher.internal, leaf_count);
+        Self::execute_with(&mut matcher.internal, leaf_count, thresholds);
         matcher.internal
     }
 
@@ -109,7 +137,10 @@ where
                 let src = src_arena.decompress_to(&src);
                 let dst = dst_arena.decompress_to(&dst);
                 let leaves = leaf_count(hyperast, src_arena.original(&src));
-                if Self::inner(hyperast, mappings, src_arena, dst_arena, src, dst, leaves) {
+                let thresholds = Self::THRESHOLDS;
+                if Self::inner(
+                    hyperast, mappings, src_arena, dst_arena, src, dst, leaves, thresholds,
+                ) {
                     break;
                 }
             }
@@ -119,6 +150,14 @@ where
     pub fn execute(
         internal: &mut Mapper<HAST, Dsrc, Ddst, M>,
         leaf_count: fn(HAST, HAST::IdN) -> usize,
+    ) {
+        Self::execute_with(internal, leaf_count, Self::THRESHOLDS)
+    }
+
+    pub fn execute_with(
+        internal: &mut Mapper<HAST, Dsrc, Ddst, M>,
+        leaf_count: fn(HAST, HAST::IdN) -> usize,
+        thresholds: Thresholds,
     ) {
         let hyperast = internal.hyperast;
         for src in internal.src_arena.iter_df_post::<true>() {
@@ -138,7 +177,9 @@ where
                 let dst_arena = &*dst_iter.arena;
                 let src = src_arena.decompress_to(&src);
                 let leaves = leaf_count(hyperast, src_arena.original(&src));
-                if Self::inner(hyperast, mappings, src_arena, dst_arena, src, dst, leaves) {
+                if Self::inner(
+                    hyperast, mappings, src_arena, dst_arena, src, dst, leaves, thresholds,
+                ) {
                     break;
                 }
             }
@@ -172,13 +213,17 @@ where
                 let mappings = &mut internal.mapping.mappings;
                 let src_arena = &*src_iter.arena;
                 let dst_arena = &*dst_iter.arena;
-                if Self::inner(hyperast, mappings, src_arena, dst_arena, src, dst, leaves) {
+                let thresholds = Self::THRESHOLDS;
+                if Self::inner(
+                    hyperast, mappings, src_arena, dst_arena, src, dst, leaves, thresholds,
+                ) {
                     break;
                 }
             }
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn inner(
         hyperast: HAST,
         mappings: &mut M,
@@ -187,6 +232,7 @@ where
         src: Dsrc::IdD,
         dst: Ddst::IdD,
         number_of_leaves: usize,
+        thresholds: Thresholds,
     ) -> bool
     where
         HAST: HyperAST + Copy,
@@ -204,10 +250,8 @@ where
                     &*mappings,
                 )
                 .chawathe();
-                let cond1 = number_of_leaves > SIZE_THRESHOLD
-                    && sim >= SIM_THRESHOLD_NUM as f64 / SIM_THRESHOLD_DEN as f64;
-                let cond2 = number_of_leaves <= SIZE_THRESHOLD
-                    && sim >= SIM_THRESHOLD2_NUM as f64 / SIM_THRESHOLD2_DEN as f64;
+                let cond1 = number_of_leaves > thresholds.size && sim >= thresholds.sim;
+                let cond2 = number_of_leaves <= thresholds.size && sim >= thresholds.sim2;
                 if cond1 || cond2 {
                     mappings.link(src.to_shallow(), dst.to_shallow());
                     return true;
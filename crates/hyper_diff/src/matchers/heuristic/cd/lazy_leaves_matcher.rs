@@ -48,6 +48,19 @@ where
     pub fn match_it(
         mapping: crate::matchers::Mapper<HAST, Dsrc, Ddst, M>,
     ) -> crate::matchers::Mapper<HAST, Dsrc, Ddst, M>
+    where
+        for<'t> <HAST as hyperast::types::AstLending<'t>>::RT:
+            WithMetaData<compo::StmtCount> + WithMetaData<compo::MemberImportCount>,
+    {
+        let sim_threshold = SIM_THRESHOLD_NUM as f64 / SIM_THRESHOLD_DEN as f64;
+        Self::match_it_with(mapping, sim_threshold)
+    }
+
+    /// Same as [`Self::match_it`] but with a label similarity threshold only known at runtime
+    pub fn match_it_with(
+        mapping: crate::matchers::Mapper<HAST, Dsrc, Ddst, M>,
+        sim_threshold: f64,
+    ) -> crate::matchers::Mapper<HAST, Dsrc, Ddst, M>
     where
         for<'t> <HAST as hyperast::types::AstLending<'t>>::RT:
             WithMetaData<compo::StmtCount> + WithMetaData<compo::MemberImportCount>,
@@ -60,10 +73,11 @@ where
             matcher.internal.mapping.src_arena.len(),
             matcher.internal.mapping.dst_arena.len(),
         );
-        Self::execute(&mut matcher.internal, is_leaf_file, is_leaf_file);
-        Self::execute(&mut matcher.internal, is_leaf_sub_file, is_leaf_sub_file);
-        Self::execute(&mut matcher.internal, is_leaf_stmt, is_leaf_stmt);
-        Self::execute(&mut matcher.internal, is_leaf, is_leaf);
+        let internal = &mut matcher.internal;
+        Self::execute_with(internal, is_leaf_file, is_leaf_file, sim_threshold);
+        Self::execute_with(internal, is_leaf_sub_file, is_leaf_sub_file, sim_threshold);
+        Self::execute_with(internal, is_leaf_stmt, is_leaf_stmt, sim_threshold);
+        Self::execute_with(internal, is_leaf, is_leaf, sim_threshold);
         matcher.internal
     }
     pub fn match_stmt(
@@ -135,6 +149,16 @@ where
         internal: &mut Mapper<HAST, Dsrc, Ddst, M>,
         is_leaf_src: fn(HAST, &Dsrc, Dsrc::IdD) -> bool,
         is_leaf_dst: fn(HAST, &Ddst, Ddst::IdD) -> bool,
+    ) {
+        let sim_threshold = SIM_THRESHOLD_NUM as f64 / SIM_THRESHOLD_DEN as f64;
+        Self::execute_with(internal, is_leaf_src, is_leaf_dst, sim_threshold)
+    }
+
+    pub fn execute_with(
+        internal: &mut Mapper<HAST, Dsrc, Ddst, M>,
+        is_leaf_src: fn(HAST, &Dsrc, Dsrc::IdD) -> bool,
+        is_leaf_dst: fn(HAST, &Ddst, Ddst::IdD) -> bool,
+        sim_threshold: f64,
     ) {
         let hyperast = internal.hyperast;
         let mapping = &mut internal.mapping;
@@ -171,7 +195,7 @@ where
                         }
                         let p = [src_iter.arena.original(&src), dst_iter.arena.original(&dst)];
                         let sim = S::norm(&hyperast, &p);
-                        if sim > SIM_THRESHOLD_NUM as f64 / SIM_THRESHOLD_DEN as f64 {
+                        if sim > sim_threshold {
                             #[cfg(not(feature = "no_precomp_sim"))]
                             leaves_mappings.push((src, dst, sim));
                             #[cfg(feature = "no_precomp_sim")]
@@ -92,6 +92,16 @@ where
 {
     pub fn match_it(
         mapping: crate::matchers::Mapper<HAST, Dsrc, Ddst, M>,
+    ) -> crate::matchers::Mapper<HAST, Dsrc, Ddst, M> {
+        let sim_threshold = SIM_THRESHOLD_NUM as f64 / SIM_THRESHOLD_DEN as f64;
+        Self::match_it_with(mapping, SIZE_THRESHOLD, sim_threshold)
+    }
+
+    /// Same as [`Self::match_it`] but with thresholds only known at runtime
+    pub fn match_it_with(
+        mapping: crate::matchers::Mapper<HAST, Dsrc, Ddst, M>,
+        size_threshold: usize,
+        sim_threshold: f64,
     ) -> crate::matchers::Mapper<HAST, Dsrc, Ddst, M> {
         let mut matcher = Self {
             internal: mapping,
@@ -101,11 +111,20 @@ where
             matcher.internal.mapping.src_arena.len(),
             matcher.internal.mapping.dst_arena.len(),
         );
-        Self::execute(&mut matcher.internal);
+        Self::execute_with(&mut matcher.internal, size_threshold, sim_threshold);
         matcher.internal
     }
 
     pub fn execute(internal: &mut Mapper<HAST, Dsrc, Ddst, M>) {
+        let sim_threshold = SIM_THRESHOLD_NUM as f64 / SIM_THRESHOLD_DEN as f64;
+        Self::execute_with(internal, SIZE_THRESHOLD, sim_threshold)
+    }
+
+    pub fn execute_with(
+        internal: &mut Mapper<HAST, Dsrc, Ddst, M>,
+        size_threshold: usize,
+        sim_threshold: f64,
+    ) {
         assert_eq!(
             // TODO move it inside the arena ...
             internal.src_arena.root(),
@@ -133,14 +152,14 @@ where
                         &internal.mappings,
                     )
                     .dice();
-                    if sim > max && sim >= SIM_THRESHOLD_NUM as f64 / SIM_THRESHOLD_DEN as f64 {
+                    if sim > max && sim >= sim_threshold {
                         max = sim;
                         best = Some(cand);
                     }
                 }
 
                 if let Some(best) = best {
-                    Self::last_chance_match_zs(internal, a, best);
+                    Self::last_chance_match_zs(internal, a, best, size_threshold);
                     internal.mappings.link(*a.shallow(), *best.shallow());
                 }
             }
@@ -152,7 +171,7 @@ where
         );
         let src = internal.src_arena.starter();
         let dst = internal.dst_arena.starter();
-        Self::last_chance_match_zs(internal, src, dst);
+        Self::last_chance_match_zs(internal, src, dst, size_threshold);
     }
 
     fn src_has_children(internal: &Mapper<HAST, Dsrc, Ddst, M>, src: Dsrc::IdD) -> bool {
@@ -174,6 +193,7 @@ where
         internal: &mut Mapper<HAST, Dsrc, Ddst, M>,
         src: Dsrc::IdD,
         dst: Ddst::IdD,
+        size_threshold: usize,
     ) {
         let stores = internal.hyperast;
         // allow using another internal mapping store
@@ -183,7 +203,7 @@ where
         let dst_arena = &mut mapping.dst_arena;
         let src_s = src_arena.descendants_count(&src);
         let dst_s = dst_arena.descendants_count(&dst);
-        if !(src_s < cast(SIZE_THRESHOLD).unwrap() || dst_s < cast(SIZE_THRESHOLD).unwrap()) {
+        if !(src_s < cast(size_threshold).unwrap() || dst_s < cast(size_threshold).unwrap()) {
             return;
         }
         let src_offset;
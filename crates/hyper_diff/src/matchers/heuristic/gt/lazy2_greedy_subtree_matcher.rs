@@ -43,6 +43,17 @@ where
     pub fn match_it<MM>(
         mapping: crate::matchers::Mapper<HAST, Dsrc, Ddst, M>,
     ) -> crate::matchers::Mapper<HAST, Dsrc, Ddst, M>
+    where
+        MM: MultiMappingStore<Src = Dsrc::IdD, Dst = Ddst::IdD> + Default,
+    {
+        Self::match_it_with::<MM>(mapping, MIN_HEIGHT)
+    }
+
+    /// Same as [`Self::match_it`] but with a minimum height only known at runtime
+    pub fn match_it_with<MM>(
+        mapping: crate::matchers::Mapper<HAST, Dsrc, Ddst, M>,
+        min_height: usize,
+    ) -> crate::matchers::Mapper<HAST, Dsrc, Ddst, M>
     where
         MM: MultiMappingStore<Src = Dsrc::IdD, Dst = Ddst::IdD> + Default,
     {
@@ -51,7 +62,8 @@ where
             matcher.internal.mapping.src_arena.len(),
             matcher.internal.mapping.dst_arena.len(),
         );
-        Self::execute::<MM>(&mut matcher);
+        let mm: MM = Self::compute_multi_mapping_with(&mut matcher.internal, min_height);
+        Self::filter_mappings(&mut matcher.internal, &mm);
         matcher.internal
     }
 
@@ -72,14 +84,24 @@ where
         MM: MultiMappingStore<Src = Dsrc::IdD, Dst = Ddst::IdD> + Default,
     >(
         internal: &mut Mapper<HAST, Dsrc, Ddst, M>,
+    ) -> MM {
+        Self::compute_multi_mapping_with(internal, MIN_HEIGHT)
+    }
+
+    pub fn compute_multi_mapping_with<
+        MM: MultiMappingStore<Src = Dsrc::IdD, Dst = Ddst::IdD> + Default,
+    >(
+        internal: &mut Mapper<HAST, Dsrc, Ddst, M>,
+        min_height: usize,
     ) -> MM {
         let mut mm: MM = Default::default();
         mm.topit(internal.src_arena.len(), internal.dst_arena.len());
-        Mapper::<HAST, Dsrc, Ddst, M>::compute_multimapping::<_, MIN_HEIGHT>(
+        Mapper::<HAST, Dsrc, Ddst, M>::compute_multimapping_with(
             internal.hyperast,
             &mut internal.mapping.src_arena,
             &mut internal.mapping.dst_arena,
             &mut mm,
+            min_height,
         );
         mm
     }
@@ -359,15 +381,27 @@ where
         dst_arena: &mut Ddst,
         multi_mappings: &mut MM,
     ) {
-        let mut src_trees = PriorityTreeList::<'_, Dsrc, M::Src, Dsrc::IdD, HAST, MIN_HEIGHT>::new(
+        Self::compute_multimapping_with(hyperast, src_arena, dst_arena, multi_mappings, MIN_HEIGHT)
+    }
+
+    pub fn compute_multimapping_with<MM: MultiMappingStore<Src = Dsrc::IdD, Dst = Ddst::IdD>>(
+        hyperast: HAST,
+        src_arena: &mut Dsrc,
+        dst_arena: &mut Ddst,
+        multi_mappings: &mut MM,
+        min_height: usize,
+    ) {
+        let mut src_trees = PriorityTreeList::<'_, Dsrc, M::Src, Dsrc::IdD, HAST>::new(
             hyperast,
             src_arena.starter(),
             src_arena,
+            min_height,
         );
-        let mut dst_trees = PriorityTreeList::<'_, Ddst, M::Dst, Ddst::IdD, HAST, MIN_HEIGHT>::new(
+        let mut dst_trees = PriorityTreeList::<'_, Ddst, M::Dst, Ddst::IdD, HAST>::new(
             hyperast,
             dst_arena.starter(),
             dst_arena,
+            min_height,
         );
         while src_trees.peek_height() != -1 && dst_trees.peek_height() != -1 {
             // println!("multi_mappings={}", multi_mappings.len());
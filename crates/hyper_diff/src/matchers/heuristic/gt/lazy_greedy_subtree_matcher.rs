@@ -421,15 +421,17 @@ where
         multi_mappings: &mut MM,
     ) {
         let now = std::time::Instant::now();
-        let mut src_trees = PriorityTreeList::<_, _, _, HAST, MIN_HEIGHT>::new(
+        let mut src_trees = PriorityTreeList::<_, _, _, HAST>::new(
             self.stores,
             self.src_arena.starter(),
             &mut self.src_arena,
+            MIN_HEIGHT,
         );
-        let mut dst_trees = PriorityTreeList::<_, _, _, HAST, MIN_HEIGHT>::new(
+        let mut dst_trees = PriorityTreeList::<_, _, _, HAST>::new(
             self.stores,
             self.dst_arena.starter(),
             &mut self.dst_arena,
+            MIN_HEIGHT,
         );
         let match_init_t = now.elapsed().as_secs_f64();
         dbg!(match_init_t);
@@ -439,7 +441,6 @@ where
             M::Src,
             Dsrc::IdD,
             HAST,
-            MIN_HEIGHT,
         >,
 
                           dst_trees: &mut PriorityTreeList<
@@ -448,7 +449,6 @@ where
             M::Dst,
             Ddst::IdD,
             HAST,
-            MIN_HEIGHT,
         >| {
             if src_trees.peek_height() > dst_trees.peek_height() {
                 src_trees.open();
@@ -566,9 +566,11 @@ where
     }
 }
 
-pub(super) struct PriorityTreeList<'b, D, IdS, IdD, S, const MIN_HEIGHT: usize> {
+pub(super) struct PriorityTreeList<'b, D, IdS, IdD, S> {
     pub trees: Vec<Option<Vec<IdD>>>,
 
+    pub min_height: usize,
+
     pub store: S,
     pub(super) arena: &'b mut D,
 
@@ -579,24 +581,24 @@ pub(super) struct PriorityTreeList<'b, D, IdS, IdD, S, const MIN_HEIGHT: usize>
     pub phantom: std::marker::PhantomData<IdS>,
 }
 
-impl<'b, D, IdD, HAST, const MIN_HEIGHT: usize>
-    PriorityTreeList<'b, D, IdD, D::IdD, HAST, MIN_HEIGHT>
+impl<'b, D, IdD, HAST> PriorityTreeList<'b, D, IdD, D::IdD, HAST>
 where
     D::IdD: Clone,
     D: LazyDecompressedTreeStore<HAST, IdD>,
     HAST: HyperAST + Copy,
     for<'t> <HAST as hyperast::types::AstLending<'t>>::RT: WithStats,
 {
-    pub(super) fn new(store: HAST, tree: D::IdD, arena: &'b mut D) -> Self {
+    pub(super) fn new(store: HAST, tree: D::IdD, arena: &'b mut D, min_height: usize) -> Self {
         let id = arena.original(&tree);
         let h = store.resolve(&id).height() - 1;
-        let list_size = if h >= MIN_HEIGHT {
-            h + 1 - MIN_HEIGHT
+        let list_size = if h >= min_height {
+            h + 1 - min_height
         } else {
             0
         };
         let mut r = Self {
             trees: vec![Default::default(); list_size],
+            min_height,
             store,
             arena,
             max_height: h,
@@ -622,7 +624,7 @@ where
     }
 
     pub(super) fn add_tree_aux(&mut self, tree: D::IdD, h: usize) {
-        if h >= MIN_HEIGHT {
+        if h >= self.min_height {
             let idx = self.idx(h);
             if self.trees[idx].is_none() {
                 self.trees[idx] = Some(vec![]);
@@ -30,6 +30,7 @@ pub struct LazyHybridBottomUpMatcher<
     const SIM_THRESHOLD_DEN: u64 = 2,
 > {
     internal: Mapper<HAST, Dsrc, Ddst, M>,
+    size_threshold: usize,
     _phantom: PhantomData<*const MZs>,
 }
 
@@ -82,9 +83,18 @@ where
 {
     pub fn match_it(
         mapping: crate::matchers::Mapper<HAST, Dsrc, Ddst, M>,
+    ) -> crate::matchers::Mapper<HAST, Dsrc, Ddst, M> {
+        Self::match_it_with(mapping, SIZE_THRESHOLD)
+    }
+
+    /// Same as [`Self::match_it`] but with a size threshold only known at runtime
+    pub fn match_it_with(
+        mapping: crate::matchers::Mapper<HAST, Dsrc, Ddst, M>,
+        size_threshold: usize,
     ) -> crate::matchers::Mapper<HAST, Dsrc, Ddst, M> {
         let mut matcher = Self {
             internal: mapping,
+            size_threshold,
             _phantom: Default::default(),
         };
         matcher.internal.mapping.mappings.topit(
@@ -157,11 +167,11 @@ where
     }
 
     /// Hybrid recovery algorithm (finds mappings between src and dst descendants)
-    /// Uses ZS (optimal) if the number of descendents is below SIZE_THRESHOLD
+    /// Uses ZS (optimal) if the number of descendents is below the size threshold
     /// Uses simple recovery otherwise
     fn last_chance_match_hybrid(&mut self, src: Dsrc::IdD, dst: Ddst::IdD) {
-        if self.internal.src_arena.descendants_count(&src) < SIZE_THRESHOLD
-            && self.internal.dst_arena.descendants_count(&dst) < SIZE_THRESHOLD
+        if self.internal.src_arena.descendants_count(&src) < self.size_threshold
+            && self.internal.dst_arena.descendants_count(&dst) < self.size_threshold
         {
             self.last_chance_match_zs(src, dst);
         } else {
@@ -177,7 +187,7 @@ where
         let dst_arena = &mut mapping.dst_arena;
         let src_s = src_arena.descendants_count(&src);
         let dst_s = dst_arena.descendants_count(&dst);
-        if !(src_s < SIZE_THRESHOLD || dst_s < SIZE_THRESHOLD) {
+        if !(src_s < self.size_threshold || dst_s < self.size_threshold) {
             return;
         }
         let src_offset;
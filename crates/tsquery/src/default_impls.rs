@@ -240,6 +240,19 @@ impl<'a> super::Node for tree_sitter::Node<'a> {
         super::BiCow::B(r)
     }
 
+    fn metadata(&self, metadata: crate::predicate::Metadata) -> Option<usize> {
+        use crate::predicate::Metadata;
+        match metadata {
+            Metadata::BytesLen => Some(self.end_byte() - self.start_byte()),
+            Metadata::LineCount => Some(self.end_position().row - self.start_position().row),
+            _ => None,
+        }
+    }
+
+    fn parent_node(&self) -> Option<Self> {
+        self.parent()
+    }
+
     // fn id(&self) -> usize {
     //     self.id()
     // }
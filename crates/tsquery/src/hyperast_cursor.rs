@@ -423,6 +423,80 @@ where
         }
         super::BiCow::B("".into()) // TODO check if it is the right behavior
     }
+
+    fn metadata(&self, metadata: crate::predicate::Metadata) -> Option<usize> {
+        let n = self.stores.node_store().resolve(self.pos.node()?);
+        self::metadata(&n, metadata)
+    }
+
+    fn derived(&self, name: &str) -> Option<f64> {
+        let n = self.stores.node_store().resolve(self.pos.node()?);
+        self::derived(&n, name)
+    }
+
+    fn parent_node(&self) -> Option<Self> {
+        let mut p = self.clone();
+        p.goto_parent().then_some(p)
+    }
+
+    fn file_path(&self) -> Option<std::path::PathBuf> {
+        let mut p = self.clone();
+        let mut segments = vec![];
+        while let Some(n) = p.pos.node() {
+            let k = self.stores.resolve_type(n);
+            if k.is_file() || k.is_directory() {
+                let n = self.stores.node_store().resolve(n);
+                let l = self.stores.label_store().resolve(n.try_get_label()?);
+                segments.push(l.to_string());
+            }
+            p.pos.pop();
+        }
+        segments.retain(|x| !x.is_empty());
+        (!segments.is_empty()).then(|| segments.into_iter().rev().collect())
+    }
+}
+
+/// Reads metadata from the components of a node, with the same defaults as the legion store
+pub(crate) fn metadata(
+    n: &impl hyperast::store::nodes::ErasedHolder,
+    metadata: crate::predicate::Metadata,
+) -> Option<usize> {
+    use crate::predicate::Metadata;
+    use hyperast::store::nodes::compo;
+    use std::any::TypeId;
+    match metadata {
+        Metadata::Size => Some(
+            n.unerase_ref::<compo::Size>(TypeId::of::<compo::Size>())
+                .map_or(1, |x| x.0 as usize),
+        ),
+        Metadata::Height => Some(
+            n.unerase_ref::<compo::Height>(TypeId::of::<compo::Height>())
+                .map_or(1, |x| x.0 as usize),
+        ),
+        Metadata::BytesLen => n
+            .unerase_ref::<compo::BytesLen>(TypeId::of::<compo::BytesLen>())
+            .map(|x| x.0 as usize),
+        Metadata::LineCount => Some(
+            n.unerase_ref::<compo::LineCount>(TypeId::of::<compo::LineCount>())
+                .map_or(0, |x| x.0 as usize),
+        ),
+    }
+}
+
+/// Reads a value computed by a prepro script, see [`hyperast::scripting::DerivedData`]
+pub(crate) fn derived(n: &impl hyperast::store::nodes::ErasedHolder, name: &str) -> Option<f64> {
+    #[cfg(feature = "scripting")]
+    {
+        use hyperast::scripting::DerivedData;
+        use std::any::TypeId;
+        n.unerase_ref::<DerivedData>(TypeId::of::<DerivedData>())?
+            .get_number(name)
+    }
+    #[cfg(not(feature = "scripting"))]
+    {
+        let _ = (n, name);
+        None
+    }
 }
 
 impl<'hast, HAST: HyperAST> Node<'hast, HAST>
@@ -400,6 +400,23 @@ where
     ) -> super::BiCow<'s, 'l, str> {
         text(self.stores, &self.pos)
     }
+
+    fn metadata(&self, metadata: crate::predicate::Metadata) -> Option<usize> {
+        crate::hyperast_cursor::metadata(&resolve(self.stores, &self.pos), metadata)
+    }
+
+    fn derived(&self, name: &str) -> Option<f64> {
+        crate::hyperast_cursor::derived(&resolve(self.stores, &self.pos), name)
+    }
+
+    fn parent_node(&self) -> Option<Self> {
+        let mut slf = self.clone();
+        goto_parent(self.stores, &mut slf.pos).then_some(slf)
+    }
+
+    fn file_path(&self) -> Option<std::path::PathBuf> {
+        file_path(self.stores, self.pos.clone())
+    }
 }
 
 impl<'a, 'b, 'hast, HAST: HyperAST> super::TextLending<'a> for self::NodeRef<'b, 'hast, HAST> {
@@ -487,6 +504,23 @@ where
     ) -> super::BiCow<'s, 'l, str> {
         text(self.stores, &self.pos)
     }
+
+    fn metadata(&self, metadata: crate::predicate::Metadata) -> Option<usize> {
+        crate::hyperast_cursor::metadata(&resolve(self.stores, &self.pos), metadata)
+    }
+
+    fn derived(&self, name: &str) -> Option<f64> {
+        crate::hyperast_cursor::derived(&resolve(self.stores, &self.pos), name)
+    }
+
+    fn parent_node(&self) -> Option<Self> {
+        let mut slf = self.clone();
+        goto_parent(self.stores, &mut slf.pos).then_some(slf)
+    }
+
+    fn file_path(&self) -> Option<std::path::PathBuf> {
+        file_path(self.stores, self.pos.clone())
+    }
 }
 
 impl<'hast, HAST: HyperAST> Node<'hast, HAST>
@@ -561,6 +595,27 @@ where
     }
 }
 
+fn file_path<'hast, HAST: HyperAST>(
+    stores: &'hast HAST,
+    mut pos: impl AAA<HAST::IdN, HAST::Idx>,
+) -> Option<std::path::PathBuf> {
+    let mut segments = vec![];
+    loop {
+        let k = kind(stores, &pos);
+        if k.is_file() || k.is_directory() {
+            let n = resolve(stores, &pos);
+            let l = stores.label_store().resolve(n.try_get_label()?);
+            if !l.is_empty() {
+                segments.push(l.to_string());
+            }
+        }
+        if !pos.up() {
+            break;
+        }
+    }
+    (!segments.is_empty()).then(|| segments.into_iter().rev().collect())
+}
+
 fn role<'hast, HAST: HyperAST>(
     stores: &'hast HAST,
     pos: &mut impl AAA<HAST::IdN, HAST::Idx>,
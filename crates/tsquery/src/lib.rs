@@ -84,6 +84,7 @@ pub struct Query {
     property_settings: predicate::PropertySettings,
     pub general_predicates: predicate::GeneralPredicates,
     immediate_predicates: Vec<predicate::ImmediateTextPredicate>,
+    custom_predicates: Vec<(Box<str>, std::sync::Arc<dyn predicate::Predicate>)>,
     precomputed_patterns: Option<query::PrecomputedPatterns>,
     used_precomputed: Precomps,
     enabled_pattern_map: Vec<u16>,
@@ -301,6 +302,21 @@ pub trait Node: for<'a> TextLending<'a> + Clone + PartialEq {
             .copied()
             .eq(other)
     }
+
+    /// Metadata used by custom predicates, see [`predicate::PredicateNode`]
+    fn metadata(&self, metadata: predicate::Metadata) -> Option<usize> {
+        None
+    }
+    /// Numeric data derived while building the tree (e.g. by a prepro script), used by custom predicates
+    fn derived(&self, name: &str) -> Option<f64> {
+        None
+    }
+    fn parent_node(&self) -> Option<Self> {
+        None
+    }
+    fn file_path(&self) -> Option<std::path::PathBuf> {
+        None
+    }
 }
 
 pub enum BiCow<'a, 'b, T: ?Sized + 'a + 'b>
@@ -382,6 +398,18 @@ where
     fn text<'s, 'l>(&'s self, text_provider: <Self as TextLending<'l>>::TP) -> BiCow<'s, 'l, str> {
         (*self).text(text_provider)
     }
+
+    fn metadata(&self, metadata: predicate::Metadata) -> Option<usize> {
+        (*self).metadata(metadata)
+    }
+
+    fn derived(&self, name: &str) -> Option<f64> {
+        (*self).derived(name)
+    }
+
+    fn file_path(&self) -> Option<std::path::PathBuf> {
+        (*self).file_path()
+    }
 }
 
 impl<'query, Cursor: self::Cursor> Iterator for QueryCursor<'query, Cursor, Cursor::Node>
@@ -397,7 +425,8 @@ where
                 self.cursor.text_provider(),
                 self.query
                     .text_predicates_for_pattern_id(result.pattern_index),
-            ) {
+            ) && result.satisfies_custom_predicates(self.cursor.text_provider(), self.query)
+            {
                 return Some(result);
             }
         }
@@ -479,6 +508,43 @@ impl<Node: self::Node> QueryMatch<Node> {
     ) -> impl Iterator<Item = &'a Node> {
         self.captures.nodes_for_capture_index(index)
     }
+
+    pub(crate) fn satisfies_custom_predicates<'l>(
+        &self,
+        text_provider: <Node as TextLending<'l>>::TP,
+        query: &Query,
+    ) -> bool {
+        use predicate::{Captured, PredicateArg, PredicateNode, QueryPredicateArg};
+        if query.custom_predicates.is_empty() {
+            return true;
+        }
+        let mut general_predicates = query
+            .general_predicates
+            .preds_for_patern_id(self.pattern_index);
+        let captured = |node: &Node| {
+            let node = node.clone();
+            Box::new(Captured {
+                node,
+                text_provider,
+            }) as Box<dyn PredicateNode + '_>
+        };
+        general_predicates.all(|p| {
+            let Some(predicate) = query.custom_predicate(&p.operator) else {
+                return true;
+            };
+            let args: Vec<_> = (p.args.iter())
+                .map(|arg| match arg {
+                    QueryPredicateArg::Capture(i) => PredicateArg::Capture(
+                        (self.nodes_for_capture_index(CaptureId::new(*i)))
+                            .map(&captured)
+                            .collect(),
+                    ),
+                    QueryPredicateArg::String(s) => PredicateArg::String(s),
+                })
+                .collect();
+            predicate.eval(&args)
+        })
+    }
 }
 
 mod precompute_pattern_predicate;
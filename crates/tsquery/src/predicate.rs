@@ -69,6 +69,10 @@ impl<P: Debug> PerPattern<P> {
         self.0 = r.into();
     }
 
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &[P]> {
+        self.0.iter().map(|x| &**x)
+    }
+
     pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = std::slice::IterMut<P>> + '_ {
         self.0.iter_mut().map(|x| x.iter_mut())
     }
@@ -183,3 +187,245 @@ impl PartialEq for ImmediateTextPredicate {
         }
     }
 }
+
+/// Metadata of a captured node that custom predicates can look at,
+/// absent when the underlying tree does not store it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Metadata {
+    Size,
+    Height,
+    BytesLen,
+    LineCount,
+}
+
+impl Metadata {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "size" => Some(Self::Size),
+            "height" => Some(Self::Height),
+            "bytes_len" => Some(Self::BytesLen),
+            "line_count" => Some(Self::LineCount),
+            _ => None,
+        }
+    }
+}
+
+/// A captured node, as seen by a [`Predicate`]
+pub trait PredicateNode {
+    fn kind(&self) -> &str;
+    fn text(&self) -> String;
+    fn metadata(&self, metadata: Metadata) -> Option<usize>;
+    /// Numeric data derived while building the tree, see [`crate::Node::derived`]
+    fn derived(&self, name: &str) -> Option<f64>;
+    fn parent(&self) -> Option<Box<dyn PredicateNode + '_>>;
+    /// Path of the file containing the node, from the root of the repository
+    fn file(&self) -> Option<std::path::PathBuf>;
+}
+
+/// Argument of a predicate, captures are given with all their nodes
+pub enum PredicateArg<'a> {
+    Capture(Vec<Box<dyn PredicateNode + 'a>>),
+    String(&'a str),
+}
+
+/// Custom predicate, installed with [`crate::Query::register_predicate`].
+///
+/// A use of an unregistered predicate is ignored, like before.
+pub trait Predicate: Send + Sync {
+    /// Checks the arguments of a use of the predicate, when registering it
+    fn check(&self, args: &[QueryPredicateArg]) -> Result<(), String> {
+        let _ = args;
+        Ok(())
+    }
+    fn eval(&self, args: &[PredicateArg<'_>]) -> bool;
+}
+
+impl<F> Predicate for F
+where
+    F: Fn(&[PredicateArg<'_>]) -> bool + Send + Sync,
+{
+    fn eval(&self, args: &[PredicateArg<'_>]) -> bool {
+        self(args)
+    }
+}
+
+fn check_capture_and_strings(args: &[QueryPredicateArg], strings: usize) -> Result<(), String> {
+    match args {
+        [QueryPredicateArg::Capture(_), rest @ ..]
+            if rest.len() == strings
+                && rest
+                    .iter()
+                    .all(|x| matches!(x, QueryPredicateArg::String(_))) =>
+        {
+            Ok(())
+        }
+        _ => Err(format!(
+            "expected a capture followed by {strings} string(s)"
+        )),
+    }
+}
+
+fn capture_and_strings<'a, 'b>(
+    args: &'b [PredicateArg<'a>],
+) -> Option<(&'b [Box<dyn PredicateNode + 'a>], Vec<&'a str>)> {
+    let [PredicateArg::Capture(nodes), rest @ ..] = args else {
+        return None;
+    };
+    let strings = rest.iter().map(|x| match x {
+        PredicateArg::String(s) => Some(*s),
+        PredicateArg::Capture(_) => None,
+    });
+    Some((nodes, strings.collect::<Option<_>>()?))
+}
+
+/// `(#has-ancestor? @capture "kind")`,
+/// each captured node is below a node of the given kind
+pub struct HasAncestor;
+
+impl Predicate for HasAncestor {
+    fn check(&self, args: &[QueryPredicateArg]) -> Result<(), String> {
+        check_capture_and_strings(args, 1)
+    }
+
+    fn eval(&self, args: &[PredicateArg<'_>]) -> bool {
+        let Some((nodes, strings)) = capture_and_strings(args) else {
+            return false;
+        };
+        nodes.iter().all(|node| {
+            let mut curr = node.parent();
+            while let Some(n) = curr {
+                if n.kind() == strings[0] {
+                    return true;
+                }
+                curr = n.parent();
+            }
+            false
+        })
+    }
+}
+
+/// `(#in-file? @capture "path")`,
+/// each captured node is in a file whose path contains the given string
+pub struct InFile;
+
+impl Predicate for InFile {
+    fn check(&self, args: &[QueryPredicateArg]) -> Result<(), String> {
+        check_capture_and_strings(args, 1)
+    }
+
+    fn eval(&self, args: &[PredicateArg<'_>]) -> bool {
+        let Some((nodes, strings)) = capture_and_strings(args) else {
+            return false;
+        };
+        nodes.iter().all(|node| {
+            node.file()
+                .is_some_and(|f| f.to_string_lossy().contains(strings[0]))
+        })
+    }
+}
+
+/// `(#metric-gt? @capture "size" "10")` when registered with [`std::cmp::Ordering::Greater`],
+/// each captured node has a metadata (see [`Metadata::from_name`]) comparing that way to the given value
+pub struct MetricCmp(pub std::cmp::Ordering);
+
+impl Predicate for MetricCmp {
+    fn check(&self, args: &[QueryPredicateArg]) -> Result<(), String> {
+        check_capture_and_strings(args, 2)?;
+        let [
+            _,
+            QueryPredicateArg::String(name),
+            QueryPredicateArg::String(value),
+        ] = args
+        else {
+            unreachable!()
+        };
+        Metadata::from_name(name).ok_or_else(|| format!("unknown metadata {name}"))?;
+        value
+            .parse::<usize>()
+            .map_err(|e| format!("invalid value {value}: {e}"))?;
+        Ok(())
+    }
+
+    fn eval(&self, args: &[PredicateArg<'_>]) -> bool {
+        let Some((nodes, strings)) = capture_and_strings(args) else {
+            return false;
+        };
+        let (Some(metadata), Ok(value)) =
+            (Metadata::from_name(strings[0]), strings[1].parse::<usize>())
+        else {
+            return false;
+        };
+        nodes.iter().all(|node| {
+            node.metadata(metadata)
+                .is_some_and(|x| x.cmp(&value) == self.0)
+        })
+    }
+}
+
+/// `(#derived-gt? @capture "mcc" "10")` when registered with [`std::cmp::Ordering::Greater`],
+/// each captured node has a derived value (e.g. computed by a prepro script) comparing that way to the given number
+pub struct DerivedCmp(pub std::cmp::Ordering);
+
+impl Predicate for DerivedCmp {
+    fn check(&self, args: &[QueryPredicateArg]) -> Result<(), String> {
+        check_capture_and_strings(args, 2)?;
+        let [_, _, QueryPredicateArg::String(value)] = args else {
+            unreachable!()
+        };
+        value
+            .parse::<f64>()
+            .map_err(|e| format!("invalid value {value}: {e}"))?;
+        Ok(())
+    }
+
+    fn eval(&self, args: &[PredicateArg<'_>]) -> bool {
+        let Some((nodes, strings)) = capture_and_strings(args) else {
+            return false;
+        };
+        let Ok(value) = strings[1].parse::<f64>() else {
+            return false;
+        };
+        nodes.iter().all(|node| {
+            node.derived(strings[0])
+                .and_then(|x| x.partial_cmp(&value))
+                .is_some_and(|x| x == self.0)
+        })
+    }
+}
+
+/// Node with its text provider, to be given to custom predicates
+pub(crate) struct Captured<'l, N: crate::Node> {
+    pub(crate) node: N,
+    pub(crate) text_provider: <N as crate::TextLending<'l>>::TP,
+}
+
+impl<'l, N: crate::Node> PredicateNode for Captured<'l, N> {
+    fn kind(&self) -> &str {
+        self.node.str_symbol()
+    }
+
+    fn text(&self) -> String {
+        self.node.text(self.text_provider).to_string()
+    }
+
+    fn metadata(&self, metadata: Metadata) -> Option<usize> {
+        self.node.metadata(metadata)
+    }
+
+    fn derived(&self, name: &str) -> Option<f64> {
+        self.node.derived(name)
+    }
+
+    fn parent(&self) -> Option<Box<dyn PredicateNode + '_>> {
+        let node = self.node.parent_node()?;
+        let text_provider = self.text_provider;
+        Some(Box::new(Captured {
+            node,
+            text_provider,
+        }))
+    }
+
+    fn file(&self) -> Option<std::path::PathBuf> {
+        self.node.file_path()
+    }
+}
@@ -1238,6 +1238,7 @@ impl Query {
             property_predicates,
             property_settings,
             immediate_predicates,
+            custom_predicates: vec![],
             precomputed_patterns: Default::default(),
             used_precomputed: Default::default(),
             enabled_pattern_map,
@@ -1510,6 +1511,36 @@ impl Query {
             .map(|i| CaptureId::new(i as u32))
     }
 
+    /// Installs a custom predicate, evaluated on matches when used in the query as `(#name ...)`.
+    ///
+    /// Errors if a use of `name` in the query has invalid arguments, see [`crate::predicate::Predicate::check`].
+    /// Replaces a predicate previously registered with the same name.
+    pub fn register_predicate(
+        &mut self,
+        name: &str,
+        predicate: impl crate::predicate::Predicate + 'static,
+    ) -> Result<(), String> {
+        for pattern in self.general_predicates.iter() {
+            for p in pattern.iter().filter(|p| &*p.operator == name) {
+                predicate
+                    .check(&p.args)
+                    .map_err(|e| format!("invalid use of #{name}: {e}"))?;
+            }
+        }
+        self.custom_predicates.retain(|(n, _)| &**n != name);
+        let predicate: std::sync::Arc<dyn crate::predicate::Predicate> =
+            std::sync::Arc::new(predicate);
+        self.custom_predicates.push((name.into(), predicate));
+        Ok(())
+    }
+
+    pub(crate) fn custom_predicate(&self, name: &str) -> Option<&dyn crate::predicate::Predicate> {
+        self.custom_predicates
+            .iter()
+            .find(|(n, _)| &**n == name)
+            .map(|(_, p)| p.as_ref())
+    }
+
     pub fn capture_quantifiers(
         &self,
         index: usize,
@@ -1868,6 +1899,14 @@ impl Debug for Query {
             .field("property_settings", &self.property_settings)
             .field("general_predicates", &self.general_predicates)
             .field("immediate_predicates", &self.immediate_predicates)
+            .field(
+                "custom_predicates",
+                &self
+                    .custom_predicates
+                    .iter()
+                    .map(|x| &x.0)
+                    .collect::<Vec<_>>(),
+            )
             .field("precomputed_patterns", &self.precomputed_patterns)
             .field("used_precomputed", &self.used_precomputed)
             .field("enabled_pattern_map", &self.enabled_pattern_map)
@@ -2117,6 +2156,51 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_custom_predicates() {
+        use crate::predicate::{DerivedCmp, HasAncestor, MetricCmp, PredicateArg};
+        let text = "class A { void f() { int x = 1; } int y = 2; }";
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&tree_sitter_java::language()).unwrap();
+        let tree = parser.parse(text, None).unwrap();
+        let count = |query: &Query| {
+            let cursor = crate::default_impls::TreeCursor::new(text.as_bytes(), tree.walk());
+            query.matches(cursor).count()
+        };
+
+        let q = "(variable_declarator) @v (#has-ancestor? @v \"method_declaration\")";
+        let mut query = Query::new(q, tree_sitter_java::language()).unwrap();
+        // unregistered predicates are ignored
+        assert_eq!(count(&query), 2);
+        query
+            .register_predicate("has-ancestor?", HasAncestor)
+            .unwrap();
+        assert_eq!(count(&query), 1);
+
+        let q = "(variable_declarator) @v (#metric-gt? @v \"bytes_len\" \"x\")";
+        let mut query = Query::new(q, tree_sitter_java::language()).unwrap();
+        let metric_gt = MetricCmp(std::cmp::Ordering::Greater);
+        assert!(query.register_predicate("metric-gt?", metric_gt).is_err());
+
+        let q = "(variable_declarator) @v (#derived-gt? @v \"mcc\" \"1\")";
+        let mut query = Query::new(q, tree_sitter_java::language()).unwrap();
+        let derived_gt = DerivedCmp(std::cmp::Ordering::Greater);
+        query.register_predicate("derived-gt?", derived_gt).unwrap();
+        // tree-sitter nodes do not carry derived data
+        assert_eq!(count(&query), 0);
+
+        let q = "(variable_declarator) @v (#named? @v \"y\")";
+        let mut query = Query::new(q, tree_sitter_java::language()).unwrap();
+        let named = |args: &[PredicateArg<'_>]| match args {
+            [PredicateArg::Capture(nodes), PredicateArg::String(name)] => {
+                nodes.iter().all(|n| n.text().starts_with(name))
+            }
+            _ => false,
+        };
+        query.register_predicate("named?", named).unwrap();
+        assert_eq!(count(&query), 1);
+    }
 }
 
 #[allow(unused)]
@@ -472,7 +472,16 @@ impl TypeTrait for Type {
     }
 
     fn is_literal(&self) -> bool {
-        todo!()
+        matches!(
+            self,
+            Self::NumberLiteral
+                | Self::StringLiteral
+                | Self::CharLiteral
+                | Self::ConcatenatedString
+                | Self::True
+                | Self::False
+                | Self::Null
+        )
     }
 
     fn is_primitive(&self) -> bool {
@@ -480,7 +489,10 @@ impl TypeTrait for Type {
     }
 
     fn is_type_declaration(&self) -> bool {
-        todo!()
+        matches!(
+            self,
+            Self::StructSpecifier | Self::UnionSpecifier | Self::EnumSpecifier
+        )
     }
 
     fn is_identifier(&self) -> bool {
@@ -500,11 +512,30 @@ impl TypeTrait for Type {
     }
 
     fn is_executable_member(&self) -> bool {
-        todo!()
+        self == &Type::FunctionDefinition
     }
 
     fn is_statement(&self) -> bool {
-        todo!()
+        matches!(
+            self,
+            Self::AttributedStatement
+                | Self::BreakStatement
+                | Self::CaseStatement
+                | Self::CompoundStatement
+                | Self::ContinueStatement
+                | Self::DoStatement
+                | Self::ExpressionStatement
+                | Self::ForStatement
+                | Self::GotoStatement
+                | Self::IfStatement
+                | Self::LabeledStatement
+                | Self::ReturnStatement
+                | Self::SehLeaveStatement
+                | Self::SehTryStatement
+                | Self::Statement
+                | Self::SwitchStatement
+                | Self::WhileStatement
+        )
     }
 
     fn is_declarative_statement(&self) -> bool {
@@ -540,7 +571,29 @@ impl TypeTrait for Type {
     }
 
     fn is_expression(&self) -> bool {
-        todo!()
+        matches!(
+            self,
+            Self::AlignofExpression
+                | Self::AssignmentExpression
+                | Self::BinaryExpression
+                | Self::CallExpression
+                | Self::CastExpression
+                | Self::CommaExpression
+                | Self::CompoundLiteralExpression
+                | Self::ConditionalExpression
+                | Self::Expression
+                | Self::ExtensionExpression
+                | Self::FieldExpression
+                | Self::GenericExpression
+                | Self::GnuAsmExpression
+                | Self::OffsetofExpression
+                | Self::ParenthesizedExpression
+                | Self::PointerExpression
+                | Self::SizeofExpression
+                | Self::SubscriptExpression
+                | Self::UnaryExpression
+                | Self::UpdateExpression
+        )
     }
 
     fn is_comment(&self) -> bool {
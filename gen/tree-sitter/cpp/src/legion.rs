@@ -1,6 +1,7 @@
 use crate::TNode;
 use crate::types::{CppEnabledTypeStore, Type};
 use hyperast::store::nodes::compo;
+use hyperast::store::nodes::legion::DedupMap;
 use hyperast::store::nodes::legion::dyn_builder;
 use hyperast::tree_gen::utils_ts::TTreeCursor;
 use hyperast::tree_gen::{
@@ -39,6 +40,8 @@ pub type LabelIdentifier = hyperast::store::labels::DefaultLabelIdentifier;
 ///   filtering hidden nodes adds complexity, thus might cause additional bugs
 pub struct CppTreeGen<'store, 'cache, TS, More = (), const HIDDEN_NODES: bool = true> {
     pub line_break: Vec<u8>,
+    /// Replaces the default dedup map, e.g. when deriving different data
+    pub dedup: Option<&'store mut DedupMap>,
     pub stores: &'store mut SimpleStores<TS>,
     pub md_cache: &'cache mut MDCache,
     pub more: More,
@@ -85,7 +88,7 @@ pub struct Local {
 }
 
 impl Local {
-    fn acc(self, acc: &mut Acc) {
+    fn acc<Scope>(self, acc: &mut Acc<Scope>) {
         if self.metrics.size_no_spaces > 0 {
             acc.no_space.push(self.compressed_node)
         }
@@ -105,7 +108,7 @@ impl Local {
     }
 }
 
-pub struct Acc {
+pub struct Acc<Scope = hyperast::scripting::Acc> {
     simple: BasicAccumulator<Type, NodeIdentifier>,
     no_space: Vec<NodeIdentifier>,
     labeled: bool,
@@ -118,10 +121,11 @@ pub struct Acc {
     indentation: Spaces,
     role: RoleAcc<crate::types::Role>,
     precomp_queries: PrecompQueries,
+    prepro: Option<Scope>,
 }
 
 pub type FNode = FullNode<BasicGlobalData, Local>;
-impl Accumulator for Acc {
+impl<Scope> Accumulator for Acc<Scope> {
     type Node = FNode;
     fn push(&mut self, full_node: Self::Node) {
         // dbg!(self.simple.kind);
@@ -129,13 +133,13 @@ impl Accumulator for Acc {
     }
 }
 
-impl AccIndentation for Acc {
+impl<Scope> AccIndentation for Acc<Scope> {
     fn indentation<'a>(&'a self) -> &'a Spaces {
         &self.indentation
     }
 }
 
-impl WithByteRange for Acc {
+impl<Scope> WithByteRange for Acc<Scope> {
     fn has_children(&self) -> bool {
         !self.simple.children.is_empty()
     }
@@ -149,7 +153,7 @@ impl WithByteRange for Acc {
     }
 }
 
-impl types::Typed for Acc {
+impl<Scope> types::Typed for Acc<Scope> {
     type Type = Type;
 
     fn get_type(&self) -> Self::Type {
@@ -157,13 +161,13 @@ impl types::Typed for Acc {
     }
 }
 
-impl hyperast::tree_gen::WithChildren<NodeIdentifier> for Acc {
+impl<Scope> hyperast::tree_gen::WithChildren<NodeIdentifier> for Acc<Scope> {
     fn children(&self) -> &[NodeIdentifier] {
         &self.simple.children
     }
 }
 
-impl hyperast::tree_gen::WithRole<Role> for Acc {
+impl<Scope> hyperast::tree_gen::WithRole<Role> for Acc<Scope> {
     fn role_at(&self, o: usize) -> Option<Role> {
         self.role
             .offsets
@@ -174,11 +178,11 @@ impl hyperast::tree_gen::WithRole<Role> for Acc {
     }
 }
 
-impl<'acc> hyperast::tree_gen::WithLabel for &'acc Acc {
+impl<'acc, Scope> hyperast::tree_gen::WithLabel for &'acc Acc<Scope> {
     type L = &'acc str;
 }
 
-impl Debug for Acc {
+impl<Scope> Debug for Acc<Scope> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Acc")
             .field("simple", &self.simple)
@@ -199,7 +203,7 @@ impl<TS, More, const HIDDEN_NODES: bool> ZippedTreeGen
 where
     TS: CppEnabledTypeStore<Ty2 = Type>,
     More: tree_gen::Prepro<SimpleStores<TS>>
-        + for<'s> tree_gen::PreproTSG<SimpleStores<TS>, Acc = Acc>,
+        + for<'s> tree_gen::PreproTSG<SimpleStores<TS>, Acc = Acc<More::Scope>>,
 {
     type Stores = SimpleStores<TS>;
     type Text = [u8];
@@ -223,6 +227,11 @@ where
         );
         let labeled = node.has_label();
         let ana = self.build_ana(&kind);
+        let prepro = if More::USING {
+            Some(self.more.preprocessing(kind).unwrap())
+        } else {
+            None
+        };
         Acc {
             simple: BasicAccumulator {
                 kind,
@@ -239,6 +248,7 @@ where
             indentation: indent,
             role: Default::default(),
             precomp_queries: Default::default(),
+            prepro,
         }
     }
 
@@ -309,6 +319,11 @@ where
             global.sum_byte_length(),
             parent_indentation,
         );
+        let prepro = if More::USING {
+            Some(self.more.preprocessing(kind).unwrap())
+        } else {
+            None
+        };
         Acc {
             labeled: node.has_label(),
             start_byte: node.start_byte(),
@@ -325,6 +340,24 @@ where
             no_space: vec![],
             role: Default::default(),
             precomp_queries: Default::default(),
+            prepro,
+        }
+    }
+
+    fn acc(
+        &mut self,
+        parent: &mut <Self as TreeGen>::Acc,
+        full_node: <<Self as TreeGen>::Acc as Accumulator>::Node,
+    ) {
+        let id = full_node.local.compressed_node;
+        let ty = parent.simple.kind;
+        parent.push(full_node);
+        if let Some(p) = &mut parent.prepro {
+            // SAFETY: this side should be fine, issue when unerasing
+            let store = unsafe { self.stores.erase_ts_unchecked() };
+            let child: hyperast::scripting::SubtreeHandle<crate::types::TType> = id.into();
+            use hyperast::scripting::Accumulable;
+            p.acc(self.more.scripts(), store, ty, child).unwrap();
         }
     }
 
@@ -352,20 +385,38 @@ where
             .is_none()
             {
                 let local = self.make_error(&text[global.sum_byte_length()..acc.end_byte]);
+                let id = local.compressed_node;
                 acc.push(FullNode {
                     global: global.simple(),
                     local,
                 });
+                if let Some(p) = &mut acc.prepro {
+                    // SAFETY: this side should be fine, issue when unerasing
+                    let store = unsafe { self.stores.erase_ts_unchecked() };
+                    let child: hyperast::scripting::SubtreeHandle<crate::types::TType> = id.into();
+                    use hyperast::scripting::Accumulable;
+                    p.acc(self.more.scripts(), store, acc.simple.kind, child)
+                        .unwrap();
+                }
                 global.set_sum_byte_length(acc.end_byte);
             }
         }
         if let Some(spacing) = spacing {
             let local = self.make_spacing(spacing);
+            let id = local.compressed_node;
             // debug_assert_ne!(parent.simple.children.len(), 0, "{:?}", parent.simple);
             parent.push(FullNode {
                 global: global.simple(),
                 local,
             });
+            if let Some(p) = &mut parent.prepro {
+                // SAFETY: this side should be fine, issue when unerasing
+                let store = unsafe { self.stores.erase_ts_unchecked() };
+                let child: hyperast::scripting::SubtreeHandle<crate::types::TType> = id.into();
+                use hyperast::scripting::Accumulable;
+                p.acc(self.more.scripts(), store, parent.simple.kind, child)
+                    .unwrap();
+            }
         }
         let label = if acc.labeled {
             std::str::from_utf8(&text[acc.start_byte..acc.end_byte])
@@ -384,6 +435,7 @@ impl<'store, 'cache, TS: CppEnabledTypeStore>
     pub fn new(stores: &'store mut SimpleStores<TS>, md_cache: &'cache mut MDCache) -> Self {
         Self {
             line_break: "\n".as_bytes().to_vec(),
+            dedup: None,
             stores,
             md_cache,
             more: Default::default(),
@@ -395,6 +447,7 @@ impl<'store, 'cache, 'acc, TS, More> CppTreeGen<'store, 'cache, TS, More, true>
     pub fn without_hidden_nodes(self) -> CppTreeGen<'store, 'cache, TS, More, false> {
         CppTreeGen {
             line_break: self.line_break,
+            dedup: self.dedup,
             stores: self.stores,
             md_cache: self.md_cache,
             more: self.more,
@@ -411,11 +464,12 @@ impl<'store, 'cache, TS, More, const HIDDEN_NODES: bool>
 where
     TS: CppEnabledTypeStore<Ty2 = Type>,
     More: tree_gen::Prepro<SimpleStores<TS>>
-        + for<'s> tree_gen::PreproTSG<SimpleStores<TS>, Acc = Acc>,
+        + for<'s> tree_gen::PreproTSG<SimpleStores<TS>, Acc = Acc<More::Scope>>,
 {
     pub fn with_more<M>(self, more: M) -> CppTreeGen<'store, 'cache, TS, M, HIDDEN_NODES> {
         CppTreeGen {
             line_break: self.line_break,
+            dedup: self.dedup,
             stores: self.stores,
             md_cache: self.md_cache,
             more,
@@ -453,7 +507,9 @@ where
             true
         };
 
-        let insertion = self.stores.node_store.prepare_insertion(&hashable, eq);
+        let dedup = &mut self.stores.node_store.dedup;
+        let dedup = self.dedup.as_mut().map_or(dedup, |x| &mut x.0);
+        let insertion = (self.stores.node_store.inner).prepare_insertion(dedup, &hashable, eq);
 
         let mut hashs = hbuilder.build();
         hashs.structt = 0;
@@ -463,11 +519,22 @@ where
             id
         } else {
             let vacant = insertion.vacant();
-            let bytes_len = compo::BytesLen(bytes_len.try_into().unwrap());
-            NodeStore::insert_after_prepare(
-                vacant,
-                (interned_kind, spacing_id, bytes_len, hashs, BloomSize::None),
-            )
+            let mut dyn_builder = dyn_builder::EntityBuilder::new();
+            dyn_builder.add(interned_kind);
+            dyn_builder.add(compo::BytesLen(bytes_len.try_into().unwrap()));
+            dyn_builder.add(spacing_id);
+            dyn_builder.add(hashs);
+            dyn_builder.add(BloomSize::None);
+            if More::USING {
+                let prepro = self.more.preprocessing(kind).unwrap();
+                let subtr = hyperast::scripting::Subtr(kind, &dyn_builder);
+                use hyperast::scripting::Finishable;
+                let ss = prepro
+                    .finish_with_label(self.more.scripts(), &subtr, &spacing)
+                    .unwrap();
+                dyn_builder.add(ss);
+            };
+            NodeStore::insert_built_after_prepare(vacant, dyn_builder.build())
         };
         Local {
             compressed_node,
@@ -504,7 +571,9 @@ where
 
         let eq = eq_node::<_, _, NodeIdentifier>(&interned_kind, Some(&label_id), &[]);
 
-        let insertion = self.stores.node_store.prepare_insertion(&hashable, eq);
+        let dedup = &mut self.stores.node_store.dedup;
+        let dedup = self.dedup.as_mut().map_or(dedup, |x| &mut x.0);
+        let insertion = (self.stores.node_store.inner).prepare_insertion(dedup, &hashable, eq);
 
         let hashs = hbuilder.build();
 
@@ -512,11 +581,22 @@ where
             id
         } else {
             let vacant = insertion.vacant();
-            let bytes_len = compo::BytesLen(bytes_len.try_into().unwrap());
-            NodeStore::insert_after_prepare(
-                vacant,
-                (interned_kind, label_id, bytes_len, hashs, BloomSize::None),
-            )
+            let mut dyn_builder = dyn_builder::EntityBuilder::new();
+            dyn_builder.add(interned_kind);
+            dyn_builder.add(compo::BytesLen(bytes_len.try_into().unwrap()));
+            dyn_builder.add(label_id);
+            dyn_builder.add(hashs);
+            dyn_builder.add(BloomSize::None);
+            if More::USING {
+                let prepro = self.more.preprocessing(kind).unwrap();
+                let subtr = hyperast::scripting::Subtr(kind, &dyn_builder);
+                use hyperast::scripting::Finishable;
+                let ss = prepro
+                    .finish_with_label(self.more.scripts(), &subtr, &text)
+                    .unwrap();
+                dyn_builder.add(ss);
+            };
+            NodeStore::insert_built_after_prepare(vacant, dyn_builder.build())
         };
         Local {
             compressed_node,
@@ -557,10 +637,20 @@ where
         if let Some(spacing) = spacing {
             global.down();
             global.set_sum_byte_length(init.start_byte);
+            let local = self.make_spacing(spacing);
+            let id = local.compressed_node;
             init.push(FullNode {
                 global: global.simple(),
-                local: self.make_spacing(spacing),
+                local,
             });
+            if let Some(p) = &mut init.prepro {
+                // SAFETY: this side should be fine, issue when unerasing
+                let store = unsafe { self.stores.erase_ts_unchecked() };
+                let child: hyperast::scripting::SubtreeHandle<crate::types::TType> = id.into();
+                use hyperast::scripting::Accumulable;
+                p.acc(self.more.scripts(), store, init.simple.kind, child)
+                    .unwrap();
+            }
             global.right();
         }
         let mut stack = init.into();
@@ -578,10 +668,20 @@ where
             );
             if let Some(spacing) = spacing {
                 global.right();
+                let local = self.make_spacing(spacing);
+                let id = local.compressed_node;
                 acc.push(FullNode {
                     global: global.simple(),
-                    local: self.make_spacing(spacing),
+                    local,
                 });
+                if let Some(p) = &mut acc.prepro {
+                    // SAFETY: this side should be fine, issue when unerasing
+                    let store = unsafe { self.stores.erase_ts_unchecked() };
+                    let child: hyperast::scripting::SubtreeHandle<crate::types::TType> = id.into();
+                    use hyperast::scripting::Accumulable;
+                    p.acc(self.more.scripts(), store, acc.simple.kind, child)
+                        .unwrap();
+                }
             }
         }
         let label = Some(std::str::from_utf8(name).unwrap().to_owned());
@@ -609,10 +709,10 @@ impl<'store, 'cache, TS, More, const HIDDEN_NODES: bool> TreeGen
 where
     TS: CppEnabledTypeStore<Ty2 = Type>,
     More: tree_gen::Prepro<SimpleStores<TS>>
-        + for<'s> tree_gen::PreproTSG<SimpleStores<TS>, Acc = Acc>,
+        + for<'s> tree_gen::PreproTSG<SimpleStores<TS>, Acc = Acc<More::Scope>>,
     TS::Ty2: hyperast::tree_gen::utils_ts::TsType,
 {
-    type Acc = Acc;
+    type Acc = Acc<More::Scope>;
     type Global = SpacedGlobalData<'store>;
     fn make(
         &mut self,
@@ -634,7 +734,12 @@ where
             .map(|label| self.stores.label_store.get_or_insert(label.as_str()));
         let eq = eq_node(&interned_kind, label_id.as_ref(), &acc.simple.children);
 
-        let insertion = self.stores.node_store.prepare_insertion(hashable, eq);
+        let node_store = &mut self.stores.node_store;
+        let dedup = self
+            .dedup
+            .as_mut()
+            .map_or(&mut node_store.dedup, |x| &mut x.0);
+        let insertion = node_store.inner.prepare_insertion(dedup, hashable, eq);
 
         let local = if let Some(compressed_node) = insertion.occupied_id() {
             let md = self.md_cache.get(&compressed_node).unwrap();
@@ -673,6 +778,11 @@ where
             acc.precomp_queries |= self
                 .more
                 .match_precomp_queries(stores, &acc, label.as_deref());
+            if More::GRAPHING {
+                self.more
+                    .compute_tsg(stores, &acc, label.as_deref())
+                    .unwrap();
+            }
             let children_is_empty = acc.simple.children.is_empty();
 
             let mut dyn_builder = dyn_builder::EntityBuilder::new();
@@ -702,6 +812,23 @@ where
             acc.simple
                 .add_primary(&mut dyn_builder, interned_kind, label_id);
 
+            if More::USING {
+                let subtr = hyperast::scripting::Subtr(kind, &dyn_builder);
+                use hyperast::scripting::Finishable;
+                let ss = if let Some(label) = &label {
+                    acc.prepro
+                        .unwrap()
+                        .finish_with_label(self.more.scripts(), &subtr, label)
+                        .unwrap()
+                } else {
+                    acc.prepro
+                        .unwrap()
+                        .finish(self.more.scripts(), &subtr)
+                        .unwrap()
+                };
+                dyn_builder.add(ss);
+            }
+
             let compressed_node =
                 NodeStore::insert_built_after_prepare(vacant, dyn_builder.build());
 
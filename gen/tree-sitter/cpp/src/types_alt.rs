@@ -582,7 +582,19 @@ impl TypeTrait for Type {
     }
 
     fn is_literal(&self) -> bool {
-        todo!()
+        matches!(
+            self,
+            Self::NumberLiteral
+                | Self::StringLiteral
+                | Self::CharLiteral
+                | Self::RawStringLiteral
+                | Self::ConcatenatedString
+                | Self::UserDefinedLiteral
+                | Self::True
+                | Self::False
+                | Self::Null
+                | Self::Nullptr
+        )
     }
 
     fn is_primitive(&self) -> bool {
@@ -590,7 +602,13 @@ impl TypeTrait for Type {
     }
 
     fn is_type_declaration(&self) -> bool {
-        todo!()
+        matches!(
+            self,
+            Self::ClassSpecifier
+                | Self::StructSpecifier
+                | Self::UnionSpecifier
+                | Self::EnumSpecifier
+        )
     }
 
     fn is_identifier(&self) -> bool {
@@ -610,11 +628,35 @@ impl TypeTrait for Type {
     }
 
     fn is_executable_member(&self) -> bool {
-        todo!()
+        self == &Type::FunctionDefinition
     }
 
     fn is_statement(&self) -> bool {
-        todo!()
+        matches!(
+            self,
+            Self::AttributedStatement
+                | Self::BreakStatement
+                | Self::CaseStatement
+                | Self::CoReturnStatement
+                | Self::CoYieldStatement
+                | Self::CompoundStatement
+                | Self::ContinueStatement
+                | Self::DoStatement
+                | Self::ExpressionStatement
+                | Self::ForStatement
+                | Self::GotoStatement
+                | Self::IfStatement
+                | Self::InitStatement
+                | Self::LabeledStatement
+                | Self::ReturnStatement
+                | Self::SehLeaveStatement
+                | Self::SehTryStatement
+                | Self::Statement
+                | Self::SwitchStatement
+                | Self::ThrowStatement
+                | Self::TryStatement
+                | Self::WhileStatement
+        )
     }
 
     fn is_declarative_statement(&self) -> bool {
@@ -650,7 +692,36 @@ impl TypeTrait for Type {
     }
 
     fn is_expression(&self) -> bool {
-        todo!()
+        matches!(
+            self,
+            Self::AlignofExpression
+                | Self::AssignmentExpression
+                | Self::BinaryExpression
+                | Self::CallExpression
+                | Self::CastExpression
+                | Self::CoAwaitExpression
+                | Self::CommaExpression
+                | Self::CompoundLiteralExpression
+                | Self::ConditionalExpression
+                | Self::DeleteExpression
+                | Self::Expression
+                | Self::ExtensionExpression
+                | Self::FieldExpression
+                | Self::FoldExpression
+                | Self::GenericExpression
+                | Self::GnuAsmExpression
+                | Self::LambdaExpression
+                | Self::NewExpression
+                | Self::OffsetofExpression
+                | Self::ParenthesizedExpression
+                | Self::PointerExpression
+                | Self::PreprocCallExpression
+                | Self::RequiresExpression
+                | Self::SizeofExpression
+                | Self::SubscriptExpression
+                | Self::UnaryExpression
+                | Self::UpdateExpression
+        )
     }
 
     fn is_comment(&self) -> bool {
@@ -0,0 +1,58 @@
+use hyperast::tree_gen::NoOpMore;
+
+use crate::{
+    legion::{Acc, tree_sitter_parse},
+    types::TStore,
+};
+
+type PythonTreeGen<'store, 'cache> =
+    crate::legion::PythonTreeGen<'store, 'cache, TStore, NoOpMore<TStore, Acc>, true>;
+type SimpleStores = hyperast::store::SimpleStores<TStore>;
+
+static EX: &str = r#"import os
+from . import utils as u
+
+
+@decorator
+class A(Base):
+    """doc"""
+
+    def f(self, x: int = 0, *args, **kwargs) -> str:
+        if x > 0:
+            return f"{x!r:>10}"
+        elif not x:
+            pass
+        else:
+            raise ValueError("neg")
+
+    async def g(self):
+        return [await y for y in self.xs if y]
+
+
+match u.value:
+    case [a, *rest] if a:
+        print(a, rest)
+    case _:
+        lambda: None
+"#;
+
+#[test]
+fn python_simple_test() {
+    let text = EX.as_bytes();
+    let tree = match tree_sitter_parse(text) {
+        Ok(t) => t,
+        Err(t) => t,
+    };
+    println!("{:#?}", tree.root_node().to_sexp());
+    let mut stores = SimpleStores::default();
+    let mut md_cache = Default::default();
+    let mut tree_gen = PythonTreeGen::new(&mut stores, &mut md_cache);
+    let x = tree_gen.generate_file(b"", text, tree.walk()).local;
+    let id = x.compressed_node;
+    println!("{}", hyperast::nodes::SyntaxSerializer::new(&stores, id));
+    println!("{}", hyperast::nodes::SexpSerializer::new(&stores, id));
+    assert_eq!(
+        hyperast::nodes::TextSerializer::new(&stores, id).to_string(),
+        EX
+    );
+}
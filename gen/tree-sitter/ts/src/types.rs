@@ -105,7 +105,7 @@ impl<IdN: Clone + Eq + AAAA> TypedNodeId for TIdN<IdN> {
 }
 
 #[derive(Clone, Copy)]
-pub(crate) struct TStore;
+pub struct TStore;
 
 impl Default for TStore {
     fn default() -> Self {
@@ -299,7 +299,17 @@ impl TypeTrait for Type {
     }
 
     fn is_literal(&self) -> bool {
-        todo!()
+        matches!(
+            self,
+            Self::Number
+                | Self::String
+                | Self::TemplateString
+                | Self::Regex
+                | Self::True
+                | Self::False
+                | Self::Null
+                | Self::Undefined
+        )
     }
 
     fn is_primitive(&self) -> bool {
@@ -307,7 +317,14 @@ impl TypeTrait for Type {
     }
 
     fn is_type_declaration(&self) -> bool {
-        todo!()
+        matches!(
+            self,
+            Self::ClassDeclaration
+                | Self::AbstractClassDeclaration
+                | Self::InterfaceDeclaration
+                | Self::EnumDeclaration
+                | Self::TypeAliasDeclaration
+        )
     }
 
     fn is_identifier(&self) -> bool {
@@ -327,11 +344,39 @@ impl TypeTrait for Type {
     }
 
     fn is_executable_member(&self) -> bool {
-        todo!()
+        matches!(
+            self,
+            Self::FunctionDeclaration
+                | Self::GeneratorFunctionDeclaration
+                | Self::MethodDefinition
+                | Self::FunctionExpression
+                | Self::ArrowFunction
+        )
     }
 
     fn is_statement(&self) -> bool {
-        todo!()
+        matches!(
+            self,
+            Self::BreakStatement
+                | Self::ContinueStatement
+                | Self::DebuggerStatement
+                | Self::DoStatement
+                | Self::EmptyStatement
+                | Self::ExportStatement
+                | Self::ExpressionStatement
+                | Self::ForInStatement
+                | Self::ForStatement
+                | Self::IfStatement
+                | Self::ImportStatement
+                | Self::LabeledStatement
+                | Self::ReturnStatement
+                | Self::Statement
+                | Self::SwitchStatement
+                | Self::ThrowStatement
+                | Self::TryStatement
+                | Self::WhileStatement
+                | Self::WithStatement
+        )
     }
 
     fn is_declarative_statement(&self) -> bool {
@@ -367,7 +412,30 @@ impl TypeTrait for Type {
     }
 
     fn is_expression(&self) -> bool {
-        todo!()
+        matches!(
+            self,
+            Self::AsExpression
+                | Self::AssignmentExpression
+                | Self::AugmentedAssignmentExpression
+                | Self::AwaitExpression
+                | Self::BinaryExpression
+                | Self::CallExpression
+                | Self::Expression
+                | Self::FunctionExpression
+                | Self::InstantiationExpression
+                | Self::MemberExpression
+                | Self::NewExpression
+                | Self::NonNullExpression
+                | Self::ParenthesizedExpression
+                | Self::PrimaryExpression
+                | Self::SatisfiesExpression
+                | Self::SequenceExpression
+                | Self::SubscriptExpression
+                | Self::TernaryExpression
+                | Self::UnaryExpression
+                | Self::UpdateExpression
+                | Self::YieldExpression
+        )
     }
 
     fn is_comment(&self) -> bool {
@@ -384,7 +452,7 @@ impl Display for Type {
     }
 }
 
-type TType = hyperast::types::TypeU16<Lang>;
+pub type TType = hyperast::types::TypeU16<Lang>;
 
 impl hyperast::types::LLang<TType> for Ts {
     type I = u16;
@@ -45,7 +45,9 @@ fn construction_group(c: &mut Criterion) {
                 },
                 |(mut repositories, repository)| {
                     let mut rw = single_commit(p.commit, &repository.repo).unwrap();
-                    repositories.pre_process_chunk(&mut rw, &repository, usize::MAX)
+                    repositories
+                        .pre_process_chunk(&mut rw, &repository, usize::MAX)
+                        .unwrap()
                 },
                 BatchSize::PerIteration,
             )
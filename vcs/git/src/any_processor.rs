@@ -0,0 +1,479 @@
+use crate::processing::erased::{
+    CommitProcessorHandle, ParametrizedCommitProc2, ParametrizedCommitProcessor2Handle,
+};
+use crate::{
+    Accumulator, BasicDirAcc, DefaultMetrics, Processor, StackEle,
+    git::BasicGitObject,
+    preprocessed::RepositoryProcessor,
+    processing::{CacheHolding, InFiles, ObjectName, ParametrizedCommitProcessorHandle},
+};
+use git2::{Oid, Repository};
+use hyperast::types::ETypeStore as _;
+use hyperast::{
+    hashed::{IndexingHashBuilder, MetaDataHashsBuilder},
+    store::{
+        defaults::{LabelIdentifier, NodeIdentifier},
+        nodes::legion::eq_node,
+    },
+    types::LabelStore,
+};
+use hyperast_gen_ts_xml::types::Type;
+use std::{
+    iter::Peekable,
+    path::{Components, PathBuf},
+};
+
+pub type SimpleStores = hyperast::store::SimpleStores<hyperast_gen_ts_xml::types::TStore>;
+
+pub struct AnyAcc {
+    pub(crate) primary: BasicDirAcc<NodeIdentifier, LabelIdentifier, DefaultMetrics>,
+}
+
+impl From<String> for AnyAcc {
+    fn from(name: String) -> Self {
+        Self {
+            primary: BasicDirAcc::new(name),
+        }
+    }
+}
+
+impl hyperast::tree_gen::Accumulator for AnyAcc {
+    type Node = (LabelIdentifier, (NodeIdentifier, DefaultMetrics));
+    fn push(&mut self, (name, (id, metrics)): Self::Node) {
+        self.primary.push(name, id, metrics);
+    }
+}
+
+impl Accumulator for AnyAcc {
+    type Unlabeled = (NodeIdentifier, DefaultMetrics);
+}
+
+/// Processes a tree without any build system,
+/// each file is handled by the generator matching its extension, other files are ignored.
+pub struct AnyProcessor<'repo, 'prepro, 'd, 'c> {
+    repository: &'repo Repository,
+    prepro: &'prepro mut RepositoryProcessor,
+    stack: Vec<StackEle<AnyAcc>>,
+    pub dir_path: &'d mut Peekable<Components<'c>>,
+    handle: ParametrizedCommitProcessorHandle,
+}
+
+impl<'repo, 'prepro, 'd, 'c> AnyProcessor<'repo, 'prepro, 'd, 'c> {
+    pub(crate) fn new(
+        repository: &'repo Repository,
+        prepro: &'prepro mut RepositoryProcessor,
+        dir_path: &'d mut Peekable<Components<'c>>,
+        name: &ObjectName,
+        oid: git2::Oid,
+        handle: ParametrizedCommitProcessorHandle,
+    ) -> Self {
+        let tree = repository.find_tree(oid).unwrap();
+        let prepared = prepare_dir_exploration(tree);
+        let name: String = name.try_into().unwrap();
+        let stack = vec![StackEle::new(oid, prepared, AnyAcc::from(name))];
+        Self {
+            stack,
+            repository,
+            prepro,
+            dir_path,
+            handle,
+        }
+    }
+}
+
+pub(crate) fn prepare_dir_exploration(tree: git2::Tree) -> Vec<BasicGitObject> {
+    tree.iter()
+        .rev()
+        .map(TryInto::try_into)
+        .filter_map(|x| x.ok())
+        .collect()
+}
+
+impl<'repo, 'prepro, 'd, 'c> Processor<AnyAcc> for AnyProcessor<'repo, 'prepro, 'd, 'c> {
+    fn pre(&mut self, current_object: BasicGitObject) {
+        match current_object {
+            BasicGitObject::Tree(oid, name) => {
+                self.handle_tree_cached(oid, name);
+            }
+            BasicGitObject::Blob(oid, name) => {
+                let parameter = self
+                    .prepro
+                    .processing_systems
+                    .mut_or_default::<AnyProcessorHolder>()
+                    .with_parameters(self.handle.1)
+                    .parameter
+                    .clone();
+                let r = self
+                    .prepro
+                    .handle_any_blob(oid, &name, self.repository, &parameter);
+                match r {
+                    Ok(Some(full_node)) => {
+                        let name = self.prepro.intern_object_name(&name);
+                        let w = &mut self.stack.last_mut().unwrap().acc;
+                        assert!(!w.primary.children_names.contains(&name));
+                        hyperast::tree_gen::Accumulator::push(w, (name, full_node));
+                    }
+                    Ok(None) => log::debug!("no generator for {:?}", name.try_str()),
+                    Err(e) => log::warn!("skipped {:?}: {:?}", name.try_str(), e),
+                }
+            }
+        }
+    }
+
+    fn post(&mut self, oid: Oid, acc: AnyAcc) -> Option<(NodeIdentifier, DefaultMetrics)> {
+        let name = acc.primary.name.clone();
+        let key = (oid, name.as_bytes().into());
+        let full_node = make(acc, self.prepro.main_stores_mut().mut_with_ts());
+        self.prepro
+            .processing_systems
+            .mut_or_default::<AnyProcessorHolder>()
+            .get_caches_mut()
+            .object_map
+            .insert(key, full_node.clone());
+        let name = self.prepro.main_stores.label_store.get_or_insert(name);
+        if self.stack.is_empty() {
+            Some(full_node)
+        } else {
+            let w = &mut self.stack.last_mut().unwrap().acc;
+            assert!(
+                !w.primary.children_names.contains(&name),
+                "{:?} {:?}",
+                w.primary.children_names,
+                name
+            );
+            hyperast::tree_gen::Accumulator::push(w, (name, full_node));
+            None
+        }
+    }
+
+    fn stack(&mut self) -> &mut Vec<StackEle<AnyAcc>> {
+        &mut self.stack
+    }
+}
+
+impl<'repo, 'prepro, 'd, 'c> AnyProcessor<'repo, 'prepro, 'd, 'c> {
+    fn handle_tree_cached(&mut self, oid: Oid, name: ObjectName) {
+        if let Some(already) = self
+            .prepro
+            .processing_systems
+            .mut_or_default::<AnyProcessorHolder>()
+            .get_caches_mut()
+            .object_map
+            .get(&(oid, name.clone()))
+        {
+            // reinit already computed node for post order
+            let full_node = already.clone();
+            let w = &mut self.stack.last_mut().unwrap().acc;
+            let name = self.prepro.intern_object_name(&name);
+            assert!(!w.primary.children_names.contains(&name));
+            hyperast::tree_gen::Accumulator::push(w, (name, full_node));
+        } else {
+            log::debug!("tree {:?}", name.try_str());
+            let tree = self.repository.find_tree(oid).unwrap();
+            let prepared = prepare_dir_exploration(tree);
+            let name: String = name.try_into().unwrap();
+            self.stack
+                .push(StackEle::new(oid, prepared, AnyAcc::from(name)));
+        }
+    }
+}
+
+impl RepositoryProcessor {
+    /// Dispatches the blob to the first enabled generator matching its name,
+    /// returns `None` when there is none.
+    fn handle_any_blob(
+        &mut self,
+        oid: Oid,
+        name: &ObjectName,
+        repository: &Repository,
+        parameter: &Parameter,
+    ) -> Result<Option<(NodeIdentifier, DefaultMetrics)>, crate::ParseErr> {
+        use crate::processing::file_sys;
+        if file_sys::Java::matches(name) {
+            let (n,) = self.handle_java_blob(oid, name, repository, parameter.java_handle)?;
+            return Ok(Some((n.compressed_node, n.metrics)));
+        }
+        if file_sys::Xml::matches(name) {
+            let n = self.handle_xml_blob(oid, name, repository, parameter.xml_handle)?;
+            return Ok(Some((n.compressed_node, n.metrics)));
+        }
+        if file_sys::C::matches(name) {
+            let (n,) = self.handle_c_blob(oid, name, repository, parameter.cpp_handle)?;
+            return Ok(Some((n.compressed_node, n.metrics)));
+        }
+        if file_sys::Cpp::matches(name) {
+            let (n,) = self.handle_cpp_blob(oid, name, repository, parameter.cpp_handle)?;
+            return Ok(Some((n.compressed_node, n.metrics)));
+        }
+        if file_sys::Python::matches(name) {
+            let (n,) = self.handle_python_blob(oid, name, repository, parameter.python_handle)?;
+            return Ok(Some((n.compressed_node, n.metrics)));
+        }
+        if file_sys::Ts::matches(name) {
+            let (n,) = self.handle_ts_blob(oid, name, repository, parameter.ts_handle)?;
+            return Ok(Some((n.compressed_node, n.metrics)));
+        }
+        Ok(None)
+    }
+}
+
+fn make(acc: AnyAcc, stores: &mut SimpleStores) -> (NodeIdentifier, DefaultMetrics) {
+    let kind = Type::Directory;
+    let interned_kind = hyperast_gen_ts_xml::types::TStore::intern(kind);
+    let label_id = stores.label_store.get_or_insert(acc.primary.name.clone());
+
+    let primary = acc
+        .primary
+        .map_metrics(|m| m.finalize(&interned_kind, &label_id, 0));
+
+    let hashable = primary.metrics.hashs.most_discriminating();
+
+    let eq = eq_node(&interned_kind, Some(&label_id), &primary.children);
+
+    let insertion = stores.node_store.prepare_insertion(&hashable, eq);
+    if let Some(id) = insertion.occupied_id() {
+        let metrics = primary.metrics.map_hashs(|h| h.build());
+        return (id, metrics);
+    }
+
+    let mut dyn_builder = hyperast::store::nodes::legion::dyn_builder::EntityBuilder::new();
+
+    let children_is_empty = primary.children.is_empty();
+
+    let metrics = primary.persist(&mut dyn_builder, interned_kind, label_id);
+    let metrics = metrics.map_hashs(|h| h.build());
+    let hashs = metrics.add_md_metrics(&mut dyn_builder, children_is_empty);
+    hashs.persist(&mut dyn_builder);
+
+    let vacant = insertion.vacant();
+    let node_id = hyperast::store::nodes::legion::NodeStore::insert_built_after_prepare(
+        vacant,
+        dyn_builder.build(),
+    );
+
+    (node_id, metrics)
+}
+
+/// Handles of the generators files are dispatched to.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Parameter {
+    pub(crate) java_handle: ParametrizedCommitProcessor2Handle<crate::java_processor::JavaProc>,
+    pub(crate) xml_handle: ParametrizedCommitProcessor2Handle<crate::maven_processor::PomProc>,
+    pub(crate) cpp_handle: ParametrizedCommitProcessor2Handle<crate::cpp_processor::CppProc>,
+    pub(crate) python_handle:
+        ParametrizedCommitProcessor2Handle<crate::python_processor::PythonProc>,
+    pub(crate) ts_handle: ParametrizedCommitProcessor2Handle<crate::ts_processor::TsProc>,
+}
+
+#[derive(Default)]
+pub(crate) struct AnyProcessorHolder(Option<AnyProc>);
+pub(crate) struct AnyProc {
+    parameter: Parameter,
+    cache: crate::processing::caches::Any,
+    commits: std::collections::HashMap<git2::Oid, crate::Commit>,
+}
+impl crate::processing::erased::Parametrized for AnyProcessorHolder {
+    type T = Parameter;
+    fn register_param(
+        &mut self,
+        t: Self::T,
+    ) -> crate::processing::erased::ParametrizedCommitProcessorHandle {
+        let l = self
+            .0
+            .iter()
+            .position(|x| &x.parameter == &t)
+            .unwrap_or_else(|| {
+                let l = 0; //self.0.len();
+                // TODO enable multi configs, do the same as the one for Java
+                self.0 = Some(AnyProc {
+                    parameter: t,
+                    cache: Default::default(),
+                    commits: Default::default(),
+                });
+                l
+            });
+        use crate::processing::erased::ConfigParametersHandle;
+        use crate::processing::erased::ParametrizedCommitProc;
+        ParametrizedCommitProcessorHandle(self.erased_handle(), ConfigParametersHandle(l))
+    }
+}
+
+struct PreparedAnyCommitProc<'repo> {
+    repository: &'repo git2::Repository,
+    commit_builder: crate::preprocessed::CommitBuilder,
+    pub(crate) handle: ParametrizedCommitProcessorHandle,
+}
+impl<'repo> crate::processing::erased::PreparedCommitProc for PreparedAnyCommitProc<'repo> {
+    fn process(
+        self: Box<PreparedAnyCommitProc<'repo>>,
+        prepro: &mut RepositoryProcessor,
+    ) -> hyperast::store::defaults::NodeIdentifier {
+        let dir_path = PathBuf::from("");
+        let mut dir_path = dir_path.components().peekable();
+        let name = ObjectName::from(b"");
+        let (root, _) = AnyProcessor::new(
+            self.repository,
+            prepro,
+            &mut dir_path,
+            &name,
+            self.commit_builder.tree_oid(),
+            self.handle,
+        )
+        .process();
+        let h = prepro
+            .processing_systems
+            .mut_or_default::<AnyProcessorHolder>();
+        let handle = self.handle;
+        let commit_oid = self.commit_builder.commit_oid();
+        let commit = self.commit_builder.finish(root);
+        h.with_parameters_mut(handle.1)
+            .commits
+            .insert(commit_oid, commit);
+        root
+    }
+}
+
+impl crate::processing::erased::CommitProc for AnyProc {
+    fn prepare_processing<'repo>(
+        &self,
+        repository: &'repo git2::Repository,
+        commit_builder: crate::preprocessed::CommitBuilder,
+        handle: crate::processing::ParametrizedCommitProcessorHandle,
+    ) -> Box<dyn crate::processing::erased::PreparedCommitProc + 'repo> {
+        Box::new(PreparedAnyCommitProc {
+            repository,
+            commit_builder,
+            handle,
+        })
+    }
+
+    fn get_commit(&self, commit_oid: git2::Oid) -> Option<&crate::Commit> {
+        self.commits.get(&commit_oid)
+    }
+
+    fn commit_count(&self) -> usize {
+        self.commits.len()
+    }
+
+    fn commits(&self) -> Box<dyn Iterator<Item = (&git2::Oid, &crate::Commit)> + '_> {
+        Box::new(self.commits.iter())
+    }
+
+    fn restore_commit(&mut self, commit_oid: git2::Oid, commit: crate::Commit) {
+        self.commits.insert(commit_oid, commit);
+    }
+
+    fn retain_commits(&mut self, keep: &mut dyn FnMut(&git2::Oid, &crate::Commit) -> bool) {
+        self.commits.retain(|oid, commit| keep(oid, commit));
+    }
+
+    fn clear_caches(&mut self) {
+        self.cache = Default::default();
+    }
+
+    fn get_lang_handle(&self, lang: &str) -> Option<ParametrizedCommitProcessorHandle> {
+        #[cfg(feature = "java")]
+        if lang.eq_ignore_ascii_case("java") {
+            return Some(ParametrizedCommitProcessorHandle(
+                CommitProcessorHandle(std::any::TypeId::of::<
+                    crate::java_processor::JavaProcessorHolder,
+                >()),
+                self.parameter.java_handle.0,
+            ));
+        }
+        #[cfg(feature = "maven")]
+        if lang.eq_ignore_ascii_case("xml") {
+            return Some(ParametrizedCommitProcessorHandle(
+                CommitProcessorHandle(std::any::TypeId::of::<
+                    crate::maven_processor::PomProcessorHolder,
+                >()),
+                self.parameter.xml_handle.0,
+            ));
+        }
+        // c files are handled by the c++ processor
+        #[cfg(all(feature = "c", feature = "cpp"))]
+        if lang.eq_ignore_ascii_case("c") {
+            return Some(ParametrizedCommitProcessorHandle(
+                CommitProcessorHandle(std::any::TypeId::of::<
+                    crate::cpp_processor::CppProcessorHolder,
+                >()),
+                self.parameter.cpp_handle.0,
+            ));
+        }
+        #[cfg(feature = "cpp")]
+        if lang.eq_ignore_ascii_case("cpp") {
+            return Some(ParametrizedCommitProcessorHandle(
+                CommitProcessorHandle(std::any::TypeId::of::<
+                    crate::cpp_processor::CppProcessorHolder,
+                >()),
+                self.parameter.cpp_handle.0,
+            ));
+        }
+        #[cfg(feature = "python")]
+        if lang.eq_ignore_ascii_case("python") {
+            return Some(ParametrizedCommitProcessorHandle(
+                CommitProcessorHandle(std::any::TypeId::of::<
+                    crate::python_processor::PythonProcessorHolder,
+                >()),
+                self.parameter.python_handle.0,
+            ));
+        }
+        #[cfg(feature = "ts")]
+        if lang.eq_ignore_ascii_case("ts") || lang.eq_ignore_ascii_case("typescript") {
+            return Some(ParametrizedCommitProcessorHandle(
+                CommitProcessorHandle(
+                    std::any::TypeId::of::<crate::ts_processor::TsProcessorHolder>(),
+                ),
+                self.parameter.ts_handle.0,
+            ));
+        }
+        let _ = lang;
+        None
+    }
+}
+
+impl crate::processing::erased::CommitProcExt for AnyProc {
+    type Holder = AnyProcessorHolder;
+}
+
+impl crate::processing::erased::ParametrizedCommitProc2 for AnyProcessorHolder {
+    type Proc = AnyProc;
+
+    fn with_parameters_mut(
+        &mut self,
+        parameters: crate::processing::erased::ConfigParametersHandle,
+    ) -> &mut Self::Proc {
+        assert_eq!(0, parameters.0);
+        self.0.as_mut().unwrap()
+    }
+
+    fn with_parameters(
+        &self,
+        parameters: crate::processing::erased::ConfigParametersHandle,
+    ) -> &Self::Proc {
+        assert_eq!(0, parameters.0);
+        self.0.as_ref().unwrap()
+    }
+
+    fn all_parameters_mut(&mut self) -> Box<dyn Iterator<Item = &mut Self::Proc> + '_> {
+        Box::new(self.0.iter_mut())
+    }
+}
+
+impl CacheHolding<crate::processing::caches::Any> for AnyProc {
+    fn get_caches_mut(&mut self) -> &mut crate::processing::caches::Any {
+        &mut self.cache
+    }
+    fn get_caches(&self) -> &crate::processing::caches::Any {
+        &self.cache
+    }
+}
+
+impl CacheHolding<crate::processing::caches::Any> for AnyProcessorHolder {
+    fn get_caches_mut(&mut self) -> &mut crate::processing::caches::Any {
+        &mut self.0.as_mut().unwrap().cache
+    }
+    fn get_caches(&self) -> &crate::processing::caches::Any {
+        &self.0.as_ref().unwrap().cache
+    }
+}
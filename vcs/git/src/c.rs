@@ -0,0 +1,48 @@
+use std::time::Instant;
+
+use crate::{
+    FailedParsing, FileProcessingResult, PROPAGATE_ERROR_ON_BAD_CST_NODE, SuccessProcessing,
+    processing::ObjectName,
+};
+
+use hyperast::tree_gen;
+
+use hyperast_gen_ts_c::{legion as c_tree_gen, types::TStore};
+
+pub type SimpleStores = hyperast::store::SimpleStores<TStore>;
+
+pub(crate) fn handle_c_file<'stores, 'cache, 'b: 'stores, More>(
+    tree_gen: &mut c_tree_gen::CTreeGen<'stores, 'cache, TStore, More>,
+    name: &ObjectName,
+    text: &'b [u8],
+) -> FileProcessingResult<c_tree_gen::FNode>
+where
+    More: tree_gen::Prepro<SimpleStores> + tree_gen::More<SimpleStores, Acc = c_tree_gen::Acc>,
+{
+    let mut parser = tree_sitter::Parser::new();
+    parser.set_language(&hyperast_gen_ts_c::language()).unwrap();
+    let time = Instant::now();
+    let tree = parser.parse(text, None);
+    let parsing_time = time.elapsed();
+    let Some(tree) = tree else {
+        unimplemented!("You set a timeout or an cancel flag, so it now requires special handling.")
+    };
+    if tree.root_node().has_error() {
+        log::warn!("bad CST: {:?}", name.try_str());
+        if PROPAGATE_ERROR_ON_BAD_CST_NODE {
+            return Err(FailedParsing {
+                parsing_time,
+                tree,
+                error: "CST contains parsing errors",
+            });
+        }
+    };
+    let time = Instant::now();
+    let node = tree_gen.generate_file(name.as_bytes(), text, tree.walk());
+    let processing_time = time.elapsed();
+    Ok(SuccessProcessing {
+        parsing_time,
+        processing_time,
+        node,
+    })
+}
@@ -0,0 +1,273 @@
+//! Extracts the build structure of C/C++ projects,
+//! from CMakeLists.txt files or from a compilation database (compile_commands.json).
+//!
+//! CMake scripts are not evaluated, i.e. variables, conditions and generator expressions are ignored.
+use std::path::{Component, Path, PathBuf};
+
+use crate::make::{Target, TargetKind};
+
+/// What a CMakeLists.txt declares
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CMakeLists {
+    /// added with `add_subdirectory`
+    pub subdirectories: Vec<String>,
+    pub targets: Vec<Target>,
+    /// added with `include_directories`
+    pub include_dirs: Vec<PathBuf>,
+}
+
+const LIBRARY_TYPES: &[&str] = &["STATIC", "SHARED", "MODULE", "OBJECT", "INTERFACE"];
+const TARGET_OPTIONS: &[&str] = &["WIN32", "MACOSX_BUNDLE", "EXCLUDE_FROM_ALL"];
+const SCOPES: &[&str] = &["PRIVATE", "PUBLIC", "INTERFACE"];
+const INCLUDE_OPTIONS: &[&str] = &["AFTER", "BEFORE", "SYSTEM"];
+
+pub fn parse_cmake_lists(text: &str) -> CMakeLists {
+    let mut r = CMakeLists::default();
+    for (command, args) in commands(text) {
+        let mut args = args.iter().map(String::as_str);
+        match command.to_ascii_lowercase().as_str() {
+            "add_subdirectory" => {
+                if let Some(dir) = args.next().and_then(module_path) {
+                    r.subdirectories.push(dir.to_string_lossy().into_owned());
+                }
+            }
+            cmd @ ("add_executable" | "add_library") => {
+                let Some(name) = args.next() else { continue };
+                let args: Vec<_> = args.collect();
+                if args.iter().any(|x| *x == "IMPORTED" || *x == "ALIAS") {
+                    continue;
+                }
+                let kind = if cmd == "add_executable" {
+                    TargetKind::Executable
+                } else {
+                    TargetKind::Library
+                };
+                let mut target = Target::new(name, kind);
+                target.sources.extend(
+                    args.into_iter()
+                        .filter(|x| !LIBRARY_TYPES.contains(x) && !TARGET_OPTIONS.contains(x))
+                        .filter_map(module_path),
+                );
+                r.targets.push(target);
+            }
+            "target_sources" => {
+                let Some(target) = args.next().and_then(|x| find(&mut r.targets, x)) else {
+                    continue;
+                };
+                target
+                    .sources
+                    .extend(args.filter(|x| !SCOPES.contains(x)).filter_map(module_path));
+            }
+            "include_directories" => {
+                r.include_dirs.extend(
+                    args.filter(|x| !INCLUDE_OPTIONS.contains(x))
+                        .filter_map(module_path),
+                );
+            }
+            "target_include_directories" => {
+                let Some(target) = args.next().and_then(|x| find(&mut r.targets, x)) else {
+                    continue;
+                };
+                target.include_dirs.extend(
+                    args.filter(|x| !SCOPES.contains(x) && !INCLUDE_OPTIONS.contains(x))
+                        .filter_map(module_path),
+                );
+            }
+            _ => (),
+        }
+    }
+    r
+}
+
+fn find<'a>(targets: &'a mut [Target], name: &str) -> Option<&'a mut Target> {
+    targets.iter_mut().find(|t| t.name == name)
+}
+
+/// Paths relative to the current module,
+/// other variables and generator expressions cannot be resolved without evaluating the script
+fn module_path(arg: &str) -> Option<PathBuf> {
+    let arg = arg
+        .strip_prefix("${CMAKE_CURRENT_SOURCE_DIR}")
+        .or_else(|| arg.strip_prefix("${CMAKE_CURRENT_LIST_DIR}"))
+        .map_or(arg, |x| x.trim_start_matches('/'));
+    if arg.contains("${") || arg.contains("$<") || Path::new(arg).is_absolute() {
+        return None;
+    }
+    Some(normalize(Path::new(arg)))
+}
+
+/// The command invocations of a script with their arguments,
+/// parentheses nested in the arguments are flattened.
+fn commands(text: &str) -> Vec<(String, Vec<String>)> {
+    let mut r = vec![];
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '#' {
+            chars.by_ref().find(|c| *c == '\n');
+            continue;
+        }
+        if !(c.is_ascii_alphabetic() || c == '_') {
+            continue;
+        }
+        let mut name = c.to_string();
+        while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_') {
+            name.push(c);
+        }
+        while chars.next_if(|c| *c == ' ' || *c == '\t').is_some() {}
+        if chars.next_if_eq(&'(').is_none() {
+            continue;
+        }
+        let mut args = vec![];
+        let mut depth = 0;
+        let mut arg = String::new();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => {
+                    while let Some(c) = chars.next() {
+                        match c {
+                            '"' => break,
+                            '\\' => arg.extend(chars.next()),
+                            c => arg.push(c),
+                        }
+                    }
+                }
+                '#' if arg.is_empty() => {
+                    chars.by_ref().find(|c| *c == '\n');
+                }
+                '(' => depth += 1,
+                ')' if depth == 0 => break,
+                ')' => depth -= 1,
+                c if c.is_whitespace() => {
+                    if !arg.is_empty() {
+                        args.push(std::mem::take(&mut arg));
+                    }
+                }
+                c => arg.push(c),
+            }
+        }
+        if !arg.is_empty() {
+            args.push(arg);
+        }
+        r.push((name, args));
+    }
+    r
+}
+
+#[derive(serde::Deserialize)]
+struct CompileCommand {
+    directory: String,
+    file: String,
+    #[serde(default)]
+    arguments: Vec<String>,
+    command: Option<String>,
+    output: Option<String>,
+}
+
+/// Groups the translation units of a compilation database into targets.
+///
+/// Targets are named after the object directories of CMake (`CMakeFiles/<target>.dir/`) or Meson (`<target>.p/`),
+/// other translation units are grouped into an `all` target.
+/// Files and include directories are resolved against the `directory` of their command,
+/// then made relative to the root of the repository, assumed to be the directory of the compilation database.
+/// The root is the deepest directory containing the build directories and the translation units,
+/// or only the translation units if the build directories are out of the repository.
+pub fn parse_compile_commands(text: &str) -> Result<Vec<Target>, String> {
+    let commands: Vec<CompileCommand> = serde_json::from_str(text).map_err(|e| e.to_string())?;
+    let units: Vec<(PathBuf, &CompileCommand)> = commands
+        .iter()
+        .map(|c| (normalize(&Path::new(&c.directory).join(&c.file)), c))
+        .collect();
+    let sources = units
+        .iter()
+        .map(|(file, _)| file.parent().unwrap_or(Path::new("")))
+        .reduce(common_prefix)
+        .unwrap_or(Path::new(""));
+    let with_build = units
+        .iter()
+        .map(|(_, c)| Path::new(&c.directory))
+        .fold(sources, common_prefix);
+    // e.g. sources in /home/dev/demo and a build in /tmp/build
+    let out_of_tree = with_build.parent().is_none();
+    let root = if out_of_tree { sources } else { with_build }.to_path_buf();
+    let relative = |path: &Path| path.strip_prefix(&root).ok().map(Path::to_path_buf);
+    let mut targets: Vec<Target> = vec![];
+    for (file, c) in &units {
+        let args = match &c.command {
+            Some(command) if c.arguments.is_empty() => {
+                command.split_whitespace().map(str::to_string).collect()
+            }
+            _ => c.arguments.clone(),
+        };
+        let output = c
+            .output
+            .as_deref()
+            .or_else(|| flag_values(&args, "-o").next());
+        let name = output.and_then(target_name).unwrap_or("all");
+        let target = match targets.iter().position(|t| t.name == name) {
+            Some(i) => &mut targets[i],
+            None => {
+                targets.push(Target::new(name, TargetKind::Unknown));
+                targets.last_mut().unwrap()
+            }
+        };
+        if let Some(file) = relative(file) {
+            target.sources.push(file);
+        }
+        let include_dirs = ["-I", "-isystem", "-iquote"]
+            .into_iter()
+            .flat_map(|flag| flag_values(&args, flag))
+            .filter_map(|dir| relative(&normalize(&Path::new(&c.directory).join(dir))));
+        for dir in include_dirs {
+            if !target.include_dirs.contains(&dir) {
+                target.include_dirs.push(dir);
+            }
+        }
+    }
+    Ok(targets)
+}
+
+/// Values of a flag, either joined (`-Idir`) or separated (`-I dir`)
+fn flag_values<'a>(args: &'a [String], flag: &'a str) -> impl Iterator<Item = &'a str> {
+    args.iter().enumerate().filter_map(move |(i, arg)| {
+        let value = arg.strip_prefix(flag)?;
+        if value.is_empty() {
+            args.get(i + 1).map(String::as_str)
+        } else if flag.len() == 2 {
+            Some(value)
+        } else {
+            None
+        }
+    })
+}
+
+fn target_name(output: &str) -> Option<&str> {
+    Path::new(output)
+        .components()
+        .filter_map(|c| c.as_os_str().to_str())
+        .find_map(|c| c.strip_suffix(".dir").or_else(|| c.strip_suffix(".p")))
+}
+
+fn common_prefix<'a>(a: &'a Path, b: &'a Path) -> &'a Path {
+    let mut prefix = a;
+    while !b.starts_with(prefix) {
+        prefix = prefix.parent().unwrap_or(Path::new(""));
+    }
+    prefix
+}
+
+/// Lexically removes `.` and `..` components
+fn normalize(path: &Path) -> PathBuf {
+    let mut r = PathBuf::new();
+    for c in path.components() {
+        match c {
+            Component::CurDir => (),
+            Component::ParentDir
+                if matches!(r.components().next_back(), Some(Component::Normal(_))) =>
+            {
+                r.pop();
+            }
+            c => r.push(c),
+        }
+    }
+    r
+}
@@ -40,8 +40,8 @@ pub(crate) fn handle_cpp_file<'stores, 'cache, 'b: 'stores, More>(
     text: &'b [u8],
 ) -> FileProcessingResult<cpp_tree_gen::FNode>
 where
-    More:
-        tree_gen::Prepro<SimpleStores> + tree_gen::PreproTSG<SimpleStores, Acc = cpp_tree_gen::Acc>,
+    More: tree_gen::Prepro<SimpleStores, Scope = hyperast::scripting::Acc>
+        + tree_gen::PreproTSG<SimpleStores, Acc = cpp_tree_gen::Acc>,
 {
     // handling the parsing explicitly in this function is a good idea
     // to control complex stuff like timeout, instead of the call on next line
@@ -85,19 +85,21 @@ where
 pub struct CppAcc {
     pub(crate) primary:
         BasicDirAcc<NodeIdentifier, LabelIdentifier, SubTreeMetrics<SyntaxNodeHashs<u32>>>,
+    pub(crate) scripting_acc: Option<hyperast::scripting::Acc>,
 }
 
 impl CppAcc {
-    pub(crate) fn new(name: String) -> Self {
+    pub(crate) fn new(name: String, prepro: Option<hyperast::scripting::Acc>) -> Self {
         Self {
             primary: BasicDirAcc::new(name),
+            scripting_acc: prepro,
         }
     }
 }
 
 impl From<String> for CppAcc {
     fn from(name: String) -> Self {
-        Self::new(name)
+        Self::new(name, None)
     }
 }
 
@@ -133,6 +135,15 @@ impl CppAcc {
         self.primary
             .push(name, full_node.compressed_node, full_node.metrics);
     }
+    #[cfg(feature = "c")]
+    pub(crate) fn push_c(
+        &mut self,
+        name: LabelIdentifier,
+        full_node: hyperast_gen_ts_c::legion::Local,
+    ) {
+        self.primary
+            .push(name, full_node.compressed_node, full_node.metrics);
+    }
 }
 
 impl hyperast::tree_gen::Accumulator for CppAcc {
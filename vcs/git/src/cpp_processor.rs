@@ -4,14 +4,17 @@ use crate::{
     git::BasicGitObject,
     make::MakeModuleAcc,
     preprocessed::RepositoryProcessor,
-    processing::{CacheHolding, InFiles, ObjectName},
+    processing::{CacheHolding, InFiles, ObjectName, erased::ParametrizedCommitProc2},
 };
 use git2::{Oid, Repository};
 use hyperast::{
     store::nodes::legion::eq_node,
     types::{ETypeStore as _, LabelStore},
 };
-use hyperast_gen_ts_cpp::{legion as cpp_gen, types::Type};
+use hyperast_gen_ts_cpp::{
+    legion::{self as cpp_gen, Acc},
+    types::{TStore, Type},
+};
 use hyperast_tsquery::ArrayStr;
 use std::{iter::Peekable, path::Components, sync::Arc};
 
@@ -33,7 +36,7 @@ pub struct CppProcessor<'repo, 'prepro, 'd, 'c, Acc> {
     parameters: &'d crate::processing::erased::ParametrizedCommitProcessor2Handle<CppProc>,
 }
 
-impl<'repo, 'b, 'd, 'c, Acc: From<String>> CppProcessor<'repo, 'b, 'd, 'c, Acc> {
+impl<'repo, 'b, 'd, 'c> CppProcessor<'repo, 'b, 'd, 'c, CppAcc> {
     pub(crate) fn new(
         repository: &'repo Repository,
         prepro: &'b mut RepositoryProcessor,
@@ -45,7 +48,17 @@ impl<'repo, 'b, 'd, 'c, Acc: From<String>> CppProcessor<'repo, 'b, 'd, 'c, Acc>
         let tree = repository.find_tree(oid).unwrap();
         let prepared = prepare_dir_exploration(tree);
         let name = name.try_into().unwrap();
-        let stack = vec![StackEle::new(oid, prepared, Acc::from(name))];
+        let scripting_acc = prep_scripting(prepro, parameters.0).map(|x| {
+            use hyperast::tree_gen::Prepro;
+            hyperast::scripting::Prepro::<SimpleStores, &Acc>::from(x.clone())
+                .preprocessing(Type::Directory)
+                .unwrap()
+        });
+        let stack = vec![StackEle::new(
+            oid,
+            prepared,
+            CppAcc::new(name, scripting_acc),
+        )];
         Self {
             stack,
             repository,
@@ -90,6 +103,19 @@ impl<'repo, 'b, 'd, 'c> Processor<CppAcc> for CppProcessor<'repo, 'b, 'd, 'c, Cp
                 self.handle_tree_cached(oid, name);
             }
             BasicGitObject::Blob(oid, name) => {
+                #[cfg(feature = "c")]
+                if crate::processing::file_sys::C::matches(&name) {
+                    self.prepro
+                        .help_handle_c_file(
+                            oid,
+                            &mut self.stack.last_mut().unwrap().acc,
+                            &name,
+                            self.repository,
+                            *self.parameters,
+                        )
+                        .unwrap();
+                    return;
+                }
                 if crate::processing::file_sys::Cpp::matches(&name) {
                     self.prepro
                         .help_handle_cpp_file(
@@ -113,6 +139,7 @@ impl<'repo, 'b, 'd, 'c> Processor<CppAcc> for CppProcessor<'repo, 'b, 'd, 'c, Cp
         self.prepro
             .processing_systems
             .mut_or_default::<CppProcessorHolder>()
+            .with_parameters_mut(self.parameters.0)
             .get_caches_mut()
             .object_map
             .insert(key, (full_node.clone(),));
@@ -127,7 +154,18 @@ impl<'repo, 'b, 'd, 'c> Processor<CppAcc> for CppProcessor<'repo, 'b, 'd, 'c, Cp
                 w.primary.children_names,
                 name
             );
+            let id = full_node.compressed_node;
             w.push(name, full_node.clone());
+            if let Some(acc) = &mut w.scripting_acc {
+                // SAFETY: this side should be fine, issue when unerasing
+                let store = unsafe { self.prepro.main_stores.erase_ts_unchecked() };
+                acc.acc::<_, hyperast_gen_ts_cpp::types::TType, _>(
+                    store,
+                    Type::Directory,
+                    id.into(),
+                )
+                .unwrap();
+            }
             None
         }
     }
@@ -146,27 +184,42 @@ impl<'repo, 'prepro, 'd, 'c> CppProcessor<'repo, 'prepro, 'd, 'c, CppAcc> {
             .prepro
             .processing_systems
             .mut_or_default::<CppProcessorHolder>()
-            .get_caches_mut()
+            .with_parameters(self.parameters.0)
+            .get_caches()
             .object_map
             .get(&(oid, name.clone()))
         {
             // reinit already computed node for post order
             let full_node = already.clone();
             // let skiped_ana = *skiped_ana;
+            let id = full_node.0.compressed_node;
             let w = &mut self.stack.last_mut().unwrap().acc;
             let name = self.prepro.intern_object_name(&name);
             assert!(!w.primary.children_names.contains(&name));
             hyperast::tree_gen::Accumulator::push(w, (name, full_node));
             // w.push(name, full_node, skiped_ana);
+            if let Some(acc) = &mut w.scripting_acc {
+                // SAFETY: this side should be fine, issue when unerasing
+                let store = unsafe { self.prepro.main_stores.erase_ts_unchecked() };
+                acc.acc::<_, hyperast_gen_ts_cpp::types::TType, _>(
+                    store,
+                    Type::Directory,
+                    id.into(),
+                )
+                .unwrap();
+            }
         } else {
             log::debug!("tree {:?}", name.try_str());
             let tree = self.repository.find_tree(oid).unwrap();
             let prepared: Vec<BasicGitObject> = prepare_dir_exploration(tree);
-            self.stack.push(StackEle::new(
-                oid,
-                prepared,
-                CppAcc::new(name.try_into().unwrap()),
-            ));
+            let prepro_acc = prep_scripting(&self.prepro, self.parameters.0).map(|more| {
+                use hyperast::tree_gen::Prepro;
+                hyperast::scripting::Prepro::<SimpleStores, &Acc>::from(more.clone())
+                    .preprocessing(Type::Directory)
+                    .unwrap()
+            });
+            let acc = CppAcc::new(name.try_into().unwrap(), prepro_acc);
+            self.stack.push(StackEle::new(oid, prepared, acc));
         }
     }
 }
@@ -174,50 +227,124 @@ impl<'repo, 'prepro, 'd, 'c> CppProcessor<'repo, 'prepro, 'd, 'c, CppAcc> {
 #[derive(Clone, PartialEq, Eq)]
 pub struct Parameter {
     pub(crate) query: Option<hyperast_tsquery::ZeroSepArrayStr>,
+    pub(crate) tsg: Option<Arc<str>>,
+    pub(crate) prepro: Option<Arc<str>>,
 }
 #[derive(Default)]
-pub(crate) struct CppProcessorHolder(Option<CppProc>);
+pub(crate) struct CppProcessorHolder(Vec<CppProc>);
 pub(crate) struct CppProc {
     parameter: Parameter,
     query: Query,
+    #[cfg(feature = "tsg")]
+    tsg: Option<(ErazedTSG, ErazedFcts)>,
     cache: crate::processing::caches::Cpp,
+    /// .c files are parsed with the C generator
+    #[cfg(feature = "c")]
+    c_cache: crate::processing::caches::C,
     commits: std::collections::HashMap<git2::Oid, crate::Commit>,
 }
+
+#[cfg(feature = "tsg")]
+type ErazedFcts = Arc<dyn std::any::Any + Send + Sync>;
+#[cfg(feature = "tsg")]
+type ErazedTSG = Box<dyn std::any::Any + Send + Sync>;
+
 impl crate::processing::erased::Parametrized for CppProcessorHolder {
     type T = Parameter;
     fn register_param(
         &mut self,
         t: Self::T,
     ) -> crate::processing::erased::ParametrizedCommitProcessorHandle {
-        let l = self
-            .0
-            .iter()
-            .position(|x| &x.parameter == &t)
-            .unwrap_or_else(|| {
-                let l = 0; //self.0.len();
-                // self.0.push(CppProc(t));
-                // TODO enable multi configs for cpp, do the same as the one for Java
-                let query = if let Some(q) = &t.query {
-                    Query::new(q.iter())
-                } else {
-                    let precomputeds = crate::cpp_processor::SUB_QUERIES;
-                    Query::new(precomputeds.into_iter().map(|x| x.as_ref()))
-                };
-                self.0 = Some(CppProc {
-                    parameter: t,
-                    query,
-                    cache: Default::default(),
-                    commits: Default::default(),
-                });
-                l
-            });
-        use crate::processing::erased::ConfigParametersHandle;
-        use crate::processing::erased::ParametrizedCommitProc;
-        use crate::processing::erased::ParametrizedCommitProcessorHandle;
+        use crate::processing::erased::{
+            ConfigParametersHandle, ParametrizedCommitProc, ParametrizedCommitProcessorHandle,
+        };
+        if let Some(l) = self.0.iter().position(|x| &x.parameter == &t) {
+            return ParametrizedCommitProcessorHandle(
+                self.erased_handle(),
+                ConfigParametersHandle(l),
+            );
+        }
+        let l = self.0.len();
+        let query = if let Some(q) = &t.query {
+            Query::new(q.iter())
+        } else {
+            let precomputeds = crate::cpp_processor::SUB_QUERIES;
+            Query::new(precomputeds.into_iter().map(|x| x.as_ref()))
+        };
+
+        #[cfg(feature = "tsg")]
+        let tsg = if let Some(q) = &t.tsg {
+            use std::ops::Deref;
+            let tsg = q.deref();
+            type M<'hast, TS, Acc> = hyperast_tsquery::QueryMatcher<TS, Acc>;
+            type ExtQ<'hast, TS, Acc> =
+                hyperast_tsquery::ExtendingStringQuery<M<'hast, TS, Acc>, tree_sitter::Language>;
+
+            let source: &str = tsg;
+            let language = hyperast_gen_ts_cpp::language();
+
+            let mut file =
+                tree_sitter_graph::ast::File::<M<&SimpleStores, &Acc>>::new(language.clone());
+
+            let query_source = if let Some(p) = &t.query {
+                ExtQ::new(language.clone(), Box::new(p.clone()), source.len())
+            } else {
+                ExtQ::new(language.clone(), Box::new(SUB_QUERIES), source.len())
+            };
+            tree_sitter_graph::parser::Parser::<ExtQ<_, _>>::with_ext(query_source, source)
+                .parse_into_file(&mut file)
+                .unwrap();
+            use tree_sitter_graph::GenQuery;
+
+            M::check(&mut file).unwrap();
+
+            let functions = tree_sitter_graph::functions::Functions::<
+                tree_sitter_graph::graph::Graph<
+                    hyperast_tsquery::stepped_query_imm::Node<
+                        hyperast::store::SimpleStores<
+                            TStore,
+                            &hyperast::store::nodes::legion::NodeStoreInner,
+                            &hyperast::store::labels::LabelStore,
+                        >,
+                        &Acc,
+                    >,
+                >,
+            >::essentials();
+            let functions = functions.as_any();
+
+            Some((file.as_any(), functions))
+        } else {
+            None
+        };
+        self.0.push(CppProc {
+            parameter: t,
+            query,
+            #[cfg(feature = "tsg")]
+            tsg,
+            cache: Default::default(),
+            #[cfg(feature = "c")]
+            c_cache: Default::default(),
+            commits: Default::default(),
+        });
         ParametrizedCommitProcessorHandle(self.erased_handle(), ConfigParametersHandle(l))
     }
 }
 
+// TODO generalize and factor similar preps, see the one for Java
+pub(crate) fn prep_scripting(
+    prepro: &RepositoryProcessor,
+    handle: crate::processing::erased::ConfigParametersHandle,
+) -> Option<&Arc<str>> {
+    prepro
+        .processing_systems
+        .get::<CppProcessorHolder>()
+        .as_ref()?
+        .with_parameters(handle)
+        .parameter
+        .prepro
+        .as_ref()
+}
+
 #[derive(Clone)]
 pub(crate) struct Query(pub(crate) hyperast_tsquery::Query, Arc<str>);
 
@@ -260,6 +387,26 @@ impl crate::processing::erased::CommitProc for CppProc {
         self.commits.len()
     }
 
+    fn commits(&self) -> Box<dyn Iterator<Item = (&git2::Oid, &crate::Commit)> + '_> {
+        Box::new(self.commits.iter())
+    }
+
+    fn restore_commit(&mut self, commit_oid: git2::Oid, commit: crate::Commit) {
+        self.commits.insert(commit_oid, commit);
+    }
+
+    fn retain_commits(&mut self, keep: &mut dyn FnMut(&git2::Oid, &crate::Commit) -> bool) {
+        self.commits.retain(|oid, commit| keep(oid, commit));
+    }
+
+    fn clear_caches(&mut self) {
+        self.cache = Default::default();
+        #[cfg(feature = "c")]
+        {
+            self.c_cache = Default::default();
+        }
+    }
+
     fn get_precomp_query(&self) -> Option<hyperast_tsquery::ZeroSepArrayStr> {
         dbg!(&self.parameter.query);
         self.parameter.query.clone()
@@ -276,16 +423,18 @@ impl crate::processing::erased::ParametrizedCommitProc2 for CppProcessorHolder {
         &mut self,
         parameters: crate::processing::erased::ConfigParametersHandle,
     ) -> &mut Self::Proc {
-        assert_eq!(0, parameters.0);
-        self.0.as_mut().unwrap()
+        &mut self.0[parameters.0]
     }
 
     fn with_parameters(
         &self,
         parameters: crate::processing::erased::ConfigParametersHandle,
     ) -> &Self::Proc {
-        assert_eq!(0, parameters.0);
-        self.0.as_ref().unwrap()
+        &self.0[parameters.0]
+    }
+
+    fn all_parameters_mut(&mut self) -> Box<dyn Iterator<Item = &mut Self::Proc> + '_> {
+        Box::new(self.0.iter_mut())
     }
 }
 impl CacheHolding<crate::processing::caches::Cpp> for CppProc {
@@ -297,65 +446,169 @@ impl CacheHolding<crate::processing::caches::Cpp> for CppProc {
     }
 }
 
-impl CacheHolding<crate::processing::caches::Cpp> for CppProcessorHolder {
-    fn get_caches_mut(&mut self) -> &mut crate::processing::caches::Cpp {
-        &mut self.0.as_mut().unwrap().cache
+#[cfg(feature = "c")]
+impl CacheHolding<crate::processing::caches::C> for CppProc {
+    fn get_caches_mut(&mut self) -> &mut crate::processing::caches::C {
+        &mut self.c_cache
     }
-    fn get_caches(&self) -> &crate::processing::caches::Cpp {
-        &self.0.as_ref().unwrap().cache
+    fn get_caches(&self) -> &crate::processing::caches::C {
+        &self.c_cache
     }
 }
 
-#[cfg(feature = "cpp")]
+#[cfg(feature = "c")]
 impl RepositoryProcessor {
-    fn handle_cpp_blob(
+    pub(crate) fn handle_c_blob(
         &mut self,
         oid: Oid,
         name: &ObjectName,
         repository: &Repository,
         parameters: crate::processing::erased::ParametrizedCommitProcessor2Handle<CppProc>,
-    ) -> Result<(cpp_gen::Local,), crate::ParseErr> {
+    ) -> Result<(hyperast_gen_ts_c::legion::Local,), crate::ParseErr> {
         self.processing_systems
-            .caching_blob_handler::<crate::processing::file_sys::Cpp>()
+            .caching_blob_handler::<crate::processing::file_sys::C>()
             .handle2(oid, repository, &name, parameters, |c, n, t| {
                 let line_break = if t.contains(&b'\r') { "\r\n" } else { "\n" }
                     .as_bytes()
                     .to_vec();
                 let holder = c.mut_or_default::<CppProcessorHolder>();
-                let cpp_proc = holder.0.as_mut().unwrap();
-                let md_cache = &mut cpp_proc.cache.md_cache;
+                let cpp_proc = holder.with_parameters_mut(parameters.0);
+                let md_cache = &mut cpp_proc.c_cache.md_cache;
                 let stores = self
                     .main_stores
-                    .mut_with_ts::<hyperast_gen_ts_cpp::types::TStore>();
-                let more = hyperast_tsquery::PreparedQuerying::<
-                    _,
-                    hyperast_gen_ts_cpp::types::TStore,
-                    cpp_gen::Acc,
-                >::from(&cpp_proc.query.0);
-                let mut cpp_tree_gen = cpp_gen::CppTreeGen {
-                    line_break,
-                    stores,
-                    md_cache,
-                    more,
-                };
-                crate::cpp::handle_cpp_file(&mut cpp_tree_gen, n, t)
+                    .mut_with_ts::<hyperast_gen_ts_c::types::TStore>();
+                let mut c_tree_gen = hyperast_gen_ts_c::legion::CTreeGen::new(stores, md_cache);
+                c_tree_gen.line_break = line_break;
+                crate::c::handle_c_file(&mut c_tree_gen, n, t)
                     .map(|x| {
-                        let local = x.node.local.clone();
                         self.parsing_time += x.parsing_time;
                         self.processing_time += x.processing_time;
-                        log::debug!(
-                            "parsing, processing, n, f: {} {} {} {}",
-                            self.parsing_time.as_secs(),
-                            self.processing_time.as_secs(),
-                            cpp_proc.cache.md_cache.len(),
-                            cpp_proc.cache.object_map.len()
-                        );
-                        (local,)
+                        (x.node.local,)
                     })
                     .map_err(|_| crate::ParseErr::IllFormed)
             })
     }
 
+    pub(crate) fn help_handle_c_file(
+        &mut self,
+        oid: Oid,
+        parent: &mut CppAcc,
+        name: &ObjectName,
+        repository: &Repository,
+        parameters: crate::processing::erased::ParametrizedCommitProcessor2Handle<CppProc>,
+    ) -> Result<(), crate::ParseErr> {
+        let (full_node,) = self.handle_c_blob(oid, name, repository, parameters)?;
+        let name = self.intern_object_name(name);
+        assert!(!parent.primary.children_names.contains(&name));
+        parent.push_c(name, full_node);
+        Ok(())
+    }
+
+    pub(crate) fn help_handle_c_file2(
+        &mut self,
+        oid: Oid,
+        parent: &mut MakeModuleAcc,
+        name: &ObjectName,
+        repository: &Repository,
+        parameters: crate::processing::erased::ParametrizedCommitProcessor2Handle<CppProc>,
+    ) -> Result<(), crate::ParseErr> {
+        let (full_node,) = self.handle_c_blob(oid, name, repository, parameters)?;
+        let name = self.intern_object_name(name);
+        assert!(!parent.primary.children_names.contains(&name));
+        parent.push_c_source_file(name, full_node);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "cpp")]
+impl RepositoryProcessor {
+    pub(crate) fn handle_cpp_blob(
+        &mut self,
+        oid: Oid,
+        name: &ObjectName,
+        repository: &Repository,
+        parameters: crate::processing::erased::ParametrizedCommitProcessor2Handle<CppProc>,
+    ) -> Result<(cpp_gen::Local,), crate::ParseErr> {
+        self.processing_systems
+            .caching_blob_handler::<crate::processing::file_sys::Cpp>()
+            .handle2(oid, repository, &name, parameters, |c, n, t| {
+                let line_break = if t.contains(&b'\r') { "\r\n" } else { "\n" }
+                    .as_bytes()
+                    .to_vec();
+                let holder = c.mut_or_default::<CppProcessorHolder>();
+                let cpp_proc = holder.with_parameters_mut(parameters.0);
+                let md_cache = &mut cpp_proc.cache.md_cache;
+                let dedup = Some(&mut cpp_proc.cache.dedup);
+                let stores = self.main_stores.mut_with_ts::<TStore>();
+                #[cfg(not(feature = "tsg"))]
+                let tsg: Option<()> = None;
+                #[cfg(feature = "tsg")]
+                let tsg = cpp_proc.tsg.as_ref();
+                if let Some(tsg) = tsg {
+                    #[cfg(not(feature = "tsg"))]
+                    {
+                        let _ = tsg;
+                        panic!();
+                    }
+                    #[cfg(feature = "tsg")]
+                    {
+                        let spec: &tree_sitter_graph::ast::File<
+                            hyperast_tsquery::QueryMatcher<_, &Acc>,
+                        > = tsg.0.downcast_ref().unwrap();
+                        let more = hyperast_tsquery::PreparedOverlay {
+                            query: Some(&cpp_proc.query.0),
+                            overlayer: spec,
+                            functions: tsg.1.clone(),
+                        };
+                        let mut cpp_tree_gen = cpp_gen::CppTreeGen {
+                            line_break,
+                            dedup,
+                            stores,
+                            md_cache,
+                            more,
+                        };
+                        crate::cpp::handle_cpp_file(&mut cpp_tree_gen, n, t)
+                    }
+                } else if let Some(precomp) = &cpp_proc.parameter.prepro {
+                    let more = hyperast::scripting::Prepro::<_, _>::from_arc(precomp.clone());
+                    let mut cpp_tree_gen = cpp_gen::CppTreeGen {
+                        line_break,
+                        dedup,
+                        stores,
+                        md_cache,
+                        more,
+                    };
+                    crate::cpp::handle_cpp_file(&mut cpp_tree_gen, n, t)
+                } else {
+                    let more = hyperast_tsquery::PreparedQuerying::<_, TStore, Acc>::from(
+                        &cpp_proc.query.0,
+                    );
+                    let mut cpp_tree_gen = cpp_gen::CppTreeGen {
+                        line_break,
+                        dedup,
+                        stores,
+                        md_cache,
+                        more,
+                    };
+                    crate::cpp::handle_cpp_file(&mut cpp_tree_gen, n, t)
+                }
+                .map(|x| {
+                    let local = x.node.local.clone();
+                    self.parsing_time += x.parsing_time;
+                    self.processing_time += x.processing_time;
+                    log::debug!(
+                        "parsing, processing, n, f: {} {} {} {}",
+                        self.parsing_time.as_secs(),
+                        self.processing_time.as_secs(),
+                        cpp_proc.cache.md_cache.len(),
+                        cpp_proc.cache.object_map.len()
+                    );
+                    (local,)
+                })
+                .map_err(|_| crate::ParseErr::IllFormed)
+            })
+    }
+
     pub(crate) fn help_handle_cpp_file(
         &mut self,
         oid: Oid,
@@ -368,7 +621,14 @@ impl RepositoryProcessor {
         let name = self.intern_object_name(name);
         assert!(!parent.primary.children_names.contains(&name));
 
+        let id = full_node.compressed_node;
         parent.push(name, full_node);
+        if let Some(acc) = &mut parent.scripting_acc {
+            // SAFETY: this side should be fine, issue when unerasing
+            let store = unsafe { self.main_stores.erase_ts_unchecked() };
+            acc.acc::<_, hyperast_gen_ts_cpp::types::TType, _>(store, Type::Directory, id.into())
+                .unwrap();
+        }
         Ok(())
     }
     pub(crate) fn help_handle_cpp_file2(
@@ -385,7 +645,14 @@ impl RepositoryProcessor {
         // parent_acc.push_pom(name, x);
         assert!(!parent.primary.children_names.contains(&name));
 
+        let id = full_node.compressed_node;
         parent.push_source_file(name, full_node);
+        if let Some(acc) = &mut parent.scripting_acc {
+            // SAFETY: this side should be fine, issue when unerasing
+            let store = unsafe { self.main_stores.erase_ts_unchecked() };
+            acc.acc::<_, hyperast_gen_ts_cpp::types::TType, _>(store, Type::Directory, id.into())
+                .unwrap();
+        }
         Ok(())
     }
 
@@ -419,7 +686,7 @@ fn make(acc: CppAcc, stores: &mut SimpleStores) -> cpp_gen::Local {
     let node_store = &mut stores.node_store;
     let label_store = &mut stores.label_store;
     let kind = Type::Directory;
-    let interned_kind = hyperast_gen_ts_cpp::types::TStore::intern(kind);
+    let interned_kind = TStore::intern(kind);
     let label_id = label_store.get_or_insert(acc.primary.name.clone());
 
     let primary = acc
@@ -458,6 +725,13 @@ fn make(acc: CppAcc, stores: &mut SimpleStores) -> cpp_gen::Local {
     let hashs = metrics.add_md_metrics(&mut dyn_builder, children_is_empty);
     hashs.persist(&mut dyn_builder);
 
+    if let Some(acc) = acc.scripting_acc {
+        let subtr = hyperast::scripting::Subtr(kind, &dyn_builder);
+        let ss = acc.finish(&subtr).unwrap();
+        use hyperast::store::nodes::EntityBuilder;
+        dyn_builder.add(ss);
+    };
+
     let vacant = insertion.vacant();
     let node_id = hyperast::store::nodes::legion::NodeStore::insert_built_after_prepare(
         vacant,
@@ -3,6 +3,7 @@ use std::{
     fs,
     path::{Path, PathBuf},
     process,
+    sync::{Arc, OnceLock},
 };
 
 pub use git2::Error;
@@ -42,6 +43,20 @@ impl<'a> Builder<'a> {
         Ok(self)
     }
 
+    /// Walks from all the refs matching `glob`, e.g. `refs/tags/*`
+    pub fn refs(mut self, glob: &str) -> Result<Self, git2::Error> {
+        self.0.push_glob(glob)?;
+        self.2 = true;
+        Ok(self)
+    }
+
+    /// Walks parents before their children, i.e. in processing order
+    pub fn parents_first(mut self) -> Result<Self, git2::Error> {
+        self.0
+            .set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+        Ok(self)
+    }
+
     pub fn first_parents(mut self) -> Result<Self, git2::Error> {
         self.0.simplify_first_parent()?;
         Ok(self)
@@ -223,11 +238,38 @@ where
     repository.unwrap()
 }
 
-#[derive(Debug, Hash, PartialEq, Eq, Copy, Clone)]
+pub const DEFAULT_CACHE_ROOT: &str = "/tmp/hyperastgitresources/repo/";
+
+static CACHE_ROOT: OnceLock<PathBuf> = OnceLock::new();
+
+/// Directory where [`Repo::fetch`] clones remote repositories.
+///
+/// Defaults to the `HYPERAST_CACHE_ROOT` env variable if set, otherwise to [`DEFAULT_CACHE_ROOT`].
+pub fn cache_root() -> &'static Path {
+    CACHE_ROOT.get_or_init(|| {
+        std::env::var_os("HYPERAST_CACHE_ROOT")
+            .map_or_else(|| PathBuf::from(DEFAULT_CACHE_ROOT), PathBuf::from)
+    })
+}
+
+/// Sets the directory returned by [`cache_root`],
+/// fails if it was already set or already used.
+pub fn set_cache_root(path: impl Into<PathBuf>) -> Result<(), PathBuf> {
+    CACHE_ROOT.set(path.into())
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
 pub enum Forge {
     Github,
     Gitlab,
     GitlabInria,
+    /// Any forge serving repositories at `<url><user>/<name>`, e.g. a Gitea or a Bitbucket server.
+    ///
+    /// The url ends with a '/'.
+    Custom(Arc<str>),
+    /// Repositories already on disk at `<path>/<user>/<name>` or `<path>/<user>/<name>.git`,
+    /// either checkouts or bare mirrors, they are never fetched.
+    Local(Arc<Path>),
 }
 
 impl std::str::FromStr for Forge {
@@ -238,17 +280,34 @@ impl std::str::FromStr for Forge {
             "github.com" => Self::Github,
             "gitlab.com" => Self::Gitlab,
             "gitlab.inria.fr" => Self::GitlabInria,
+            x if x.contains("://") && !x.starts_with("file://") => Self::custom(x),
+            x if x.starts_with("file://") => Self::local(&x["file://".len()..]),
+            x if x.starts_with('/') || x.starts_with('.') => Self::local(x),
             x => return Err(format!("'{}' is not an authorize forge", x)),
         })
     }
 }
 
 impl Forge {
-    fn url(&self) -> &str {
+    pub fn custom(url: &str) -> Self {
+        if url.ends_with('/') {
+            Self::Custom(url.into())
+        } else {
+            Self::Custom(format!("{}/", url).into())
+        }
+    }
+
+    pub fn local(path: impl AsRef<Path>) -> Self {
+        Self::Local(path.as_ref().into())
+    }
+
+    fn url(&self) -> String {
         match self {
-            Forge::Github => "https://github.com/",
-            Forge::Gitlab => "https://gitlab.com/",
-            Forge::GitlabInria => "https://gitlab.inria.fr/",
+            Forge::Github => "https://github.com/".to_string(),
+            Forge::Gitlab => "https://gitlab.com/".to_string(),
+            Forge::GitlabInria => "https://gitlab.inria.fr/".to_string(),
+            Forge::Custom(url) => url.to_string(),
+            Forge::Local(path) => format!("file://{}/", path.display()),
         }
     }
 
@@ -262,12 +321,13 @@ impl Forge {
         user: impl Into<String>,
         name: impl Into<String>,
     ) -> Result<Repo, String> {
+        let is_injecting = |x: &str| x.contains("#") || x.contains("/") || x == "." || x == "..";
         let user = user.into();
-        if user.contains("#") || user.contains("/") {
+        if is_injecting(&user) {
             return Err("attempting to inject stuff!".to_string());
         }
         let name = name.into();
-        if name.contains("#") || name.contains("/") {
+        if is_injecting(&name) {
             return Err("attempting to inject stuff!".to_string());
         }
         Ok(Repo {
@@ -291,30 +351,101 @@ impl Repo {
         format!("{}{}/{}", self.forge.url(), self.user, self.name)
     }
     pub fn fetch(&self) -> Repository {
-        let url = self.url();
-        let path = format!("{}", "/tmp/hyperastgitresources/repo/");
-        fetch_repository(url, path)
+        self.fetch_to(cache_root())
     }
     pub fn nofetch(&self) -> Repository {
-        let url = self.url();
-        let path = format!("{}", "/tmp/hyperastgitresources/repo/");
-        nofetch_repository(url, path)
+        self.nofetch_to(cache_root())
     }
 
+    /// Local repositories are opened in place, whatever the `path`.
+    ///
+    /// panics if a local repository cannot be opened, see [`Repo::try_fetch`]
     pub fn fetch_to(&self, path: impl Into<PathBuf>) -> Repository {
+        if let Forge::Local(_) = &self.forge {
+            return self.open_local().unwrap_or_else(|e| panic!("{}", e));
+        }
         let url = self.url();
-        let path = path.into();
+        let path = self.cache_dir(path.into());
         fetch_repository(url, path)
     }
 
+    /// Local repositories are opened in place, whatever the `path`.
+    ///
+    /// panics if a local repository cannot be opened, see [`Repo::try_fetch`]
     pub fn nofetch_to(&self, path: impl Into<PathBuf>) -> Repository {
+        if let Forge::Local(_) = &self.forge {
+            return self.open_local().unwrap_or_else(|e| panic!("{}", e));
+        }
         let url = self.url();
-        let path = path.into();
+        let path = self.cache_dir(path.into());
         nofetch_repository(url, path)
     }
 
+    /// avoid mixing the clones of custom forges with the ones of github
+    fn cache_dir(&self, mut path: PathBuf) -> PathBuf {
+        if let Forge::Custom(url) = &self.forge {
+            let url: Url = url.as_ref().try_into().unwrap();
+            path.push(url.domain);
+        }
+        path
+    }
+
+    /// Like [`Repo::fetch`], but returns an error instead of panicking
+    /// when the repository of a [`Forge::Local`] cannot be opened,
+    /// e.g. when its name comes from a request.
+    pub fn try_fetch(&self) -> Result<Repository, git2::Error> {
+        if let Forge::Local(_) = &self.forge {
+            return self.open_local();
+        }
+        Ok(self.fetch())
+    }
+
+    /// Opens the repository of a [`Forge::Local`] in place.
+    ///
+    /// Fails if the repository does not exist or if the forge is not local.
+    pub fn open_local(&self) -> Result<Repository, git2::Error> {
+        let Forge::Local(root) = &self.forge else {
+            return Err(git2::Error::from_str(&format!(
+                "{} is not a local repository",
+                self.url()
+            )));
+        };
+        let path = self.local_path(root);
+        Repository::open(&path).map_err(|e| {
+            git2::Error::from_str(&format!(
+                "failed to open {}: {}",
+                path.display(),
+                e.message()
+            ))
+        })
+    }
+
+    fn local_path(&self, root: &Path) -> PathBuf {
+        let path = root.join(&self.user).join(&self.name);
+        if path.exists() {
+            path
+        } else {
+            root.join(&self.user).join(format!("{}.git", self.name))
+        }
+    }
+
+    /// Opens the repository as it was last fetched, without accessing the network,
+    /// `None` if it was never fetched.
+    pub fn fetched(&self) -> Option<Repository> {
+        let path = if let Forge::Local(root) = &self.forge {
+            self.local_path(root)
+        } else {
+            let url: Url = self.url().try_into().ok()?;
+            let mut path = self.cache_dir(cache_root().into());
+            path.push(url.path);
+            path
+        };
+        Repository::open(path).ok()
+    }
+
+    /// Cheap to clone, custom and local forges only hold an [`Arc`]
     pub fn forge(&self) -> Forge {
-        self.forge
+        self.forge.clone()
     }
     pub fn user(&self) -> &str {
         &self.user
@@ -334,6 +465,16 @@ impl std::str::FromStr for Repo {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.contains("://") || s.starts_with('/') || s.starts_with('.') {
+            // custom forge or local directory, e.g. https://git.example.org/user/name or /srv/git/user/name
+            let s = s.trim_end_matches('/');
+            let mut it = s.rsplitn(3, "/");
+            let (Some(name), Some(user), Some(forge)) = (it.next(), it.next(), it.next()) else {
+                return Err("give a valid repository address ending with <user>/<name>".to_string());
+            };
+            let forge: Forge = forge.parse()?;
+            return forge.try_repo(user, name);
+        }
         let (forge, repo) = s
             .split_once("/")
             .ok_or("give a valid repository address without 'https://' and '.git'")?;
@@ -353,8 +494,7 @@ impl std::str::FromStr for Repo {
 
 pub fn fetch_github_repository(repo_name: &str) -> Repository {
     let url = format!("{}{}", "https://github.com/", repo_name);
-    let path = format!("{}", "/tmp/hyperastgitresources/repo/");
-    fetch_repository(url, path)
+    fetch_repository(url, cache_root())
 }
 
 pub fn fetch_fork(mut x: git2::Remote, head: &str) -> Result<(), git2::Error> {
@@ -375,6 +515,22 @@ pub fn fetch_fork(mut x: git2::Remote, head: &str) -> Result<(), git2::Error> {
     x.fetch(&[head], Some(&mut fo), None)
 }
 
+/// Updates the refs selected by `refspecs` from the `origin` remote of `repository`, pruning the deleted ones,
+/// e.g. `+refs/heads/*:refs/heads/*` as [`up_to_date_repo`] only fetches `main`.
+///
+/// Repositories without remote, like the ones of [`Forge::Local`], are left as is.
+pub fn fetch_refspecs(repository: &Repository, refspecs: &[String]) -> Result<(), git2::Error> {
+    let mut remote = match repository.find_remote("origin") {
+        Ok(remote) => remote,
+        Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    let mut fo = git2::FetchOptions::new();
+    fo.prune(git2::FetchPrune::On);
+    log::info!("fetch {:?}: {:?}", repository.path(), refspecs);
+    remote.fetch(refspecs, Some(&mut fo), None)
+}
+
 /// avoid mixing providers
 pub fn up_to_date_repo(
     path: &Path,
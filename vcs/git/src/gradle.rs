@@ -0,0 +1,325 @@
+use crate::{Accumulator, BasicDirAcc, DefaultMetrics, ParseErr, processing::ObjectName};
+use enumset::EnumSet;
+use hyperast::{
+    store::defaults::{LabelIdentifier, NodeIdentifier},
+    tree_gen::SubTreeMetrics,
+};
+use hyperast_gen_ts_java::legion_with_refs as java_tree_gen;
+use std::path::PathBuf;
+
+/// Reads the layout of the project declared by a build script,
+/// they are not stored in the hyperast, it needs a groovy and a kotlin generator.
+pub(crate) fn handle_gradle_file(name: &ObjectName, text: &[u8]) -> Result<BuildScript, ParseErr> {
+    let text = std::str::from_utf8(text)?;
+    if crate::processing::file_sys::GradleScript::is_settings(name) {
+        let sub_projects = parse_includes(text);
+        return Ok(BuildScript {
+            sub_projects,
+            ..Default::default()
+        });
+    }
+    let (main_dirs, test_dirs) = parse_source_sets(text);
+    Ok(BuildScript {
+        main_dirs,
+        test_dirs,
+        ..Default::default()
+    })
+}
+
+/// Extracts the directories of the subprojects included by a settings script,
+/// e.g. `include ':app', ':lib:core'` or `include("app")` give `app` and `lib/core`,
+/// unless relocated with `project(':app').projectDir = file('modules/app')`.
+pub(crate) fn parse_includes(text: &str) -> Vec<String> {
+    let mut includes: Vec<String> = vec![];
+    let mut relocated: Vec<(String, String)> = vec![];
+    let mut continued = false;
+    for line in text.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        let is_include = line
+            .strip_prefix("include")
+            .is_some_and(|x| x.starts_with([' ', '\t', '(']));
+        if continued || is_include {
+            includes.extend(quoted(line).map(project_dir));
+            continued = line.ends_with(',');
+        } else if line.starts_with("project(") && line.contains(".projectDir") {
+            let mut paths = quoted(line);
+            if let (Some(path), Some(dir)) = (paths.next(), paths.next()) {
+                let dir = dir.trim_start_matches("./").trim_end_matches('/');
+                relocated.push((project_dir(path), dir.to_string()));
+            }
+        }
+    }
+    for include in &mut includes {
+        if let Some((_, dir)) = relocated.iter().find(|(path, _)| path == include) {
+            *include = dir.clone();
+        }
+    }
+    includes
+}
+
+/// `:lib:core` is located in `lib/core`
+fn project_dir(path: &str) -> String {
+    path.trim_start_matches(':').replace(':', "/")
+}
+
+/// Extracts the java directories of the source sets declared by a build script,
+/// e.g. `sourceSets { main { java { srcDirs = ['src'] } } }`, `sourceSets.test.java.srcDir 'tests'`
+/// or `sourceSets { named("integrationTest") { java.srcDir("it") } }`,
+/// source sets named after tests, e.g. `test` or `integrationTest`, hold tests.
+pub(crate) fn parse_source_sets(text: &str) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let tokens = tokenize(text);
+    let mut main_dirs = vec![];
+    let mut test_dirs = vec![];
+    // the names of the enclosing blocks
+    let mut blocks: Vec<Vec<&str>> = vec![];
+    for (i, token) in tokens.iter().enumerate() {
+        let x = match token {
+            Token::Open => {
+                let name = match tokens[..i] {
+                    [
+                        ..,
+                        Token::Ident("named" | "getByName" | "create" | "register"),
+                        Token::Str(name),
+                    ] => {
+                        vec![name]
+                    }
+                    [.., Token::Ident(x)] => x.split('.').collect(),
+                    _ => vec![],
+                };
+                blocks.push(name);
+                continue;
+            }
+            Token::Close => {
+                blocks.pop();
+                continue;
+            }
+            Token::Str(_) => continue,
+            Token::Ident(x) => x,
+        };
+        let mut path: Vec<&str> = blocks.iter().flatten().copied().collect();
+        path.extend(x.split('.'));
+        let Some("srcDir" | "srcDirs" | "setSrcDirs") = path.pop() else {
+            continue;
+        };
+        let Some(at) = path.iter().position(|x| *x == "sourceSets") else {
+            continue;
+        };
+        let (Some(set), true) = (path.get(at + 1), path[at + 1..].contains(&"java")) else {
+            continue;
+        };
+        let dirs = tokens[i + 1..]
+            .iter()
+            .map_while(|t| match t {
+                Token::Str(x) => Some(Some(*x)),
+                Token::Ident("file" | "files" | "project.file" | "listOf") => Some(None),
+                _ => None,
+            })
+            .flatten()
+            // interpolated paths are not resolved
+            .filter(|x| !x.contains('$'))
+            .map(|x| PathBuf::from(x.trim_start_matches("./").trim_end_matches('/')));
+        if set.to_ascii_lowercase().contains("test") {
+            test_dirs.extend(dirs);
+        } else {
+            main_dirs.extend(dirs);
+        }
+    }
+    (main_dirs, test_dirs)
+}
+
+enum Token<'a> {
+    /// possibly dotted, e.g. `sourceSets.main.java`
+    Ident(&'a str),
+    Str(&'a str),
+    Open,
+    Close,
+}
+
+/// Splits groovy and kotlin scripts just enough to find blocks and their string arguments
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if rest.starts_with("//") {
+            rest = rest.find('\n').map_or("", |i| &rest[i..]);
+        } else if rest.starts_with("/*") {
+            rest = rest.find("*/").map_or("", |i| &rest[i + 2..]);
+        } else if c == '\'' || c == '"' {
+            let end = rest[1..].find(c).map_or(rest.len(), |i| i + 1);
+            tokens.push(Token::Str(&rest[1..end]));
+            rest = rest.get(end + 1..).unwrap_or_default();
+        } else if c.is_alphabetic() || c == '_' {
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+                .unwrap_or(rest.len());
+            tokens.push(Token::Ident(&rest[..end]));
+            rest = &rest[end..];
+        } else {
+            match c {
+                '{' => tokens.push(Token::Open),
+                '}' => tokens.push(Token::Close),
+                _ => {}
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    tokens
+}
+
+fn quoted(line: &str) -> impl Iterator<Item = &str> {
+    let mut rest = line;
+    std::iter::from_fn(move || {
+        let start = rest.find(['\'', '"'])?;
+        let quote = rest.as_bytes()[start] as char;
+        let after = &rest[start + 1..];
+        let end = after.find(quote)?;
+        rest = &after[end + 1..];
+        Some(&after[..end])
+    })
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BuildScript {
+    /// only given by settings scripts
+    sub_projects: Vec<String>,
+    /// java directories of the source sets declared outside of the conventional layout
+    main_dirs: Vec<PathBuf>,
+    test_dirs: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MD {
+    pub(crate) metrics: DefaultMetrics,
+    pub(crate) status: EnumSet<SemFlag>,
+}
+
+pub struct GradleProjectAcc {
+    pub(crate) primary: BasicDirAcc<NodeIdentifier, LabelIdentifier, DefaultMetrics>,
+    pub(crate) sub_projects: Option<Vec<PathBuf>>,
+    pub(crate) main_dirs: Option<Vec<PathBuf>>,
+    pub(crate) test_dirs: Option<Vec<PathBuf>>,
+    pub(crate) status: EnumSet<SemFlag>,
+}
+
+impl From<String> for GradleProjectAcc {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+impl GradleProjectAcc {
+    pub(crate) fn new(name: String) -> Self {
+        Self {
+            primary: BasicDirAcc::new(name),
+            sub_projects: None,
+            main_dirs: None,
+            test_dirs: None,
+            status: Default::default(),
+        }
+    }
+    pub(crate) fn with_content(
+        name: String,
+        sub_projects: Vec<PathBuf>,
+        main_dirs: Vec<PathBuf>,
+        test_dirs: Vec<PathBuf>,
+    ) -> Self {
+        let non_empty = |v: Vec<PathBuf>| if v.is_empty() { None } else { Some(v) };
+        Self {
+            primary: BasicDirAcc::new(name),
+            sub_projects: non_empty(sub_projects),
+            main_dirs: non_empty(main_dirs),
+            test_dirs: non_empty(test_dirs),
+            status: Default::default(),
+        }
+    }
+}
+
+#[derive(enumset::EnumSetType, Debug)]
+pub enum SemFlag {
+    IsGradleProject,
+    HoldMainFolder,
+    HoldTestFolder,
+    HoldGradleSubProject,
+}
+
+impl GradleProjectAcc {
+    pub(crate) fn is_project(&self) -> bool {
+        self.status.contains(SemFlag::IsGradleProject)
+    }
+    /// Makes it a project, with the java directories of its source sets
+    pub(crate) fn set_project(&mut self, main_dirs: Vec<PathBuf>, test_dirs: Vec<PathBuf>) {
+        self.status |= SemFlag::IsGradleProject;
+        self.main_dirs.get_or_insert_default().extend(main_dirs);
+        self.test_dirs.get_or_insert_default().extend(test_dirs);
+    }
+    pub(crate) fn push_build_script(&mut self, script: BuildScript) {
+        if !script.sub_projects.is_empty() {
+            let sub_projects = script.sub_projects.iter().map(|x| x.into());
+            self.sub_projects
+                .get_or_insert_default()
+                .extend(sub_projects);
+        }
+        if !script.main_dirs.is_empty() {
+            self.main_dirs
+                .get_or_insert_default()
+                .extend(script.main_dirs);
+        }
+        if !script.test_dirs.is_empty() {
+            self.test_dirs
+                .get_or_insert_default()
+                .extend(script.test_dirs);
+        }
+    }
+    pub fn push_sub_project(&mut self, name: LabelIdentifier, full_node: (NodeIdentifier, MD)) {
+        if full_node.1.status.contains(SemFlag::HoldGradleSubProject)
+            || full_node.1.status.contains(SemFlag::IsGradleProject)
+        {
+            self.status |= SemFlag::HoldGradleSubProject;
+        }
+        self.primary.children.push(full_node.0);
+        self.primary.children_names.push(name);
+        self.primary.metrics.acc(full_node.1.metrics);
+    }
+    pub(crate) fn push_source_directory(
+        &mut self,
+        name: LabelIdentifier,
+        full_node: java_tree_gen::Local,
+    ) {
+        self.status |= SemFlag::HoldMainFolder;
+        self.push_java_directory(name, full_node);
+    }
+    pub(crate) fn push_test_source_directory(
+        &mut self,
+        name: LabelIdentifier,
+        full_node: java_tree_gen::Local,
+    ) {
+        self.status |= SemFlag::HoldTestFolder;
+        self.push_java_directory(name, full_node);
+    }
+    fn push_java_directory(&mut self, name: LabelIdentifier, full_node: java_tree_gen::Local) {
+        self.primary.children.push(full_node.compressed_node);
+        self.primary.children_names.push(name);
+        self.primary.metrics.acc(SubTreeMetrics {
+            hashs: full_node.metrics.hashs,
+            size: full_node.metrics.size,
+            height: full_node.metrics.height,
+            size_no_spaces: full_node.metrics.size_no_spaces,
+            line_count: 0,
+        });
+    }
+}
+
+impl hyperast::tree_gen::Accumulator for GradleProjectAcc {
+    type Node = (LabelIdentifier, (NodeIdentifier, MD));
+    fn push(&mut self, (name, full_node): Self::Node) {
+        let s = full_node.1.status - SemFlag::IsGradleProject;
+        self.status |= s;
+        self.primary.children.push(full_node.0);
+        self.primary.children_names.push(name);
+        self.primary.metrics.acc(full_node.1.metrics);
+    }
+}
+
+impl Accumulator for GradleProjectAcc {
+    type Unlabeled = (NodeIdentifier, MD);
+}
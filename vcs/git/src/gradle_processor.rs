@@ -0,0 +1,735 @@
+use crate::StackEle;
+use crate::processing::ParametrizedCommitProcessorHandle;
+use crate::processing::erased::{
+    CommitProcessorHandle, ParametrizedCommitProcessor2Handle as PCP2Handle,
+};
+use crate::{
+    Processor,
+    git::BasicGitObject,
+    gradle::{GradleProjectAcc, MD, SemFlag},
+    preprocessed::RepositoryProcessor,
+    processing::{CacheHolding, InFiles, ObjectName, erased::ParametrizedCommitProc2},
+};
+use git2::{Oid, Repository};
+use hyperast::store::nodes::compo;
+use hyperast::types::ETypeStore as _;
+use hyperast::{
+    hashed::{IndexingHashBuilder, MetaDataHashsBuilder},
+    store::{
+        defaults::{LabelIdentifier, NodeIdentifier},
+        nodes::{EntityBuilder, legion::eq_node},
+    },
+    types::LabelStore,
+};
+use hyperast_gen_ts_xml::types::Type;
+use std::{
+    iter::Peekable,
+    path::{Components, PathBuf},
+};
+pub type SimpleStores = hyperast::store::SimpleStores<hyperast_gen_ts_xml::types::TStore>;
+
+/// RMS: Resursive Module Search
+pub struct GradleProcessor<'a, 'b, 'c, const RMS: bool, Acc> {
+    prepro: &'b mut RepositoryProcessor,
+    repository: &'a Repository,
+    stack: Vec<StackEle<Acc>>,
+    dir_path: &'c mut Peekable<Components<'c>>,
+    handle: ParametrizedCommitProcessorHandle,
+}
+
+impl<'a, 'b, 'c, const RMS: bool> GradleProcessor<'a, 'b, 'c, RMS, GradleProjectAcc> {
+    pub fn new(
+        repository: &'a Repository,
+        prepro: &'b mut RepositoryProcessor,
+        mut dir_path: &'c mut Peekable<Components<'c>>,
+        name: &[u8],
+        oid: git2::Oid,
+        handle: ParametrizedCommitProcessorHandle,
+    ) -> Self {
+        let tree = repository.find_tree(oid).unwrap();
+        let prepared = prepare_dir_exploration(tree, &mut dir_path);
+        let name = std::str::from_utf8(name).unwrap().to_string();
+        let mut acc = GradleProjectAcc::new(name);
+        if dir_path.peek().is_none() {
+            // the root project does not need a build script
+            let (main_dirs, test_dirs) = source_sets(repository, oid);
+            acc.set_project(main_dirs, test_dirs);
+        }
+        let stack = vec![StackEle::new(oid, prepared, acc)];
+        Self {
+            stack,
+            repository,
+            prepro,
+            dir_path,
+            handle,
+        }
+    }
+}
+
+impl<'a, 'b, 'c, const RMS: bool> Processor<GradleProjectAcc>
+    for GradleProcessor<'a, 'b, 'c, RMS, GradleProjectAcc>
+{
+    fn pre(&mut self, current_dir: BasicGitObject) {
+        match current_dir {
+            BasicGitObject::Tree(oid, name) => {
+                self.handle_tree_cached(name, oid);
+            }
+            BasicGitObject::Blob(oid, name)
+                if self.dir_path.peek().is_none()
+                    && crate::processing::file_sys::GradleScript::matches(&name) =>
+            {
+                let script_handle = self
+                    .prepro
+                    .processing_systems
+                    .mut_or_default::<GradleProcessorHolder>()
+                    .with_parameters(self.handle.1)
+                    .parameter
+                    .script_handle;
+                let top = self.stack.last_mut().unwrap();
+                if let Err(err) = self.prepro.handle_gradle_script(
+                    oid,
+                    &mut top.acc,
+                    name,
+                    self.repository,
+                    script_handle,
+                ) {
+                    log::debug!("{:?}", err);
+                }
+                // e.g. buildSrc/ or an included build
+                if !top.acc.is_project() {
+                    let (main_dirs, test_dirs) = source_sets(self.repository, top.id);
+                    top.acc.set_project(main_dirs, test_dirs);
+                }
+            }
+            _ => {}
+        }
+    }
+    fn post(&mut self, oid: Oid, acc: GradleProjectAcc) -> Option<(NodeIdentifier, MD)> {
+        let name = acc.primary.name.clone();
+        let key = (oid, name.as_bytes().into());
+        let full_node = make(acc, self.prepro.main_stores_mut().mut_with_ts());
+        log::info!("tree size: {}", full_node.1.metrics.size);
+        self.prepro
+            .processing_systems
+            .mut_or_default::<GradleProcessorHolder>()
+            .with_parameters_mut(self.handle.1)
+            .get_caches_mut()
+            .object_map
+            .insert(key, full_node.clone());
+        let name = self.prepro.intern_label(&name);
+        if self.stack.is_empty() {
+            Some(full_node)
+        } else {
+            let w = &mut self.stack.last_mut().unwrap().acc;
+            assert!(
+                !w.primary.children_names.contains(&name),
+                "{:?} {:?}",
+                w.primary.children_names,
+                name
+            );
+            push_directory(w, name, full_node);
+            None
+        }
+    }
+
+    fn stack(&mut self) -> &mut Vec<StackEle<GradleProjectAcc>> {
+        &mut self.stack
+    }
+}
+
+fn push_directory(
+    w: &mut GradleProjectAcc,
+    name: LabelIdentifier,
+    full_node: (NodeIdentifier, MD),
+) {
+    if full_node.1.status.contains(SemFlag::IsGradleProject) {
+        w.push_sub_project(name, full_node);
+    } else {
+        hyperast::tree_gen::Accumulator::push(w, (name, full_node));
+    }
+}
+
+impl<'a, 'b, 'c, const RMS: bool> GradleProcessor<'a, 'b, 'c, RMS, GradleProjectAcc> {
+    fn handle_tree_cached(&mut self, name: ObjectName, oid: Oid) {
+        if let Some(s) = self.dir_path.peek() {
+            if name
+                .as_bytes()
+                .eq(std::ffi::OsStr::as_encoded_bytes(s.as_os_str()))
+            {
+                self.dir_path.next();
+                self.stack.last_mut().expect("never empty").cs.clear();
+                let tree = self.repository.find_tree(oid).unwrap();
+                let prepared = prepare_dir_exploration(tree, &mut self.dir_path);
+                let mut acc = GradleProjectAcc::new(name.try_into().unwrap());
+                if self.dir_path.peek().is_none() {
+                    let (main_dirs, test_dirs) = source_sets(self.repository, oid);
+                    acc.set_project(main_dirs, test_dirs);
+                }
+                self.stack.push(StackEle::new(oid, prepared, acc));
+            }
+            return;
+        }
+        log::debug!("gradle tree {:?}", name.try_str());
+        let parent_acc = &mut self.stack.last_mut().unwrap().acc;
+        let helper = GradleProjectHelper::from((parent_acc, &name));
+        let gradle_proc = self
+            .prepro
+            .processing_systems
+            .mut_or_default::<GradleProcessorHolder>()
+            .with_parameters_mut(self.handle.1);
+        let java_handle = gradle_proc.parameter.java_handle;
+        if helper.source_directories.0 || helper.test_source_directories.0 {
+            // handle as source dir
+            let (name, (full_node,)) = self.prepro.help_handle_java_folder(
+                &self.repository,
+                self.dir_path,
+                oid,
+                &name,
+                java_handle,
+            );
+            let parent_acc = &mut self.stack.last_mut().unwrap().acc;
+            assert!(!parent_acc.primary.children_names.contains(&name));
+            if helper.source_directories.0 {
+                parent_acc.push_source_directory(name, full_node);
+            } else {
+                parent_acc.push_test_source_directory(name, full_node);
+            }
+            return;
+        }
+        if let Some(already) = gradle_proc
+            .get_caches_mut()
+            .object_map
+            .get(&(oid, name.clone()))
+        {
+            // reinit already computed node for post order
+            let full_node = already.clone();
+            let name = self.prepro.intern_object_name(&name);
+            let w = &mut self.stack.last_mut().unwrap().acc;
+            assert!(!w.primary.children_names.contains(&name));
+            push_directory(w, name, full_node);
+            return;
+        }
+        if helper.sub_projects.0
+            || !helper.sub_projects.1.is_empty()
+            || !helper.source_directories.1.is_empty()
+            || !helper.test_source_directories.1.is_empty()
+            || RMS
+        {
+            let tree = self.repository.find_tree(oid).unwrap();
+            let prepared = prepare_dir_exploration(tree, &mut self.dir_path);
+            let is_sub_project = helper.sub_projects.0;
+            let mut acc = helper.into_acc();
+            if is_sub_project {
+                // handle as gradle project, even without a build script
+                let (main_dirs, test_dirs) = source_sets(self.repository, oid);
+                acc.set_project(main_dirs, test_dirs);
+            }
+            self.stack.push(StackEle::new(oid, prepared, acc));
+        }
+    }
+}
+
+/// Finds the java directories of the source sets following the conventional layout, i.e. `src/<set>/java/`,
+/// source sets named after tests, e.g. `test` or `integrationTest`, hold tests.
+fn source_sets(repository: &Repository, oid: Oid) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let mut main_dirs = vec![];
+    let mut test_dirs = vec![];
+    let sub_tree = |tree: &git2::Tree, name: &str| {
+        let entry = tree.get_name(name)?;
+        if entry.kind() != Some(git2::ObjectType::Tree) {
+            return None;
+        }
+        repository.find_tree(entry.id()).ok()
+    };
+    let Some(src) = repository
+        .find_tree(oid)
+        .ok()
+        .and_then(|tree| sub_tree(&tree, "src"))
+    else {
+        return (main_dirs, test_dirs);
+    };
+    for set in src.iter() {
+        let Some(name) = set.name() else {
+            continue;
+        };
+        let Some(set) = sub_tree(&src, name) else {
+            continue;
+        };
+        if sub_tree(&set, "java").is_none() {
+            continue;
+        }
+        let dir = PathBuf::from("src").join(name).join("java");
+        if name.to_ascii_lowercase().contains("test") {
+            test_dirs.push(dir);
+        } else {
+            main_dirs.push(dir);
+        }
+    }
+    (main_dirs, test_dirs)
+}
+
+pub(crate) fn make(acc: GradleProjectAcc, stores: &mut SimpleStores) -> (NodeIdentifier, MD) {
+    // projects are the modules of gradle, they share the directory kind of maven modules
+    let kind = Type::MavenDirectory;
+    let interned_kind = hyperast_gen_ts_xml::types::TStore::intern(kind);
+    let label_id = stores.label_store.get_or_insert(acc.primary.name.clone());
+
+    let missing: Vec<_> = [&acc.sub_projects, &acc.main_dirs, &acc.test_dirs]
+        .into_iter()
+        .flatten()
+        .flatten()
+        .collect();
+    if !missing.is_empty() {
+        log::warn!("not found in {:?}: {:?}", acc.primary.name, missing);
+    }
+
+    let primary = acc
+        .primary
+        .map_metrics(|m| m.finalize(&interned_kind, &label_id, 0));
+
+    let hashable = primary.metrics.hashs.most_discriminating();
+
+    let eq = eq_node(&interned_kind, Some(&label_id), &primary.children);
+
+    let insertion = stores.node_store.prepare_insertion(&hashable, eq);
+    let status = acc.status;
+    if let Some(id) = insertion.occupied_id() {
+        let metrics = primary.metrics.map_hashs(|h| h.build());
+        return (id, MD { metrics, status });
+    }
+
+    log::info!("make gradle {} {}", &primary.name, primary.children.len());
+    assert_eq!(primary.children_names.len(), primary.children.len());
+    let mut dyn_builder = hyperast::store::nodes::legion::dyn_builder::EntityBuilder::new();
+    let children_is_empty = primary.children.is_empty();
+    if !status.is_empty() {
+        dyn_builder.add(compo::Flags(status));
+    }
+    let metrics = primary.persist(&mut dyn_builder, interned_kind, label_id);
+    let metrics = metrics.map_hashs(|h| h.build());
+    let hashs = metrics.add_md_metrics(&mut dyn_builder, children_is_empty);
+    hashs.persist(&mut dyn_builder);
+
+    let vacant = insertion.vacant();
+    let node_id = hyperast::store::nodes::legion::NodeStore::insert_built_after_prepare(
+        vacant,
+        dyn_builder.build(),
+    );
+
+    (node_id, MD { metrics, status })
+}
+
+impl RepositoryProcessor {
+    fn handle_gradle_script(
+        &mut self,
+        oid: Oid,
+        parent_acc: &mut GradleProjectAcc,
+        name: ObjectName,
+        repository: &Repository,
+        parameters: PCP2Handle<GradleScriptProc>,
+    ) -> Result<(), crate::ParseErr> {
+        let x = self
+            .processing_systems
+            .caching_blob_handler::<crate::processing::file_sys::GradleScript>()
+            .handle2(oid, repository, &name, parameters, |_c, n, t| {
+                crate::gradle::handle_gradle_file(n, t)
+            })?;
+        parent_acc.push_build_script(x);
+        Ok(())
+    }
+}
+
+struct GradleProjectHelper {
+    name: String,
+    sub_projects: (bool, Vec<PathBuf>),
+    source_directories: (bool, Vec<PathBuf>),
+    test_source_directories: (bool, Vec<PathBuf>),
+}
+
+impl From<(&mut GradleProjectAcc, &ObjectName)> for GradleProjectHelper {
+    fn from((parent_acc, name): (&mut GradleProjectAcc, &ObjectName)) -> Self {
+        let process = |v: &mut Option<Vec<PathBuf>>| {
+            let mut v = drain_filter_strip(v, name.as_bytes());
+            let c = vec_extract_if_polyfill::MakeExtractIf::extract_if(&mut v, |x| {
+                x.components().next().is_none()
+            })
+            .count();
+            (c > 0, v)
+        };
+        Self {
+            name: name.try_into().unwrap(),
+            sub_projects: process(&mut parent_acc.sub_projects),
+            source_directories: process(&mut parent_acc.main_dirs),
+            test_source_directories: process(&mut parent_acc.test_dirs),
+        }
+    }
+}
+
+impl GradleProjectHelper {
+    fn into_acc(self) -> GradleProjectAcc {
+        GradleProjectAcc::with_content(
+            self.name,
+            self.sub_projects.1,
+            self.source_directories.1,
+            self.test_source_directories.1,
+        )
+    }
+}
+
+fn drain_filter_strip(v: &mut Option<Vec<PathBuf>>, name: &[u8]) -> Vec<PathBuf> {
+    let mut new_sub_projects = vec![];
+    let name = std::str::from_utf8(name).unwrap();
+    if let Some(sub_projects) = v {
+        vec_extract_if_polyfill::MakeExtractIf::extract_if(sub_projects, |x| x.starts_with(name))
+            .for_each(|x| {
+                let x = x.strip_prefix(name).unwrap().to_owned();
+                new_sub_projects.push(x);
+            });
+    }
+    new_sub_projects
+}
+
+/// Explores the build scripts first, they tell which directories are subprojects
+pub(crate) fn prepare_dir_exploration(
+    tree: git2::Tree,
+    dir_path: &mut Peekable<Components>,
+) -> Vec<BasicGitObject> {
+    let mut children_objects: Vec<BasicGitObject> = tree
+        .iter()
+        .rev()
+        .map(TryInto::try_into)
+        .filter_map(|x| x.ok())
+        .collect();
+    if dir_path.peek().is_none() {
+        // we use it like a stack
+        children_objects.sort_by_key(|x| match x {
+            BasicGitObject::Blob(_, n) => crate::processing::file_sys::GradleScript::matches(n),
+            _ => false,
+        });
+    }
+    children_objects
+}
+
+// # Gradle scripts
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Parameter {
+    pub java_handle: crate::processing::erased::ParametrizedCommitProcessor2Handle<
+        crate::java_processor::JavaProc,
+    >,
+    pub script_handle: PCP2Handle<GradleScriptProc>,
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct GradleScriptParameter {}
+
+#[derive(Default)]
+pub struct GradleScriptProcessorHolder(Vec<GradleScriptProc>);
+pub struct GradleScriptProc {
+    parameter: GradleScriptParameter,
+    cache: crate::processing::caches::GradleScript,
+    commits: std::collections::HashMap<git2::Oid, crate::Commit>,
+}
+impl crate::processing::erased::Parametrized for GradleScriptProcessorHolder {
+    type T = GradleScriptParameter;
+    fn register_param(
+        &mut self,
+        t: Self::T,
+    ) -> crate::processing::erased::ParametrizedCommitProcessorHandle {
+        let l = self
+            .0
+            .iter()
+            .position(|x| x.parameter == t)
+            .unwrap_or_else(|| {
+                let l = self.0.len();
+                self.0.push(GradleScriptProc {
+                    parameter: t,
+                    cache: Default::default(),
+                    commits: Default::default(),
+                });
+                l
+            });
+        use crate::processing::erased::ConfigParametersHandle;
+        use crate::processing::erased::ParametrizedCommitProc;
+        ParametrizedCommitProcessorHandle(self.erased_handle(), ConfigParametersHandle(l))
+    }
+}
+
+struct PreparedGradleScriptCommitProc<'repo> {
+    repository: &'repo git2::Repository,
+    commit_builder: crate::preprocessed::CommitBuilder,
+    handle: ParametrizedCommitProcessorHandle,
+}
+
+impl<'repo> crate::processing::erased::PreparedCommitProc
+    for PreparedGradleScriptCommitProc<'repo>
+{
+    /// Processes the root of the commit as a gradle project, with the default java parameters
+    fn process(
+        self: Box<PreparedGradleScriptCommitProc<'repo>>,
+        prepro: &mut RepositoryProcessor,
+    ) -> hyperast::store::defaults::NodeIdentifier {
+        use crate::processing::erased::{CommitProcExt, Parametrized};
+        let h_java = prepro
+            .processing_systems
+            .mut_or_default::<crate::java_processor::JavaProcessorHolder>();
+        let java_handle =
+            CommitProcExt::register_param(h_java, crate::java_processor::Parameter::faster());
+        let script_handle = PCP2Handle(self.handle.1, std::marker::PhantomData);
+        let gradle_handle = prepro
+            .processing_systems
+            .mut_or_default::<GradleProcessorHolder>()
+            .register_param(Parameter {
+                java_handle,
+                script_handle,
+            });
+        let dir_path = PathBuf::from("");
+        let mut dir_path = dir_path.components().peekable();
+        let root_full_node = GradleProcessor::<true, GradleProjectAcc>::new(
+            self.repository,
+            prepro,
+            &mut dir_path,
+            b"",
+            self.commit_builder.tree_oid(),
+            gradle_handle,
+        )
+        .process();
+        let commit_oid = self.commit_builder.commit_oid();
+        let commit = self.commit_builder.finish(root_full_node.0);
+        prepro
+            .processing_systems
+            .mut_or_default::<GradleScriptProcessorHolder>()
+            .with_parameters_mut(self.handle.1)
+            .commits
+            .insert(commit_oid, commit);
+        root_full_node.0
+    }
+}
+
+impl crate::processing::erased::CommitProc for GradleScriptProc {
+    fn prepare_processing<'repo>(
+        &self,
+        repository: &'repo git2::Repository,
+        commit_builder: crate::preprocessed::CommitBuilder,
+        handle: crate::processing::erased::ParametrizedCommitProcessorHandle,
+    ) -> Box<dyn crate::processing::erased::PreparedCommitProc + 'repo> {
+        Box::new(PreparedGradleScriptCommitProc {
+            repository,
+            commit_builder,
+            handle,
+        })
+    }
+
+    fn get_commit(&self, commit_oid: git2::Oid) -> Option<&crate::Commit> {
+        self.commits.get(&commit_oid)
+    }
+
+    fn commit_count(&self) -> usize {
+        self.commits.len()
+    }
+
+    fn commits(&self) -> Box<dyn Iterator<Item = (&git2::Oid, &crate::Commit)> + '_> {
+        Box::new(self.commits.iter())
+    }
+
+    fn restore_commit(&mut self, commit_oid: git2::Oid, commit: crate::Commit) {
+        self.commits.insert(commit_oid, commit);
+    }
+
+    fn retain_commits(&mut self, keep: &mut dyn FnMut(&git2::Oid, &crate::Commit) -> bool) {
+        self.commits.retain(|oid, commit| keep(oid, commit));
+    }
+
+    fn clear_caches(&mut self) {
+        self.cache = Default::default();
+    }
+}
+
+impl crate::processing::erased::CommitProcExt for GradleScriptProc {
+    type Holder = GradleScriptProcessorHolder;
+}
+impl crate::processing::erased::ParametrizedCommitProc2 for GradleScriptProcessorHolder {
+    type Proc = GradleScriptProc;
+
+    fn with_parameters_mut(
+        &mut self,
+        parameters: crate::processing::erased::ConfigParametersHandle,
+    ) -> &mut Self::Proc {
+        &mut self.0[parameters.0]
+    }
+
+    fn with_parameters(
+        &self,
+        parameters: crate::processing::erased::ConfigParametersHandle,
+    ) -> &Self::Proc {
+        &self.0[parameters.0]
+    }
+
+    fn all_parameters_mut(&mut self) -> Box<dyn Iterator<Item = &mut Self::Proc> + '_> {
+        Box::new(self.0.iter_mut())
+    }
+}
+impl CacheHolding<crate::processing::caches::GradleScript> for GradleScriptProc {
+    fn get_caches_mut(&mut self) -> &mut crate::processing::caches::GradleScript {
+        &mut self.cache
+    }
+
+    fn get_caches(&self) -> &crate::processing::caches::GradleScript {
+        &self.cache
+    }
+}
+
+// # Gradle
+#[derive(Default)]
+pub struct GradleProcessorHolder(Vec<GradleProc>);
+pub struct GradleProc {
+    parameter: Parameter,
+    cache: crate::processing::caches::Gradle,
+    commits: std::collections::HashMap<git2::Oid, crate::Commit>,
+}
+impl crate::processing::erased::Parametrized for GradleProcessorHolder {
+    type T = Parameter;
+    fn register_param(
+        &mut self,
+        t: Self::T,
+    ) -> crate::processing::erased::ParametrizedCommitProcessorHandle {
+        let l = self
+            .0
+            .iter()
+            .position(|x| x.parameter == t)
+            .unwrap_or_else(|| {
+                let l = self.0.len();
+                self.0.push(GradleProc {
+                    parameter: t,
+                    cache: Default::default(),
+                    commits: Default::default(),
+                });
+                l
+            });
+
+        use crate::processing::erased::ConfigParametersHandle;
+        use crate::processing::erased::ParametrizedCommitProc;
+        ParametrizedCommitProcessorHandle(self.erased_handle(), ConfigParametersHandle(l))
+    }
+}
+
+struct PreparedGradleCommitProc<'repo> {
+    repository: &'repo git2::Repository,
+    commit_builder: crate::preprocessed::CommitBuilder,
+    pub(crate) handle: ParametrizedCommitProcessorHandle,
+}
+
+impl<'repo> crate::processing::erased::PreparedCommitProc for PreparedGradleCommitProc<'repo> {
+    fn process(
+        self: Box<PreparedGradleCommitProc<'repo>>,
+        prepro: &mut RepositoryProcessor,
+    ) -> hyperast::store::defaults::NodeIdentifier {
+        let dir_path = PathBuf::from("");
+        let mut dir_path = dir_path.components().peekable();
+        let name = b"";
+        let root_full_node = GradleProcessor::<true, GradleProjectAcc>::new(
+            self.repository,
+            prepro,
+            &mut dir_path,
+            name,
+            self.commit_builder.tree_oid(),
+            self.handle,
+        )
+        .process();
+        let h = prepro
+            .processing_systems
+            .mut_or_default::<GradleProcessorHolder>();
+        let handle = self.handle;
+        let commit_oid = self.commit_builder.commit_oid();
+        let commit = self.commit_builder.finish(root_full_node.0);
+        h.with_parameters_mut(handle.1)
+            .commits
+            .insert(commit_oid, commit);
+        root_full_node.0
+    }
+}
+
+impl crate::processing::erased::CommitProc for GradleProc {
+    fn prepare_processing<'repo>(
+        &self,
+        repository: &'repo git2::Repository,
+        commit_builder: crate::preprocessed::CommitBuilder,
+        handle: crate::processing::ParametrizedCommitProcessorHandle,
+    ) -> Box<dyn crate::processing::erased::PreparedCommitProc + 'repo> {
+        Box::new(PreparedGradleCommitProc {
+            repository,
+            commit_builder,
+            handle,
+        })
+    }
+
+    fn get_commit(&self, commit_oid: git2::Oid) -> Option<&crate::Commit> {
+        self.commits.get(&commit_oid)
+    }
+
+    fn commit_count(&self) -> usize {
+        self.commits.len()
+    }
+
+    fn commits(&self) -> Box<dyn Iterator<Item = (&git2::Oid, &crate::Commit)> + '_> {
+        Box::new(self.commits.iter())
+    }
+
+    fn restore_commit(&mut self, commit_oid: git2::Oid, commit: crate::Commit) {
+        self.commits.insert(commit_oid, commit);
+    }
+
+    fn retain_commits(&mut self, keep: &mut dyn FnMut(&git2::Oid, &crate::Commit) -> bool) {
+        self.commits.retain(|oid, commit| keep(oid, commit));
+    }
+
+    fn clear_caches(&mut self) {
+        self.cache = Default::default();
+    }
+
+    fn get_lang_handle(&self, lang: &str) -> Option<ParametrizedCommitProcessorHandle> {
+        if lang.eq_ignore_ascii_case("java") {
+            Some(ParametrizedCommitProcessorHandle(
+                CommitProcessorHandle(std::any::TypeId::of::<
+                    crate::java_processor::JavaProcessorHolder,
+                >()),
+                self.parameter.java_handle.0,
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+impl crate::processing::erased::CommitProcExt for GradleProc {
+    type Holder = GradleProcessorHolder;
+}
+impl crate::processing::erased::ParametrizedCommitProc2 for GradleProcessorHolder {
+    type Proc = GradleProc;
+
+    fn with_parameters_mut(
+        &mut self,
+        parameters: crate::processing::erased::ConfigParametersHandle,
+    ) -> &mut Self::Proc {
+        &mut self.0[parameters.0]
+    }
+
+    fn with_parameters(
+        &self,
+        parameters: crate::processing::erased::ConfigParametersHandle,
+    ) -> &Self::Proc {
+        &self.0[parameters.0]
+    }
+
+    fn all_parameters_mut(&mut self) -> Box<dyn Iterator<Item = &mut Self::Proc> + '_> {
+        Box::new(self.0.iter_mut())
+    }
+}
+impl CacheHolding<crate::processing::caches::Gradle> for GradleProc {
+    fn get_caches_mut(&mut self) -> &mut crate::processing::caches::Gradle {
+        &mut self.cache
+    }
+    fn get_caches(&self) -> &crate::processing::caches::Gradle {
+        &self.cache
+    }
+}
@@ -524,6 +524,22 @@ impl crate::processing::erased::CommitProc for JavaProc {
         self.commits.len()
     }
 
+    fn commits(&self) -> Box<dyn Iterator<Item = (&git2::Oid, &crate::Commit)> + '_> {
+        Box::new(self.commits.iter())
+    }
+
+    fn restore_commit(&mut self, commit_oid: git2::Oid, commit: crate::Commit) {
+        self.commits.insert(commit_oid, commit);
+    }
+
+    fn retain_commits(&mut self, keep: &mut dyn FnMut(&git2::Oid, &crate::Commit) -> bool) {
+        self.commits.retain(|oid, commit| keep(oid, commit));
+    }
+
+    fn clear_caches(&mut self) {
+        self.cache = Default::default();
+    }
+
     fn get_precomp_query(&self) -> Option<hyperast_tsquery::ZeroSepArrayStr> {
         dbg!(&self.parameter.query);
         // if self.parameter.query.is_none() {
@@ -563,6 +579,10 @@ impl crate::processing::erased::ParametrizedCommitProc2 for JavaProcessorHolder
     ) -> &Self::Proc {
         &self.0[parameters.0]
     }
+
+    fn all_parameters_mut(&mut self) -> Box<dyn Iterator<Item = &mut Self::Proc> + '_> {
+        Box::new(self.0.iter_mut())
+    }
 }
 impl CacheHolding<crate::processing::caches::Java> for JavaProc {
     fn get_caches_mut(&mut self) -> &mut crate::processing::caches::Java {
@@ -681,7 +701,7 @@ impl RepositoryProcessor {
         (name, full_node)
     }
 
-    fn handle_java_blob(
+    pub(crate) fn handle_java_blob(
         &mut self,
         oid: Oid,
         name: &ObjectName,
@@ -1,13 +1,27 @@
 #[cfg(feature = "impact")]
 pub mod allrefs;
+#[cfg(feature = "c")]
+pub mod c;
+#[cfg(feature = "cmake")]
+pub mod cmake;
 pub mod cpp;
 pub mod git;
+#[cfg(feature = "gradle")]
+pub mod gradle;
 pub mod java;
 pub mod make;
 pub mod maven;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "ts")]
+pub mod ts;
 
+#[cfg(feature = "any")]
+pub mod any_processor;
 #[cfg(feature = "cpp")]
 pub mod cpp_processor;
+#[cfg(feature = "gradle")]
+pub mod gradle_processor;
 #[cfg(feature = "java")]
 pub mod java_processor;
 #[cfg(feature = "make")]
@@ -16,10 +30,20 @@ pub mod make_processor;
 pub mod maven_processor;
 pub mod multi_preprocessed;
 pub mod no_space;
+#[cfg(feature = "npm")]
+pub mod npm_processor;
+pub mod path_filter;
+#[cfg(feature = "pip")]
+pub mod pip_processor;
 /// for now only tested on maven repositories with a pom in root.
 pub mod preprocessed;
 pub mod processing;
+#[cfg(feature = "python")]
+pub mod python_processor;
+#[cfg(feature = "ts")]
+pub mod ts_processor;
 mod utils;
+pub mod watcher;
 
 #[cfg(test)]
 pub mod tests;
@@ -125,6 +149,14 @@ impl From<std::str::Utf8Error> for ParseErr {
     }
 }
 
+#[cfg(feature = "c")]
+fn ts_lang_c() -> Option<tree_sitter::Language> {
+    Some(hyperast_gen_ts_c::language())
+}
+#[cfg(not(feature = "c"))]
+fn ts_lang_c() -> Option<tree_sitter::Language> {
+    None
+}
 #[cfg(feature = "cpp")]
 fn ts_lang_cpp() -> Option<tree_sitter::Language> {
     Some(hyperast_gen_ts_cpp::language())
@@ -141,11 +173,66 @@ fn ts_lang_java() -> Option<tree_sitter::Language> {
 fn ts_lang_java() -> Option<tree_sitter::Language> {
     None
 }
+#[cfg(feature = "python")]
+fn ts_lang_python() -> Option<tree_sitter::Language> {
+    Some(hyperast_gen_ts_python::language())
+}
+#[cfg(not(feature = "python"))]
+fn ts_lang_python() -> Option<tree_sitter::Language> {
+    None
+}
+#[cfg(feature = "ts")]
+fn ts_lang_ts() -> Option<tree_sitter::Language> {
+    Some(hyperast_gen_ts_ts::language())
+}
+#[cfg(not(feature = "ts"))]
+fn ts_lang_ts() -> Option<tree_sitter::Language> {
+    None
+}
+
+#[cfg(any(feature = "maven", feature = "gradle", feature = "any"))]
+fn ts_lang_xml() -> Option<tree_sitter::Language> {
+    Some(hyperast_gen_ts_xml::language())
+}
+#[cfg(not(any(feature = "maven", feature = "gradle", feature = "any")))]
+fn ts_lang_xml() -> Option<tree_sitter::Language> {
+    None
+}
 
 pub fn resolve_language(language: &str) -> Option<tree_sitter::Language> {
+    match language_name(language)? {
+        "Java" => ts_lang_java(),
+        "C" => ts_lang_c(),
+        "Cpp" => ts_lang_cpp(),
+        "Python" => ts_lang_python(),
+        "Ts" => ts_lang_ts(),
+        "Xml" => ts_lang_xml(),
+        _ => None,
+    }
+}
+
+/// The canonical name of a language accepted by [`resolve_language`], e.g. `Ts` for `typescript`
+pub fn language_name(language: &str) -> Option<&'static str> {
     match language {
-        "Java" | "java" => ts_lang_java(),
-        "Cpp" | "cpp" => ts_lang_cpp(),
+        "Java" | "java" => Some("Java"),
+        "C" | "c" => Some("C"),
+        "Cpp" | "cpp" => Some("Cpp"),
+        "Python" | "python" => Some("Python"),
+        "Ts" | "ts" | "TypeScript" | "typescript" => Some("Ts"),
+        "Xml" | "xml" => Some("Xml"),
+        _ => None,
+    }
+}
+
+/// The canonical name of the language of a node, from the name of its [`hyperast::types::LangRef`]
+pub fn node_language(lang: &str) -> Option<&'static str> {
+    match lang {
+        "hyperast_gen_ts_java::types::Lang" => Some("Java"),
+        "hyperast_gen_ts_c::types::Lang" => Some("C"),
+        "hyperast_gen_ts_cpp::types_alt::Lang" | "hyperast_gen_ts_cpp::types::Lang" => Some("Cpp"),
+        "hyperast_gen_ts_python::types::Lang" => Some("Python"),
+        "hyperast_gen_ts_ts::types::Lang" => Some("Ts"),
+        "hyperast_gen_ts_xml::types::Lang" => Some("Xml"),
         _ => None,
     }
 }
@@ -18,8 +18,8 @@ pub(crate) fn handle_makefile_file<'a>(
     text: &'a [u8],
 ) -> Result<MakeFile, ()> {
     log::trace!("not parsing {} bytes long Makefile", text.len()); // TODO parse the makefile
-    let text = b"<proj></proj>";
-    let tree = match hyperast_gen_ts_xml::legion::tree_sitter_parse_xml(text) {
+    let script = b"<proj></proj>";
+    let tree = match hyperast_gen_ts_xml::legion::tree_sitter_parse_xml(script) {
         Ok(tree) => tree,
         Err(tree) => {
             log::warn!("bad CST");
@@ -33,7 +33,7 @@ pub(crate) fn handle_makefile_file<'a>(
         }
     };
     let x = tree_gen
-        .generate_file(name.as_bytes(), text, tree.walk())
+        .generate_file(name.as_bytes(), script, tree.walk())
         .local;
     // TODO extract submodules, dependencies and directories. maybe even more ie. artefact id, ...
     let x = MakeFile {
@@ -42,10 +42,68 @@ pub(crate) fn handle_makefile_file<'a>(
         submodules: vec![],
         source_dirs: vec![".".to_owned()],
         test_source_dirs: vec!["../tests".to_owned()],
+        targets: vec![],
+        include_dirs: vec![],
     };
+    #[cfg(feature = "cmake")]
+    let x = with_cmake_structure(x, name, text)?;
     Ok(x)
 }
 
+/// Reads the modules and targets declared by CMake build files
+#[cfg(feature = "cmake")]
+fn with_cmake_structure(mut x: MakeFile, name: &ObjectName, text: &[u8]) -> Result<MakeFile, ()> {
+    use crate::processing::file_sys::MakeFile as Sys;
+    if Sys::is_cmake_lists(name) {
+        let text = std::str::from_utf8(text).map_err(|_| ())?;
+        let lists = crate::cmake::parse_cmake_lists(text);
+        x.submodules = lists.subdirectories;
+        x.test_source_dirs = vec![];
+        x.targets = lists.targets;
+        x.include_dirs = lists.include_dirs;
+    } else if Sys::is_compile_commands(name) {
+        x.test_source_dirs = vec![];
+        // e.g. a symlink to the build directory
+        let targets = std::str::from_utf8(text)
+            .map_err(|e| e.to_string())
+            .and_then(crate::cmake::parse_compile_commands);
+        match targets {
+            Ok(targets) => x.targets = targets,
+            Err(err) => log::warn!("bad compilation database {:?}: {}", name.try_str(), err),
+        }
+    }
+    Ok(x)
+}
+
+/// A build target and the sources it compiles
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Target {
+    pub name: String,
+    pub kind: TargetKind,
+    /// translation units and headers, relative to the module declaring the target
+    pub sources: Vec<PathBuf>,
+    pub include_dirs: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetKind {
+    Executable,
+    Library,
+    /// e.g. a target only known through its compile commands
+    Unknown,
+}
+
+impl Target {
+    pub(crate) fn new(name: &str, kind: TargetKind) -> Self {
+        Self {
+            name: name.to_string(),
+            kind,
+            sources: vec![],
+            include_dirs: vec![],
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MakeFile {
     pub compressed_node: NodeIdentifier,
@@ -53,11 +111,16 @@ pub struct MakeFile {
     submodules: Vec<String>,
     source_dirs: Vec<String>,
     test_source_dirs: Vec<String>,
+    targets: Vec<Target>,
+    include_dirs: Vec<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
 pub struct MD {
     pub(crate) metrics: DefaultMetrics,
+    /// declared by the build files of the module
+    pub(crate) targets: Vec<Target>,
+    pub(crate) include_dirs: Vec<PathBuf>,
 }
 
 pub struct MakeModuleAcc {
@@ -65,6 +128,11 @@ pub struct MakeModuleAcc {
     pub(crate) sub_modules: Option<Vec<PathBuf>>,
     pub(crate) main_dirs: Option<Vec<PathBuf>>,
     pub(crate) test_dirs: Option<Vec<PathBuf>>,
+    /// holds a build file
+    pub(crate) is_module: bool,
+    pub(crate) targets: Vec<Target>,
+    pub(crate) include_dirs: Vec<PathBuf>,
+    pub(crate) scripting_acc: Option<hyperast::scripting::Acc>,
 }
 
 impl From<String> for MakeModuleAcc {
@@ -74,6 +142,10 @@ impl From<String> for MakeModuleAcc {
             sub_modules: None,
             main_dirs: None,
             test_dirs: None,
+            is_module: false,
+            targets: vec![],
+            include_dirs: vec![],
+            scripting_acc: None,
         }
     }
 }
@@ -85,6 +157,10 @@ impl MakeModuleAcc {
             sub_modules: None,
             main_dirs: None,
             test_dirs: None,
+            is_module: false,
+            targets: vec![],
+            include_dirs: vec![],
+            scripting_acc: None,
         }
     }
     pub(crate) fn with_content(
@@ -110,6 +186,10 @@ impl MakeModuleAcc {
             } else {
                 Some(test_dirs)
             },
+            is_module: false,
+            targets: vec![],
+            include_dirs: vec![],
+            scripting_acc: None,
         }
     }
 }
@@ -118,15 +198,16 @@ impl MakeModuleAcc {
     pub(crate) fn push_makefile(&mut self, name: LabelIdentifier, full_node: MakeFile) {
         self.primary.children.push(full_node.compressed_node);
         self.primary.children_names.push(name);
-        self.main_dirs = Some(full_node.source_dirs.iter().map(|x| x.into()).collect());
-        self.test_dirs = Some(
-            full_node
-                .test_source_dirs
-                .iter()
-                .map(|x| x.into())
-                .collect(),
-        );
-        self.sub_modules = Some(full_node.submodules.iter().map(|x| x.into()).collect());
+        // a module can have multiple build files, e.g. a Makefile and a CMakeLists.txt
+        let main_dirs = full_node.source_dirs.iter().map(|x| x.into());
+        self.main_dirs.get_or_insert_default().extend(main_dirs);
+        let test_dirs = full_node.test_source_dirs.iter().map(|x| x.into());
+        self.test_dirs.get_or_insert_default().extend(test_dirs);
+        let sub_modules = full_node.submodules.iter().map(|x| x.into());
+        self.sub_modules.get_or_insert_default().extend(sub_modules);
+        self.is_module = true;
+        self.targets.extend(full_node.targets);
+        self.include_dirs.extend(full_node.include_dirs);
         self.primary.metrics.acc(full_node.metrics);
     }
     pub fn push_submodule(&mut self, name: LabelIdentifier, full_node: (NodeIdentifier, MD)) {
@@ -149,6 +230,22 @@ impl MakeModuleAcc {
             line_count: 0,
         });
     }
+    #[cfg(feature = "c")]
+    pub(crate) fn push_c_source_file(
+        &mut self,
+        name: LabelIdentifier,
+        full_node: hyperast_gen_ts_c::legion::Local,
+    ) {
+        self.primary.children.push(full_node.compressed_node);
+        self.primary.children_names.push(name);
+        self.primary.metrics.acc(SubTreeMetrics {
+            hashs: full_node.metrics.hashs,
+            size: full_node.metrics.size,
+            height: full_node.metrics.height,
+            size_no_spaces: full_node.metrics.size_no_spaces,
+            line_count: 0,
+        });
+    }
     pub(crate) fn push_source_directory(
         &mut self,
         name: LabelIdentifier,
@@ -35,8 +35,8 @@ pub struct MakeProcessor<'a, 'b, 'c, const RMS: bool, const FFWD: bool, Acc> {
     handle: ParametrizedCommitProcessorHandle,
 }
 
-impl<'a, 'b, 'c, const RMS: bool, const FFWD: bool, Acc: From<String>>
-    MakeProcessor<'a, 'b, 'c, RMS, FFWD, Acc>
+impl<'a, 'b, 'c, const RMS: bool, const FFWD: bool>
+    MakeProcessor<'a, 'b, 'c, RMS, FFWD, MakeModuleAcc>
 {
     pub fn new(
         repository: &'a Repository,
@@ -49,7 +49,8 @@ impl<'a, 'b, 'c, const RMS: bool, const FFWD: bool, Acc: From<String>>
         let tree = repository.find_tree(oid).unwrap();
         let prepared = prepare_dir_exploration(tree, &mut dir_path);
         let name = std::str::from_utf8(&name).unwrap().to_string();
-        let stack = vec![StackEle::new(oid, prepared, Acc::from(name))];
+        let acc = MakeModuleAcc::new(name).init_scripting(prep_scripting(prepro, handle.1));
+        let stack = vec![StackEle::new(oid, prepared, acc)];
         Self {
             stack,
             repository,
@@ -75,6 +76,26 @@ impl<'a, 'b, 'c, const RMS: bool, const FFWD: bool> Processor<MakeModuleAcc>
                 if self.dir_path.peek().is_some() {
                     return;
                 }
+                let cpp_handle = self
+                    .prepro
+                    .processing_systems
+                    .mut_or_default::<MakeProcessorHolder>()
+                    .with_parameters(self.handle.1)
+                    .parameter
+                    .cpp_handle;
+                #[cfg(feature = "c")]
+                if crate::processing::file_sys::C::matches(&name) {
+                    self.prepro
+                        .help_handle_c_file2(
+                            oid,
+                            &mut self.stack.last_mut().unwrap().acc,
+                            &name,
+                            self.repository,
+                            cpp_handle,
+                        )
+                        .unwrap();
+                    return;
+                }
                 if crate::processing::file_sys::MakeFile::matches(&name) {
                     self.prepro
                         .help_handle_makefile(
@@ -92,7 +113,7 @@ impl<'a, 'b, 'c, const RMS: bool, const FFWD: bool> Processor<MakeModuleAcc>
                             &mut self.stack.last_mut().unwrap().acc,
                             &name,
                             self.repository,
-                            PCP2Handle(self.handle.1, std::marker::PhantomData),
+                            cpp_handle,
                         )
                         .unwrap();
                 // } else if name.ends_with(b".h") || name.ends_with(b".hpp") {
@@ -129,7 +150,18 @@ impl<'a, 'b, 'c, const RMS: bool, const FFWD: bool> Processor<MakeModuleAcc>
                 w.primary.children_names,
                 name
             );
+            let id = full_node.0;
             w.push_submodule(name, full_node);
+            if let Some(acc) = &mut w.scripting_acc {
+                // SAFETY: this side should be fine, issue when unerasing
+                let store = unsafe { self.prepro.main_stores.erase_ts_unchecked() };
+                acc.acc::<_, hyperast_gen_ts_xml::types::TType, _>(
+                    store,
+                    Type::Directory,
+                    id.into(),
+                )
+                .unwrap();
+            }
             None
         }
     }
@@ -156,21 +188,28 @@ impl<'a, 'b, 'c, const RMS: bool, const FFWD: bool>
                 self.stack.last_mut().expect("never empty").cs.clear();
                 let tree = self.repository.find_tree(oid).unwrap();
                 let prepared = prepare_dir_exploration(tree, &mut self.dir_path);
-                self.stack.push(StackEle::new(
-                    oid,
-                    prepared,
-                    MakeModuleAcc::new(name.try_into().unwrap()),
-                ));
+                let acc = MakeModuleAcc::new(name.try_into().unwrap())
+                    .init_scripting(prep_scripting(&self.prepro, self.handle.1));
+                self.stack.push(StackEle::new(oid, prepared, acc));
                 return;
             } else {
                 return;
             }
         }
+        let tree = self.repository.find_tree(oid).unwrap();
+        let is_build_dir = tree.iter().any(|x| {
+            crate::processing::file_sys::Make::is_build_dir_marker(&x.name_bytes().into())
+        });
         let make_proc = self
             .prepro
             .processing_systems
             .mut_or_default::<MakeProcessorHolder>()
             .with_parameters_mut(self.handle.1);
+        let ignored_dirs = &make_proc.parameter.ignored_dirs;
+        if crate::processing::file_sys::Make::is_ignored_dir(&name, ignored_dirs) || is_build_dir {
+            log::debug!("skip generated or vendored dir {:?}", name.try_str());
+            return;
+        }
         let cpp_handle = make_proc.parameter.cpp_handle;
         if let Some(already) = make_proc.get_caches_mut().object_map.get(&oid) {
             // reinit already computed node for post order
@@ -178,73 +217,103 @@ impl<'a, 'b, 'c, const RMS: bool, const FFWD: bool>
             let w = &mut self.stack.last_mut().unwrap().acc;
             let name = self.prepro.intern_object_name(name);
             assert!(!w.primary.children_names.contains(&name));
+            let id = full_node.0;
             w.push_submodule(name, full_node);
+            if let Some(acc) = &mut w.scripting_acc {
+                // SAFETY: this side should be fine, issue when unerasing
+                let store = unsafe { self.prepro.main_stores.erase_ts_unchecked() };
+                acc.acc::<_, hyperast_gen_ts_xml::types::TType, _>(
+                    store,
+                    Type::Directory,
+                    id.into(),
+                )
+                .unwrap();
+            }
             return;
         }
         log::debug!("make tree {:?}", name.try_str());
         let parent_acc = &mut self.stack.last_mut().unwrap().acc;
-        if true {
-            // TODO also try to handle nested Makefiles
-            let (name, (full_node,)) = self.prepro.help_handle_cpp_folder(
-                &self.repository,
-                &mut self.dir_path,
-                oid,
-                &name,
-                cpp_handle,
-            );
-            assert!(!parent_acc.primary.children_names.contains(&name));
-            parent_acc.push_source_directory(name, full_node);
-            return;
-        }
         let helper = MakeModuleHelper::from((parent_acc, &name));
-        if helper.source_directories.0 || helper.test_source_directories.0 {
-            // handle as source dir
-            let (name, (full_node,)) = self.prepro.help_handle_cpp_folder(
-                &self.repository,
-                self.dir_path,
-                oid,
-                &name,
-                cpp_handle,
-            );
-            let parent_acc = &mut self.stack.last_mut().unwrap().acc;
-            assert!(!parent_acc.primary.children_names.contains(&name));
-            if helper.source_directories.0 {
-                parent_acc.push_source_directory(name, full_node);
-            } else {
-                // test_source_folders.0
-                parent_acc.push_test_source_directory(name, full_node);
-            }
-        }
-        // check if module or src/main/java or src/test/java
-        // TODO use Make pom.xml to find source_dir  and tests_dir ie. ignore resources, maybe also tests
-        // TODO maybe at some point try to handle Make modules and source dirs that reference parent directory in their path
-
-        // TODO check it we can use more info from context and prepare analysis more specifically
+        // check if it is a module, e.g. added with add_subdirectory, or if it contains modules
+        // TODO maybe at some point try to handle modules that reference parent directory in their path
         if helper.submodules.0
             || !helper.submodules.1.is_empty()
             || !helper.source_directories.1.is_empty()
             || !helper.test_source_directories.1.is_empty()
         {
-            let tree = self.repository.find_tree(oid).unwrap();
-            let prepared = prepare_dir_exploration(tree, &mut self.dir_path);
-            if helper.submodules.0 {
-                // handle as Make module
-                self.stack.push(StackEle::new(oid, prepared, helper.into()));
-            } else {
-                // search further inside
-                self.stack.push(StackEle::new(oid, prepared, helper.into()));
-            };
-        } else if RMS && !(helper.source_directories.0 || helper.test_source_directories.0) {
-            let tree = self.repository.find_tree(oid).unwrap();
-            // anyway try to find Make modules, but maybe can do better
             let prepared = prepare_dir_exploration(tree, &mut self.dir_path);
-            self.stack.push(StackEle::new(oid, prepared, helper.into()));
+            let acc = MakeModuleAcc::from(helper)
+                .init_scripting(prep_scripting(&self.prepro, self.handle.1));
+            self.stack.push(StackEle::new(oid, prepared, acc));
+            return;
+        }
+        // TODO also try to handle nested Makefiles
+        // handle as source dir
+        let (name, (full_node,)) = self.prepro.help_handle_cpp_folder(
+            &self.repository,
+            self.dir_path,
+            oid,
+            &name,
+            cpp_handle,
+        );
+        let id = full_node.compressed_node;
+        let parent_acc = &mut self.stack.last_mut().unwrap().acc;
+        assert!(!parent_acc.primary.children_names.contains(&name));
+        if helper.test_source_directories.0 {
+            parent_acc.push_test_source_directory(name, full_node);
+        } else {
+            parent_acc.push_source_directory(name, full_node);
+        }
+        if let Some(acc) = &mut parent_acc.scripting_acc {
+            // SAFETY: this side should be fine, issue when unerasing
+            let store = unsafe { self.prepro.main_stores.erase_ts_unchecked() };
+            acc.acc::<_, hyperast_gen_ts_cpp::types::TType, _>(store, Type::Directory, id.into())
+                .unwrap();
+        }
+    }
+}
+
+impl MakeModuleAcc {
+    fn init_scripting(mut self, prep_scripting: Option<&std::sync::Arc<str>>) -> Self {
+        if let Some(more) = prep_scripting {
+            use hyperast::tree_gen::Prepro;
+            let more = hyperast::scripting::Prepro::<
+                crate::cpp_processor::SimpleStores,
+                &hyperast_gen_ts_cpp::legion::Acc,
+            >::from(more.clone());
+            match more.preprocessing(hyperast_gen_ts_cpp::types::Type::Directory) {
+                Ok(acc) => self.scripting_acc = Some(acc),
+                Err(err) => {
+                    log::error!("error when handling make modules {}", err);
+                }
+            }
         }
+        self
     }
 }
 
+/// The scripts are the ones of the cpp generator used by the config
+fn prep_scripting(
+    prepro: &RepositoryProcessor,
+    handle: crate::processing::erased::ConfigParametersHandle,
+) -> Option<&std::sync::Arc<str>> {
+    let cpp_handle = prepro
+        .processing_systems
+        .get::<MakeProcessorHolder>()?
+        .with_parameters(handle)
+        .parameter
+        .cpp_handle;
+    crate::cpp_processor::prep_scripting(prepro, cpp_handle.0)
+}
+
 pub(crate) fn make(acc: MakeModuleAcc, stores: &mut SimpleStores) -> (NodeIdentifier, MD) {
-    let kind = Type::Directory;
+    let kind = if acc.is_module {
+        Type::MavenDirectory
+    } else {
+        Type::Directory
+    };
+    let targets = acc.targets;
+    let include_dirs = acc.include_dirs;
     let interned_kind = hyperast_gen_ts_xml::types::TStore::intern(kind);
     let label_id = stores.label_store.get_or_insert(acc.primary.name.clone());
 
@@ -263,7 +332,12 @@ pub(crate) fn make(acc: MakeModuleAcc, stores: &mut SimpleStores) -> (NodeIdenti
         let metrics = primary
             .metrics
             .map_hashs(|h| MetaDataHashsBuilder::build(h));
-        return (id, MD { metrics });
+        let md = MD {
+            metrics,
+            targets,
+            include_dirs,
+        };
+        return (id, md);
     }
 
     log::info!("make mm {} {}", &primary.name, primary.children.len());
@@ -277,13 +351,25 @@ pub(crate) fn make(acc: MakeModuleAcc, stores: &mut SimpleStores) -> (NodeIdenti
     let hashs = metrics.add_md_metrics(&mut dyn_builder, children_is_empty);
     hashs.persist(&mut dyn_builder);
 
+    if let Some(acc) = acc.scripting_acc {
+        let subtr = hyperast::scripting::Subtr(kind, &dyn_builder);
+        let ss = acc.finish(&subtr).unwrap();
+        use hyperast::store::nodes::EntityBuilder;
+        dyn_builder.add(ss);
+    };
+
     let vacant = insertion.vacant();
     let node_id = hyperast::store::nodes::legion::NodeStore::insert_built_after_prepare(
         vacant,
         dyn_builder.build(),
     );
 
-    let full_node = (node_id.clone(), MD { metrics });
+    let md = MD {
+        metrics,
+        targets,
+        include_dirs,
+    };
+    let full_node = (node_id.clone(), md);
     full_node
 }
 
@@ -300,7 +386,7 @@ impl RepositoryProcessor {
         let x = self
             .processing_systems
             .caching_blob_handler::<crate::processing::file_sys::MakeFile>()
-            .handle(oid, repository, &name, parameters, |_c, n, t| {
+            .handle2(oid, repository, &name, parameters, |_c, n, t| {
                 crate::make::handle_makefile_file(
                     &mut XmlTreeGen {
                         line_break: "\n".as_bytes().to_vec(),
@@ -400,15 +486,14 @@ pub(crate) fn prepare_dir_exploration(
         .map(TryInto::try_into)
         .filter_map(|x| x.ok())
         .collect();
-    if dir_path.peek().is_none() {
-        let p = children_objects.iter().position(|x| match x {
-            BasicGitObject::Blob(_, n) => crate::processing::file_sys::MakeFile::matches(n),
-            _ => false,
-        });
-        if let Some(p) = p {
-            children_objects.swap(0, p); // priority to config file processing
-            children_objects.reverse(); // we use it like a stack
-        }
+    let is_build_file = |x: &BasicGitObject| match x {
+        BasicGitObject::Blob(_, n) => crate::processing::file_sys::MakeFile::matches(n),
+        _ => false,
+    };
+    if dir_path.peek().is_none() && children_objects.iter().any(is_build_file) {
+        children_objects.reverse(); // we use it like a stack
+        // priority to build files processing, a module can have more than one of them
+        children_objects.sort_by_key(is_build_file);
     }
     children_objects
 }
@@ -420,6 +505,10 @@ pub struct Parameter {
     pub(crate) cpp_handle: crate::processing::erased::ParametrizedCommitProcessor2Handle<
         crate::cpp_processor::CppProc,
     >,
+    /// the slice of the repository to process
+    pub(crate) filter: crate::path_filter::PathFilter,
+    /// directories skipped wherever they are, by default [`crate::processing::file_sys::Make::IGNORED_DIRS`]
+    pub(crate) ignored_dirs: Vec<String>,
 }
 impl From<crate::processing::erased::ParametrizedCommitProcessor2Handle<MakeProc>>
     for crate::processing::erased::ParametrizedCommitProcessor2Handle<MakefileProc>
@@ -497,6 +586,10 @@ impl crate::processing::erased::CommitProc for MakefileProc {
     fn commit_count(&self) -> usize {
         unimplemented!()
     }
+
+    fn clear_caches(&mut self) {
+        self.1 = Default::default();
+    }
 }
 
 impl crate::processing::erased::CommitProcExt for MakefileProc {
@@ -521,6 +614,10 @@ impl crate::processing::erased::ParametrizedCommitProc2 for MakefileProcessorHol
         assert_eq!(0, parameters.0);
         self.0.as_ref().unwrap()
     }
+
+    fn all_parameters_mut(&mut self) -> Box<dyn Iterator<Item = &mut Self::Proc> + '_> {
+        Box::new(self.0.iter_mut())
+    }
 }
 impl CacheHolding<crate::processing::caches::Makefile> for MakefileProc {
     fn get_caches_mut(&mut self) -> &mut crate::processing::caches::Makefile {
@@ -624,6 +721,10 @@ impl crate::processing::erased::CommitProc for MakeProc {
         })
     }
 
+    fn path_filter(&self) -> Option<&crate::path_filter::PathFilter> {
+        Some(&self.parameter.filter)
+    }
+
     fn get_commit(&self, commit_oid: git2::Oid) -> Option<&crate::Commit> {
         self.commits.get(&commit_oid)
     }
@@ -632,6 +733,22 @@ impl crate::processing::erased::CommitProc for MakeProc {
         self.commits.len()
     }
 
+    fn commits(&self) -> Box<dyn Iterator<Item = (&git2::Oid, &crate::Commit)> + '_> {
+        Box::new(self.commits.iter())
+    }
+
+    fn restore_commit(&mut self, commit_oid: git2::Oid, commit: crate::Commit) {
+        self.commits.insert(commit_oid, commit);
+    }
+
+    fn retain_commits(&mut self, keep: &mut dyn FnMut(&git2::Oid, &crate::Commit) -> bool) {
+        self.commits.retain(|oid, commit| keep(oid, commit));
+    }
+
+    fn clear_caches(&mut self) {
+        self.cache = Default::default();
+    }
+
     fn get_lang_handle(&self, lang: &str) -> Option<ParametrizedCommitProcessorHandle> {
         dbg!(self.parameter.cpp_handle.0.0);
         if lang.eq_ignore_ascii_case("cpp") {
@@ -674,6 +791,10 @@ impl crate::processing::erased::ParametrizedCommitProc2 for MakeProcessorHolder
         assert_eq!(0, parameters.0);
         self.0.as_ref().unwrap()
     }
+
+    fn all_parameters_mut(&mut self) -> Box<dyn Iterator<Item = &mut Self::Proc> + '_> {
+        Box::new(self.0.iter_mut())
+    }
 }
 
 impl CacheHolding<crate::processing::caches::Make> for MakeProc {
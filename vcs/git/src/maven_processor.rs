@@ -461,10 +461,24 @@ impl RepositoryProcessor {
         repository: &Repository,
         parameters: PCP2Handle<PomProc>,
     ) -> Result<(), crate::ParseErr> {
-        let x = self
-            .processing_systems
+        let x = self.handle_xml_blob(oid, &name, repository, parameters)?;
+        let name = self.intern_object_name(&name);
+        assert!(!parent_acc.primary.children_names.contains(&name));
+        parent_acc.push_pom(name, x);
+        Ok(())
+    }
+
+    /// Parses any xml file, a pom being a particular one
+    pub(crate) fn handle_xml_blob(
+        &mut self,
+        oid: Oid,
+        name: &ObjectName,
+        repository: &Repository,
+        parameters: PCP2Handle<PomProc>,
+    ) -> Result<crate::maven::POM, crate::ParseErr> {
+        self.processing_systems
             .caching_blob_handler::<crate::processing::file_sys::Pom>()
-            .handle(oid, repository, &name, parameters, |_c, n, t| {
+            .handle(oid, repository, name, parameters, |_c, n, t| {
                 let line_break = if t.contains(&b'\r') {
                     "\r\n".as_bytes().to_vec()
                 } else {
@@ -478,11 +492,7 @@ impl RepositoryProcessor {
                     stores: self.main_stores.mut_with_ts(),
                 };
                 crate::maven::handle_pom_file(&mut xml_tree_gen, n, t)
-            })?;
-        let name = self.intern_object_name(&name);
-        assert!(!parent_acc.primary.children_names.contains(&name));
-        parent_acc.push_pom(name, x);
-        Ok(())
+            })
     }
 }
 
@@ -593,6 +603,8 @@ pub struct Parameter {
     pub pom_handle: crate::processing::erased::ParametrizedCommitProcessor2Handle<
         crate::maven_processor::PomProc,
     >,
+    /// the slice of the repository to process
+    pub filter: crate::path_filter::PathFilter,
 }
 
 impl From<PCP2Handle<MavenProc>> for PCP2Handle<PomProc> {
@@ -659,6 +671,10 @@ impl crate::processing::erased::CommitProc for PomProc {
     fn commit_count(&self) -> usize {
         unimplemented!()
     }
+
+    fn clear_caches(&mut self) {
+        self.cache = Default::default();
+    }
 }
 
 impl crate::processing::erased::CommitProcExt for PomProc {
@@ -680,6 +696,10 @@ impl crate::processing::erased::ParametrizedCommitProc2 for PomProcessorHolder {
     ) -> &Self::Proc {
         &self.0[parameters.0]
     }
+
+    fn all_parameters_mut(&mut self) -> Box<dyn Iterator<Item = &mut Self::Proc> + '_> {
+        Box::new(self.0.iter_mut())
+    }
 }
 impl CacheHolding<crate::processing::caches::Pom> for PomProc {
     fn get_caches_mut(&mut self) -> &mut crate::processing::caches::Pom {
@@ -785,6 +805,10 @@ impl crate::processing::erased::CommitProc for MavenProc {
         })
     }
 
+    fn path_filter(&self) -> Option<&crate::path_filter::PathFilter> {
+        Some(&self.parameter.filter)
+    }
+
     fn get_commit(&self, commit_oid: git2::Oid) -> Option<&crate::Commit> {
         self.commits.get(&commit_oid)
     }
@@ -793,6 +817,22 @@ impl crate::processing::erased::CommitProc for MavenProc {
         self.commits.len()
     }
 
+    fn commits(&self) -> Box<dyn Iterator<Item = (&git2::Oid, &crate::Commit)> + '_> {
+        Box::new(self.commits.iter())
+    }
+
+    fn restore_commit(&mut self, commit_oid: git2::Oid, commit: crate::Commit) {
+        self.commits.insert(commit_oid, commit);
+    }
+
+    fn retain_commits(&mut self, keep: &mut dyn FnMut(&git2::Oid, &crate::Commit) -> bool) {
+        self.commits.retain(|oid, commit| keep(oid, commit));
+    }
+
+    fn clear_caches(&mut self) {
+        self.cache = Default::default();
+    }
+
     fn get_lang_handle(&self, lang: &str) -> Option<ParametrizedCommitProcessorHandle> {
         if lang.eq_ignore_ascii_case("java") {
             Some(ParametrizedCommitProcessorHandle(
@@ -831,6 +871,10 @@ impl crate::processing::erased::ParametrizedCommitProc2 for MavenProcessorHolder
     ) -> &Self::Proc {
         &self.0[parameters.0]
     }
+
+    fn all_parameters_mut(&mut self) -> Box<dyn Iterator<Item = &mut Self::Proc> + '_> {
+        Box::new(self.0.iter_mut())
+    }
 }
 impl CacheHolding<crate::processing::caches::Maven> for MavenProc {
     fn get_caches_mut(&mut self) -> &mut crate::processing::caches::Maven {
@@ -1,5 +1,10 @@
 use std::collections::HashMap;
 
+#[cfg(feature = "frozen")]
+pub mod frozen;
+#[cfg(feature = "persist")]
+pub mod persist;
+
 use hyperast::store::nodes::DefaultNodeIdentifier as NodeIdentifier;
 
 use crate::processing::ConfiguredRepo2;
@@ -23,6 +28,8 @@ pub struct PreProcessedRepositories {
     pub processor: RepositoryProcessor,
     // pub processing_ordered_commits: HashMap<String,Vec<git2::Oid>>,
     configs: HashMap<Repo, ParametrizedCommitProcessorHandle>,
+    #[cfg(feature = "persist")]
+    snapshot: hyperast::store::nodes::legion::persist::Snapshot,
 }
 
 // #[derive(Default)]
@@ -100,11 +107,67 @@ pub struct PreProcessedRepositories {
 //     // }
 // }
 
+/// Branches and tags
+pub const DEFAULT_REF_GLOBS: &[&str] = &["refs/heads/*", "refs/tags/*"];
+
+#[derive(Debug, Clone)]
+pub struct ProcessedRef {
+    pub name: String,
+    pub commit: git2::Oid,
+    /// None while the commit is not processed
+    pub ast_root: Option<NodeIdentifier>,
+}
+
+/// Mirrors the refs matching `globs` from the remote of `repository`,
+/// clones only track `main` otherwise
+pub fn fetch_refs(
+    repository: &ConfiguredRepo2,
+    globs: &[impl AsRef<str>],
+) -> Result<(), git2::Error> {
+    let refspecs: Vec<_> = globs
+        .iter()
+        .map(|glob| format!("+{0}:{0}", glob.as_ref()))
+        .collect();
+    crate::git::fetch_refspecs(&repository.repo, &refspecs)
+}
+
+fn default_ignored_dirs() -> Vec<String> {
+    let ignored = crate::processing::file_sys::Make::IGNORED_DIRS;
+    ignored.iter().map(|x| x.to_string()).collect()
+}
+
 impl PreProcessedRepositories {
     pub fn purge_caches(&mut self) {
         self.processor.purge_caches()
     }
 
+    /// Reclaims the subtrees and labels unreachable from the processed commits accepted by `keep`.
+    ///
+    /// Rejected commits are forgotten and the caches of all processors are cleared,
+    /// so commits processed afterward are built again, but deduplicated against the remaining subtrees.
+    /// Nodes and labels kept outside of the processors must be discarded,
+    /// labels get new identifiers.
+    pub fn collect_garbage(
+        &mut self,
+        mut keep: impl FnMut(&git2::Oid, &Commit) -> bool,
+    ) -> hyperast::store::nodes::legion::gc::GcStats {
+        let mut roots = vec![];
+        for processor in self.processor.processing_systems.values_mut() {
+            processor.for_each_mut(&mut |proc| {
+                proc.retain_commits(&mut keep);
+                proc.clear_caches();
+                roots.extend(proc.commits().map(|(_, commit)| commit.ast_root));
+            });
+        }
+        // the persisted nodes and labels do not match the stores anymore,
+        // the next snapshot is written from scratch
+        #[cfg(feature = "persist")]
+        {
+            self.snapshot = Default::default();
+        }
+        hyperast::store::nodes::legion::gc::collect(&mut self.processor.main_stores, roots)
+    }
+
     pub fn commit_count(&self, config: &ParametrizedCommitProcessorHandle) -> usize {
         let proc = self
             .processor
@@ -130,6 +193,29 @@ impl PreProcessedRepositories {
     }
 
     pub fn register_config(&mut self, repo: Repo, config: RepoConfig) -> ConfiguredRepoHandle2 {
+        self.register_config_aux(repo, config, Default::default())
+    }
+
+    /// Only process the slice of the repository selected by `filter`,
+    /// available with [`RepoConfig::JavaMaven`] and [`RepoConfig::CppMake`].
+    pub fn register_config_with_filter(
+        &mut self,
+        repo: Repo,
+        config: RepoConfig,
+        filter: crate::path_filter::PathFilter,
+    ) -> Result<ConfiguredRepoHandle2, String> {
+        if !filter.is_identity() && !matches!(config, RepoConfig::JavaMaven | RepoConfig::CppMake) {
+            return Err(format!("no path filters on {config:?} repositories"));
+        }
+        Ok(self.register_config_aux(repo, config, filter))
+    }
+
+    fn register_config_aux(
+        &mut self,
+        repo: Repo,
+        config: RepoConfig,
+        filter: crate::path_filter::PathFilter,
+    ) -> ConfiguredRepoHandle2 {
         use crate::processing::erased::Parametrized;
         let r = match config {
             RepoConfig::JavaMaven => {
@@ -146,13 +232,21 @@ impl PreProcessedRepositories {
                 let config = h.register_param(crate::maven_processor::Parameter {
                     java_handle,
                     pom_handle,
+                    filter,
                 });
                 ConfiguredRepoHandle2 { spec: repo, config }
             }
+            RepoConfig::JavaGradle => {
+                let t = crate::java_processor::Parameter::faster();
+                let config = self.register_gradle_param(t);
+                ConfiguredRepoHandle2 { spec: repo, config }
+            }
             RepoConfig::CppMake => {
                 let q: &[&str] = &["(translation_unit)"];
                 let t = crate::cpp_processor::Parameter {
                     query: Some(q.into()),
+                    tsg: None,
+                    prepro: None,
                 };
                 let h_cpp = self
                     .processor
@@ -163,22 +257,106 @@ impl PreProcessedRepositories {
                     .processor
                     .processing_systems
                     .mut_or_default::<crate::make_processor::MakeProcessorHolder>();
-                let config = h.register_param(crate::make_processor::Parameter { cpp_handle });
+                let config = h.register_param(crate::make_processor::Parameter {
+                    cpp_handle,
+                    filter,
+                    ignored_dirs: default_ignored_dirs(),
+                });
+                ConfiguredRepoHandle2 { spec: repo, config }
+            }
+            RepoConfig::PythonPip => {
+                let q: &[&str] = &["(module)"];
+                let t = crate::python_processor::Parameter {
+                    query: Some(q.into()),
+                };
+                let h_python = self
+                    .processor
+                    .processing_systems
+                    .mut_or_default::<crate::python_processor::PythonProcessorHolder>();
+                let python_handle =
+                    crate::processing::erased::CommitProcExt::register_param(h_python, t);
+                let h = self
+                    .processor
+                    .processing_systems
+                    .mut_or_default::<crate::pip_processor::PipProcessorHolder>();
+                let config = h.register_param(crate::pip_processor::Parameter { python_handle });
+                ConfiguredRepoHandle2 { spec: repo, config }
+            }
+            RepoConfig::TsNpm => {
+                let t = crate::ts_processor::Parameter {};
+                let h_ts = self
+                    .processor
+                    .processing_systems
+                    .mut_or_default::<crate::ts_processor::TsProcessorHolder>();
+                let ts_handle = crate::processing::erased::CommitProcExt::register_param(h_ts, t);
+                let h = self
+                    .processor
+                    .processing_systems
+                    .mut_or_default::<crate::npm_processor::NpmProcessorHolder>();
+                let config = h.register_param(crate::npm_processor::Parameter { ts_handle });
+                ConfiguredRepoHandle2 { spec: repo, config }
+            }
+            RepoConfig::Any => {
+                let cpp: &[&str] = &["(translation_unit)"];
+                let python: &[&str] = &["(module)"];
+                let config = self.register_any_param(Some(cpp.into()), Some(python.into()));
                 ConfiguredRepoHandle2 { spec: repo, config }
             }
-            _ => todo!(),
         };
 
         self.configs.insert(r.spec.clone(), r.config);
         r
     }
 
+    /// Skips the directories named by `ignored_dirs` instead of
+    /// [`crate::processing::file_sys::Make::IGNORED_DIRS`], only available with [`RepoConfig::CppMake`]
+    pub fn register_config_with_ignored_dirs(
+        &mut self,
+        repo: Repo,
+        config: RepoConfig,
+        ignored_dirs: &[&str],
+    ) -> Result<ConfiguredRepoHandle2, String> {
+        use crate::processing::erased::Parametrized;
+        let RepoConfig::CppMake = config else {
+            return Err(format!("no ignored directories on {config:?} repositories"));
+        };
+        let q: &[&str] = &["(translation_unit)"];
+        let t = crate::cpp_processor::Parameter {
+            query: Some(q.into()),
+            tsg: None,
+            prepro: None,
+        };
+        let h_cpp = self
+            .processor
+            .processing_systems
+            .mut_or_default::<crate::cpp_processor::CppProcessorHolder>();
+        let cpp_handle = crate::processing::erased::CommitProcExt::register_param(h_cpp, t);
+        let h = self
+            .processor
+            .processing_systems
+            .mut_or_default::<crate::make_processor::MakeProcessorHolder>();
+        let config = h.register_param(crate::make_processor::Parameter {
+            cpp_handle,
+            filter: Default::default(),
+            ignored_dirs: ignored_dirs.iter().map(|x| x.to_string()).collect(),
+        });
+        let r = ConfiguredRepoHandle2 { spec: repo, config };
+        self.configs.insert(r.spec.clone(), r.config);
+        Ok(r)
+    }
+
+    /// Runs the Lua `prepro` script on the java files of maven and gradle projects
+    /// and on the c++ files of make projects.
+    ///
+    /// Not supported yet, and deferred: the typescript, python and xml generators cannot run scripts,
+    /// so `TsNpm`, `PythonPip` and `Any` configs return an error
+    /// and the pom.xml files of maven projects are processed without the script.
     pub fn register_config_with_prepro(
         &mut self,
         repo: Repo,
         config: RepoConfig,
         prepro: std::sync::Arc<str>,
-    ) -> ConfiguredRepoHandle2 {
+    ) -> Result<ConfiguredRepoHandle2, String> {
         use crate::processing::erased::Parametrized;
         let r = match config {
             RepoConfig::JavaMaven => {
@@ -204,11 +382,25 @@ impl PreProcessedRepositories {
                     config: h.register_param(crate::maven_processor::Parameter {
                         java_handle,
                         pom_handle,
+                        filter: Default::default(),
                     }),
                 }
             }
+            RepoConfig::JavaGradle => {
+                let t = crate::java_processor::Parameter {
+                    prepro: Some(prepro),
+                    query: None,
+                    tsg: None,
+                };
+                let config = self.register_gradle_param(t);
+                ConfiguredRepoHandle2 { spec: repo, config }
+            }
             RepoConfig::CppMake => {
-                let t = crate::cpp_processor::Parameter { query: None };
+                let t = crate::cpp_processor::Parameter {
+                    query: None,
+                    tsg: None,
+                    prepro: Some(prepro),
+                };
                 let h_cpp = self
                     .processor
                     .processing_systems
@@ -218,13 +410,22 @@ impl PreProcessedRepositories {
                     .processor
                     .processing_systems
                     .mut_or_default::<crate::make_processor::MakeProcessorHolder>();
-                let config = h.register_param(crate::make_processor::Parameter { cpp_handle });
+                let config = h.register_param(crate::make_processor::Parameter {
+                    cpp_handle,
+                    filter: Default::default(),
+                    ignored_dirs: default_ignored_dirs(),
+                });
                 ConfiguredRepoHandle2 { spec: repo, config }
             }
-            _ => todo!(),
+            RepoConfig::PythonPip | RepoConfig::TsNpm | RepoConfig::Any => {
+                return Err(format!(
+                    "prepro scripts are not supported on {:?} repositories yet",
+                    config
+                ));
+            }
         };
         self.configs.insert(r.spec.clone(), r.config);
-        r
+        Ok(r)
     }
 
     pub fn register_config_with_prequeries(
@@ -258,12 +459,24 @@ impl PreProcessedRepositories {
                     config: h.register_param(crate::maven_processor::Parameter {
                         java_handle,
                         pom_handle,
+                        filter: Default::default(),
                     }),
                 }
             }
+            RepoConfig::JavaGradle => {
+                let t = crate::java_processor::Parameter {
+                    prepro: None,
+                    query: Some(query.into()),
+                    tsg: None,
+                };
+                let config = self.register_gradle_param(t);
+                ConfiguredRepoHandle2 { spec: repo, config }
+            }
             RepoConfig::CppMake => {
                 let t = crate::cpp_processor::Parameter {
                     query: Some(query.into()),
+                    tsg: None,
+                    prepro: None,
                 };
                 let h_cpp = self
                     .processor
@@ -274,21 +487,69 @@ impl PreProcessedRepositories {
                     .processor
                     .processing_systems
                     .mut_or_default::<crate::make_processor::MakeProcessorHolder>();
-                let config = h.register_param(crate::make_processor::Parameter { cpp_handle });
+                let config = h.register_param(crate::make_processor::Parameter {
+                    cpp_handle,
+                    filter: Default::default(),
+                    ignored_dirs: default_ignored_dirs(),
+                });
+                ConfiguredRepoHandle2 { spec: repo, config }
+            }
+            RepoConfig::PythonPip => {
+                let t = crate::python_processor::Parameter {
+                    query: Some(query.into()),
+                };
+                let h_python = self
+                    .processor
+                    .processing_systems
+                    .mut_or_default::<crate::python_processor::PythonProcessorHolder>();
+                let python_handle =
+                    crate::processing::erased::CommitProcExt::register_param(h_python, t);
+                let h = self
+                    .processor
+                    .processing_systems
+                    .mut_or_default::<crate::pip_processor::PipProcessorHolder>();
+                let config = h.register_param(crate::pip_processor::Parameter { python_handle });
+                ConfiguredRepoHandle2 { spec: repo, config }
+            }
+            RepoConfig::TsNpm => {
+                // the ts generator cannot precompute queries yet
+                log::warn!("ignored precomputed queries for {}", repo);
+                let t = crate::ts_processor::Parameter {};
+                let h_ts = self
+                    .processor
+                    .processing_systems
+                    .mut_or_default::<crate::ts_processor::TsProcessorHolder>();
+                let ts_handle = crate::processing::erased::CommitProcExt::register_param(h_ts, t);
+                let h = self
+                    .processor
+                    .processing_systems
+                    .mut_or_default::<crate::npm_processor::NpmProcessorHolder>();
+                let config = h.register_param(crate::npm_processor::Parameter { ts_handle });
+                ConfiguredRepoHandle2 { spec: repo, config }
+            }
+            RepoConfig::Any => {
+                // queries are specific to a language, files are not
+                log::warn!("ignored precomputed queries for {}", repo);
+                let config = self.register_any_param(None, None);
                 ConfiguredRepoHandle2 { spec: repo, config }
             }
-            _ => todo!(),
         };
         self.configs.insert(r.spec.clone(), r.config);
         r
     }
 
+    /// Overlays the tree-sitter-graph `tsg` on the java files of maven and gradle projects
+    /// and on the c++ files of make projects.
+    ///
+    /// Not supported yet, and deferred: the typescript, python and xml generators cannot overlay graphs,
+    /// so `TsNpm`, `PythonPip` and `Any` configs return an error
+    /// and the pom.xml files of maven projects are processed without the overlay.
     pub fn register_config_with_tsg(
         &mut self,
         repo: Repo,
         config: RepoConfig,
         tsg: std::sync::Arc<str>,
-    ) -> ConfiguredRepoHandle2 {
+    ) -> Result<ConfiguredRepoHandle2, String> {
         use crate::processing::erased::Parametrized;
         let r = match config {
             RepoConfig::JavaMaven => {
@@ -312,16 +573,113 @@ impl PreProcessedRepositories {
                 let config = h.register_param(crate::maven_processor::Parameter {
                     java_handle,
                     pom_handle,
+                    filter: Default::default(),
                 });
                 ConfiguredRepoHandle2 { spec: repo, config }
             }
+            RepoConfig::JavaGradle => {
+                let t = crate::java_processor::Parameter {
+                    prepro: None,
+                    query: None,
+                    tsg: Some(tsg),
+                };
+                let config = self.register_gradle_param(t);
+                ConfiguredRepoHandle2 { spec: repo, config }
+            }
             RepoConfig::CppMake => {
-                unimplemented!()
+                let t = crate::cpp_processor::Parameter {
+                    query: None,
+                    tsg: Some(tsg),
+                    prepro: None,
+                };
+                let h_cpp = self
+                    .processor
+                    .processing_systems
+                    .mut_or_default::<crate::cpp_processor::CppProcessorHolder>();
+                let cpp_handle = crate::processing::erased::CommitProcExt::register_param(h_cpp, t);
+                let h = self
+                    .processor
+                    .processing_systems
+                    .mut_or_default::<crate::make_processor::MakeProcessorHolder>();
+                let config = h.register_param(crate::make_processor::Parameter {
+                    cpp_handle,
+                    filter: Default::default(),
+                    ignored_dirs: default_ignored_dirs(),
+                });
+                ConfiguredRepoHandle2 { spec: repo, config }
+            }
+            RepoConfig::PythonPip | RepoConfig::TsNpm | RepoConfig::Any => {
+                return Err(format!(
+                    "tsg overlays are not supported on {:?} repositories yet",
+                    config
+                ));
             }
-            _ => todo!(),
         };
         self.configs.insert(r.spec.clone(), r.config);
-        r
+        Ok(r)
+    }
+
+    /// Registers the java generator and the build scripts handler, then the processor of gradle projects.
+    fn register_gradle_param(
+        &mut self,
+        t: crate::java_processor::Parameter,
+    ) -> ParametrizedCommitProcessorHandle {
+        use crate::processing::erased::Parametrized;
+        let processor_map = &mut self.processor.processing_systems;
+        use crate::java_processor::JavaProcessorHolder;
+        let h_java = processor_map.mut_or_default::<JavaProcessorHolder>();
+        let java_handle = CommitProcExt::register_param(h_java, t);
+        use crate::gradle_processor::{GradleProcessorHolder, GradleScriptProcessorHolder};
+        let h_script = processor_map.mut_or_default::<GradleScriptProcessorHolder>();
+        let script_handle = CommitProcExt::register_param(
+            h_script,
+            crate::gradle_processor::GradleScriptParameter {},
+        );
+        let h = processor_map.mut_or_default::<GradleProcessorHolder>();
+        h.register_param(crate::gradle_processor::Parameter {
+            java_handle,
+            script_handle,
+        })
+    }
+
+    /// Registers the generators of the enabled languages, then the processor dispatching files to them.
+    fn register_any_param(
+        &mut self,
+        cpp_query: Option<hyperast_tsquery::ZeroSepArrayStr>,
+        python_query: Option<hyperast_tsquery::ZeroSepArrayStr>,
+    ) -> ParametrizedCommitProcessorHandle {
+        use crate::processing::erased::Parametrized;
+        let processor_map = &mut self.processor.processing_systems;
+        let t = crate::cpp_processor::Parameter {
+            query: cpp_query,
+            tsg: None,
+            prepro: None,
+        };
+        let h_cpp = processor_map.mut_or_default::<crate::cpp_processor::CppProcessorHolder>();
+        let cpp_handle = CommitProcExt::register_param(h_cpp, t);
+        let t = crate::python_processor::Parameter {
+            query: python_query,
+        };
+        let h_python =
+            processor_map.mut_or_default::<crate::python_processor::PythonProcessorHolder>();
+        let python_handle = CommitProcExt::register_param(h_python, t);
+        let t = crate::ts_processor::Parameter {};
+        let h_ts = processor_map.mut_or_default::<crate::ts_processor::TsProcessorHolder>();
+        let ts_handle = CommitProcExt::register_param(h_ts, t);
+        let t = crate::java_processor::Parameter::faster();
+        let h_java = processor_map.mut_or_default::<crate::java_processor::JavaProcessorHolder>();
+        let java_handle = CommitProcExt::register_param(h_java, t);
+        let t = crate::maven_processor::PomParameter {};
+        let h_xml = processor_map.mut_or_default::<crate::maven_processor::PomProcessorHolder>();
+        let xml_handle = CommitProcExt::register_param(h_xml, t);
+        let h = processor_map.mut_or_default::<crate::any_processor::AnyProcessorHolder>();
+        h.register_param(crate::any_processor::Parameter {
+            java_handle,
+            xml_handle,
+            cpp_handle,
+            python_handle,
+            ts_handle,
+        })
     }
 
     pub fn get_config(&self, repo: Repo) -> Option<ConfiguredRepoHandle2> {
@@ -358,7 +716,7 @@ impl PreProcessedRepositories {
         rw: &mut impl Iterator<Item = git2::Oid>,
         repository: &ConfiguredRepo2,
         size: usize,
-    ) -> Vec<git2::Oid> {
+    ) -> Result<Vec<git2::Oid>, git2::Error> {
         self.processor.pre_pro(rw, repository, size)
     }
 
@@ -374,6 +732,79 @@ impl PreProcessedRepositories {
             .pre_process_with_limit(repository, before, after, limit)
     }
 
+    /// Preprocesses the commit graph reachable from the refs matching `globs`,
+    /// e.g. [`DEFAULT_REF_GLOBS`], including the commits only reachable through merges,
+    /// after fetching these refs from the remote of the repository, if any.
+    ///
+    /// Parents are processed before their children, already processed commits are skipped,
+    /// at most `limit` commits are processed, see [`Self::unprocessed_from_refs`] for the remaining ones.
+    pub fn pre_process_refs(
+        &mut self,
+        repository: &ConfiguredRepo2,
+        globs: &[impl AsRef<str>],
+        limit: usize,
+    ) -> Result<Vec<git2::Oid>, git2::Error> {
+        fetch_refs(repository, globs)?;
+        let new = self.unprocessed_from_refs(repository, globs)?;
+        log::info!(
+            "commits to process in {}: {}",
+            repository.spec.url(),
+            new.len()
+        );
+        self.pre_process_chunk(&mut new.into_iter(), repository, limit)
+    }
+
+    /// The commits reachable from the refs matching `globs` that are not processed yet, parents first
+    pub fn unprocessed_from_refs(
+        &self,
+        repository: &ConfiguredRepo2,
+        globs: &[impl AsRef<str>],
+    ) -> Result<Vec<git2::Oid>, git2::Error> {
+        let mut rw = crate::git::Builder::new(&repository.repo)?.parents_first()?;
+        for glob in globs {
+            rw = rw.refs(glob.as_ref())?;
+        }
+        let mut new = vec![];
+        for oid in rw.walk()? {
+            let oid = oid?;
+            if self.get_commit(&repository.config, &oid).is_none() {
+                new.push(oid);
+            }
+        }
+        Ok(new)
+    }
+
+    /// The refs matching `globs` with the root of their commit if it is processed
+    pub fn refs(
+        &self,
+        repository: &ConfiguredRepo2,
+        globs: &[impl AsRef<str>],
+    ) -> Result<Vec<ProcessedRef>, git2::Error> {
+        let mut refs = vec![];
+        for glob in globs {
+            for r in repository.repo.references_glob(glob.as_ref())? {
+                let r = r?;
+                let Some(name) = r.name() else {
+                    continue;
+                };
+                // e.g. tags of trees
+                let Ok(commit) = r.peel_to_commit() else {
+                    continue;
+                };
+                let commit = commit.id();
+                let ast_root = self
+                    .get_commit(&repository.config, &commit)
+                    .map(|c| c.ast_root);
+                refs.push(ProcessedRef {
+                    name: name.to_string(),
+                    commit,
+                    ast_root,
+                });
+            }
+        }
+        Ok(refs)
+    }
+
     pub fn ensure_pre_processed_with_limit(
         &self,
         repository: &ConfiguredRepo2,
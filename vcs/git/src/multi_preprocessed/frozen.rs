@@ -0,0 +1,58 @@
+//! Freezes preprocessed repositories into a memory-mapped file,
+//! so that other processes can traverse them without preprocessing again.
+//!
+//! Each processed commit of the configured repositories is a root named by its oid.
+use std::{io, path::Path};
+
+use hyperast::store::nodes::frozen::{self, FreezeStats, FrozenStores, Langs};
+
+use super::PreProcessedRepositories;
+use crate::TStore;
+
+/// The languages produced by the enabled processors
+pub fn langs() -> Langs {
+    let mut l = Langs::new();
+    #[cfg(feature = "java")]
+    l.register::<hyperast_gen_ts_java::types::Lang>();
+    #[cfg(feature = "c")]
+    l.register::<hyperast_gen_ts_c::types::Lang>();
+    #[cfg(feature = "cpp")]
+    l.register::<hyperast_gen_ts_cpp::types::Lang>();
+    #[cfg(any(feature = "maven", feature = "gradle", feature = "any"))]
+    l.register::<hyperast_gen_ts_xml::types::Lang>();
+    #[cfg(feature = "python")]
+    l.register::<hyperast_gen_ts_python::types::Lang>();
+    #[cfg(feature = "ts")]
+    l.register::<hyperast_gen_ts_ts::types::Lang>();
+    l
+}
+
+impl PreProcessedRepositories {
+    /// Writes the processed commits of all configured repositories to `path`
+    pub fn freeze(&self, path: &Path) -> io::Result<FreezeStats> {
+        let mut roots = vec![];
+        for (repo, handle) in &self.configs {
+            let proc = self
+                .processor
+                .processing_systems
+                .by_id(&handle.0)
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("no processor for the config of {repo}"),
+                    )
+                })?
+                .get(handle.1);
+            for (oid, commit) in proc.commits() {
+                roots.push((oid.to_string(), commit.ast_root));
+            }
+        }
+        frozen::freeze(&self.processor.main_stores, &langs(), roots, path)
+    }
+}
+
+/// Maps the frozen repositories at `path`, commits are resolved with
+/// [`frozen::NodeStore::root`] given their oid
+pub fn open(path: &Path) -> io::Result<FrozenStores<TStore>> {
+    frozen::open(path, &langs())
+}
@@ -0,0 +1,164 @@
+//! Persists preprocessed repositories between runs.
+//!
+//! The stores are appended to a [`Snapshot`] and the processed commits of each configured repository
+//! are written in `commits.bin`, referring to their root by its index in the snapshot.
+use std::{
+    collections::HashMap,
+    io::{self, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use hyperast::store::nodes::legion::persist::{
+    FORMAT_VERSION, Reader, Registry, SegmentStats, Snapshot, Writer,
+};
+
+use super::PreProcessedRepositories;
+use crate::Commit;
+
+const COMMITS_MAGIC: &[u8; 8] = b"HASTCMTS";
+const COMMITS_FILE: &str = "commits.bin";
+
+/// The components produced by the enabled processors
+pub fn registry() -> Registry {
+    let mut r = Registry::with_defaults();
+    #[cfg(feature = "java")]
+    r.register_type::<hyperast_gen_ts_java::types::Lang>();
+    #[cfg(feature = "c")]
+    r.register_type::<hyperast_gen_ts_c::types::Lang>();
+    #[cfg(feature = "cpp")]
+    r.register_type::<hyperast_gen_ts_cpp::types::Lang>();
+    #[cfg(any(feature = "maven", feature = "gradle", feature = "any"))]
+    r.register_type::<hyperast_gen_ts_xml::types::Lang>();
+    #[cfg(feature = "python")]
+    r.register_type::<hyperast_gen_ts_python::types::Lang>();
+    #[cfg(feature = "ts")]
+    r.register_type::<hyperast_gen_ts_ts::types::Lang>();
+    r
+}
+
+fn invalid_data(e: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+impl PreProcessedRepositories {
+    /// Appends the subtrees processed since the last snapshot to `dir`,
+    /// then rewrites the processed commits of all configured repositories.
+    pub fn snapshot(&mut self, dir: &Path) -> io::Result<SegmentStats> {
+        let stats = self
+            .snapshot
+            .append(dir, &registry(), &self.processor.main_stores)?;
+
+        let path = dir.join(COMMITS_FILE);
+        let tmp = path.with_extension("tmp");
+        let mut out = BufWriter::new(std::fs::File::create(&tmp)?);
+        out.write_all(COMMITS_MAGIC)?;
+        out.write_all(&FORMAT_VERSION.to_le_bytes())?;
+        let mut w = Writer::new(&mut out, &self.snapshot);
+        w.u32(self.configs.len() as u32)?;
+        for (repo, handle) in &self.configs {
+            let proc = self
+                .processor
+                .processing_systems
+                .by_id(&handle.0)
+                .unwrap()
+                .get(handle.1);
+            w.str(&repo.url())?;
+            w.u32(proc.commits().count() as u32)?;
+            for (oid, commit) in proc.commits() {
+                w.bytes(oid.as_bytes())?;
+                w.bytes(commit.tree_oid.as_bytes())?;
+                w.u32(commit.parents.len() as u32)?;
+                for p in &commit.parents {
+                    w.bytes(p.as_bytes())?;
+                }
+                w.node(&commit.ast_root)?;
+                w.u64(commit.processing_time as u64)?;
+                w.i64(commit.memory_used.bytes() as i64)?;
+            }
+        }
+        out.flush()?;
+        drop(out);
+        std::fs::rename(&tmp, &path)?;
+        Ok(stats)
+    }
+
+    /// Restores the stores and the processed commits persisted in `dir`.
+    ///
+    /// Must be called after registering the configs and before processing anything,
+    /// commits of repositories that are not configured are skipped.
+    /// Returns the number of restored commits.
+    pub fn restore(&mut self, dir: &Path) -> io::Result<usize> {
+        self.snapshot = Snapshot::restore(dir, &registry(), &mut self.processor.main_stores)?;
+
+        let path = dir.join(COMMITS_FILE);
+        if !path.exists() {
+            return Ok(0);
+        }
+        let mut input = BufReader::new(std::fs::File::open(&path)?);
+        let mut r = Reader::new(&mut input, &self.snapshot);
+        let mut magic = [0; 8];
+        for b in &mut magic {
+            *b = r.u8()?;
+        }
+        if &magic != COMMITS_MAGIC {
+            return Err(invalid_data("not a hyperast commits file"));
+        }
+        let version = r.u32()?;
+        if version != FORMAT_VERSION {
+            return Err(invalid_data(format!(
+                "unsupported format version {}, expected {}",
+                version, FORMAT_VERSION
+            )));
+        }
+        let oid = |r: &mut Reader<'_>| git2::Oid::from_bytes(&r.bytes()?).map_err(invalid_data);
+        let mut persisted: HashMap<String, Vec<(git2::Oid, Commit)>> = Default::default();
+        for _ in 0..r.u32()? {
+            let url = r.string()?;
+            let count = r.u32()?;
+            let mut commits = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                let commit_oid = oid(&mut r)?;
+                let tree_oid = oid(&mut r)?;
+                let parents = (0..r.u32()?)
+                    .map(|_| oid(&mut r))
+                    .collect::<io::Result<_>>()?;
+                let ast_root = r.node()?;
+                let processing_time = r.u64()? as u128;
+                let memory_used = (r.i64()? as isize).into();
+                commits.push((
+                    commit_oid,
+                    Commit {
+                        parents,
+                        processing_time,
+                        memory_used,
+                        ast_root,
+                        tree_oid,
+                    },
+                ));
+            }
+            persisted.insert(url, commits);
+        }
+
+        let mut restored = 0;
+        for (repo, handle) in &self.configs {
+            let Some(commits) = persisted.remove(&repo.url()) else {
+                continue;
+            };
+            let proc = self
+                .processor
+                .processing_systems
+                .by_id_mut(&handle.0)
+                .unwrap()
+                .get_mut(handle.1);
+            for (oid, commit) in commits {
+                proc.restore_commit(oid, commit);
+                restored += 1;
+            }
+        }
+        for url in persisted.keys() {
+            log::warn!("skipped commits of {url}, the repository is not configured");
+        }
+        log::info!("restored {restored} commits");
+        Ok(restored)
+    }
+}
@@ -111,6 +111,12 @@ impl<'a, T> types::WithStats for NoSpaceWrapper<'a, T> {
     }
 }
 
+impl<'a, T, C: 'static + Send + Sync> types::WithMetaData<C> for NoSpaceWrapper<'a, T> {
+    fn get_metadata(&self) -> Option<&C> {
+        types::WithMetaData::<C>::get_metadata(&self.0)
+    }
+}
+
 impl<'a, T> types::WithSerialization for NoSpaceWrapper<'a, T> {
     /// WARN return the len with spaces ? YES
     fn try_bytes_len(&self) -> Option<usize> {
@@ -0,0 +1,164 @@
+use crate::processing::erased::{
+    CommitProcessorHandle, ParametrizedCommitProc2, ParametrizedCommitProcessor2Handle,
+};
+use crate::{
+    preprocessed::RepositoryProcessor,
+    processing::{ObjectName, ParametrizedCommitProcessorHandle},
+    ts_processor::TsProc,
+};
+use std::path::PathBuf;
+
+// # Npm
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Parameter {
+    pub(crate) ts_handle: ParametrizedCommitProcessor2Handle<TsProc>,
+}
+
+#[derive(Default)]
+pub(crate) struct NpmProcessorHolder(Option<NpmProc>);
+pub(crate) struct NpmProc {
+    parameter: Parameter,
+    commits: std::collections::HashMap<git2::Oid, crate::Commit>,
+}
+impl crate::processing::erased::Parametrized for NpmProcessorHolder {
+    type T = Parameter;
+    fn register_param(
+        &mut self,
+        t: Self::T,
+    ) -> crate::processing::erased::ParametrizedCommitProcessorHandle {
+        let l = self
+            .0
+            .iter()
+            .position(|x| &x.parameter == &t)
+            .unwrap_or_else(|| {
+                let l = 0; //self.0.len();
+                // TODO enable multi configs for npm, do the same as the one for Java
+                self.0 = Some(NpmProc {
+                    parameter: t,
+                    commits: Default::default(),
+                });
+                l
+            });
+        use crate::processing::erased::ConfigParametersHandle;
+        use crate::processing::erased::ParametrizedCommitProc;
+        ParametrizedCommitProcessorHandle(self.erased_handle(), ConfigParametersHandle(l))
+    }
+}
+
+struct PreparedNpmCommitProc<'repo> {
+    repository: &'repo git2::Repository,
+    commit_builder: crate::preprocessed::CommitBuilder,
+    pub(crate) handle: ParametrizedCommitProcessorHandle,
+}
+impl<'repo> crate::processing::erased::PreparedCommitProc for PreparedNpmCommitProc<'repo> {
+    fn process(
+        self: Box<PreparedNpmCommitProc<'repo>>,
+        prepro: &mut RepositoryProcessor,
+    ) -> hyperast::store::defaults::NodeIdentifier {
+        let dir_path = PathBuf::from("");
+        let mut dir_path = dir_path.components().peekable();
+        let name = ObjectName::from(b"");
+        let ts_handle = prepro
+            .processing_systems
+            .mut_or_default::<NpmProcessorHolder>()
+            .with_parameters(self.handle.1)
+            .parameter
+            .ts_handle;
+        // package.json is not parsed yet, so workspaces are not distinguished,
+        // the whole tree is processed as sources, except the installed node_modules
+        let (root_full_node,) = prepro.handle_ts_directory(
+            self.repository,
+            &mut dir_path,
+            &name,
+            self.commit_builder.tree_oid(),
+            ts_handle,
+        );
+        let h = prepro
+            .processing_systems
+            .mut_or_default::<NpmProcessorHolder>();
+        let handle = self.handle;
+        let commit_oid = self.commit_builder.commit_oid();
+        let commit = self.commit_builder.finish(root_full_node.compressed_node);
+        h.with_parameters_mut(handle.1)
+            .commits
+            .insert(commit_oid, commit);
+        root_full_node.compressed_node
+    }
+}
+
+impl crate::processing::erased::CommitProc for NpmProc {
+    fn prepare_processing<'repo>(
+        &self,
+        repository: &'repo git2::Repository,
+        commit_builder: crate::preprocessed::CommitBuilder,
+        handle: crate::processing::ParametrizedCommitProcessorHandle,
+    ) -> Box<dyn crate::processing::erased::PreparedCommitProc + 'repo> {
+        Box::new(PreparedNpmCommitProc {
+            repository,
+            commit_builder,
+            handle,
+        })
+    }
+
+    fn get_commit(&self, commit_oid: git2::Oid) -> Option<&crate::Commit> {
+        self.commits.get(&commit_oid)
+    }
+
+    fn commit_count(&self) -> usize {
+        self.commits.len()
+    }
+
+    fn commits(&self) -> Box<dyn Iterator<Item = (&git2::Oid, &crate::Commit)> + '_> {
+        Box::new(self.commits.iter())
+    }
+
+    fn restore_commit(&mut self, commit_oid: git2::Oid, commit: crate::Commit) {
+        self.commits.insert(commit_oid, commit);
+    }
+
+    fn retain_commits(&mut self, keep: &mut dyn FnMut(&git2::Oid, &crate::Commit) -> bool) {
+        self.commits.retain(|oid, commit| keep(oid, commit));
+    }
+
+    fn get_lang_handle(&self, lang: &str) -> Option<ParametrizedCommitProcessorHandle> {
+        if lang.eq_ignore_ascii_case("ts") || lang.eq_ignore_ascii_case("typescript") {
+            Some(ParametrizedCommitProcessorHandle(
+                CommitProcessorHandle(
+                    std::any::TypeId::of::<crate::ts_processor::TsProcessorHolder>(),
+                ),
+                self.parameter.ts_handle.0,
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+impl crate::processing::erased::CommitProcExt for NpmProc {
+    type Holder = NpmProcessorHolder;
+}
+
+impl crate::processing::erased::ParametrizedCommitProc2 for NpmProcessorHolder {
+    type Proc = NpmProc;
+
+    fn with_parameters_mut(
+        &mut self,
+        parameters: crate::processing::erased::ConfigParametersHandle,
+    ) -> &mut Self::Proc {
+        assert_eq!(0, parameters.0);
+        self.0.as_mut().unwrap()
+    }
+
+    fn with_parameters(
+        &self,
+        parameters: crate::processing::erased::ConfigParametersHandle,
+    ) -> &Self::Proc {
+        assert_eq!(0, parameters.0);
+        self.0.as_ref().unwrap()
+    }
+
+    fn all_parameters_mut(&mut self) -> Box<dyn Iterator<Item = &mut Self::Proc> + '_> {
+        Box::new(self.0.iter_mut())
+    }
+}
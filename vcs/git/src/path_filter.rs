@@ -0,0 +1,314 @@
+//! Restricts the processing of a repository to a slice of its tree,
+//! e.g. a few services of a monorepo, optionally including the content of its submodules.
+//!
+//! The filtered trees are written in memory, see [`write_in_memory`],
+//! unchanged subtrees keep their oids and still hit the caches of the processors.
+use git2::{FileMode, ObjectType, Oid, Repository};
+
+use crate::git::Repo;
+
+/// Include and exclude globs over the paths of a tree, relative to its root and separated by `/`.
+///
+/// `*` and `?` match inside a path segment, `**` matches any number of segments.
+/// A file is kept if it matches one of the `include` globs (or if there is none),
+/// and none of the `exclude` globs. A directory matching an `exclude` glob is skipped entirely.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, serde::Deserialize)]
+pub struct PathFilter {
+    #[serde(default)]
+    pub include: Vec<String>,
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Replace the submodules by the tree of their commit,
+    /// otherwise they are ignored like in a plain git tree.
+    #[serde(default)]
+    pub submodules: bool,
+}
+
+impl PathFilter {
+    pub fn include(mut self, glob: impl Into<String>) -> Self {
+        self.include.push(glob.into());
+        self
+    }
+    pub fn exclude(mut self, glob: impl Into<String>) -> Self {
+        self.exclude.push(glob.into());
+        self
+    }
+    pub fn with_submodules(mut self) -> Self {
+        self.submodules = true;
+        self
+    }
+
+    /// Nothing to filter, the tree of a commit can be used as is
+    pub fn is_identity(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty() && !self.submodules
+    }
+
+    pub fn is_file_kept(&self, path: &str) -> bool {
+        let path = segments(path);
+        (self.include.is_empty() || self.include.iter().any(|g| matches(&segments(g), &path)))
+            && !self.is_excluded(&path)
+    }
+
+    pub fn is_dir_explored(&self, path: &str) -> bool {
+        let path = segments(path);
+        (self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|g| may_contain(&segments(g), &path)))
+            && !self.is_excluded(&path)
+    }
+
+    fn is_excluded(&self, path: &[&str]) -> bool {
+        self.exclude.iter().any(|g| {
+            let g = segments(g);
+            matches(&g, path)
+                || g.split_last().is_some_and(|(l, g)| {
+                    *l == "**" && (0..path.len()).any(|i| matches(g, &path[..i]))
+                })
+        })
+    }
+}
+
+/// Parses the syntax of a query string, e.g. `include=services/payments/**&exclude=**/generated/**&submodules`
+impl std::str::FromStr for PathFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut r = Self::default();
+        for param in s.split('&').filter(|x| !x.is_empty()) {
+            match param.split_once('=') {
+                Some(("include", glob)) => r.include.push(glob.to_string()),
+                Some(("exclude", glob)) => r.exclude.push(glob.to_string()),
+                None if param == "submodules" => r.submodules = true,
+                _ => {
+                    return Err(format!(
+                        "'{}' is not a path filter, use include=<glob>, exclude=<glob> or submodules",
+                        param
+                    ));
+                }
+            }
+        }
+        Ok(r)
+    }
+}
+
+fn segments(path: &str) -> Vec<&str> {
+    path.split('/').filter(|x| !x.is_empty()).collect()
+}
+
+fn matches(glob: &[&str], path: &[&str]) -> bool {
+    match glob.split_first() {
+        None => path.is_empty(),
+        Some((&"**", glob)) => (0..=path.len()).any(|i| matches(glob, &path[i..])),
+        Some((g, glob)) => path.split_first().is_some_and(|(s, path)| {
+            matches_segment(g.as_bytes(), s.as_bytes()) && matches(glob, path)
+        }),
+    }
+}
+
+/// `dir` is a prefix of some path matched by `glob`
+fn may_contain(glob: &[&str], dir: &[&str]) -> bool {
+    match (glob.split_first(), dir.split_first()) {
+        (_, None) => true,
+        (None, Some(_)) => false,
+        (Some((&"**", _)), Some(_)) => true,
+        (Some((g, glob)), Some((s, dir))) => {
+            matches_segment(g.as_bytes(), s.as_bytes()) && may_contain(glob, dir)
+        }
+    }
+}
+
+pub(crate) fn matches_segment(glob: &[u8], s: &[u8]) -> bool {
+    match glob.split_first() {
+        None => s.is_empty(),
+        Some((b'*', glob)) => (0..=s.len()).any(|i| matches_segment(glob, &s[i..])),
+        Some((b'?', glob)) => !s.is_empty() && matches_segment(glob, &s[1..]),
+        Some((c, glob)) => s.first() == Some(c) && matches_segment(glob, &s[1..]),
+    }
+}
+
+/// Writes the tree of `tree_oid` restricted by `filter` in the object database of `repository`.
+///
+/// Returns `tree_oid` itself when nothing is filtered out.
+/// The objects of the submodules are only looked up in `repository` and in their clones made by [`Repo::fetch`],
+/// see [`Repo::fetched`], submodules that are missing are left out.
+pub fn filter_tree(
+    repository: &Repository,
+    tree_oid: Oid,
+    filter: &PathFilter,
+) -> Result<Oid, git2::Error> {
+    if filter.is_identity() {
+        return Ok(tree_oid);
+    }
+    let mut prefix = String::new();
+    match filter_subtree(repository, tree_oid, &mut prefix, filter, &[])? {
+        Some(oid) => Ok(oid),
+        None => {
+            log::warn!("nothing left in {} after filtering", tree_oid);
+            repository.treebuilder(None)?.write()
+        }
+    }
+}
+
+/// `None` if the whole subtree is filtered out
+fn filter_subtree(
+    repository: &Repository,
+    tree_oid: Oid,
+    prefix: &mut String,
+    filter: &PathFilter,
+    submodules: &[(String, String)],
+) -> Result<Option<Oid>, git2::Error> {
+    let tree = repository.find_tree(tree_oid)?;
+    let own_submodules;
+    let submodules = if filter.submodules && prefix.is_empty() {
+        own_submodules = read_gitmodules(repository, &tree);
+        &own_submodules[..]
+    } else {
+        submodules
+    };
+    let mut builder = repository.treebuilder(None)?;
+    let mut changed = false;
+    for entry in tree.iter() {
+        let Ok(name) = entry.name() else {
+            changed = true;
+            continue;
+        };
+        let len = prefix.len();
+        if !prefix.is_empty() {
+            prefix.push('/');
+        }
+        prefix.push_str(name);
+        let kept = match entry.kind() {
+            Some(ObjectType::Tree) if filter.is_dir_explored(prefix) => {
+                filter_subtree(repository, entry.id(), prefix, filter, submodules)?
+                    .map(|oid| (oid, i32::from(FileMode::Tree)))
+            }
+            Some(ObjectType::Blob) if filter.is_file_kept(prefix) => {
+                Some((entry.id(), entry.filemode()))
+            }
+            Some(ObjectType::Commit) if filter.submodules && filter.is_dir_explored(prefix) => {
+                submodule_tree(repository, entry.id(), prefix, submodules)?
+                    .map(|oid| filter_subtree_in_submodule(repository, oid, prefix, filter))
+                    .transpose()?
+                    .flatten()
+                    .map(|oid| (oid, i32::from(FileMode::Tree)))
+            }
+            Some(ObjectType::Commit) if filter.is_dir_explored(prefix) => {
+                Some((entry.id(), entry.filemode()))
+            }
+            _ => None,
+        };
+        prefix.truncate(len);
+        match kept {
+            Some((oid, mode)) => {
+                changed |= oid != entry.id() || mode != entry.filemode();
+                builder.insert(name, oid, mode)?;
+            }
+            None => changed = true,
+        }
+    }
+    if !changed {
+        Ok(Some(tree_oid))
+    } else if builder.len() == 0 {
+        Ok(None)
+    } else {
+        builder.write().map(Some)
+    }
+}
+
+/// The submodule can declare its own submodules
+fn filter_subtree_in_submodule(
+    repository: &Repository,
+    tree_oid: Oid,
+    prefix: &mut String,
+    filter: &PathFilter,
+) -> Result<Option<Oid>, git2::Error> {
+    let tree = repository.find_tree(tree_oid)?;
+    let submodules: Vec<_> = read_gitmodules(repository, &tree)
+        .into_iter()
+        .map(|(path, url)| (format!("{}/{}", prefix, path), url))
+        .collect();
+    filter_subtree(repository, tree_oid, prefix, filter, &submodules)
+}
+
+/// The `(path, url)` of the submodules declared in the .gitmodules file at the root of `tree`
+fn read_gitmodules(repository: &Repository, tree: &git2::Tree) -> Vec<(String, String)> {
+    let Some(entry) = tree.get_name(".gitmodules") else {
+        return vec![];
+    };
+    let Ok(blob) = repository.find_blob(entry.id()) else {
+        return vec![];
+    };
+    parse_gitmodules(&String::from_utf8_lossy(blob.content()))
+}
+
+pub(crate) fn parse_gitmodules(text: &str) -> Vec<(String, String)> {
+    let mut r = vec![];
+    let (mut path, mut url) = (None, None);
+    for line in text.lines().map(str::trim) {
+        if line.starts_with('[') {
+            if let (Some(path), Some(url)) = (path.take(), url.take()) {
+                r.push((path, url));
+            }
+        } else if let Some((k, v)) = line.split_once('=') {
+            let v = v.trim().trim_matches('"').to_string();
+            match k.trim() {
+                "path" => path = Some(v.trim_matches('/').to_string()),
+                "url" => url = Some(v),
+                _ => (),
+            }
+        }
+    }
+    if let (Some(path), Some(url)) = (path, url) {
+        r.push((path, url));
+    }
+    r
+}
+
+/// Makes the objects of the submodule available in `repository`,
+/// fetching it if needed, then returns the tree of `commit_oid`
+fn submodule_tree(
+    repository: &Repository,
+    commit_oid: Oid,
+    path: &str,
+    submodules: &[(String, String)],
+) -> Result<Option<Oid>, git2::Error> {
+    if let Ok(commit) = repository.find_commit(commit_oid) {
+        return Ok(Some(commit.tree_id()));
+    }
+    let Some((_, url)) = submodules.iter().find(|(p, _)| p == path) else {
+        log::warn!("submodule {} is not declared in .gitmodules", path);
+        return Ok(None);
+    };
+    let url = url.trim_end_matches(".git");
+    let repo: Repo = match url.parse() {
+        Ok(repo) => repo,
+        Err(e) => {
+            log::warn!("cannot resolve submodule {} at {}: {}", path, url, e);
+            return Ok(None);
+        }
+    };
+    let Some(sub) = repo.fetched() else {
+        log::warn!("submodule {} at {} was not fetched", path, url);
+        return Ok(None);
+    };
+    if sub.find_commit(commit_oid).is_err() {
+        log::warn!("commit {} of submodule {} is missing", commit_oid, path);
+        return Ok(None);
+    }
+    // only added to this handle of the repository, the alternates file is left untouched
+    let objects = sub.path().join("objects");
+    repository
+        .odb()?
+        .add_disk_alternate(&objects.to_string_lossy())?;
+    Ok(Some(repository.find_commit(commit_oid)?.tree_id()))
+}
+
+/// Makes the objects written afterward through this handle of `repository`, e.g. filtered trees,
+/// only live in memory, they are dropped with the handle.
+pub fn write_in_memory(repository: &Repository) -> Result<(), git2::Error> {
+    // above the loose and packed backends
+    repository.odb()?.add_new_mempack_backend(1000)?;
+    Ok(())
+}
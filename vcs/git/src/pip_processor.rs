@@ -0,0 +1,167 @@
+use crate::processing::erased::{
+    CommitProcessorHandle, ParametrizedCommitProc2, ParametrizedCommitProcessor2Handle,
+};
+use crate::{
+    preprocessed::RepositoryProcessor,
+    processing::{ObjectName, ParametrizedCommitProcessorHandle},
+    python_processor::PythonProc,
+};
+use std::path::PathBuf;
+
+// # Pip
+//
+// A pip project contains a setup.py, a setup.cfg or a pyproject.toml (often with a requirements.txt),
+// then python packages, i.e. directories with an __init__.py, often in src/ and tests/.
+// Only python files are processed, so setup.py is parsed as any other module,
+// while setup.cfg, pyproject.toml and requirements.txt are ignored.
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Parameter {
+    pub(crate) python_handle: ParametrizedCommitProcessor2Handle<PythonProc>,
+}
+
+#[derive(Default)]
+pub(crate) struct PipProcessorHolder(Option<PipProc>);
+pub(crate) struct PipProc {
+    parameter: Parameter,
+    commits: std::collections::HashMap<git2::Oid, crate::Commit>,
+}
+impl crate::processing::erased::Parametrized for PipProcessorHolder {
+    type T = Parameter;
+    fn register_param(
+        &mut self,
+        t: Self::T,
+    ) -> crate::processing::erased::ParametrizedCommitProcessorHandle {
+        let l = self
+            .0
+            .iter()
+            .position(|x| &x.parameter == &t)
+            .unwrap_or_else(|| {
+                let l = 0; //self.0.len();
+                // TODO enable multi configs for python, do the same as the one for Java
+                self.0 = Some(PipProc {
+                    parameter: t,
+                    commits: Default::default(),
+                });
+                l
+            });
+        use crate::processing::erased::ConfigParametersHandle;
+        use crate::processing::erased::ParametrizedCommitProc;
+        ParametrizedCommitProcessorHandle(self.erased_handle(), ConfigParametersHandle(l))
+    }
+}
+
+struct PreparedPipCommitProc<'repo> {
+    repository: &'repo git2::Repository,
+    commit_builder: crate::preprocessed::CommitBuilder,
+    pub(crate) handle: ParametrizedCommitProcessorHandle,
+}
+impl<'repo> crate::processing::erased::PreparedCommitProc for PreparedPipCommitProc<'repo> {
+    fn process(
+        self: Box<PreparedPipCommitProc<'repo>>,
+        prepro: &mut RepositoryProcessor,
+    ) -> hyperast::store::defaults::NodeIdentifier {
+        let dir_path = PathBuf::from("");
+        let mut dir_path = dir_path.components().peekable();
+        let name = ObjectName::from(b"");
+        let python_handle = prepro
+            .processing_systems
+            .mut_or_default::<PipProcessorHolder>()
+            .with_parameters(self.handle.1)
+            .parameter
+            .python_handle;
+        let (root_full_node,) = prepro.handle_python_directory(
+            self.repository,
+            &mut dir_path,
+            &name,
+            self.commit_builder.tree_oid(),
+            python_handle,
+        );
+        let h = prepro
+            .processing_systems
+            .mut_or_default::<PipProcessorHolder>();
+        let handle = self.handle;
+        let commit_oid = self.commit_builder.commit_oid();
+        let commit = self.commit_builder.finish(root_full_node.compressed_node);
+        h.with_parameters_mut(handle.1)
+            .commits
+            .insert(commit_oid, commit);
+        root_full_node.compressed_node
+    }
+}
+
+impl crate::processing::erased::CommitProc for PipProc {
+    fn prepare_processing<'repo>(
+        &self,
+        repository: &'repo git2::Repository,
+        commit_builder: crate::preprocessed::CommitBuilder,
+        handle: crate::processing::ParametrizedCommitProcessorHandle,
+    ) -> Box<dyn crate::processing::erased::PreparedCommitProc + 'repo> {
+        Box::new(PreparedPipCommitProc {
+            repository,
+            commit_builder,
+            handle,
+        })
+    }
+
+    fn get_commit(&self, commit_oid: git2::Oid) -> Option<&crate::Commit> {
+        self.commits.get(&commit_oid)
+    }
+
+    fn commit_count(&self) -> usize {
+        self.commits.len()
+    }
+
+    fn commits(&self) -> Box<dyn Iterator<Item = (&git2::Oid, &crate::Commit)> + '_> {
+        Box::new(self.commits.iter())
+    }
+
+    fn restore_commit(&mut self, commit_oid: git2::Oid, commit: crate::Commit) {
+        self.commits.insert(commit_oid, commit);
+    }
+
+    fn retain_commits(&mut self, keep: &mut dyn FnMut(&git2::Oid, &crate::Commit) -> bool) {
+        self.commits.retain(|oid, commit| keep(oid, commit));
+    }
+
+    fn get_lang_handle(&self, lang: &str) -> Option<ParametrizedCommitProcessorHandle> {
+        if lang.eq_ignore_ascii_case("python") {
+            Some(ParametrizedCommitProcessorHandle(
+                CommitProcessorHandle(std::any::TypeId::of::<
+                    crate::python_processor::PythonProcessorHolder,
+                >()),
+                self.parameter.python_handle.0,
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+impl crate::processing::erased::CommitProcExt for PipProc {
+    type Holder = PipProcessorHolder;
+}
+
+impl crate::processing::erased::ParametrizedCommitProc2 for PipProcessorHolder {
+    type Proc = PipProc;
+
+    fn with_parameters_mut(
+        &mut self,
+        parameters: crate::processing::erased::ConfigParametersHandle,
+    ) -> &mut Self::Proc {
+        assert_eq!(0, parameters.0);
+        self.0.as_mut().unwrap()
+    }
+
+    fn with_parameters(
+        &self,
+        parameters: crate::processing::erased::ConfigParametersHandle,
+    ) -> &Self::Proc {
+        assert_eq!(0, parameters.0);
+        self.0.as_ref().unwrap()
+    }
+
+    fn all_parameters_mut(&mut self) -> Box<dyn Iterator<Item = &mut Self::Proc> + '_> {
+        Box::new(self.0.iter_mut())
+    }
+}
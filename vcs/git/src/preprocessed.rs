@@ -141,8 +141,7 @@ impl RepositoryProcessor {
         );
         let rw = all_commits_between(&repository.repo, before, after)?;
         let mut rw = rw.map(|x| x.unwrap());
-        let r = self.pre_pro(&mut rw, repository, usize::MAX);
-        Ok(r)
+        self.pre_pro(&mut rw, repository, usize::MAX)
     }
 
     /// If `before` and `after` are unrelated then only one commit will be retrieved.
@@ -200,8 +199,7 @@ impl RepositoryProcessor {
         );
         let rw = all_commits_between(&repository.repo, before, after)?;
         let mut rw = rw.take(limit).map(|x| x.unwrap());
-        let r = self.pre_pro(&mut rw, repository, usize::MAX);
-        Ok(r)
+        self.pre_pro(&mut rw, repository, usize::MAX)
     }
 
     pub fn pre_pro(
@@ -209,22 +207,31 @@ impl RepositoryProcessor {
         rw: &mut impl Iterator<Item = git2::Oid>,
         repository: &ConfiguredRepo2,
         size: usize,
-    ) -> Vec<Oid> {
+    ) -> Result<Vec<Oid>, git2::Error> {
         let mut r = Vec::with_capacity(rw.size_hint().0);
+        let mut in_memory = false;
         for _ in 0..size {
             let Some(oid) = rw.next() else { break };
-            let builder = crate::preprocessed::CommitBuilder::start(&repository.repo, oid);
+            let mut builder = crate::preprocessed::CommitBuilder::start(&repository.repo, oid);
             let commit_processor = self
                 .processing_systems
                 .by_id_mut(&repository.config.0)
                 .unwrap()
                 .get_mut(repository.config.1);
+            if let Some(filter) = commit_processor.path_filter() {
+                if !filter.is_identity() && !in_memory {
+                    // filtered trees must not end up in the repository
+                    crate::path_filter::write_in_memory(&repository.repo)?;
+                    in_memory = true;
+                }
+                builder = builder.filter(&repository.repo, filter)?;
+            }
             let _id = commit_processor
                 .prepare_processing(&repository.repo, builder, repository.config)
                 .process(self);
             r.push(oid);
         }
-        r
+        Ok(r)
     }
 }
 
@@ -611,6 +618,16 @@ impl CommitBuilder {
         self.tree_oid
     }
 
+    /// Only process the part of the tree selected by `filter`
+    pub(crate) fn filter(
+        mut self,
+        repository: &Repository,
+        filter: &crate::path_filter::PathFilter,
+    ) -> Result<Self, git2::Error> {
+        self.tree_oid = crate::path_filter::filter_tree(repository, self.tree_oid, filter)?;
+        Ok(self)
+    }
+
     pub(crate) fn commit_oid(&self) -> git2::Oid {
         self.commit_oid
     }
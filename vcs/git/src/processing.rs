@@ -9,15 +9,19 @@ pub use erased::ParametrizedCommitProcessorHandle;
 
 pub enum BuildSystem {
     Maven,
+    Gradle,
     Make,
     Npm,
+    Pip,
     None,
 }
 
 pub enum ProcessingConfig<P> {
     JavaMaven { limit: usize, dir_path: P },
+    JavaGradle { limit: usize, dir_path: P },
     CppMake { limit: usize, dir_path: P },
     TsNpm { limit: usize, dir_path: P },
+    PythonPip { limit: usize, dir_path: P },
     Any { limit: usize, dir_path: P },
 }
 
@@ -27,7 +31,9 @@ pub enum ProcessingConfig<P> {
 pub enum RepoConfig {
     CppMake,
     JavaMaven,
+    JavaGradle,
     TsNpm,
+    PythonPip,
     Any,
 }
 
@@ -40,10 +46,14 @@ impl std::str::FromStr for RepoConfig {
             "cpp" => Self::CppMake,
             "Java" => Self::JavaMaven,
             "java" => Self::JavaMaven,
+            "Gradle" => Self::JavaGradle,
+            "gradle" => Self::JavaGradle,
             "typescript" => Self::TsNpm,
             "javascript" => Self::TsNpm,
             "Ts" => Self::TsNpm,
             "ts" => Self::TsNpm,
+            "Python" => Self::PythonPip,
+            "python" => Self::PythonPip,
             "any" => Self::Any,
             x => return Err(format!("'{}' is not anvailable config", x)),
         })
@@ -61,8 +71,22 @@ impl From<&RepoConfig> for ProcessingConfig<&'static str> {
                 limit: 3,
                 dir_path: "",
             },
-            RepoConfig::TsNpm => todo!(),
-            RepoConfig::Any => todo!(),
+            RepoConfig::JavaGradle => Self::JavaGradle {
+                limit: 3,
+                dir_path: "",
+            },
+            RepoConfig::TsNpm => Self::TsNpm {
+                limit: 3,
+                dir_path: "",
+            },
+            RepoConfig::PythonPip => Self::PythonPip {
+                limit: 3,
+                dir_path: "",
+            },
+            RepoConfig::Any => Self::Any {
+                limit: 3,
+                dir_path: "",
+            },
         }
     }
 }
@@ -136,6 +160,14 @@ impl ConfiguredRepoHandle2 {
             config: self.config,
         }
     }
+    /// see [`Repo::try_fetch`]
+    pub fn try_fetch(self) -> Result<ConfiguredRepo2, git2::Error> {
+        Ok(ConfiguredRepo2 {
+            repo: self.spec.try_fetch()?,
+            spec: self.spec,
+            config: self.config,
+        })
+    }
 }
 
 pub struct ConfiguredRepo {
@@ -299,6 +331,8 @@ pub(crate) mod caches {
     #[derive(Default)]
     pub struct Cpp {
         pub(crate) md_cache: hyperast_gen_ts_cpp::legion::MDCache,
+        /// Passed to subtree builder when deriving different data (assumed to be incompatible).
+        pub(crate) dedup: hyperast::store::nodes::legion::DedupMap,
         pub object_map: NamedMap<(hyperast_gen_ts_cpp::legion::Local,)>,
     }
 
@@ -316,6 +350,78 @@ pub(crate) mod caches {
         }
     }
 
+    #[cfg(feature = "c")]
+    #[derive(Default)]
+    pub struct C {
+        pub(crate) md_cache: hyperast_gen_ts_c::legion::MDCache,
+        pub object_map: NamedMap<(hyperast_gen_ts_c::legion::Local,)>,
+    }
+
+    #[cfg(feature = "c")]
+    impl super::ObjectMapper for C {
+        type K = (git2::Oid, ObjectName);
+
+        type V = (hyperast_gen_ts_c::legion::Local,);
+
+        fn get(&self, key: &Self::K) -> Option<&Self::V> {
+            self.object_map.get(key)
+        }
+
+        fn insert(&mut self, key: Self::K, value: Self::V) -> Option<Self::V> {
+            self.object_map.insert(key, value)
+        }
+    }
+
+    #[cfg(feature = "python")]
+    #[derive(Default)]
+    pub struct Python {
+        pub(crate) md_cache: hyperast_gen_ts_python::legion::MDCache,
+        pub object_map: NamedMap<(hyperast_gen_ts_python::legion::Local,)>,
+    }
+
+    #[cfg(feature = "python")]
+    impl super::ObjectMapper for Python {
+        type K = (git2::Oid, ObjectName);
+
+        type V = (hyperast_gen_ts_python::legion::Local,);
+
+        fn get(&self, key: &Self::K) -> Option<&Self::V> {
+            self.object_map.get(key)
+        }
+
+        fn insert(&mut self, key: Self::K, value: Self::V) -> Option<Self::V> {
+            self.object_map.insert(key, value)
+        }
+    }
+
+    #[cfg(feature = "ts")]
+    #[derive(Default)]
+    pub struct Ts {
+        pub(crate) md_cache: hyperast_gen_ts_ts::legion::MDCache,
+        pub object_map: NamedMap<(hyperast_gen_ts_ts::legion::Local,)>,
+    }
+
+    #[cfg(feature = "ts")]
+    impl super::ObjectMapper for Ts {
+        type K = (git2::Oid, ObjectName);
+
+        type V = (hyperast_gen_ts_ts::legion::Local,);
+
+        fn get(&self, key: &Self::K) -> Option<&Self::V> {
+            self.object_map.get(key)
+        }
+
+        fn insert(&mut self, key: Self::K, value: Self::V) -> Option<Self::V> {
+            self.object_map.insert(key, value)
+        }
+    }
+
+    #[cfg(feature = "any")]
+    #[derive(Default)]
+    pub struct Any {
+        pub object_map: NamedMap<(NodeIdentifier, crate::DefaultMetrics)>,
+    }
+
     #[derive(Default)]
     pub struct Maven {
         pub object_map: OidMap<(NodeIdentifier, crate::maven::MD)>,
@@ -340,6 +446,33 @@ pub(crate) mod caches {
         }
     }
 
+    #[cfg(feature = "gradle")]
+    #[derive(Default)]
+    pub struct Gradle {
+        pub object_map: NamedMap<(NodeIdentifier, crate::gradle::MD)>,
+    }
+
+    #[cfg(feature = "gradle")]
+    #[derive(Default)]
+    pub struct GradleScript {
+        pub object_map: NamedMap<crate::gradle::BuildScript>,
+    }
+
+    #[cfg(feature = "gradle")]
+    impl super::ObjectMapper for GradleScript {
+        type K = (git2::Oid, ObjectName);
+
+        type V = crate::gradle::BuildScript;
+
+        fn get(&self, key: &Self::K) -> Option<&Self::V> {
+            self.object_map.get(key)
+        }
+
+        fn insert(&mut self, key: Self::K, value: Self::V) -> Option<Self::V> {
+            self.object_map.insert(key, value)
+        }
+    }
+
     #[derive(Default)]
     pub struct Make {
         pub object_map: OidMap<(NodeIdentifier, crate::make::MD)>,
@@ -347,11 +480,11 @@ pub(crate) mod caches {
 
     #[derive(Default)]
     pub struct Makefile {
-        pub object_map: OidMap<crate::make::MakeFile>,
+        pub object_map: NamedMap<crate::make::MakeFile>,
     }
 
     impl super::ObjectMapper for Makefile {
-        type K = git2::Oid;
+        type K = (git2::Oid, ObjectName);
 
         type V = crate::make::MakeFile;
 
@@ -402,8 +535,10 @@ pub mod file_sys {
     // TODO move these things to their respective modules
     use super::{CachesHolding, ObjectName};
 
-    // /// The default file system, directories and files
-    // pub struct Any;
+    /// The default file system, directories and files,
+    /// each file being processed by the generator matching its extension.
+    #[cfg(feature = "any")]
+    pub struct Any;
 
     /// The maven scheme https://maven.apache.org/guides/introduction/introduction-to-the-standard-directory-layout.html ,
     /// made of nested maven modules.
@@ -433,6 +568,52 @@ pub mod file_sys {
         }
     }
 
+    /// Any xml file, handled like a pom without looking for modules
+    #[cfg(feature = "maven")]
+    pub struct Xml;
+
+    #[cfg(feature = "maven")]
+    impl super::InFiles for Xml {
+        fn matches(name: &ObjectName) -> bool {
+            name.0.ends_with(b".xml")
+        }
+    }
+
+    /// The gradle scheme https://docs.gradle.org/current/userguide/multi_project_builds.html ,
+    /// made of a root project and the subprojects included by its settings script (settings.gradle or settings.gradle.kts).
+    /// Each project can have a build script (build.gradle or build.gradle.kts),
+    /// and source sets, by convention src/<set>/java/ for java, e.g. src/main/java/ and src/test/java/.
+    #[cfg(feature = "gradle")]
+    pub struct Gradle;
+
+    #[cfg(feature = "gradle")]
+    impl CachesHolding for Gradle {
+        type Caches = super::caches::Gradle;
+    }
+
+    #[cfg(feature = "gradle")]
+    pub struct GradleScript;
+
+    #[cfg(feature = "gradle")]
+    impl CachesHolding for GradleScript {
+        type Caches = super::caches::GradleScript;
+    }
+
+    #[cfg(feature = "gradle")]
+    impl GradleScript {
+        /// Settings scripts include the subprojects
+        pub fn is_settings(name: &ObjectName) -> bool {
+            name.0.eq(b"settings.gradle") || name.0.eq(b"settings.gradle.kts")
+        }
+    }
+
+    #[cfg(feature = "gradle")]
+    impl super::InFiles for GradleScript {
+        fn matches(name: &ObjectName) -> bool {
+            name.0.eq(b"build.gradle") || name.0.eq(b"build.gradle.kts") || Self::is_settings(name)
+        }
+    }
+
     /// The java scheme,
     /// made of packages and modules https://docs.oracle.com/javase/specs/jls/se11/html/jls-7.html
     #[cfg(feature = "maven")]
@@ -465,9 +646,51 @@ pub mod file_sys {
         type Caches = super::caches::Makefile;
     }
 
+    #[cfg(feature = "make")]
+    impl Make {
+        /// Generated directories, e.g. build directories, and vendored dependencies,
+        /// they are not part of the project
+        pub const IGNORED_DIRS: &[&str] = &[
+            "build",
+            "CMakeFiles",
+            "_deps",
+            "cmake-build-*",
+            "third_party",
+            "third-party",
+            "thirdparty",
+            "3rdparty",
+            "vendor",
+            "external",
+        ];
+
+        /// `ignored` are names of directories, where `*` and `?` are wildcards
+        pub fn is_ignored_dir(name: &ObjectName, ignored: &[String]) -> bool {
+            ignored
+                .iter()
+                .any(|g| crate::path_filter::matches_segment(g.as_bytes(), &name.0))
+        }
+
+        /// Only found in build directories
+        pub fn is_build_dir_marker(name: &ObjectName) -> bool {
+            name.0.eq(b"CMakeCache.txt")
+        }
+    }
+
+    #[cfg(feature = "make")]
+    impl MakeFile {
+        pub fn is_cmake_lists(name: &ObjectName) -> bool {
+            cfg!(feature = "cmake") && name.0.eq(b"CMakeLists.txt")
+        }
+
+        /// A compilation database, as generated by CMake, Meson or Bear
+        pub fn is_compile_commands(name: &ObjectName) -> bool {
+            cfg!(feature = "cmake") && name.0.eq(b"compile_commands.json")
+        }
+    }
+
     impl super::InFiles for MakeFile {
         fn matches(name: &ObjectName) -> bool {
-            name.0.eq(b"Makefile")
+            name.0.eq(b"Makefile") || Self::is_cmake_lists(name) || Self::is_compile_commands(name)
         }
     }
 
@@ -478,6 +701,22 @@ pub mod file_sys {
         type Caches = super::caches::Cpp;
     }
 
+    #[cfg(feature = "c")]
+    pub struct C;
+
+    #[cfg(feature = "c")]
+    impl CachesHolding for C {
+        type Caches = super::caches::C;
+    }
+
+    /// Only sources, headers are ambiguous so they are handled as cpp
+    #[cfg(feature = "c")]
+    impl super::InFiles for C {
+        fn matches(name: &ObjectName) -> bool {
+            name.0.ends_with(b".c")
+        }
+    }
+
     /// CAUTION about when you change this value,
     /// advice: change it only at the very begining
     #[doc(hidden)]
@@ -498,12 +737,56 @@ pub mod file_sys {
         }
     }
 
+    #[cfg(feature = "python")]
+    pub struct Python;
+
+    #[cfg(feature = "python")]
+    impl CachesHolding for Python {
+        type Caches = super::caches::Python;
+    }
+
+    #[cfg(feature = "python")]
+    impl super::InFiles for Python {
+        fn matches(name: &ObjectName) -> bool {
+            name.0.ends_with(b".py") || name.0.ends_with(b".pyi")
+        }
+    }
+
     /// The npm scheme,
     /// it contains a package.json then,
     /// in its simplest form contains an index.js and a src/ directory,
     /// or is a collection of packages that contains a packages/ directory where each package is located
     #[cfg(feature = "npm")]
     pub struct Npm;
+
+    #[cfg(feature = "ts")]
+    pub struct Ts;
+
+    #[cfg(feature = "ts")]
+    impl CachesHolding for Ts {
+        type Caches = super::caches::Ts;
+    }
+
+    #[cfg(feature = "ts")]
+    impl Ts {
+        /// Dependencies installed by npm, they are not part of the project
+        pub fn is_ignored_dir(name: &ObjectName) -> bool {
+            name.0.eq(b"node_modules")
+        }
+    }
+
+    /// The typescript grammar is a superset of javascript, so both are handled
+    #[cfg(feature = "ts")]
+    impl super::InFiles for Ts {
+        fn matches(name: &ObjectName) -> bool {
+            name.0.ends_with(b".ts")
+                || name.0.ends_with(b".mts")
+                || name.0.ends_with(b".cts")
+                || name.0.ends_with(b".js")
+                || name.0.ends_with(b".mjs")
+                || name.0.ends_with(b".cjs")
+        }
+    }
 }
 
 impl crate::preprocessed::RepositoryProcessor {
@@ -62,9 +62,26 @@ pub trait CommitProc {
         commit_builder: crate::preprocessed::CommitBuilder,
         param_handle: ParametrizedCommitProcessorHandle,
     ) -> Box<dyn PreparedCommitProc + 'repo>;
+    /// The slice of the tree of commits to process, applied before [`CommitProc::prepare_processing`]
+    fn path_filter(&self) -> Option<&crate::path_filter::PathFilter> {
+        None
+    }
 
     fn commit_count(&self) -> usize;
     fn get_commit(&self, commit_oid: git2::Oid) -> Option<&crate::Commit>;
+    /// Processed commits, used to persist them
+    fn commits(&self) -> Box<dyn Iterator<Item = (&git2::Oid, &crate::Commit)> + '_> {
+        Box::new(std::iter::empty())
+    }
+    /// Registers a commit processed during a previous run, see [`crate::multi_preprocessed::persist`]
+    fn restore_commit(&mut self, commit_oid: git2::Oid, _commit: crate::Commit) {
+        log::warn!("cannot restore {commit_oid}, this processor does not keep commits")
+    }
+    /// Forgets the processed commits rejected by `keep`, before a garbage collection
+    fn retain_commits(&mut self, _keep: &mut dyn FnMut(&git2::Oid, &crate::Commit) -> bool) {}
+    /// Clears the caches of processed git objects,
+    /// they refer to subtrees and labels that a garbage collection can reclaim
+    fn clear_caches(&mut self) {}
     fn get_precomp_query(&self) -> Option<hyperast_tsquery::ZeroSepArrayStr> {
         None
     }
@@ -100,12 +117,15 @@ pub trait ParametrizedCommitProc: std::any::Any {
 
     fn get_mut(&mut self, parameters: ConfigParametersHandle) -> &mut dyn CommitProc;
     fn get(&self, parameters: ConfigParametersHandle) -> &dyn CommitProc;
+    /// Applies `f` on the processor of each registered parameter
+    fn for_each_mut(&mut self, f: &mut dyn FnMut(&mut dyn CommitProc));
 }
 
 pub trait ParametrizedCommitProc2: ParametrizedCommitProc {
     type Proc: CommitProcExt;
     fn with_parameters(&self, parameters: ConfigParametersHandle) -> &Self::Proc;
     fn with_parameters_mut(&mut self, parameters: ConfigParametersHandle) -> &mut Self::Proc;
+    fn all_parameters_mut(&mut self) -> Box<dyn Iterator<Item = &mut Self::Proc> + '_>;
 }
 
 impl<T: ParametrizedCommitProc2> ParametrizedCommitProc for T {
@@ -116,6 +136,12 @@ impl<T: ParametrizedCommitProc2> ParametrizedCommitProc for T {
     fn get(&self, parameters: ConfigParametersHandle) -> &dyn CommitProc {
         ParametrizedCommitProc2::with_parameters(self, parameters)
     }
+
+    fn for_each_mut(&mut self, f: &mut dyn FnMut(&mut dyn CommitProc)) {
+        for proc in ParametrizedCommitProc2::all_parameters_mut(self) {
+            f(proc)
+        }
+    }
 }
 
 #[test]
@@ -164,6 +190,9 @@ fn t() {
         fn get(&self, parameters: ConfigParametersHandle) -> &dyn CommitProc {
             &self.0[parameters.0]
         }
+        fn for_each_mut(&mut self, f: &mut dyn FnMut(&mut dyn CommitProc)) {
+            self.0.iter_mut().for_each(|p| f(p))
+        }
     }
 
     pub struct ProcessorMap<V>(std::collections::HashMap<std::any::TypeId, V>);
@@ -243,6 +272,9 @@ mod spreaded {
         pub(crate) fn clear(&mut self) {
             self.0.clear()
         }
+        pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+            self.0.values_mut()
+        }
     }
 
     unsafe impl<V> Send for ProcessorMap<V> {}
@@ -369,6 +401,9 @@ mod spreaded {
             ) -> &mut Self::Proc {
                 &mut self.0[parameters.0]
             }
+            fn all_parameters_mut(&mut self) -> Box<dyn Iterator<Item = &mut Self::Proc> + '_> {
+                Box::new(self.0.iter_mut())
+            }
         }
 
         let mut h = ProcessorMap::<Box<dyn ErasableProcessor>>::default();
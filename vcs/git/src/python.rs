@@ -0,0 +1,100 @@
+use std::time::Instant;
+
+use crate::{
+    Accumulator, BasicDirAcc, FailedParsing, FileProcessingResult, PROPAGATE_ERROR_ON_BAD_CST_NODE,
+    SuccessProcessing, processing::ObjectName, python_processor::SimpleStores,
+};
+
+use hyperast::{
+    hashed::SyntaxNodeHashs,
+    store::defaults::{LabelIdentifier, NodeIdentifier},
+    tree_gen::{self, SubTreeMetrics},
+};
+
+use hyperast_gen_ts_python::{legion as python_tree_gen, types::TStore};
+
+pub(crate) fn handle_python_file<'stores, 'cache, 'b: 'stores, More>(
+    tree_gen: &mut python_tree_gen::PythonTreeGen<'stores, 'cache, TStore, More>,
+    name: &ObjectName,
+    text: &'b [u8],
+) -> FileProcessingResult<python_tree_gen::FNode>
+where
+    More: tree_gen::Prepro<SimpleStores>
+        + tree_gen::PreproTSG<SimpleStores, Acc = python_tree_gen::Acc>,
+{
+    // handling the parsing explicitly in this function is a good idea
+    // to control complex stuff like timeout, instead of the call on next line
+    // let tree_sitter_parse = python_tree_gen::PythonTreeGen::<TStore>::tree_sitter_parse(text);
+
+    let mut parser = tree_sitter::Parser::new();
+    // TODO see if a timeout of a cancellation flag could be useful
+    // const MINUTE: u64 = 60 * 1000 * 1000;
+    // parser.set_timeout_micros(MINUTE);
+    // parser.set_cancellation_flag(flag);
+    parser
+        .set_language(&hyperast_gen_ts_python::language())
+        .unwrap();
+    let time = Instant::now();
+    let tree = parser.parse(text, None);
+    let parsing_time = time.elapsed();
+    let Some(tree) = tree else {
+        unimplemented!("You set a timeout or an cancel flag, so it now requires special handling.")
+        // return FileProcessingResult::ParsingTimedout(parsing_time)
+    };
+    if tree.root_node().has_error() {
+        log::warn!("bad CST: {:?}", name.try_str());
+        if PROPAGATE_ERROR_ON_BAD_CST_NODE {
+            return Err(FailedParsing {
+                parsing_time,
+                tree,
+                error: "CST contains parsing errors",
+            });
+        }
+    };
+    let time = Instant::now();
+    let node = tree_gen.generate_file(name.as_bytes(), text, tree.walk());
+    let processing_time = time.elapsed();
+    Ok(SuccessProcessing {
+        parsing_time,
+        processing_time,
+        node,
+    })
+}
+
+pub struct PythonAcc {
+    pub(crate) primary:
+        BasicDirAcc<NodeIdentifier, LabelIdentifier, SubTreeMetrics<SyntaxNodeHashs<u32>>>,
+}
+
+impl PythonAcc {
+    pub(crate) fn new(name: String) -> Self {
+        Self {
+            primary: BasicDirAcc::new(name),
+        }
+    }
+}
+
+impl From<String> for PythonAcc {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+impl PythonAcc {
+    pub(crate) fn push(&mut self, name: LabelIdentifier, full_node: python_tree_gen::Local) {
+        self.primary
+            .push(name, full_node.compressed_node, full_node.metrics);
+    }
+}
+
+impl hyperast::tree_gen::Accumulator for PythonAcc {
+    type Node = (LabelIdentifier, (python_tree_gen::Local,));
+    fn push(&mut self, (name, (full_node,)): Self::Node) {
+        self.primary
+            .push(name, full_node.compressed_node, full_node.metrics);
+    }
+}
+
+impl Accumulator for PythonAcc {
+    type Unlabeled = (python_tree_gen::Local,);
+}
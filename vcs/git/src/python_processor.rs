@@ -0,0 +1,437 @@
+use crate::{
+    Processor, StackEle,
+    git::BasicGitObject,
+    preprocessed::RepositoryProcessor,
+    processing::{CacheHolding, InFiles, ObjectName},
+    python::PythonAcc,
+};
+use git2::{Oid, Repository};
+use hyperast::{
+    store::nodes::legion::eq_node,
+    types::{ETypeStore as _, LabelStore},
+};
+use hyperast_gen_ts_python::{legion as python_gen, types::Type};
+use hyperast_tsquery::ArrayStr;
+use std::{iter::Peekable, path::Components, sync::Arc};
+
+pub(crate) fn prepare_dir_exploration(tree: git2::Tree) -> Vec<BasicGitObject> {
+    tree.iter()
+        .rev()
+        .map(TryInto::try_into)
+        .filter_map(|x| x.ok())
+        .collect()
+}
+
+pub type SimpleStores = hyperast::store::SimpleStores<hyperast_gen_ts_python::types::TStore>;
+
+pub struct PythonProcessor<'repo, 'prepro, 'd, 'c, Acc> {
+    repository: &'repo Repository,
+    prepro: &'prepro mut RepositoryProcessor,
+    stack: Vec<StackEle<Acc>>,
+    pub dir_path: &'d mut Peekable<Components<'c>>,
+    parameters: &'d crate::processing::erased::ParametrizedCommitProcessor2Handle<PythonProc>,
+}
+
+impl<'repo, 'b, 'd, 'c, Acc: From<String>> PythonProcessor<'repo, 'b, 'd, 'c, Acc> {
+    pub(crate) fn new(
+        repository: &'repo Repository,
+        prepro: &'b mut RepositoryProcessor,
+        dir_path: &'d mut Peekable<Components<'c>>,
+        name: &ObjectName,
+        oid: git2::Oid,
+        parameters: &'d crate::processing::erased::ParametrizedCommitProcessor2Handle<PythonProc>,
+    ) -> Self {
+        let tree = repository.find_tree(oid).unwrap();
+        let prepared = prepare_dir_exploration(tree);
+        let name = name.try_into().unwrap();
+        let stack = vec![StackEle::new(oid, prepared, Acc::from(name))];
+        Self {
+            stack,
+            repository,
+            prepro,
+            dir_path,
+            parameters,
+        }
+    }
+}
+
+pub static SUB_QUERIES: &[&str] = &[r#"(class_definition)"#, r#"(function_definition)"#];
+
+impl<'repo, 'b, 'd, 'c> Processor<PythonAcc> for PythonProcessor<'repo, 'b, 'd, 'c, PythonAcc> {
+    fn pre(&mut self, current_object: BasicGitObject) {
+        match current_object {
+            BasicGitObject::Tree(oid, name) => {
+                self.handle_tree_cached(oid, name);
+            }
+            BasicGitObject::Blob(oid, name) => {
+                if crate::processing::file_sys::Python::matches(&name) {
+                    self.prepro
+                        .help_handle_python_file(
+                            oid,
+                            &mut self.stack.last_mut().unwrap().acc,
+                            &name,
+                            self.repository,
+                            *self.parameters,
+                        )
+                        .unwrap();
+                } else {
+                    log::debug!("not python source file {:?}", name.try_str());
+                }
+            }
+        }
+    }
+    fn post(&mut self, oid: Oid, acc: PythonAcc) -> Option<(python_gen::Local,)> {
+        let name = acc.primary.name.clone();
+        let key = (oid, name.as_bytes().into());
+        let full_node = make(acc, self.prepro.main_stores_mut().mut_with_ts());
+        self.prepro
+            .processing_systems
+            .mut_or_default::<PythonProcessorHolder>()
+            .get_caches_mut()
+            .object_map
+            .insert(key, (full_node.clone(),));
+        let name = self.prepro.main_stores.label_store.get_or_insert(name);
+        if self.stack.is_empty() {
+            Some((full_node,))
+        } else {
+            let w = &mut self.stack.last_mut().unwrap().acc;
+            assert!(
+                !w.primary.children_names.contains(&name),
+                "{:?} {:?}",
+                w.primary.children_names,
+                name
+            );
+            w.push(name, full_node.clone());
+            None
+        }
+    }
+
+    fn stack(&mut self) -> &mut Vec<StackEle<PythonAcc>> {
+        &mut self.stack
+    }
+}
+
+impl<'repo, 'prepro, 'd, 'c> PythonProcessor<'repo, 'prepro, 'd, 'c, PythonAcc> {
+    fn handle_tree_cached(&mut self, oid: Oid, name: ObjectName) {
+        if let Some(
+            // (already, skiped_ana)
+            already,
+        ) = self
+            .prepro
+            .processing_systems
+            .mut_or_default::<PythonProcessorHolder>()
+            .get_caches_mut()
+            .object_map
+            .get(&(oid, name.clone()))
+        {
+            // reinit already computed node for post order
+            let full_node = already.clone();
+            // let skiped_ana = *skiped_ana;
+            let w = &mut self.stack.last_mut().unwrap().acc;
+            let name = self.prepro.intern_object_name(&name);
+            assert!(!w.primary.children_names.contains(&name));
+            hyperast::tree_gen::Accumulator::push(w, (name, full_node));
+            // w.push(name, full_node, skiped_ana);
+        } else {
+            log::debug!("tree {:?}", name.try_str());
+            let tree = self.repository.find_tree(oid).unwrap();
+            let prepared: Vec<BasicGitObject> = prepare_dir_exploration(tree);
+            self.stack.push(StackEle::new(
+                oid,
+                prepared,
+                PythonAcc::new(name.try_into().unwrap()),
+            ));
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Parameter {
+    pub(crate) query: Option<hyperast_tsquery::ZeroSepArrayStr>,
+}
+#[derive(Default)]
+pub(crate) struct PythonProcessorHolder(Option<PythonProc>);
+pub(crate) struct PythonProc {
+    parameter: Parameter,
+    query: Query,
+    cache: crate::processing::caches::Python,
+    commits: std::collections::HashMap<git2::Oid, crate::Commit>,
+}
+impl crate::processing::erased::Parametrized for PythonProcessorHolder {
+    type T = Parameter;
+    fn register_param(
+        &mut self,
+        t: Self::T,
+    ) -> crate::processing::erased::ParametrizedCommitProcessorHandle {
+        let l = self
+            .0
+            .iter()
+            .position(|x| &x.parameter == &t)
+            .unwrap_or_else(|| {
+                let l = 0; //self.0.len();
+                // TODO enable multi configs for python, do the same as the one for Java
+                let query = if let Some(q) = &t.query {
+                    Query::new(q.iter())
+                } else {
+                    let precomputeds = crate::python_processor::SUB_QUERIES;
+                    Query::new(precomputeds.into_iter().map(|x| x.as_ref()))
+                };
+                self.0 = Some(PythonProc {
+                    parameter: t,
+                    query,
+                    cache: Default::default(),
+                    commits: Default::default(),
+                });
+                l
+            });
+        use crate::processing::erased::ConfigParametersHandle;
+        use crate::processing::erased::ParametrizedCommitProc;
+        use crate::processing::erased::ParametrizedCommitProcessorHandle;
+        ParametrizedCommitProcessorHandle(self.erased_handle(), ConfigParametersHandle(l))
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Query(pub(crate) hyperast_tsquery::Query, Arc<str>);
+
+impl PartialEq for Query {
+    fn eq(&self, other: &Self) -> bool {
+        self.1 == other.1
+    }
+}
+impl Eq for Query {}
+
+impl Query {
+    fn new<'a>(precomputeds: impl Iterator<Item = &'a str>) -> Self {
+        static DQ: &str = "(_)";
+        let precomputeds = precomputeds.collect::<Vec<_>>();
+        let (precomp, _) = hyperast_tsquery::Query::with_precomputed(
+            DQ,
+            hyperast_gen_ts_python::language(),
+            precomputeds.as_slice(),
+        )
+        .unwrap();
+        Self(precomp.into(), precomputeds.join("\n").into())
+    }
+}
+
+impl crate::processing::erased::CommitProc for PythonProc {
+    fn prepare_processing(
+        &self,
+        _repository: &git2::Repository,
+        _builder: crate::preprocessed::CommitBuilder,
+        _handle: crate::processing::ParametrizedCommitProcessorHandle,
+    ) -> Box<dyn crate::processing::erased::PreparedCommitProc> {
+        unimplemented!("required for processing python at the root of a project")
+    }
+
+    fn get_commit(&self, commit_oid: git2::Oid) -> Option<&crate::Commit> {
+        self.commits.get(&commit_oid)
+    }
+
+    fn commit_count(&self) -> usize {
+        self.commits.len()
+    }
+
+    fn commits(&self) -> Box<dyn Iterator<Item = (&git2::Oid, &crate::Commit)> + '_> {
+        Box::new(self.commits.iter())
+    }
+
+    fn restore_commit(&mut self, commit_oid: git2::Oid, commit: crate::Commit) {
+        self.commits.insert(commit_oid, commit);
+    }
+
+    fn retain_commits(&mut self, keep: &mut dyn FnMut(&git2::Oid, &crate::Commit) -> bool) {
+        self.commits.retain(|oid, commit| keep(oid, commit));
+    }
+
+    fn clear_caches(&mut self) {
+        self.cache = Default::default();
+    }
+
+    fn get_precomp_query(&self) -> Option<hyperast_tsquery::ZeroSepArrayStr> {
+        self.parameter.query.clone()
+    }
+}
+
+impl crate::processing::erased::CommitProcExt for PythonProc {
+    type Holder = PythonProcessorHolder;
+}
+impl crate::processing::erased::ParametrizedCommitProc2 for PythonProcessorHolder {
+    type Proc = PythonProc;
+
+    fn with_parameters_mut(
+        &mut self,
+        parameters: crate::processing::erased::ConfigParametersHandle,
+    ) -> &mut Self::Proc {
+        assert_eq!(0, parameters.0);
+        self.0.as_mut().unwrap()
+    }
+
+    fn with_parameters(
+        &self,
+        parameters: crate::processing::erased::ConfigParametersHandle,
+    ) -> &Self::Proc {
+        assert_eq!(0, parameters.0);
+        self.0.as_ref().unwrap()
+    }
+
+    fn all_parameters_mut(&mut self) -> Box<dyn Iterator<Item = &mut Self::Proc> + '_> {
+        Box::new(self.0.iter_mut())
+    }
+}
+impl CacheHolding<crate::processing::caches::Python> for PythonProc {
+    fn get_caches_mut(&mut self) -> &mut crate::processing::caches::Python {
+        &mut self.cache
+    }
+    fn get_caches(&self) -> &crate::processing::caches::Python {
+        &self.cache
+    }
+}
+
+impl CacheHolding<crate::processing::caches::Python> for PythonProcessorHolder {
+    fn get_caches_mut(&mut self) -> &mut crate::processing::caches::Python {
+        &mut self.0.as_mut().unwrap().cache
+    }
+    fn get_caches(&self) -> &crate::processing::caches::Python {
+        &self.0.as_ref().unwrap().cache
+    }
+}
+
+impl RepositoryProcessor {
+    pub(crate) fn handle_python_blob(
+        &mut self,
+        oid: Oid,
+        name: &ObjectName,
+        repository: &Repository,
+        parameters: crate::processing::erased::ParametrizedCommitProcessor2Handle<PythonProc>,
+    ) -> Result<(python_gen::Local,), crate::ParseErr> {
+        self.processing_systems
+            .caching_blob_handler::<crate::processing::file_sys::Python>()
+            .handle2(oid, repository, &name, parameters, |c, n, t| {
+                let line_break = if t.contains(&b'\r') { "\r\n" } else { "\n" }
+                    .as_bytes()
+                    .to_vec();
+                let holder = c.mut_or_default::<PythonProcessorHolder>();
+                let python_proc = holder.0.as_mut().unwrap();
+                let md_cache = &mut python_proc.cache.md_cache;
+                let stores = self
+                    .main_stores
+                    .mut_with_ts::<hyperast_gen_ts_python::types::TStore>();
+                let more = hyperast_tsquery::PreparedQuerying::<
+                    _,
+                    hyperast_gen_ts_python::types::TStore,
+                    python_gen::Acc,
+                >::from(&python_proc.query.0);
+                let mut python_tree_gen = python_gen::PythonTreeGen {
+                    line_break,
+                    stores,
+                    md_cache,
+                    more,
+                };
+                crate::python::handle_python_file(&mut python_tree_gen, n, t)
+                    .map(|x| {
+                        let local = x.node.local.clone();
+                        self.parsing_time += x.parsing_time;
+                        self.processing_time += x.processing_time;
+                        log::debug!(
+                            "parsing, processing, n, f: {} {} {} {}",
+                            self.parsing_time.as_secs(),
+                            self.processing_time.as_secs(),
+                            python_proc.cache.md_cache.len(),
+                            python_proc.cache.object_map.len()
+                        );
+                        (local,)
+                    })
+                    .map_err(|_| crate::ParseErr::IllFormed)
+            })
+    }
+
+    pub(crate) fn help_handle_python_file(
+        &mut self,
+        oid: Oid,
+        parent: &mut PythonAcc,
+        name: &ObjectName,
+        repository: &Repository,
+        parameters: crate::processing::erased::ParametrizedCommitProcessor2Handle<PythonProc>,
+    ) -> Result<(), crate::ParseErr> {
+        let (full_node,) = self.handle_python_blob(oid, name, repository, parameters)?;
+        let name = self.intern_object_name(name);
+        assert!(!parent.primary.children_names.contains(&name));
+
+        parent.push(name, full_node);
+        Ok(())
+    }
+
+    pub(crate) fn handle_python_directory<'b, 'd: 'b>(
+        &mut self,
+        repository: &Repository,
+        dir_path: &'b mut Peekable<Components<'d>>,
+        name: &ObjectName,
+        oid: git2::Oid,
+        handle: crate::processing::erased::ParametrizedCommitProcessor2Handle<PythonProc>,
+    ) -> (python_gen::Local,) {
+        PythonProcessor::<PythonAcc>::new(repository, self, dir_path, name, oid, &handle).process()
+    }
+}
+
+fn make(acc: PythonAcc, stores: &mut SimpleStores) -> python_gen::Local {
+    use hyperast::hashed::{IndexingHashBuilder, MetaDataHashsBuilder};
+    let node_store = &mut stores.node_store;
+    let label_store = &mut stores.label_store;
+    let kind = Type::Directory;
+    let interned_kind = hyperast_gen_ts_python::types::TStore::intern(kind);
+    let label_id = label_store.get_or_insert(acc.primary.name.clone());
+
+    let primary = acc
+        .primary
+        .map_metrics(|m| m.finalize(&interned_kind, &label_id, 0));
+    let hashable = primary.metrics.hashs.most_discriminating();
+    let eq = eq_node(&Type::Directory, Some(&label_id), &primary.children);
+    let insertion = node_store.prepare_insertion(&hashable, eq);
+
+    if let Some(id) = insertion.occupied_id() {
+        // NOTE this cituation should not happen often, due to cache based on oids, so there is no point caching md.
+        // If git objects are changed but ignored, then it goes through this branch.
+        // TODO bench
+        // TODO in the oid cache the values could be NodeIdentifiers, then current cache would be used with an indirection.
+
+        let metrics = primary.metrics.map_hashs(|h| h.build());
+        let ana = None;
+        return python_gen::Local {
+            compressed_node: id,
+            metrics,
+            ana,
+            role: None,
+            precomp_queries: Default::default(),
+            viz_cs_count: 0,
+        };
+    }
+
+    let mut dyn_builder = hyperast::store::nodes::legion::dyn_builder::EntityBuilder::new();
+
+    let ana = None;
+
+    let children_is_empty = primary.children.is_empty();
+
+    let metrics = primary.persist(&mut dyn_builder, interned_kind, label_id);
+    let metrics = metrics.map_hashs(|h| h.build());
+    let hashs = metrics.add_md_metrics(&mut dyn_builder, children_is_empty);
+    hashs.persist(&mut dyn_builder);
+
+    let vacant = insertion.vacant();
+    let node_id = hyperast::store::nodes::legion::NodeStore::insert_built_after_prepare(
+        vacant,
+        dyn_builder.build(),
+    );
+
+    let full_node = python_gen::Local {
+        compressed_node: node_id.clone(),
+        metrics,
+        ana,
+        role: None,
+        precomp_queries: Default::default(),
+        viz_cs_count: 0,
+    };
+    full_node
+}
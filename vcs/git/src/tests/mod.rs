@@ -273,3 +273,861 @@ public class ClientClass extends ChildClass {
 	private class InnerClass3a {}
 }
 "#;
+
+/// Bare repositories in a temporary directory, served by [`crate::git::Forge::local`] as `team/<name>`.
+/// The directory is removed when dropped, even if the test panics.
+pub(crate) struct TempForge {
+    root: std::path::PathBuf,
+}
+
+impl TempForge {
+    pub(crate) fn new(name: &str) -> Self {
+        let root = std::env::temp_dir().join(format!("hyperast_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        Self { root }
+    }
+
+    pub(crate) fn forge(&self) -> crate::git::Forge {
+        crate::git::Forge::local(&self.root)
+    }
+
+    pub(crate) fn repo(&self, name: &str) -> crate::git::Repo {
+        self.forge().repo("team", name)
+    }
+
+    pub(crate) fn path(&self, name: &str) -> std::path::PathBuf {
+        self.root.join(format!("team/{name}.git"))
+    }
+
+    pub(crate) fn init(&self, name: &str) -> git2::Repository {
+        git2::Repository::init_bare(self.path(name)).unwrap()
+    }
+}
+
+impl Drop for TempForge {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.root);
+    }
+}
+
+/// Commits `files` on HEAD, as a child of the current HEAD if any
+pub(crate) fn commit(repository: &git2::Repository, files: &[(&str, &str)]) -> git2::Oid {
+    let tree = repository.find_tree(write_tree(repository, files)).unwrap();
+    let sig = git2::Signature::now("hyperast", "hyperast@example.org").unwrap();
+    let parent = repository.head().ok().and_then(|h| h.peel_to_commit().ok());
+    let parents: Vec<_> = parent.iter().collect();
+    repository
+        .commit(Some("HEAD"), &sig, &sig, "commit", &tree, &parents)
+        .unwrap()
+}
+
+/// Writes the nested trees of `files`, given their `/` separated paths and contents
+pub(crate) fn write_tree(repository: &git2::Repository, files: &[(&str, &str)]) -> git2::Oid {
+    let mut tree = repository.treebuilder(None).unwrap();
+    let mut dirs: std::collections::BTreeMap<&str, Vec<(&str, &str)>> = Default::default();
+    for (path, text) in files {
+        match path.split_once('/') {
+            Some((dir, rest)) => dirs.entry(dir).or_default().push((rest, *text)),
+            None => {
+                let blob = repository.blob(text.as_bytes()).unwrap();
+                tree.insert(*path, blob, 0o100644).unwrap();
+            }
+        }
+    }
+    for (dir, files) in dirs {
+        let oid = write_tree(repository, &files);
+        tree.insert(dir, oid, 0o040000).unwrap();
+    }
+    tree.write().unwrap()
+}
+
+#[test]
+fn repo_spec_from_str() {
+    use crate::git::{Forge, Repo};
+    let repo: Repo = "github.com/INRIA/spoon".parse().unwrap();
+    assert_eq!(repo, Forge::Github.repo("INRIA", "spoon"));
+    let repo: Repo = "https://git.example.org/team/project".parse().unwrap();
+    assert_eq!(repo.forge(), Forge::custom("https://git.example.org"));
+    assert_eq!(repo.url(), "https://git.example.org/team/project");
+    let repo: Repo = "/srv/git/team/project".parse().unwrap();
+    assert_eq!(repo.forge(), Forge::local("/srv/git"));
+    assert_eq!((repo.user(), repo.name()), ("team", "project"));
+    assert!("/srv/git/../project".parse::<Repo>().is_err());
+    assert!("example.org/team/project".parse::<Repo>().is_err());
+}
+
+#[test]
+fn open_local_bare_mirror() {
+    let forge = TempForge::new("local_forge");
+    forge.init("project");
+    let repository = forge.repo("project").fetch();
+    assert!(repository.is_bare());
+    assert!(forge.repo("missing").try_fetch().is_err());
+    let err = forge.repo("missing").open_local().err().unwrap();
+    assert!(err.message().contains("missing"), "{}", err.message());
+    assert!(
+        crate::git::Forge::Github
+            .repo("team", "project")
+            .open_local()
+            .is_err()
+    );
+}
+
+#[test]
+fn process_npm_and_any_projects() {
+    use crate::multi_preprocessed::PreProcessedRepositories;
+    use crate::processing::RepoConfig;
+    let forge = TempForge::new("npm_any");
+    let repository = forge.init("project");
+    let commit = commit(
+        &repository,
+        &[
+            ("node_modules/index.js", "module.exports = 0;\n"),
+            (
+                "src/main.ts",
+                "export const inc = (x: number): number => x + 1;\n",
+            ),
+            ("src/util.py", "def inc(x):\n    return x + 1\n"),
+            ("README.md", "# project\n"),
+        ],
+    )
+    .to_string();
+
+    let process = |config| {
+        let mut repos = PreProcessedRepositories::default();
+        let repo = repos.register_config(forge.repo("project"), config).fetch();
+        let oids = repos.pre_process_with_limit(&repo, "", &commit, 1).unwrap();
+        let id = repos.get_commit(&repo.config, &oids[0]).unwrap().ast_root;
+        let stores = &repos.processor.main_stores;
+        hyperast::nodes::SyntaxSerializer::new(stores, id).to_string()
+    };
+    let npm = process(RepoConfig::TsNpm);
+    assert!(npm.contains("main.ts"));
+    assert!(!npm.contains("index.js") && !npm.contains("util.py"));
+    let any = process(RepoConfig::Any);
+    assert!(any.contains("main.ts") && any.contains("util.py"));
+    assert!(!any.contains("README.md"));
+}
+
+#[cfg(feature = "persist")]
+#[test]
+fn snapshot_round_trip() {
+    use crate::multi_preprocessed::PreProcessedRepositories;
+    use crate::processing::RepoConfig;
+    let forge = TempForge::new("snapshot");
+    let repository = forge.init("project");
+    let a = ("src/A.java", "class A { int a; }");
+    let first = commit(&repository, &[a]);
+    let b = ("src/B.java", "class B { void b() { a(); } }");
+    let second = commit(&repository, &[a, b]);
+    let dir = forge.root.join("snapshot");
+
+    let text = |repos: &PreProcessedRepositories, config, oid| {
+        let id = repos.get_commit(config, oid).unwrap().ast_root;
+        let stores = &repos.processor.main_stores;
+        hyperast::nodes::SyntaxSerializer::new(stores, id).to_string()
+    };
+    let restore = || {
+        let mut repos = PreProcessedRepositories::default();
+        let repo = repos
+            .register_config(forge.repo("project"), RepoConfig::Any)
+            .fetch();
+        let restored = repos.restore(&dir).unwrap();
+        (repos, repo, restored)
+    };
+
+    let (mut repos, repo) = {
+        let (repos, repo, restored) = restore();
+        assert_eq!(restored, 0);
+        (repos, repo)
+    };
+    let oids = repos
+        .pre_process_with_limit(&repo, "", &first.to_string(), 1)
+        .unwrap();
+    assert_eq!(oids, [first]);
+    let stats = repos.snapshot(&dir).unwrap();
+    assert!(stats.nodes > 0 && stats.labels > 0);
+    let expected_first = text(&repos, &repo.config, &first);
+
+    // only the new subtrees are appended
+    let (mut repos, repo, restored) = restore();
+    assert_eq!(restored, 1);
+    assert_eq!(text(&repos, &repo.config, &first), expected_first);
+    repos
+        .pre_process_with_limit(&repo, "", &second.to_string(), 1)
+        .unwrap();
+    let expected_second = text(&repos, &repo.config, &second);
+    let appended = repos.snapshot(&dir).unwrap();
+    let node_count = repos.processor.main_stores.node_store.len();
+    assert!(appended.nodes > 0);
+    assert_eq!(stats.nodes + appended.nodes, node_count);
+    assert_eq!(repos.snapshot(&dir).unwrap().nodes, 0);
+
+    let (repos, repo, restored) = restore();
+    assert_eq!(restored, 2);
+    assert_eq!(text(&repos, &repo.config, &first), expected_first);
+    assert_eq!(text(&repos, &repo.config, &second), expected_second);
+    assert!(expected_second.contains("void b()"));
+}
+
+#[test]
+fn gradle_settings_includes() {
+    let settings = r#"
+rootProject.name = 'project'
+include ':app', ':lib:core' // the core library
+include("cli")
+include ':plugins:a',
+    ':plugins:b'
+includeBuild 'build-logic'
+project(':cli').projectDir = file('tools/cli')
+"#;
+    let includes = crate::gradle::parse_includes(settings);
+    assert_eq!(
+        includes,
+        ["app", "lib/core", "tools/cli", "plugins/a", "plugins/b"]
+    );
+}
+
+#[test]
+fn gradle_source_sets() {
+    use std::path::PathBuf;
+    let groovy = r#"
+plugins { id 'java' }
+sourceSets {
+    main {
+        java { srcDirs = ['src', "generated/"] } // not src/main/java
+        resources { srcDirs = ['res'] }
+    }
+    test.java.srcDir './tests'
+}
+sourceSets.main.java.srcDir "$buildDir/gen"
+dependencies { implementation 'org.example:lib:1.0' }
+"#;
+    let kotlin = r#"
+sourceSets {
+    named("integrationTest") {
+        java.srcDir("it")
+    }
+    main { java.setSrcDirs(listOf("src/java")) }
+}
+"#;
+    let dirs = |x: &[&str]| x.iter().map(PathBuf::from).collect::<Vec<_>>();
+    let (main_dirs, test_dirs) = crate::gradle::parse_source_sets(groovy);
+    assert_eq!(main_dirs, dirs(&["src", "generated"]));
+    assert_eq!(test_dirs, dirs(&["tests"]));
+    let (main_dirs, test_dirs) = crate::gradle::parse_source_sets(kotlin);
+    assert_eq!(main_dirs, dirs(&["src/java"]));
+    assert_eq!(test_dirs, dirs(&["it"]));
+}
+
+#[test]
+fn process_gradle_project() {
+    use crate::multi_preprocessed::PreProcessedRepositories;
+    use crate::processing::RepoConfig;
+    let forge = TempForge::new("gradle");
+    let repository = forge.init("project");
+    let commit = commit(
+        &repository,
+        &[
+            (
+                "settings.gradle",
+                "include ':app'\ninclude(\":lib:core\")\n",
+            ),
+            (
+                "app/build.gradle",
+                "plugins { id 'application' }\nsourceSets.main.java.srcDir 'gen'\n",
+            ),
+            ("app/src/main/java/App.java", "class App {}"),
+            ("app/gen/Generated.java", "class Generated {}"),
+            ("app/src/integrationTest/java/AppIT.java", "class AppIT {}"),
+            ("lib/core/src/main/java/Core.java", "class Core {}"),
+            ("docs/Example.java", "class Example {}"),
+        ],
+    )
+    .to_string();
+
+    let mut repos = PreProcessedRepositories::default();
+    let repo = repos
+        .register_config(forge.repo("project"), RepoConfig::JavaGradle)
+        .fetch();
+    let oids = repos.pre_process_with_limit(&repo, "", &commit, 1).unwrap();
+    let id = repos.get_commit(&repo.config, &oids[0]).unwrap().ast_root;
+    let stores = &repos.processor.main_stores;
+    let text = hyperast::nodes::SyntaxSerializer::new(stores, id).to_string();
+    assert!(text.contains("App.java") && text.contains("AppIT.java"));
+    assert!(text.contains("Core.java") && text.contains("Generated.java"));
+    // not a source set of a project
+    assert!(!text.contains("Example.java"));
+    // build scripts are only read for the layout
+    assert!(!text.contains("<project>"));
+}
+
+#[test]
+fn cmake_lists_structure() {
+    use crate::make::TargetKind;
+    use std::path::PathBuf;
+    let lists = r#"
+cmake_minimum_required(VERSION 3.10)
+project(demo C)
+include_directories(include ${PROJECT_SOURCE_DIR}/generated)
+add_subdirectory(lib) # the util library
+add_executable(demo
+    src/main.c
+    "src/args.c"
+)
+target_sources(demo PRIVATE ${CMAKE_CURRENT_SOURCE_DIR}/src/extra.c)
+add_library(demo::alias ALIAS demo)
+if(WIN32)
+  target_include_directories(demo PRIVATE win)
+endif()
+"#;
+    let lists = crate::cmake::parse_cmake_lists(lists);
+    assert_eq!(lists.subdirectories, ["lib"]);
+    assert_eq!(lists.include_dirs, [PathBuf::from("include")]);
+    assert_eq!(lists.targets.len(), 1);
+    let demo = &lists.targets[0];
+    assert_eq!(demo.name, "demo");
+    assert_eq!(demo.kind, TargetKind::Executable);
+    let sources: Vec<PathBuf> = ["src/main.c", "src/args.c", "src/extra.c"]
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    assert_eq!(demo.sources, sources);
+    assert_eq!(demo.include_dirs, [PathBuf::from("win")]);
+}
+
+#[test]
+fn compile_commands_targets() {
+    use std::path::PathBuf;
+    let commands = r#"[
+  {
+    "directory": "/home/dev/demo/build",
+    "command": "cc -I/home/dev/demo/include -o CMakeFiles/demo.dir/src/main.c.o -c /home/dev/demo/src/main.c",
+    "file": "/home/dev/demo/src/main.c"
+  },
+  {
+    "directory": "/home/dev/demo/build",
+    "arguments": ["cc", "-I", "../lib", "-o", "lib/CMakeFiles/util.dir/util.c.o", "-c", "../lib/util.c"],
+    "file": "../lib/util.c"
+  }
+]"#;
+    let targets = crate::cmake::parse_compile_commands(commands).unwrap();
+    let names: Vec<_> = targets.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, ["demo", "util"]);
+    assert_eq!(targets[0].sources, [PathBuf::from("src/main.c")]);
+    assert_eq!(targets[0].include_dirs, [PathBuf::from("include")]);
+    assert_eq!(targets[1].sources, [PathBuf::from("lib/util.c")]);
+    assert_eq!(targets[1].include_dirs, [PathBuf::from("lib")]);
+    assert!(crate::cmake::parse_compile_commands("build/compile_commands.json").is_err());
+    // the root is not the directory of the sources
+    let commands = r#"[
+  {
+    "directory": "/home/dev/demo/build",
+    "command": "cc -o CMakeFiles/demo.dir/main.c.o -c ../src/main.c",
+    "file": "../src/main.c"
+  }
+]"#;
+    let targets = crate::cmake::parse_compile_commands(commands).unwrap();
+    assert_eq!(targets[0].sources, [PathBuf::from("src/main.c")]);
+    // out of tree build
+    let commands = r#"[
+  {
+    "directory": "/tmp/build",
+    "command": "cc -I/home/dev/demo/include -o CMakeFiles/demo.dir/main.c.o -c /home/dev/demo/main.c",
+    "file": "/home/dev/demo/main.c"
+  }
+]"#;
+    let targets = crate::cmake::parse_compile_commands(commands).unwrap();
+    assert_eq!(targets[0].sources, [PathBuf::from("main.c")]);
+    assert_eq!(targets[0].include_dirs, [PathBuf::from("include")]);
+}
+
+#[test]
+fn process_cmake_project() {
+    use crate::multi_preprocessed::PreProcessedRepositories;
+    use crate::processing::{CacheHolding, RepoConfig};
+    use std::path::PathBuf;
+    let forge = TempForge::new("cmake");
+    let repository = forge.init("project");
+    let lib_lists = "add_library(util STATIC util.c)\ntarget_include_directories(util PUBLIC .)\n";
+    let lists = "project(demo C)\nadd_subdirectory(lib)\nadd_executable(demo main.c)\n";
+    let commit = commit(
+        &repository,
+        &[
+            ("CMakeLists.txt", lists),
+            ("main.c", "int main() { return 0; }\n"),
+            ("lib/CMakeLists.txt", lib_lists),
+            ("lib/util.c", "int inc(int x) { return x + 1; }\n"),
+            ("lib/util.h", "int inc(int x);\n"),
+            ("third_party/dep.c", "int dep;\n"),
+            ("out/CMakeCache.txt", "# cache\n"),
+            ("out/generated.c", "int generated;\n"),
+        ],
+    );
+    let tree = repository.find_commit(commit).unwrap().tree().unwrap();
+    let lib = tree.get_path(std::path::Path::new("lib")).unwrap().id();
+    let commit = commit.to_string();
+
+    let mut repos = PreProcessedRepositories::default();
+    let repo = repos
+        .register_config(forge.repo("project"), RepoConfig::CppMake)
+        .fetch();
+    let oids = repos.pre_process_with_limit(&repo, "", &commit, 1).unwrap();
+    let id = repos.get_commit(&repo.config, &oids[0]).unwrap().ast_root;
+    let stores = &repos.processor.main_stores;
+    let text = hyperast::nodes::SyntaxSerializer::new(stores, id).to_string();
+    assert!(text.contains("main.c") && text.contains("util.c") && text.contains("util.h"));
+    // vendored and generated
+    assert!(!text.contains("dep.c") && !text.contains("generated.c"));
+
+    let modules = &repos
+        .processor
+        .processing_systems
+        .mut_or_default::<crate::make_processor::MakeProcessorHolder>()
+        .get_caches()
+        .object_map;
+    let (_, root_md) = &modules[&tree.id()];
+    assert_eq!(root_md.targets[0].name, "demo");
+    let (_, lib_md) = &modules[&lib];
+    let util = &lib_md.targets[0];
+    assert_eq!(util.name, "util");
+    assert_eq!(util.sources, [PathBuf::from("util.c")]);
+    assert_eq!(util.include_dirs, [PathBuf::from("")]);
+}
+
+#[test]
+fn process_cmake_project_with_ignored_dirs() {
+    use crate::multi_preprocessed::PreProcessedRepositories;
+    use crate::processing::RepoConfig;
+    let forge = TempForge::new("ignored_dirs");
+    let repository = forge.init("project");
+    let commit = commit(
+        &repository,
+        &[
+            (
+                "CMakeLists.txt",
+                "project(demo C)\nadd_executable(demo main.c)\n",
+            ),
+            ("main.c", "int main() { return 0; }\n"),
+            ("third_party/dep.c", "int dep;\n"),
+            ("gen-1/generated.c", "int generated;\n"),
+        ],
+    )
+    .to_string();
+
+    let mut repos = PreProcessedRepositories::default();
+    let repo = repos
+        .register_config_with_ignored_dirs(forge.repo("project"), RepoConfig::CppMake, &["gen-*"])
+        .unwrap()
+        .fetch();
+    let oids = repos.pre_process_with_limit(&repo, "", &commit, 1).unwrap();
+    let id = repos.get_commit(&repo.config, &oids[0]).unwrap().ast_root;
+    let stores = &repos.processor.main_stores;
+    let text = hyperast::nodes::SyntaxSerializer::new(stores, id).to_string();
+    assert!(text.contains("dep.c") && !text.contains("generated.c"));
+    let any = repos.register_config_with_ignored_dirs(forge.repo("project"), RepoConfig::Any, &[]);
+    assert!(any.is_err());
+}
+
+#[test]
+fn process_make_project_with_prepro() {
+    use crate::multi_preprocessed::PreProcessedRepositories;
+    use crate::processing::RepoConfig;
+    let forge = TempForge::new("prepro");
+    let repository = forge.init("project");
+    let commit = commit(
+        &repository,
+        &[
+            (
+                "CMakeLists.txt",
+                "project(demo CXX)\nadd_executable(demo main.cpp)\n",
+            ),
+            ("main.cpp", "int main() { return 0; }\n"),
+        ],
+    )
+    .to_string();
+
+    let mut repos = PreProcessedRepositories::default();
+    let prepro = hyperast::scripting::lua_scripting::PREPRO_SIZE_WITH_FINISH;
+    let repo = repos
+        .register_config_with_prepro(forge.repo("project"), RepoConfig::CppMake, prepro.into())
+        .unwrap()
+        .fetch();
+    let oids = repos.pre_process_with_limit(&repo, "", &commit, 1).unwrap();
+    let id = repos.get_commit(&repo.config, &oids[0]).unwrap().ast_root;
+    let n = repos.processor.main_stores.node_store.resolve(id);
+    let dd = n
+        .get_component::<hyperast::scripting::DerivedData>()
+        .unwrap();
+    assert!(dd.0.contains_key("size"));
+}
+
+#[cfg(feature = "tsg")]
+#[test]
+fn process_make_project_with_tsg() {
+    use crate::multi_preprocessed::PreProcessedRepositories;
+    use crate::processing::RepoConfig;
+    let forge = TempForge::new("make_tsg");
+    let repository = forge.init("project");
+    let commit = commit(
+        &repository,
+        &[
+            (
+                "CMakeLists.txt",
+                "project(demo CXX)\nadd_library(demo a.cpp)\n",
+            ),
+            (
+                "a.cpp",
+                "class A {\n  int f() { return 0; }\n};\nstruct B {};\n",
+            ),
+        ],
+    )
+    .to_string();
+    let tsg = r#"
+(class_specifier) @class {
+    node @class.decl
+    attr (@class.decl) kind = "class"
+}
+"#;
+
+    let mut repos = PreProcessedRepositories::default();
+    let serialize = |repos: &mut PreProcessedRepositories,
+                     repo: &crate::processing::ConfiguredRepo2| {
+        let oids = repos.pre_process_with_limit(repo, "", &commit, 1).unwrap();
+        let id = repos.get_commit(&repo.config, &oids[0]).unwrap().ast_root;
+        let stores = &repos.processor.main_stores;
+        hyperast::nodes::SyntaxSerializer::new(stores, id).to_string()
+    };
+    let repo = repos
+        .register_config_with_tsg(forge.repo("project"), RepoConfig::CppMake, tsg.into())
+        .unwrap()
+        .fetch();
+    let overlaid = serialize(&mut repos, &repo);
+    assert!(overlaid.contains("class_specifier"));
+    // the overlay is computed on the way, the tree itself is unchanged
+    let repo = repos
+        .register_config(forge.repo("project"), RepoConfig::CppMake)
+        .fetch();
+    assert_eq!(serialize(&mut repos, &repo), overlaid);
+}
+
+#[test]
+fn collect_garbage_keeps_newer_commit() {
+    use crate::multi_preprocessed::PreProcessedRepositories;
+    use crate::processing::RepoConfig;
+    use hyperast::types::{Childrn, LabelStore, Labeled, WithChildren};
+    let forge = TempForge::new("gc");
+    let repository = forge.init("project");
+    let util = "def a():\n    pass\n";
+    let old = commit(
+        &repository,
+        &[
+            ("util.py", util),
+            ("old.py", "def dropped(x):\n    return x\n"),
+        ],
+    );
+    let new = commit(&repository, &[("util.py", util)]);
+
+    let mut repos = PreProcessedRepositories::default();
+    let repo = repos
+        .register_config(forge.repo("project"), RepoConfig::Any)
+        .fetch();
+    let oids = repos
+        .pre_process_with_limit(&repo, "", &new.to_string(), 2)
+        .unwrap();
+    assert_eq!(oids.len(), 2);
+    let root = |repos: &PreProcessedRepositories, oid| {
+        repos.get_commit(&repo.config, &oid).unwrap().ast_root
+    };
+    let serialize = |repos: &PreProcessedRepositories, id| {
+        hyperast::nodes::SyntaxSerializer::new(&repos.processor.main_stores, id).to_string()
+    };
+    let child = |repos: &PreProcessedRepositories, id, name: &str| {
+        let stores = &repos.processor.main_stores;
+        let n = stores.node_store.resolve(id);
+        let cs = n.children().unwrap();
+        cs.iter_children()
+            .find(|c| {
+                let c = stores.node_store.resolve(*c);
+                c.try_get_label()
+                    .is_some_and(|l| stores.label_store.resolve(l) == name)
+            })
+            .unwrap()
+    };
+    let new_root = root(&repos, new);
+    let before = serialize(&repos, new_root);
+
+    let stats = repos.collect_garbage(|oid, _| *oid == new);
+    assert!(stats.reclaimed_nodes() > 0);
+    assert!(stats.reclaimed_labels() > 0);
+    assert!(repos.get_commit(&repo.config, &old).is_none());
+    assert_eq!(serialize(&repos, new_root), before);
+    let labels = &repos.processor.main_stores.label_store;
+    assert!(labels.get("dropped").is_none() && labels.get("a").is_some());
+
+    // rebuilt, but the unchanged file is shared with the surviving commit
+    repos
+        .pre_process_with_limit(&repo, "", &old.to_string(), 1)
+        .unwrap();
+    let old_root = root(&repos, old);
+    assert_eq!(
+        child(&repos, old_root, "util.py"),
+        child(&repos, new_root, "util.py")
+    );
+    assert!(serialize(&repos, old_root).contains("dropped"));
+}
+
+#[cfg(feature = "frozen")]
+#[test]
+fn freeze_then_open_processed_commit() {
+    use crate::multi_preprocessed::{PreProcessedRepositories, frozen};
+    use crate::processing::RepoConfig;
+    use hyperast::hashed::SyntaxNodeHashsKinds;
+    use hyperast::store::nodes::compo::{MemberImportCount, StmtCount};
+    use hyperast::types::{
+        Childrn, HyperAST, HyperType, LabelStore, Labeled, WithChildren, WithHashs, WithMetaData,
+        WithStats,
+    };
+    let forge = TempForge::new("frozen");
+    let repository = forge.init("project");
+    let java = "package a;\nimport b.C;\nclass A {\n  int f() { int x = 1; return x; }\n}\n";
+    let commit = commit(
+        &repository,
+        &[("src/A.java", java), ("util.py", "def a():\n    pass\n")],
+    )
+    .to_string();
+
+    let mut repos = PreProcessedRepositories::default();
+    let repo = repos
+        .register_config(forge.repo("project"), RepoConfig::Any)
+        .fetch();
+    let oids = repos.pre_process_with_limit(&repo, "", &commit, 1).unwrap();
+    let path = forge.root.join("frozen");
+    let stats = repos.freeze(&path).unwrap();
+    assert!(stats.nodes > 0);
+    let frozen = frozen::open(&path).unwrap();
+
+    let stores = &repos.processor.main_stores;
+    let id = repos.get_commit(&repo.config, &oids[0]).unwrap().ast_root;
+    let mut stack = vec![(id, frozen.node_store.root(&commit).unwrap())];
+    while let Some((id, fid)) = stack.pop() {
+        let n = stores.node_store.resolve(id);
+        let f = frozen.node_store.try_resolve(fid).unwrap();
+        let ty = stores.resolve_type(&id);
+        assert_eq!(
+            ty.as_static_str(),
+            frozen.resolve_type(&fid).as_static_str()
+        );
+        assert_eq!(
+            n.try_get_label().map(|l| stores.label_store.resolve(l)),
+            f.try_get_label().map(|l| frozen.label_store.resolve(l)),
+            "{}",
+            ty.as_static_str()
+        );
+        for kind in [
+            SyntaxNodeHashsKinds::Struct,
+            SyntaxNodeHashsKinds::Label,
+            SyntaxNodeHashsKinds::Syntax,
+        ] {
+            assert_eq!(n.hash(&kind), f.hash(&kind));
+        }
+        assert_eq!((n.size(), n.height()), (f.size(), f.height()));
+        assert_eq!(n.line_count(), f.line_count());
+        let stmt = |x: Option<&StmtCount>| x.map(|x| x.0);
+        assert_eq!(stmt(n.get_metadata()), stmt(f.get_metadata()));
+        let imports = |x: Option<&MemberImportCount>| x.map(|x| x.0);
+        assert_eq!(imports(n.get_metadata()), imports(f.get_metadata()));
+        assert_eq!(n.child_count(), f.child_count());
+        if let (Some(cs), Some(fcs)) = (n.children(), f.children()) {
+            stack.extend(cs.iter_children().zip(fcs.iter_children()));
+        }
+    }
+    // only existing labels can be interned in a frozen store
+    let mut labels = &frozen.label_store;
+    let l = labels.get_or_insert("util.py");
+    assert_eq!(labels.resolve(&l), "util.py");
+    assert!(labels.get("missing.py").is_none());
+}
+
+#[test]
+fn path_filter_globs() {
+    use crate::path_filter::PathFilter;
+    let filter: PathFilter = "include=services/payments/**&include=*.md&exclude=**/generated/**"
+        .parse()
+        .unwrap();
+    assert!(filter.is_file_kept("services/payments/src/pay.c"));
+    assert!(filter.is_file_kept("README.md"));
+    assert!(!filter.is_file_kept("docs/README.md"));
+    assert!(!filter.is_file_kept("services/billing/bill.c"));
+    assert!(!filter.is_file_kept("services/payments/generated/gen.c"));
+    assert!(filter.is_dir_explored("services"));
+    assert!(!filter.is_dir_explored("services/billing"));
+    assert!(!filter.is_dir_explored("services/payments/generated"));
+    let filter: PathFilter = "exclude=vendor&submodules".parse().unwrap();
+    assert!(filter.submodules);
+    assert!(!filter.is_dir_explored("vendor") && filter.is_dir_explored("src/vendor"));
+    assert!("only=src".parse::<PathFilter>().is_err());
+}
+
+#[test]
+fn process_filtered_monorepo() {
+    use crate::multi_preprocessed::PreProcessedRepositories;
+    use crate::path_filter::{PathFilter, filter_tree};
+    use crate::processing::RepoConfig;
+    let forge = TempForge::new("monorepo");
+    let common = forge.init("common");
+    let common = commit(&common, &[("common.c", "int common;\n")]);
+
+    let repository = forge.init("monorepo");
+    let gitmodules = format!(
+        "[submodule \"common\"]\n\tpath = libs/common\n\turl = {}\n",
+        forge.path("common").display()
+    );
+    let files = write_tree(
+        &repository,
+        &[
+            (".gitmodules", gitmodules.as_str()),
+            ("services/payments/pay.c", "int pay;\n"),
+            ("services/payments/generated/gen.c", "int gen;\n"),
+            ("services/billing/bill.c", "int bill;\n"),
+        ],
+    );
+    let mut libs = repository.treebuilder(None).unwrap();
+    libs.insert("common", common, 0o160000).unwrap();
+    let libs = libs.write().unwrap();
+    let files = repository.find_tree(files).unwrap();
+    let mut tree = repository.treebuilder(Some(&files)).unwrap();
+    tree.insert("libs", libs, 0o040000).unwrap();
+    let tree = repository.find_tree(tree.write().unwrap()).unwrap();
+    let sig = git2::Signature::now("hyperast", "hyperast@example.org").unwrap();
+    let commit = repository
+        .commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+        .unwrap()
+        .to_string();
+    // unchanged trees are reused as is
+    let everything = PathFilter::default().include("**");
+    let unchanged = filter_tree(&repository, tree.id(), &everything).unwrap();
+    assert_eq!(unchanged, tree.id());
+
+    let filter = PathFilter::default()
+        .include("services/payments/**")
+        .include("libs/**")
+        .exclude("**/generated")
+        .with_submodules();
+    let mut repos = PreProcessedRepositories::default();
+    let any = repos.register_config_with_filter(forge.repo("any"), RepoConfig::Any, filter.clone());
+    assert!(any.is_err());
+    let repo = repos
+        .register_config_with_filter(forge.repo("monorepo"), RepoConfig::CppMake, filter.clone())
+        .unwrap()
+        .fetch();
+    let oids = repos.pre_process_with_limit(&repo, "", &commit, 1).unwrap();
+    let id = repos.get_commit(&repo.config, &oids[0]).unwrap().ast_root;
+    let stores = &repos.processor.main_stores;
+    let text = hyperast::nodes::SyntaxSerializer::new(stores, id).to_string();
+    assert!(text.contains("pay.c") && text.contains("common.c"));
+    assert!(!text.contains("bill.c") && !text.contains("gen.c"));
+
+    // filtered trees never reach the repository on disk
+    let scratch = git2::Repository::open(forge.path("monorepo")).unwrap();
+    crate::path_filter::write_in_memory(&scratch).unwrap();
+    let filtered = filter_tree(&scratch, tree.id(), &filter).unwrap();
+    assert!(scratch.find_tree(filtered).is_ok());
+    let on_disk = git2::Repository::open(forge.path("monorepo")).unwrap();
+    assert!(on_disk.find_tree(filtered).is_err());
+    assert!(on_disk.find_commit(common).is_err());
+}
+
+#[test]
+fn watcher_fetches_pushed_branches() {
+    use crate::multi_preprocessed::PreProcessedRepositories;
+    use crate::processing::RepoConfig;
+    use crate::watcher::Watcher;
+    let forge = TempForge::new("watcher");
+    let upstream = forge.init("upstream");
+    let first = commit(&upstream, &[("a.py", "a = 1\n")]);
+    let default_branch = upstream.head().unwrap().name().unwrap().to_string();
+    // like the clones of remote forges, only knows the refs it fetched
+    let clone = forge.init("clone");
+    let url = forge.path("upstream");
+    clone.remote("origin", &url.to_string_lossy()).unwrap();
+
+    let mut repos = PreProcessedRepositories::default();
+    let repo = repos
+        .register_config(forge.repo("clone"), RepoConfig::Any)
+        .fetch();
+    let mut watcher = Watcher::new();
+    let update = watcher.poll(&repo, &mut repos).unwrap();
+    assert_eq!(update.processed, vec![first]);
+
+    let first = upstream.find_commit(first).unwrap();
+    upstream.branch("feature", &first, false).unwrap();
+    upstream.set_head("refs/heads/feature").unwrap();
+    let second = commit(&upstream, &[("a.py", "a = 2\n")]);
+    let update = watcher.poll(&repo, &mut repos).unwrap();
+    assert_eq!(update.processed, vec![second]);
+    let moved: Vec<_> = update
+        .moved
+        .iter()
+        .map(|m| (m.name.as_str(), m.before))
+        .collect();
+    assert_eq!(moved, vec![("refs/heads/feature", None)]);
+
+    let update = watcher.poll(&repo, &mut repos).unwrap();
+    assert!(update.moved.is_empty() && update.processed.is_empty());
+
+    upstream.set_head(&default_branch).unwrap();
+    upstream
+        .find_branch("feature", git2::BranchType::Local)
+        .unwrap()
+        .delete()
+        .unwrap();
+    let update = watcher.poll(&repo, &mut repos).unwrap();
+    assert_eq!(update.deleted, vec!["refs/heads/feature".to_string()]);
+}
+
+#[test]
+fn pre_process_fetched_refs_in_chunks() {
+    use crate::multi_preprocessed::{DEFAULT_REF_GLOBS, PreProcessedRepositories};
+    use crate::processing::RepoConfig;
+    let forge = TempForge::new("refs");
+    let upstream = forge.init("upstream");
+    let first = commit(&upstream, &[("a.py", "a = 1\n")]);
+    let second = commit(&upstream, &[("a.py", "a = 2\n")]);
+    let first_commit = upstream.find_commit(first).unwrap();
+    upstream.branch("feature", &first_commit, false).unwrap();
+    upstream
+        .tag_lightweight("v1", first_commit.as_object(), false)
+        .unwrap();
+    upstream.set_head("refs/heads/feature").unwrap();
+    let third = commit(&upstream, &[("b.py", "b = 3\n")]);
+    let clone = forge.init("clone");
+    let url = forge.path("upstream");
+    clone.remote("origin", &url.to_string_lossy()).unwrap();
+
+    let mut repos = PreProcessedRepositories::default();
+    let repo = repos
+        .register_config(forge.repo("clone"), RepoConfig::Any)
+        .fetch();
+    let oids = repos.pre_process_refs(&repo, DEFAULT_REF_GLOBS, 2).unwrap();
+    assert_eq!(oids.len(), 2);
+    // parents first
+    assert_eq!(oids[0], first);
+    let remaining = repos
+        .unprocessed_from_refs(&repo, DEFAULT_REF_GLOBS)
+        .unwrap();
+    assert_eq!(remaining.len(), 1);
+    let oids = repos.pre_process_refs(&repo, DEFAULT_REF_GLOBS, 2).unwrap();
+    assert_eq!(oids, remaining);
+    let processed = [first, second, third];
+    assert!(
+        processed
+            .iter()
+            .all(|c| repos.get_commit(&repo.config, c).is_some())
+    );
+
+    let refs = repos.refs(&repo, DEFAULT_REF_GLOBS).unwrap();
+    let names: Vec<_> = refs.iter().map(|r| r.name.as_str()).collect();
+    assert!(names.contains(&"refs/heads/feature") && names.contains(&"refs/tags/v1"));
+    assert!(refs.iter().all(|r| r.ast_root.is_some()));
+}
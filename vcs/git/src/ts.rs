@@ -0,0 +1,88 @@
+use std::time::Instant;
+
+use crate::{
+    BasicDirAcc, FailedParsing, FileProcessingResult, PROPAGATE_ERROR_ON_BAD_CST_NODE,
+    SuccessProcessing, processing::ObjectName,
+};
+
+use hyperast::{
+    hashed::SyntaxNodeHashs,
+    store::defaults::{LabelIdentifier, NodeIdentifier},
+    tree_gen::SubTreeMetrics,
+};
+
+use hyperast_gen_ts_ts::{legion as ts_tree_gen, types::TStore};
+
+pub(crate) fn handle_ts_file<'stores, 'cache, 'b: 'stores>(
+    tree_gen: &mut ts_tree_gen::TsTreeGen<'stores, 'cache, TStore>,
+    name: &ObjectName,
+    text: &'b [u8],
+) -> FileProcessingResult<ts_tree_gen::FNode> {
+    let mut parser = tree_sitter::Parser::new();
+    // TODO see if a timeout of a cancellation flag could be useful
+    parser
+        .set_language(&hyperast_gen_ts_ts::language())
+        .unwrap();
+    let time = Instant::now();
+    let tree = parser.parse(text, None);
+    let parsing_time = time.elapsed();
+    let Some(tree) = tree else {
+        unimplemented!("You set a timeout or an cancel flag, so it now requires special handling.")
+    };
+    if tree.root_node().has_error() {
+        log::warn!("bad CST: {:?}", name.try_str());
+        if PROPAGATE_ERROR_ON_BAD_CST_NODE {
+            return Err(FailedParsing {
+                parsing_time,
+                tree,
+                error: "CST contains parsing errors",
+            });
+        }
+    };
+    let time = Instant::now();
+    let node = tree_gen.generate_file(name.as_bytes(), text, tree.walk());
+    let processing_time = time.elapsed();
+    Ok(SuccessProcessing {
+        parsing_time,
+        processing_time,
+        node,
+    })
+}
+
+pub struct TsAcc {
+    pub(crate) primary:
+        BasicDirAcc<NodeIdentifier, LabelIdentifier, SubTreeMetrics<SyntaxNodeHashs<u32>>>,
+}
+
+impl TsAcc {
+    pub(crate) fn new(name: String) -> Self {
+        Self {
+            primary: BasicDirAcc::new(name),
+        }
+    }
+}
+
+impl From<String> for TsAcc {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+impl TsAcc {
+    pub(crate) fn push(&mut self, name: LabelIdentifier, full_node: ts_tree_gen::Local) {
+        self.primary
+            .push(name, full_node.compressed_node, full_node.metrics);
+    }
+}
+
+impl hyperast::tree_gen::Accumulator for TsAcc {
+    type Node = (LabelIdentifier, (ts_tree_gen::Local,));
+    fn push(&mut self, (name, (full_node,)): Self::Node) {
+        self.primary
+            .push(name, full_node.compressed_node, full_node.metrics);
+    }
+}
+
+impl crate::Accumulator for TsAcc {
+    type Unlabeled = (ts_tree_gen::Local,);
+}
@@ -5,12 +5,18 @@ use hyperast::types::{AnyType, HyperType, LangRef, LangWrapper, TypeStore};
 #[derive(Clone, Copy)]
 pub struct TStore;
 
+#[cfg(feature = "c")]
+impl hyperast::store::TyDown<hyperast_gen_ts_c::types::TStore> for TStore {}
 #[cfg(feature = "cpp")]
 impl hyperast::store::TyDown<hyperast_gen_ts_cpp::types::TStore> for TStore {}
 #[cfg(feature = "java")]
 impl hyperast::store::TyDown<hyperast_gen_ts_java::types::TStore> for TStore {}
-#[cfg(feature = "maven")]
+#[cfg(any(feature = "maven", feature = "gradle", feature = "any"))]
 impl hyperast::store::TyDown<hyperast_gen_ts_xml::types::TStore> for TStore {}
+#[cfg(feature = "python")]
+impl hyperast::store::TyDown<hyperast_gen_ts_python::types::TStore> for TStore {}
+#[cfg(feature = "ts")]
+impl hyperast::store::TyDown<hyperast_gen_ts_ts::types::TStore> for TStore {}
 
 impl Default for TStore {
     fn default() -> Self {
@@ -39,6 +45,12 @@ impl<'a> hyperast::types::RoleStore for TStore {
                 );
                 hyperast_gen_ts_cpp::types::TStore::resolve_field(t.get_lang(), field_id)
             }
+            #[cfg(feature = "c")]
+            "hyperast_gen_ts_c::types::Lang" => {
+                let t =
+                    hyperast_gen_ts_c::types::TType::new(hyperast_gen_ts_c::types::Type::Spaces);
+                hyperast_gen_ts_c::types::TStore::resolve_field(t.get_lang(), field_id)
+            }
             #[cfg(feature = "maven")]
             "hyperast_gen_ts_xml::types::Lang" => {
                 let t = hyperast_gen_ts_xml::types::TType::new(
@@ -46,6 +58,13 @@ impl<'a> hyperast::types::RoleStore for TStore {
                 );
                 hyperast_gen_ts_xml::types::TStore::resolve_field(t.get_lang(), field_id)
             }
+            #[cfg(feature = "python")]
+            "hyperast_gen_ts_python::types::Lang" => {
+                let t = hyperast_gen_ts_python::types::TType::new(
+                    hyperast_gen_ts_python::types::Type::Spaces,
+                );
+                hyperast_gen_ts_python::types::TStore::resolve_field(t.get_lang(), field_id)
+            }
             x => panic!("{}", x),
         }
     }
@@ -67,6 +86,12 @@ impl<'a> hyperast::types::RoleStore for TStore {
                 );
                 hyperast_gen_ts_cpp::types::TStore::intern_role(t.get_lang(), role)
             }
+            #[cfg(feature = "c")]
+            "hyperast_gen_ts_c::types::Lang" => {
+                let t =
+                    hyperast_gen_ts_c::types::TType::new(hyperast_gen_ts_c::types::Type::Spaces);
+                hyperast_gen_ts_c::types::TStore::intern_role(t.get_lang(), role)
+            }
             #[cfg(feature = "maven")]
             "hyperast_gen_ts_xml::types::Lang" => {
                 let t = hyperast_gen_ts_xml::types::TType::new(
@@ -74,6 +99,13 @@ impl<'a> hyperast::types::RoleStore for TStore {
                 );
                 hyperast_gen_ts_xml::types::TStore::intern_role(t.get_lang(), role)
             }
+            #[cfg(feature = "python")]
+            "hyperast_gen_ts_python::types::Lang" => {
+                let t = hyperast_gen_ts_python::types::TType::new(
+                    hyperast_gen_ts_python::types::Type::Spaces,
+                );
+                hyperast_gen_ts_python::types::TStore::intern_role(t.get_lang(), role)
+            }
             x => panic!("{}", x),
         }
     }
@@ -100,6 +132,17 @@ impl TypeStore for TStore {
             }
             .map(|t| t.as_static().into())
         })
+        .or_else(|| {
+            #[cfg(feature = "c")]
+            return unsafe {
+                erazed.unerase_ref_unchecked::<hyperast_gen_ts_c::types::TType>(
+                    std::any::TypeId::of::<hyperast_gen_ts_c::types::TType>(),
+                )
+            }
+            .map(|t| t.as_static().into());
+            #[cfg(not(feature = "c"))]
+            None
+        })
         .or_else(|| {
             unsafe {
                 erazed.unerase_ref_unchecked::<hyperast_gen_ts_xml::types::TType>(
@@ -108,6 +151,28 @@ impl TypeStore for TStore {
             }
             .map(|t| t.as_static().into())
         })
+        .or_else(|| {
+            #[cfg(feature = "python")]
+            return unsafe {
+                erazed.unerase_ref_unchecked::<hyperast_gen_ts_python::types::TType>(
+                    std::any::TypeId::of::<hyperast_gen_ts_python::types::TType>(),
+                )
+            }
+            .map(|t| t.as_static().into());
+            #[cfg(not(feature = "python"))]
+            None
+        })
+        .or_else(|| {
+            #[cfg(feature = "ts")]
+            return unsafe {
+                erazed.unerase_ref_unchecked::<hyperast_gen_ts_ts::types::TType>(
+                    std::any::TypeId::of::<hyperast_gen_ts_ts::types::TType>(),
+                )
+            }
+            .map(|t| t.as_static().into());
+            #[cfg(not(feature = "ts"))]
+            None
+        })
         .unwrap_or_else(|| {
             dbg!(tid);
             dbg!(std::any::type_name::<Self::Ty>());
@@ -0,0 +1,160 @@
+//! Keeps preprocessed repositories up to date with their refs.
+//!
+//! A [`Watcher`] remembers the tip of each watched ref,
+//! each [`Watcher::poll`] only preprocesses the commits reachable from the refs that moved
+//! but neither from their previous tips nor already processed.
+//! Blobs are cached by the processors, so files left unchanged by new commits are not parsed again.
+//!
+//! Polling can be periodic or triggered by a notification, e.g. a post-receive hook of a bare repository.
+//! The watched refs of clones are fetched from their remote beforehand, see [`Watcher::fetch`].
+use std::collections::HashMap;
+
+use git2::{Oid, Repository};
+
+use crate::multi_preprocessed::PreProcessedRepositories;
+use crate::processing::ConfiguredRepo2;
+
+/// Prefixes of the refs watched by default, i.e. branches and tags
+pub const DEFAULT_REFS: &[&str] = &["refs/heads/", "refs/tags/"];
+
+#[derive(Debug, Clone)]
+pub struct Watcher {
+    /// prefixes of the watched refs
+    refs: Vec<String>,
+    tips: HashMap<String, Oid>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MovedRef {
+    pub name: String,
+    /// None for a new ref
+    pub before: Option<Oid>,
+    pub after: Oid,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Update {
+    pub moved: Vec<MovedRef>,
+    pub deleted: Vec<String>,
+    /// newly processed commits, children first
+    pub processed: Vec<Oid>,
+}
+
+impl Default for Watcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Watcher {
+    /// Watches branches and tags, the first poll processes all their commits not yet processed
+    pub fn new() -> Self {
+        Self {
+            refs: DEFAULT_REFS.iter().map(|x| x.to_string()).collect(),
+            tips: Default::default(),
+        }
+    }
+
+    /// Watches the refs starting with one of `prefixes` instead of branches and tags
+    pub fn with_refs(mut self, prefixes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.refs = prefixes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Considers the current tips as handled, so only commits pushed afterward get processed
+    pub fn skip_current(&mut self, repository: &Repository) -> Result<(), git2::Error> {
+        self.fetch(repository)?;
+        self.tips = self.current_tips(repository)?;
+        Ok(())
+    }
+
+    /// Mirrors the watched refs of the remote of `repository`, if any,
+    /// clones only track `main` otherwise
+    pub fn fetch(&self, repository: &Repository) -> Result<(), git2::Error> {
+        let refspecs: Vec<_> = self.refs.iter().map(|p| format!("+{p}*:{p}*")).collect();
+        crate::git::fetch_refspecs(repository, &refspecs)
+    }
+
+    pub fn tips(&self) -> impl Iterator<Item = (&str, Oid)> {
+        self.tips.iter().map(|(name, oid)| (name.as_str(), *oid))
+    }
+
+    fn current_tips(&self, repository: &Repository) -> Result<HashMap<String, Oid>, git2::Error> {
+        let mut tips = HashMap::new();
+        for r in repository.references()? {
+            let r = r?;
+            let Some(name) = r.name() else {
+                continue;
+            };
+            if !self.refs.iter().any(|p| name.starts_with(p.as_str())) {
+                continue;
+            }
+            // e.g. tags of trees
+            let Ok(commit) = r.peel_to_commit() else {
+                continue;
+            };
+            tips.insert(name.to_string(), commit.id());
+        }
+        Ok(tips)
+    }
+
+    /// Preprocesses the commits added to the watched refs since the previous poll
+    pub fn poll(
+        &mut self,
+        repository: &ConfiguredRepo2,
+        repositories: &mut PreProcessedRepositories,
+    ) -> Result<Update, git2::Error> {
+        self.fetch(&repository.repo)?;
+        let tips = self.current_tips(&repository.repo)?;
+        let mut update = Update::default();
+        for (name, &after) in &tips {
+            let before = self.tips.get(name).copied();
+            if before != Some(after) {
+                update.moved.push(MovedRef {
+                    name: name.clone(),
+                    before,
+                    after,
+                });
+            }
+        }
+        update.deleted = self
+            .tips
+            .keys()
+            .filter(|name| !tips.contains_key(*name))
+            .cloned()
+            .collect();
+        if update.moved.is_empty() {
+            self.tips = tips;
+            return Ok(update);
+        }
+
+        let mut rw = repository.repo.revwalk()?;
+        rw.set_sorting(git2::Sort::TOPOLOGICAL)?;
+        for moved in &update.moved {
+            rw.push(moved.after)?;
+        }
+        for tip in self.tips.values() {
+            // the previous tip of a force-pushed ref might have been pruned
+            if let Err(err) = rw.hide(*tip) {
+                log::warn!("previous tip {tip} is not in the repository anymore: {err}");
+            }
+        }
+        let mut new = vec![];
+        for oid in rw {
+            let oid = oid?;
+            if repositories.get_commit(&repository.config, &oid).is_none() {
+                new.push(oid);
+            }
+        }
+        log::info!(
+            "{} refs moved in {}, {} commits to process",
+            update.moved.len(),
+            repository.spec.url(),
+            new.len()
+        );
+        update.processed =
+            repositories.pre_process_chunk(&mut new.into_iter(), repository, usize::MAX)?;
+        self.tips = tips;
+        Ok(update)
+    }
+}